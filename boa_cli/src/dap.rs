@@ -0,0 +1,653 @@
+//! A [Debug Adapter Protocol][dap] server, to debug scripts from editors.
+//!
+//! The server speaks the protocol over stdio and debugs a single script, given by the `program`
+//! argument of the `launch` request. It supports breakpoints, stepping, stack traces, scopes and
+//! variables, and evaluating expressions in a frame.
+//!
+//! [dap]: https://microsoft.github.io/debug-adapter-protocol/
+
+use boa_engine::{
+    object::{JsObject, ObjectInitializer},
+    property::Attribute,
+    vm::{Debugger, PauseReason, StepAction},
+    Context, JsError, JsNativeError, JsResult, JsValue,
+};
+use serde_json::{json, Value};
+use std::{
+    cell::RefCell,
+    collections::BTreeSet,
+    fs,
+    io::{self, BufRead, BufReader, Write},
+    path::Path,
+    rc::Rc,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// The only thread of the debugged script.
+const THREAD_ID: u64 = 1;
+
+thread_local! {
+    /// Output of the `console` functions, waiting to be sent as `output` events.
+    static OUTPUT: RefCell<Vec<(&'static str, String)>> = RefCell::new(Vec::new());
+}
+
+/// Runs the server over stdio until the client disconnects.
+pub(crate) fn run() -> io::Result<()> {
+    serve(BufReader::new(io::stdin()), io::stdout()).map(drop)
+}
+
+/// Runs the server until the client disconnects or closes `reader`, returning `writer`.
+pub(crate) fn serve<R, W>(reader: R, writer: W) -> io::Result<W>
+where
+    R: BufRead + 'static,
+    W: Write + 'static,
+{
+    let session = Rc::new(RefCell::new(Session::new(reader, writer)));
+    let mut context = Context::default();
+    install_console(&mut context);
+    context.set_debugger(DapDebugger {
+        session: session.clone(),
+    });
+
+    loop {
+        let request = session.borrow_mut().read_message()?;
+        let request = match request {
+            Some(request) => request,
+            None => break,
+        };
+
+        let action = session.borrow_mut().handle(&request, &mut context)?;
+        if action == Action::Disconnect {
+            break;
+        }
+
+        let ready = session.borrow().ready();
+        if ready {
+            launch(&session, &mut context)?;
+            if session.borrow().disconnected {
+                break;
+            }
+        }
+    }
+
+    // Dropping the context releases the debugger and its reference to the session.
+    drop(context);
+    match Rc::try_unwrap(session) {
+        Ok(session) => Ok(session.into_inner().writer),
+        Err(_) => Err(io::Error::new(
+            io::ErrorKind::Other,
+            "the session is still used by the debugger",
+        )),
+    }
+}
+
+/// Returns the lines of the script at `path` where a statement starts.
+///
+/// The script is compiled in its own `Context`, so that the debugged one doesn't declare its
+/// bindings before it runs.
+fn statement_lines(path: &str) -> Result<BTreeSet<u32>, String> {
+    let source = fs::read(path).map_err(|error| format!("could not read {path}: {error}"))?;
+    let mut context = Context::default();
+    let statements = context
+        .parse(&source)
+        .map_err(|error| format!("could not parse {path}: {error}"))?;
+    let code = context
+        .compile_with_name(&statements, path)
+        .map_err(|error| format!("{}", error.to_opaque(&mut context).display()))?;
+    Ok(code.statement_lines())
+}
+
+/// Runs the program of the `launch` request, then reports its termination.
+fn launch<R, W>(session: &Rc<RefCell<Session<R, W>>>, context: &mut Context) -> io::Result<()>
+where
+    R: BufRead,
+    W: Write,
+{
+    let (program, source, stop_on_entry) = {
+        let mut session = session.borrow_mut();
+        let (program, source) = match session.program.take() {
+            Some(program) => program,
+            None => return Ok(()),
+        };
+        session.launched = true;
+        session.entry = session.stop_on_entry;
+        (program, source, session.stop_on_entry)
+    };
+
+    if stop_on_entry {
+        context.debugger_pause();
+    }
+    let result = context
+        .eval_with_name(&source, &program)
+        .map_err(|error| format!("Uncaught {}", error.to_opaque(context).display()));
+
+    let mut session = session.borrow_mut();
+    let exit_code = match result {
+        Ok(_) => 0,
+        Err(message) => {
+            session.output("stderr", &format!("{message}\n"))?;
+            1
+        }
+    };
+    session.event("terminated", json!({}))?;
+    session.event("exited", json!({ "exitCode": exit_code }))
+}
+
+/// What the caller of [`Session::handle`] must do after a request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Action {
+    /// Keep reading requests.
+    None,
+    /// Resume the paused script.
+    Resume(StepAction),
+    /// Stop the server.
+    Disconnect,
+}
+
+/// A value that can be expanded with a `variables` request.
+#[derive(Debug)]
+enum Reference {
+    /// The bindings of the frame at the given depth.
+    Frame(usize),
+    /// The properties of an object.
+    Object(JsObject),
+}
+
+/// The state of a debugging session.
+struct Session<R, W> {
+    reader: R,
+    writer: W,
+    seq: u64,
+    /// The path and the source of the program to launch.
+    program: Option<(String, Vec<u8>)>,
+    stop_on_entry: bool,
+    configured: bool,
+    launched: bool,
+    disconnected: bool,
+    /// Whether the next pause is the pause requested by `stopOnEntry`.
+    entry: bool,
+    /// The values with a `variablesReference`, valid until the script resumes.
+    references: Vec<Reference>,
+}
+
+impl<R, W> Session<R, W>
+where
+    R: BufRead,
+    W: Write,
+{
+    fn new(reader: R, writer: W) -> Self {
+        Self {
+            reader,
+            writer,
+            seq: 0,
+            program: None,
+            stop_on_entry: false,
+            configured: false,
+            launched: false,
+            disconnected: false,
+            entry: false,
+            references: Vec::new(),
+        }
+    }
+
+    /// Returns `true` if the program can be launched.
+    fn ready(&self) -> bool {
+        self.configured && self.program.is_some() && !self.launched
+    }
+
+    /// Reads the next message, or `None` if the client closed the connection.
+    fn read_message(&mut self) -> io::Result<Option<Value>> {
+        let mut length = None;
+        loop {
+            let mut line = String::new();
+            if self.reader.read_line(&mut line)? == 0 {
+                return Ok(None);
+            }
+            let line = line.trim_end();
+            if line.is_empty() {
+                break;
+            }
+            if let Some(value) = line.strip_prefix("Content-Length:") {
+                length = value.trim().parse::<usize>().ok();
+            }
+        }
+
+        let length = length.ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidData, "missing Content-Length header")
+        })?;
+        let mut buffer = vec![0; length];
+        self.reader.read_exact(&mut buffer)?;
+        serde_json::from_slice(&buffer)
+            .map(Some)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+    }
+
+    /// Sends a message, after the pending output of the script.
+    fn send(&mut self, message: Value) -> io::Result<()> {
+        self.flush_output()?;
+        self.write_message(message)
+    }
+
+    fn write_message(&mut self, mut message: Value) -> io::Result<()> {
+        self.seq += 1;
+        message["seq"] = self.seq.into();
+        let body = message.to_string();
+        write!(self.writer, "Content-Length: {}\r\n\r\n{body}", body.len())?;
+        self.writer.flush()
+    }
+
+    fn event(&mut self, event: &str, body: Value) -> io::Result<()> {
+        let mut message = json!({ "type": "event", "event": event });
+        message["body"] = body;
+        self.send(message)
+    }
+
+    fn output(&mut self, category: &str, output: &str) -> io::Result<()> {
+        self.event("output", json!({ "category": category, "output": output }))
+    }
+
+    /// Sends the output of the `console` functions.
+    fn flush_output(&mut self) -> io::Result<()> {
+        for (category, output) in OUTPUT.with(RefCell::take) {
+            self.write_message(json!({
+                "type": "event",
+                "event": "output",
+                "body": { "category": category, "output": output },
+            }))?;
+        }
+        Ok(())
+    }
+
+    /// Handles a request, sending its response.
+    fn handle(&mut self, request: &Value, context: &mut Context) -> io::Result<Action> {
+        let command = request["command"].as_str().unwrap_or_default();
+        let arguments = &request["arguments"];
+
+        let result = match command {
+            "initialize" => Ok((
+                json!({
+                    "supportsConfigurationDoneRequest": true,
+                    "supportsEvaluateForHovers": true,
+                }),
+                Action::None,
+            )),
+            "launch" => self.launch(arguments).map(|()| (json!({}), Action::None)),
+            "setBreakpoints" => match arguments["source"]["path"].as_str() {
+                Some(path) => {
+                    let lines = arguments["breakpoints"]
+                        .as_array()
+                        .into_iter()
+                        .flatten()
+                        .filter_map(|breakpoint| breakpoint["line"].as_u64())
+                        .map(|line| line as u32)
+                        .collect::<Vec<_>>();
+                    // The script only pauses where a statement starts, so each breakpoint moves
+                    // to the next line with a statement.
+                    let statement_lines = statement_lines(path);
+                    context.clear_source_breakpoints(path);
+                    let mut breakpoints = Vec::with_capacity(lines.len());
+                    for line in lines {
+                        let breakpoint = match &statement_lines {
+                            Ok(statement_lines) => match statement_lines.range(line..).next() {
+                                Some(&line) => {
+                                    context.set_breakpoint(path, line);
+                                    json!({ "verified": true, "line": line })
+                                }
+                                None => json!({
+                                    "verified": false,
+                                    "line": line,
+                                    "message": "no statement at or after this line",
+                                }),
+                            },
+                            Err(message) => {
+                                json!({ "verified": false, "line": line, "message": message })
+                            }
+                        };
+                        breakpoints.push(breakpoint);
+                    }
+                    Ok((json!({ "breakpoints": breakpoints }), Action::None))
+                }
+                None => Err("missing source path".to_owned()),
+            },
+            "setExceptionBreakpoints" => Ok((json!({}), Action::None)),
+            "configurationDone" => {
+                self.configured = true;
+                Ok((json!({}), Action::None))
+            }
+            "threads" => Ok((
+                json!({ "threads": [{ "id": THREAD_ID, "name": "main" }] }),
+                Action::None,
+            )),
+            "stackTrace" => Ok((stack_trace(context), Action::None)),
+            "scopes" => {
+                let depth = arguments["frameId"].as_u64().unwrap_or_default() as usize;
+                self.references.push(Reference::Frame(depth));
+                let scopes = json!([{
+                    "name": "Locals",
+                    "variablesReference": self.references.len(),
+                    "expensive": false,
+                }]);
+                Ok((json!({ "scopes": scopes }), Action::None))
+            }
+            "variables" => {
+                let reference = arguments["variablesReference"].as_u64().unwrap_or_default();
+                self.variables(reference as usize, context)
+                    .map(|variables| (json!({ "variables": variables }), Action::None))
+            }
+            "evaluate" => {
+                let expression = arguments["expression"].as_str().unwrap_or_default();
+                let depth = arguments["frameId"].as_u64().map(|depth| depth as usize);
+                self.evaluate(expression, depth, context)
+                    .map(|body| (body, Action::None))
+            }
+            "continue" => Ok((
+                json!({ "allThreadsContinued": true }),
+                Action::Resume(StepAction::Continue),
+            )),
+            "next" => Ok((json!({}), Action::Resume(StepAction::StepOver))),
+            "stepIn" => Ok((json!({}), Action::Resume(StepAction::StepIn))),
+            "stepOut" => Ok((json!({}), Action::Resume(StepAction::StepOut))),
+            "disconnect" | "terminate" => Ok((json!({}), Action::Disconnect)),
+            _ => Err(format!("unsupported request `{command}`")),
+        };
+
+        let mut response = json!({
+            "type": "response",
+            "request_seq": request["seq"],
+            "command": command,
+        });
+        let action = match result {
+            Ok((body, action)) => {
+                response["success"] = true.into();
+                response["body"] = body;
+                action
+            }
+            Err(message) => {
+                response["success"] = false.into();
+                response["message"] = message.into();
+                Action::None
+            }
+        };
+        self.send(response)?;
+
+        if command == "initialize" {
+            self.event("initialized", json!({}))?;
+        }
+        Ok(action)
+    }
+
+    /// Reads the program of a `launch` request, which is run once the client is configured.
+    fn launch(&mut self, arguments: &Value) -> Result<(), String> {
+        if self.program.is_some() || self.launched {
+            return Err("a program was already launched".to_owned());
+        }
+        let program = arguments["program"]
+            .as_str()
+            .ok_or_else(|| "missing program".to_owned())?;
+        let source =
+            fs::read(program).map_err(|error| format!("could not read {program}: {error}"))?;
+        self.program = Some((program.to_owned(), source));
+        self.stop_on_entry = arguments["stopOnEntry"].as_bool().unwrap_or(false);
+        Ok(())
+    }
+
+    /// Reports a pause, then handles requests until the client resumes the script.
+    fn pause(&mut self, reason: PauseReason, context: &mut Context) -> io::Result<StepAction> {
+        let reason = match reason {
+            PauseReason::Breakpoint => "breakpoint",
+            PauseReason::Step if self.entry => "entry",
+            PauseReason::Step => "step",
+        };
+        self.entry = false;
+        self.event(
+            "stopped",
+            json!({ "reason": reason, "threadId": THREAD_ID, "allThreadsStopped": true }),
+        )?;
+
+        loop {
+            let request = match self.read_message()? {
+                Some(request) => request,
+                None => {
+                    self.disconnected = true;
+                    break;
+                }
+            };
+            match self.handle(&request, context)? {
+                Action::None => {}
+                Action::Resume(step) => {
+                    self.references.clear();
+                    return Ok(step);
+                }
+                Action::Disconnect => {
+                    self.disconnected = true;
+                    break;
+                }
+            }
+        }
+
+        context.interrupt_handle().terminate();
+        Ok(StepAction::Continue)
+    }
+
+    fn variables(&mut self, reference: usize, context: &mut Context) -> Result<Vec<Value>, String> {
+        let reference = reference
+            .checked_sub(1)
+            .and_then(|index| self.references.get(index));
+        let values = match reference {
+            Some(Reference::Frame(depth)) => {
+                let frame = context
+                    .debug_frame(*depth)
+                    .ok_or_else(|| format!("no frame at depth {depth}"))?;
+                let mut values = vec![("this".to_owned(), Some(frame.this().clone()))];
+                values.extend(
+                    frame
+                        .locals()
+                        .iter()
+                        .map(|(name, value)| (name.to_string(), value.clone())),
+                );
+                values
+            }
+            Some(Reference::Object(object)) => object
+                .borrow()
+                .properties()
+                .iter()
+                .map(|(key, property)| (key.to_string(), property.value().cloned()))
+                .collect(),
+            None => return Err("invalid variables reference".to_owned()),
+        };
+
+        Ok(values
+            .into_iter()
+            .map(|(name, value)| {
+                let (value, reference) = match value {
+                    Some(value) => (preview(&value), self.reference(&value)),
+                    None => ("<uninitialized>".to_owned(), 0),
+                };
+                json!({ "name": name, "value": value, "variablesReference": reference })
+            })
+            .collect())
+    }
+
+    /// Evaluates `expression`, in the scope of the frame at `depth` if given.
+    fn evaluate(
+        &mut self,
+        expression: &str,
+        depth: Option<usize>,
+        context: &mut Context,
+    ) -> Result<Value, String> {
+        let result = match depth {
+            Some(depth) => evaluate_in_frame(expression, depth, context),
+            None => context.eval(expression),
+        };
+
+        match result {
+            Ok(value) => Ok(json!({
+                "result": preview(&value),
+                "variablesReference": self.reference(&value),
+            })),
+            Err(error) => Err(format!("Uncaught {}", error.to_opaque(context).display())),
+        }
+    }
+
+    /// Returns the `variablesReference` of `value`, which is `0` if it can't be expanded.
+    fn reference(&mut self, value: &JsValue) -> usize {
+        match value.as_object() {
+            Some(object) => {
+                self.references.push(Reference::Object(object.clone()));
+                self.references.len()
+            }
+            None => 0,
+        }
+    }
+}
+
+/// Describes the call stack in the body of a `stackTrace` response.
+fn stack_trace(context: &Context) -> Value {
+    let frames = (0..context.frame_count())
+        .filter_map(|depth| context.debug_frame(depth).map(|frame| (depth, frame)))
+        .map(|(depth, frame)| {
+            // Code evaluated by the script, like with `eval`, has no source.
+            let source = (!frame.source().is_empty())
+                .then(|| dap_source(Path::new(frame.source().as_str())));
+            json!({
+                "id": depth,
+                "name": frame.name().to_string(),
+                "line": frame.line().unwrap_or_default(),
+                "column": 1,
+                "source": source,
+            })
+        })
+        .collect::<Vec<_>>();
+    json!({ "stackFrames": frames, "totalFrames": frames.len() })
+}
+
+/// Evaluates `expression` with the bindings of the frame at `depth`.
+///
+/// The bindings are passed by value to a function evaluating the expression, which returns
+/// them with the result. The bindings that changed are written back to the frame.
+fn evaluate_in_frame(
+    expression: &str,
+    depth: usize,
+    context: &mut Context,
+) -> JsResult<JsValue, JsError> {
+    let frame = context
+        .debug_frame(depth)
+        .ok_or_else(|| JsNativeError::range().with_message(format!("no frame at depth {depth}")))?;
+    let (names, values): (Vec<_>, Vec<_>) = frame
+        .locals()
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.clone().unwrap_or_default()))
+        .unzip();
+    let names = names.join(", ");
+    let source = format!("(function ({names}) {{ return [({expression}\n), [{names}]]; }})");
+
+    let function = context.eval(source)?;
+    let function = function
+        .as_callable()
+        .ok_or_else(|| JsNativeError::typ().with_message("not a function"))?;
+    let result = function.call(frame.this(), &values, context)?;
+    let result = result
+        .as_object()
+        .ok_or_else(|| JsNativeError::typ().with_message("not an object"))?;
+    let locals = result.get(1, context)?;
+    let locals = locals
+        .as_object()
+        .ok_or_else(|| JsNativeError::typ().with_message("not an object"))?;
+
+    for (index, (name, value)) in frame.locals().iter().enumerate() {
+        // Bindings that are not initialized yet can't be assigned.
+        if let Some(value) = value {
+            let new_value = locals.get(index, context)?;
+            if !JsValue::same_value(value, &new_value) {
+                context.set_frame_local(depth, name.as_str(), new_value);
+            }
+        }
+    }
+    Ok(result.get(0, context)?)
+}
+
+/// Forwards the hooks of the VM to the session.
+struct DapDebugger<R, W> {
+    session: Rc<RefCell<Session<R, W>>>,
+}
+
+impl<R, W> Debugger for DapDebugger<R, W>
+where
+    R: BufRead,
+    W: Write,
+{
+    fn on_statement(&mut self, _context: &mut Context, _line: u32) {
+        if OUTPUT.with(|queue| !queue.borrow().is_empty()) {
+            // A write error means the client is gone, which is handled on the next pause.
+            self.session.borrow_mut().flush_output().ok();
+        }
+    }
+
+    fn on_pause(&mut self, context: &mut Context, reason: PauseReason, _line: u32) -> StepAction {
+        let mut session = self.session.borrow_mut();
+        session.pause(reason, context).unwrap_or_else(|_| {
+            session.disconnected = true;
+            context.interrupt_handle().terminate();
+            StepAction::Continue
+        })
+    }
+}
+
+/// Describes a value in one line. Objects are described by their kind, and can be expanded.
+fn preview(value: &JsValue) -> String {
+    match value.as_object() {
+        Some(object) if object.is_callable() => "function".to_owned(),
+        Some(object) if object.is_array() => "Array".to_owned(),
+        Some(_) => "Object".to_owned(),
+        None => value.display().to_string(),
+    }
+}
+
+/// Describes the program as a DAP `Source`.
+fn dap_source(program: &Path) -> Value {
+    json!({
+        "name": program.file_name().map(|name| name.to_string_lossy()),
+        "path": program.to_string_lossy(),
+    })
+}
+
+/// Replaces the global `console`, which prints to stdout, by one sending `output` events.
+fn install_console(context: &mut Context) {
+    let console = ObjectInitializer::new(context)
+        .function(console_stdout, "log", 0)
+        .function(console_stdout, "info", 0)
+        .function(console_stdout, "debug", 0)
+        .function(console_stderr, "warn", 0)
+        .function(console_stderr, "error", 0)
+        .build();
+    context.register_global_property(
+        "console",
+        console,
+        Attribute::WRITABLE | Attribute::CONFIGURABLE,
+    );
+}
+
+fn console_stdout(_: &JsValue, args: &[JsValue], _: &mut Context) -> JsResult<JsValue> {
+    push_output("stdout", args);
+    Ok(JsValue::undefined())
+}
+
+fn console_stderr(_: &JsValue, args: &[JsValue], _: &mut Context) -> JsResult<JsValue> {
+    push_output("stderr", args);
+    Ok(JsValue::undefined())
+}
+
+fn push_output(category: &'static str, args: &[JsValue]) {
+    let mut output = args
+        .iter()
+        .map(|arg| match arg.as_string() {
+            Some(string) => string.to_string(),
+            None => arg.display().to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+    output.push('\n');
+    OUTPUT.with(|queue| queue.borrow_mut().push((category, output)));
+}
@@ -0,0 +1,212 @@
+use super::{serve, Session};
+use serde_json::{json, Value};
+use std::{env, fs, io::Cursor};
+
+/// Runs the server on the requests of `transcript`, returning the messages it sent.
+fn replay(transcript: &[Value]) -> Vec<Value> {
+    let mut input = Vec::new();
+    for (seq, request) in transcript.iter().enumerate() {
+        let mut request = request.clone();
+        request["seq"] = (seq + 1).into();
+        request["type"] = "request".into();
+        let body = request.to_string();
+        input.extend_from_slice(format!("Content-Length: {}\r\n\r\n{body}", body.len()).as_bytes());
+    }
+
+    let output = serve(Cursor::new(input), Vec::new()).expect("the server failed");
+    let mut session = Session::new(Cursor::new(output), Vec::new());
+    let mut messages = Vec::new();
+    while let Some(message) = session.read_message().expect("invalid message") {
+        messages.push(message);
+    }
+    messages
+}
+
+/// Returns the body of the response to the request with the sequence number `seq`.
+fn response(messages: &[Value], seq: u64) -> &Value {
+    let response = messages
+        .iter()
+        .find(|message| message["type"] == "response" && message["request_seq"] == seq)
+        .expect("missing response");
+    assert_eq!(response["success"], true, "{response}");
+    &response["body"]
+}
+
+/// Returns the error message of the failed response to the request with the sequence number
+/// `seq`.
+fn failure(messages: &[Value], seq: u64) -> &str {
+    let response = messages
+        .iter()
+        .find(|message| message["type"] == "response" && message["request_seq"] == seq)
+        .expect("missing response");
+    assert_eq!(response["success"], false, "{response}");
+    response["message"].as_str().unwrap_or_default()
+}
+
+/// Returns the bodies of the `event` events, in order.
+fn events<'a>(messages: &'a [Value], event: &str) -> Vec<&'a Value> {
+    messages
+        .iter()
+        .filter(|message| message["type"] == "event" && message["event"] == event)
+        .map(|message| &message["body"])
+        .collect()
+}
+
+#[test]
+fn dap_transcript() {
+    let program = env::temp_dir().join(format!("boa_dap_{}.js", std::process::id()));
+    fs::write(
+        &program,
+        r#"let a = 1;
+console.log("a is", a);
+let b = { c: 2 };
+a = a + b.c;
+"#,
+    )
+    .expect("could not write the program");
+    let path = program.to_string_lossy();
+
+    let messages = replay(&[
+        json!({ "command": "initialize", "arguments": { "adapterID": "boa" } }),
+        json!({ "command": "launch", "arguments": { "program": path } }),
+        json!({
+            "command": "setBreakpoints",
+            "arguments": { "source": { "path": path }, "breakpoints": [{ "line": 3 }] },
+        }),
+        json!({ "command": "configurationDone" }),
+        json!({ "command": "stackTrace", "arguments": { "threadId": 1 } }),
+        json!({ "command": "scopes", "arguments": { "frameId": 0 } }),
+        json!({ "command": "variables", "arguments": { "variablesReference": 1 } }),
+        json!({ "command": "evaluate", "arguments": { "expression": "a + 1", "frameId": 0 } }),
+        json!({ "command": "evaluate", "arguments": { "expression": "a = 5", "frameId": 0 } }),
+        json!({ "command": "next", "arguments": { "threadId": 1 } }),
+        json!({ "command": "evaluate", "arguments": { "expression": "a + b.c", "frameId": 0 } }),
+        json!({ "command": "continue", "arguments": { "threadId": 1 } }),
+        json!({ "command": "disconnect" }),
+    ]);
+    fs::remove_file(&program).expect("could not remove the program");
+
+    assert_eq!(events(&messages, "initialized").len(), 1);
+    assert_eq!(
+        response(&messages, 3)["breakpoints"],
+        json!([{ "verified": true, "line": 3 }])
+    );
+
+    let output = events(&messages, "output");
+    assert_eq!(output[0]["category"], "stdout");
+    assert_eq!(output[0]["output"], "a is 1\n");
+
+    let stopped = events(&messages, "stopped");
+    assert_eq!(stopped.len(), 2);
+    assert_eq!(stopped[0]["reason"], "breakpoint");
+    assert_eq!(stopped[1]["reason"], "step");
+
+    let frames = &response(&messages, 5)["stackFrames"];
+    assert_eq!(frames[0]["name"], "<main>");
+    assert_eq!(frames[0]["line"], 3);
+    assert_eq!(frames[0]["source"]["path"], path.as_ref());
+
+    let variables = response(&messages, 7)["variables"]
+        .as_array()
+        .expect("missing variables")
+        .iter()
+        .map(|variable| {
+            (
+                variable["name"].as_str().unwrap_or_default(),
+                variable["value"].as_str().unwrap_or_default(),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert!(variables.contains(&("this", "Object")));
+    assert!(variables.contains(&("a", "1")));
+    assert!(variables.contains(&("b", "<uninitialized>")));
+
+    assert_eq!(response(&messages, 8)["result"], "2");
+    // Assignments are written back to the frame.
+    assert_eq!(response(&messages, 9)["result"], "5");
+    assert_eq!(response(&messages, 11)["result"], "7");
+
+    assert_eq!(events(&messages, "terminated").len(), 1);
+    assert_eq!(events(&messages, "exited")[0]["exitCode"], 0);
+}
+
+#[test]
+fn dap_breakpoints_move_to_statements() {
+    let program = env::temp_dir().join(format!("boa_dap_lines_{}.js", std::process::id()));
+    fs::write(&program, "let a = 1;\n\n// Set a.\na = 2;\n").expect("could not write the program");
+    let path = program.to_string_lossy();
+
+    let messages = replay(&[
+        json!({ "command": "initialize", "arguments": { "adapterID": "boa" } }),
+        json!({ "command": "launch", "arguments": { "program": path } }),
+        json!({
+            "command": "setBreakpoints",
+            "arguments": {
+                "source": { "path": path },
+                "breakpoints": [{ "line": 2 }, { "line": 5 }],
+            },
+        }),
+        json!({ "command": "configurationDone" }),
+        json!({ "command": "stackTrace", "arguments": { "threadId": 1 } }),
+        json!({ "command": "continue", "arguments": { "threadId": 1 } }),
+        json!({ "command": "disconnect" }),
+    ]);
+    fs::remove_file(&program).expect("could not remove the program");
+
+    assert_eq!(
+        response(&messages, 3)["breakpoints"],
+        json!([
+            { "verified": true, "line": 4 },
+            { "verified": false, "line": 5, "message": "no statement at or after this line" },
+        ])
+    );
+    assert_eq!(events(&messages, "stopped").len(), 1);
+    assert_eq!(response(&messages, 5)["stackFrames"][0]["line"], 4);
+}
+
+#[test]
+fn dap_breakpoints_of_other_sources() {
+    let program = env::temp_dir().join(format!("boa_dap_other_{}.js", std::process::id()));
+    fs::write(&program, "let a = 1;\nlet b = 2;\n").expect("could not write the program");
+    let path = program.to_string_lossy();
+
+    let messages = replay(&[
+        json!({ "command": "initialize", "arguments": { "adapterID": "boa" } }),
+        json!({ "command": "launch", "arguments": { "program": path } }),
+        json!({
+            "command": "setBreakpoints",
+            "arguments": { "source": { "path": "other.js" }, "breakpoints": [{ "line": 2 }] },
+        }),
+        json!({ "command": "setBreakpoints", "arguments": { "breakpoints": [{ "line": 2 }] } }),
+        json!({ "command": "configurationDone" }),
+        json!({ "command": "disconnect" }),
+    ]);
+    fs::remove_file(&program).expect("could not remove the program");
+
+    assert_eq!(
+        response(&messages, 3)["breakpoints"][0]["verified"],
+        json!(false)
+    );
+    assert_eq!(failure(&messages, 4), "missing source path");
+    assert!(events(&messages, "stopped").is_empty());
+    assert_eq!(events(&messages, "exited")[0]["exitCode"], 0);
+}
+
+#[test]
+fn dap_launch_errors() {
+    let program = env::temp_dir().join(format!("boa_dap_missing_{}.js", std::process::id()));
+    let path = program.to_string_lossy();
+
+    let messages = replay(&[
+        json!({ "command": "initialize", "arguments": { "adapterID": "boa" } }),
+        json!({ "command": "launch", "arguments": {} }),
+        json!({ "command": "launch", "arguments": { "program": path } }),
+        json!({ "command": "configurationDone" }),
+        json!({ "command": "disconnect" }),
+    ]);
+
+    assert_eq!(failure(&messages, 2), "missing program");
+    assert!(failure(&messages, 3).starts_with("could not read"));
+    response(&messages, 4);
+    assert!(events(&messages, "exited").is_empty());
+}
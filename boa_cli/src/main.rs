@@ -59,11 +59,17 @@
     rustdoc::missing_doc_code_examples
 )]
 
-use boa_engine::{syntax::ast::node::StatementList, Context};
+use boa_engine::{syntax::ast::node::StatementList, vm::VmStats, Context};
 use clap::{ArgEnum, Parser};
 use colored::{Color, Colorize};
 use rustyline::{config::Config, error::ReadlineError, EditMode, Editor};
-use std::{fs::read, io, path::PathBuf};
+use std::{
+    fs::{read, write},
+    io,
+    path::PathBuf,
+    time::Duration,
+};
+mod dap;
 mod helper;
 
 #[cfg(all(target_arch = "x86_64", target_os = "linux", target_env = "gnu"))]
@@ -78,6 +84,9 @@ static CLI_HISTORY: &str = ".boa_history";
 
 const READLINE_COLOR: Color = Color::Cyan;
 
+/// Interval between the samples of the `--cpu-prof` profiler.
+const CPU_PROFILE_INTERVAL: Duration = Duration::from_micros(100);
+
 // Added #[allow(clippy::option_option)] because to StructOpt an Option<Option<T>>
 // is an optional argument that optionally takes a value ([--opt=[val]]).
 // https://docs.rs/structopt/0.3.11/structopt/#type-magic
@@ -97,9 +106,30 @@ struct Opt {
     #[clap(long = "trace", short = 't')]
     trace: bool,
 
+    /// Fold constant expressions and remove dead branches before compiling.
+    #[clap(long = "optimize", short = 'O')]
+    optimize: bool,
+
+    /// Record the coverage of the given files, and write it to OUTPUT in the LCOV format.
+    #[clap(long, value_name = "OUTPUT", parse(from_os_str))]
+    coverage: Option<PathBuf>,
+
+    /// Profile the given files, and write the profile to OUTPUT (by default `boa.cpuprofile`),
+    /// and its folded stacks next to it.
+    #[clap(long, value_name = "OUTPUT", require_equals = true)]
+    cpu_prof: Option<Option<PathBuf>>,
+
+    /// Count the executed instructions, and print the top opcodes and hottest functions on exit.
+    #[clap(long = "vm-stats")]
+    vm_stats: bool,
+
     /// Use vi mode in the REPL
     #[clap(long = "vi")]
     vi_mode: bool,
+
+    /// Run a Debug Adapter Protocol server over stdio, to debug scripts from an editor.
+    #[clap(long = "dap")]
+    dap: bool,
 }
 
 impl Opt {
@@ -183,11 +213,26 @@ where
 pub fn main() -> Result<(), std::io::Error> {
     let args = Opt::parse();
 
+    if args.dap {
+        return dap::run();
+    }
+
     let mut context = Context::default();
 
     // Trace Output
     context.set_trace(args.trace);
 
+    context.set_optimize(args.optimize);
+
+    context.set_vm_stats(args.vm_stats);
+
+    context.set_coverage(args.coverage.is_some());
+    let mut lcov = String::new();
+
+    if args.cpu_prof.is_some() {
+        context.start_cpu_profile(CPU_PROFILE_INTERVAL);
+    }
+
     for file in &args.files {
         let buffer = read(file)?;
 
@@ -198,9 +243,28 @@ pub fn main() -> Result<(), std::io::Error> {
         } else {
             match context.eval(&buffer) {
                 Ok(v) => println!("{}", v.display()),
-                Err(v) => eprintln!("Uncaught {}", v.display()),
+                Err(e) => eprintln!("Uncaught {}", e.to_opaque(&mut context).display()),
             }
         }
+
+        if args.coverage.is_some() {
+            lcov.push_str(&context.take_coverage().to_lcov(&file.to_string_lossy()));
+        }
+    }
+
+    if let Some(ref output) = args.coverage {
+        write(output, lcov)?;
+        context.set_coverage(false);
+    }
+
+    if let Some(ref output) = args.cpu_prof {
+        let output = output
+            .clone()
+            .unwrap_or_else(|| PathBuf::from("boa.cpuprofile"));
+        if let Some(profile) = context.stop_cpu_profile() {
+            write(&output, profile.to_cpuprofile().to_string())?;
+            write(output.with_extension("folded"), profile.to_folded())?;
+        }
     }
 
     if args.files.is_empty() {
@@ -237,11 +301,11 @@ pub fn main() -> Result<(), std::io::Error> {
                     } else {
                         match context.eval(line.trim_end()) {
                             Ok(v) => println!("{}", v.display()),
-                            Err(v) => {
+                            Err(e) => {
                                 eprintln!(
                                     "{}: {}",
                                     "Uncaught".red(),
-                                    v.display().to_string().red()
+                                    e.to_opaque(&mut context).display().to_string().red()
                                 );
                             }
                         }
@@ -260,5 +324,53 @@ pub fn main() -> Result<(), std::io::Error> {
             .expect("could not save CLI history");
     }
 
+    if args.vm_stats {
+        print_vm_stats(&context.vm_stats());
+    }
+
     Ok(())
 }
+
+/// Prints the most executed opcodes, opcode categories and functions to stderr.
+fn print_vm_stats(stats: &VmStats) {
+    const TOP: usize = 10;
+
+    eprintln!("{:-^60}", " VM Stats ");
+    eprintln!("Instructions executed: {}\n", stats.instructions);
+
+    eprintln!("{:<28} {:>14} {:>14}", "Opcode", "Count", "Time");
+    for opcode in stats.opcodes.iter().take(TOP) {
+        eprintln!(
+            "{:<28} {:>14} {:>12}μs",
+            opcode.opcode.as_str(),
+            opcode.count,
+            opcode.time.as_micros()
+        );
+    }
+
+    eprintln!("\n{:<28} {:>14} {:>14}", "Category", "Count", "Time");
+    for category in &stats.categories {
+        eprintln!(
+            "{:<28} {:>14} {:>12}μs",
+            category.category.as_str(),
+            category.count,
+            category.time.as_micros()
+        );
+    }
+
+    eprintln!("\n{:<28} {:>14}", "Function", "Instructions");
+    for function in stats.functions.iter().take(TOP) {
+        eprintln!("{:<28} {:>14}", function.name, function.instructions);
+    }
+
+    eprintln!("\n{:<28} {:>14}", "Hottest instruction", "Count");
+    for instruction in stats.hot_instructions.iter().take(TOP) {
+        let location = format!("{}@{:04}", instruction.function, instruction.pc);
+        eprintln!(
+            "{location:<28} {:>14} {} {}",
+            instruction.count,
+            instruction.opcode.as_str(),
+            instruction.operands
+        );
+    }
+}
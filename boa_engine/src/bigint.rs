@@ -78,6 +78,22 @@ impl JsBigInt {
         self.inner.to_f64().unwrap_or(f64::INFINITY)
     }
 
+    /// Converts the `BigInt` to an i128 type.
+    ///
+    /// Returns `None` if the `BigInt` is out of the range of `i128`.
+    #[inline]
+    pub fn to_i128(&self) -> Option<i128> {
+        self.inner.to_i128()
+    }
+
+    /// Converts the `BigInt` to a u128 type.
+    ///
+    /// Returns `None` if the `BigInt` is out of the range of `u128`.
+    #[inline]
+    pub fn to_u128(&self) -> Option<u128> {
+        self.inner.to_u128()
+    }
+
     /// Converts a string to a `BigInt` with the specified radix.
     #[inline]
     pub fn from_string_radix(buf: &str, radix: u32) -> Option<Self> {
@@ -370,6 +386,24 @@ impl From<u64> for JsBigInt {
     }
 }
 
+impl From<i128> for JsBigInt {
+    #[inline]
+    fn from(value: i128) -> Self {
+        Self {
+            inner: Rc::new(RawBigInt::from(value)),
+        }
+    }
+}
+
+impl From<u128> for JsBigInt {
+    #[inline]
+    fn from(value: u128) -> Self {
+        Self {
+            inner: Rc::new(RawBigInt::from(value)),
+        }
+    }
+}
+
 impl From<isize> for JsBigInt {
     #[inline]
     fn from(value: isize) -> Self {
@@ -13,7 +13,7 @@ use crate::{
     value::{IntegerOrInfinity, Numeric},
     Context, JsResult, JsValue,
 };
-use boa_gc::{Finalize, Trace};
+use boa_gc::{ExternalMemory, Finalize, Trace};
 use boa_profiler::Profiler;
 use num_traits::{Signed, ToPrimitive};
 use tap::{Conv, Pipe};
@@ -23,6 +23,8 @@ pub struct ArrayBuffer {
     pub array_buffer_data: Option<Vec<u8>>,
     pub array_buffer_byte_length: usize,
     pub array_buffer_detach_key: JsValue,
+    /// The memory of `array_buffer_data`, counted against the heap limit.
+    pub(crate) external_memory: ExternalMemory,
 }
 
 impl ArrayBuffer {
@@ -324,6 +326,10 @@ impl ArrayBuffer {
         let obj = context.construct_object();
         obj.set_prototype(prototype.into());
 
+        // The block is counted against the heap limit before it is allocated.
+        let external_memory = ExternalMemory::try_reserve(byte_length)
+            .ok_or_else(|| context.heap_limit_exceeded())?;
+
         // 2. Let block be ? CreateByteDataBlock(byteLength).
         let block = create_byte_data_block(byte_length, context)?;
 
@@ -333,6 +339,7 @@ impl ArrayBuffer {
             array_buffer_data: Some(block),
             array_buffer_byte_length: byte_length,
             array_buffer_detach_key: JsValue::Undefined,
+            external_memory,
         });
 
         // 5. Return obj.
@@ -1,4 +1,4 @@
-use crate::{forward, Context};
+use crate::{forward, property::Attribute, Context, JsValue};
 
 #[test]
 fn error_to_string() {
@@ -84,3 +84,129 @@ fn uri_error_to_string() {
         "\"URIError\""
     );
 }
+
+#[test]
+fn native_error_to_opaque() {
+    use crate::{JsError, JsNativeError, JsNativeErrorKind};
+
+    let mut context = Context::default();
+    let error: JsError = JsNativeError::range()
+        .with_message("too big")
+        .with_cause(JsNativeError::typ().with_message("not a number"))
+        .into();
+    assert_eq!(error.to_string(), "RangeError: too big");
+
+    let value = error.to_opaque(&mut context);
+    context.register_global_property("err", value.clone(), Attribute::all());
+    assert_eq!(forward(&mut context, "err instanceof RangeError"), "true");
+    assert_eq!(forward(&mut context, "err.message"), "\"too big\"");
+    assert_eq!(
+        forward(&mut context, "err.cause.toString()"),
+        "\"TypeError: not a number\""
+    );
+
+    let native = JsError::from_opaque(value)
+        .try_native(&mut context)
+        .expect("thrown value must be a native error");
+    assert_eq!(native.kind(), JsNativeErrorKind::Range);
+    assert_eq!(native.message(), "too big");
+    assert!(native.cause().is_some());
+
+    let thrown = JsError::from_opaque(JsValue::new(1));
+    assert_eq!(thrown.try_native(&mut context), None);
+    let thrown = context
+        .throw::<_, ()>(JsNativeError::syntax())
+        .expect_err("throw must return an error");
+    assert!(thrown
+        .as_object()
+        .expect("error must be an object")
+        .is_error());
+}
+
+#[test]
+fn erased_error() {
+    use crate::{JsErasedError, JsNativeErrorKind};
+    use std::error::Error;
+
+    fn assert_send_sync<T: Send + Sync + 'static>(_: &T) {}
+
+    let mut context = Context::default();
+    let error = context
+        .eval(
+            r#"
+            const inner = new RangeError("inner");
+            const outer = new TypeError("outer", { cause: inner });
+            inner.cause = outer;
+            throw outer;
+            "#,
+        )
+        .expect_err("script must throw");
+    let erased = error.into_erased(&mut context);
+    assert_send_sync(&erased);
+
+    let native = erased.as_native().expect("error must be native");
+    assert_eq!(native.kind(), JsNativeErrorKind::Type);
+    assert_eq!(erased.to_string(), "TypeError: outer");
+    let source = erased.source().expect("error must have a cause");
+    assert_eq!(source.to_string(), "RangeError: inner");
+
+    // The cycle of causes is cut.
+    let mut depth = 0;
+    let mut cause = native.cause();
+    while let Some(error) = cause {
+        depth += 1;
+        cause = error.as_native().and_then(|native| native.cause());
+    }
+    assert!(depth > 1 && depth <= 32);
+
+    let error = context
+        .eval("throw 'plain'")
+        .expect_err("script must throw");
+    assert_eq!(
+        error.into_erased(&mut context),
+        JsErasedError::Opaque("\"plain\"".to_owned())
+    );
+
+    let error = context.eval("(").expect_err("script must not parse");
+    assert_eq!(
+        error.as_native().map(crate::JsNativeError::kind),
+        Some(JsNativeErrorKind::Syntax)
+    );
+}
+
+#[test]
+fn termination_error() {
+    use crate::{
+        vm::{RuntimeLimits, TerminationReason},
+        JsErasedError,
+    };
+
+    let mut context = Context::default();
+    let mut limits = RuntimeLimits::default();
+    limits.set_loop_iteration_limit(10);
+    context.set_runtime_limits(limits);
+
+    let error = context
+        .eval("try { while (true) {} } catch (e) {}")
+        .expect_err("script must be terminated");
+    assert_eq!(
+        error.as_termination(),
+        Some(TerminationReason::LoopIterationLimit)
+    );
+    assert_eq!(error.as_opaque(), None);
+    assert_eq!(error.try_native(&mut context), None);
+    assert_eq!(
+        error.to_string(),
+        "execution terminated: loop iteration limit exceeded"
+    );
+
+    let thrown = error.to_opaque(&mut context);
+    assert!(thrown
+        .as_object()
+        .expect("error must be an object")
+        .is_error());
+    assert_eq!(
+        error.into_erased(&mut context),
+        JsErasedError::Termination(TerminationReason::LoopIterationLimit)
+    );
+}
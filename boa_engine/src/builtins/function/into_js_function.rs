@@ -0,0 +1,256 @@
+//! Native functions with typed parameters.
+//!
+//! Rust functions and `Copy` closures taking parameters that implement [`TryFromJs`], and
+//! returning a type that implements [`TryIntoJs`], implement [`IntoJsFunction`]. Their arguments
+//! are converted before calling them, throwing a `TypeError` if an argument has the wrong type.
+
+use super::{Captures, Function, NativeFunctionSignature};
+use crate::{
+    builtins::JsArgs,
+    value::{TryFromJs, TryIntoJs},
+    Context, JsResult, JsValue,
+};
+use std::{
+    any::Any,
+    mem::{self, size_of},
+};
+
+/// A Rust function that can be called from JavaScript.
+///
+/// This is implemented for:
+///  - functions with the [`NativeFunctionSignature`][super::NativeFunctionSignature],
+///    `fn(&JsValue, &[JsValue], &mut Context) -> JsResult<JsValue>`;
+///  - functions taking up to 8 [`JsFunctionParameter`]s, such as `i32`, `String` or
+///    [`This<T>`], and returning a [`TryIntoJs`] type;
+///  - the same functions, taking the `&mut Context` as their last parameter.
+///
+/// The functions must be `Copy`, like the closures of
+/// [`FunctionBuilder::closure`](crate::object::FunctionBuilder::closure), because the garbage
+/// collector can't trace the values they capture. Closures capturing garbage collected or
+/// non-`Copy` values can use
+/// [`FunctionBuilder::closure_with_captures`](crate::object::FunctionBuilder::closure_with_captures)
+/// instead.
+///
+/// `Args` only distinguishes the implementations, and is inferred.
+///
+/// # Examples
+///
+/// ```
+/// # use boa_engine::{Context, JsValue};
+/// let mut context = Context::default();
+///
+/// context.register_global_function("add", 2, |a: i32, b: i32| a + b);
+/// context.register_global_function("greet", 1, |name: Option<String>| {
+///     format!("Hello, {}!", name.as_deref().unwrap_or("world"))
+/// });
+///
+/// assert_eq!(context.eval("add(1, 2)").unwrap(), JsValue::new(3));
+/// assert_eq!(context.eval("greet()").unwrap(), JsValue::new("Hello, world!"));
+/// assert!(context.eval("add('1', 2)").is_err());
+/// ```
+pub trait IntoJsFunction<Args>: Copy + 'static {
+    /// The number of arguments taken by the function, which is its default `length`.
+    const LENGTH: usize;
+
+    /// Calls the function with `this` and the arguments of the call.
+    fn call_js(&self, this: &JsValue, args: &[JsValue], context: &mut Context)
+        -> JsResult<JsValue>;
+
+    /// Returns the function as a [`NativeFunctionSignature`], if it doesn't need to be boxed.
+    #[inline]
+    fn as_native(&self) -> Option<NativeFunctionSignature> {
+        None
+    }
+}
+
+/// Creates the [`Function`] of a typed native function.
+///
+/// Functions with the [`NativeFunctionSignature`] are stored as native functions, like with
+/// [`FunctionBuilder::native`](crate::object::FunctionBuilder::native), and the other functions
+/// are boxed as closures.
+pub(crate) fn typed_function<F, Args>(function: F) -> Function
+where
+    F: IntoJsFunction<Args>,
+{
+    match function.as_native() {
+        Some(function) => Function::Native {
+            function,
+            constructor: false,
+        },
+        None => Function::Closure {
+            function: Box::new(move |this, args, _, context| function.call_js(this, args, context)),
+            constructor: false,
+            captures: Captures::new(()),
+        },
+    }
+}
+
+/// Marks the implementation of [`IntoJsFunction`] for the [`NativeFunctionSignature`][sig].
+///
+/// [sig]: super::NativeFunctionSignature
+#[doc(hidden)]
+#[derive(Debug, Clone, Copy)]
+pub struct NativeArgs;
+
+/// Marks the implementations of [`IntoJsFunction`] for functions taking the `&mut Context` as
+/// their last parameter.
+#[doc(hidden)]
+#[derive(Debug, Clone, Copy)]
+pub struct WithContext;
+
+impl<F> IntoJsFunction<NativeArgs> for F
+where
+    F: Fn(&JsValue, &[JsValue], &mut Context) -> JsResult<JsValue> + Copy + 'static,
+{
+    const LENGTH: usize = 0;
+
+    #[inline]
+    fn call_js(
+        &self,
+        this: &JsValue,
+        args: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        self(this, args, context)
+    }
+
+    #[inline]
+    fn as_native(&self) -> Option<NativeFunctionSignature> {
+        if let Some(function) = (self as &dyn Any).downcast_ref::<NativeFunctionSignature>() {
+            Some(*function)
+        } else if size_of::<F>() == 0 {
+            // Function items and closures that don't capture anything.
+            Some(call_zero_sized::<F>)
+        } else {
+            None
+        }
+    }
+}
+
+/// Calls the zero-sized function `F`, which doesn't need to be stored.
+fn call_zero_sized<F>(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue>
+where
+    F: Fn(&JsValue, &[JsValue], &mut Context) -> JsResult<JsValue> + Copy + 'static,
+{
+    assert_eq!(size_of::<F>(), 0, "the function must be zero-sized");
+    // SAFETY: `F` is zero-sized, so its values don't have any bytes to initialize. A value of
+    // `F` was returned by `as_native`, and `F` is `Copy`, so creating another one is the same
+    // as copying it.
+    let function: F = unsafe { mem::zeroed() };
+    function(this, args, context)
+}
+
+/// A parameter of a typed native function.
+///
+/// Every [`TryFromJs`] type is a parameter taking the next argument of the call, `undefined` if
+/// the argument is missing. [`This<T>`] is a parameter taking the `this` value.
+pub trait JsFunctionParameter: Sized {
+    /// The number of arguments taken by the parameter.
+    const ARGUMENTS: usize = 1;
+
+    /// Converts the parameter, from `this` or from the argument at `index`, advancing `index`
+    /// past the arguments it takes.
+    fn from_arguments(
+        this: &JsValue,
+        args: &[JsValue],
+        index: &mut usize,
+        context: &mut Context,
+    ) -> JsResult<Self>;
+}
+
+impl<T: TryFromJs> JsFunctionParameter for T {
+    #[inline]
+    fn from_arguments(
+        _: &JsValue,
+        args: &[JsValue],
+        index: &mut usize,
+        context: &mut Context,
+    ) -> JsResult<Self> {
+        let value = args.get_or_undefined(*index);
+        *index += 1;
+        T::try_from_js(value, context)
+    }
+}
+
+/// The `this` value of a call, converted to `T`.
+///
+/// # Examples
+///
+/// ```
+/// # use boa_engine::{builtins::function::This, object::JsObject, Context, JsResult};
+/// fn get_answer(This(this): This<JsObject>, context: &mut Context) -> JsResult<bool> {
+///     this.has_property("answer", context)
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct This<T>(pub T);
+
+impl<T: TryFromJs> JsFunctionParameter for This<T> {
+    const ARGUMENTS: usize = 0;
+
+    #[inline]
+    fn from_arguments(
+        this: &JsValue,
+        _: &[JsValue],
+        _: &mut usize,
+        context: &mut Context,
+    ) -> JsResult<Self> {
+        T::try_from_js(this, context).map(This)
+    }
+}
+
+macro_rules! impl_into_js_function {
+    ($($param:ident $arg:ident),*) => {
+        impl<F, R, $($param,)*> IntoJsFunction<(R, ($($param,)*))> for F
+        where
+            F: Fn($($param),*) -> R + Copy + 'static,
+            R: TryIntoJs,
+            $($param: JsFunctionParameter,)*
+        {
+            const LENGTH: usize = 0 $(+ $param::ARGUMENTS)*;
+
+            #[allow(unused_variables, unused_mut)]
+            fn call_js(
+                &self,
+                this: &JsValue,
+                args: &[JsValue],
+                context: &mut Context,
+            ) -> JsResult<JsValue> {
+                let mut index = 0;
+                $(let $arg = $param::from_arguments(this, args, &mut index, context)?;)*
+                self($($arg),*).try_into_js(context)
+            }
+        }
+
+        impl<F, R, $($param,)*> IntoJsFunction<(R, ($($param,)*), WithContext)> for F
+        where
+            F: Fn($($param,)* &mut Context) -> R + Copy + 'static,
+            R: TryIntoJs,
+            $($param: JsFunctionParameter,)*
+        {
+            const LENGTH: usize = 0 $(+ $param::ARGUMENTS)*;
+
+            #[allow(unused_variables, unused_mut)]
+            fn call_js(
+                &self,
+                this: &JsValue,
+                args: &[JsValue],
+                context: &mut Context,
+            ) -> JsResult<JsValue> {
+                let mut index = 0;
+                $(let $arg = $param::from_arguments(this, args, &mut index, context)?;)*
+                self($($arg,)* context).try_into_js(context)
+            }
+        }
+    };
+}
+
+impl_into_js_function!();
+impl_into_js_function!(A a);
+impl_into_js_function!(A a, B b);
+impl_into_js_function!(A a, B b, C c);
+impl_into_js_function!(A a, B b, C c, D d);
+impl_into_js_function!(A a, B b, C c, D d, E e);
+impl_into_js_function!(A a, B b, C c, D d, E e, G g);
+impl_into_js_function!(A a, B b, C c, D d, E e, G g, H h);
+impl_into_js_function!(A a, B b, C c, D d, E e, G g, H h, I i);
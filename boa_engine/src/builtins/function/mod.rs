@@ -37,9 +37,15 @@ use std::{
 use tap::{Conv, Pipe};
 
 pub(crate) mod arguments;
+mod into_js_function;
 #[cfg(test)]
 mod tests;
 
+pub(crate) use into_js_function::typed_function;
+pub use into_js_function::{IntoJsFunction, JsFunctionParameter, This};
+#[doc(hidden)]
+pub use into_js_function::{NativeArgs, WithContext};
+
 /// Type representing a native built-in function a.k.a. function pointer.
 ///
 /// Native functions need to have this signature in order to
@@ -456,13 +462,9 @@ impl BuiltInFunctionObject {
         };
 
         match (function, name) {
-            (
-                Function::Native {
-                    function: _,
-                    constructor: _,
-                },
-                Some(name),
-            ) => Ok(format!("function {name}() {{\n  [native Code]\n}}").into()),
+            (Function::Native { .. } | Function::Closure { .. }, Some(name)) => {
+                Ok(format!("function {name}() {{\n  [native Code]\n}}").into())
+            }
             (Function::Ordinary { .. }, Some(name)) if name.is_empty() => {
                 Ok("[Function (anonymous)]".into())
             }
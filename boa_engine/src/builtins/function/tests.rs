@@ -260,3 +260,121 @@ fn closure_capture_clone() {
 
     assert_eq!(forward(&mut context, "closure()"), "\"Hello world!\"");
 }
+
+#[test]
+fn typed_function() {
+    use crate::{builtins::function::This, object::JsObject, JsError, JsNativeError, JsValue};
+
+    let mut context = Context::default();
+
+    context.register_global_function("add", 0, |a: i32, b: f64| f64::from(a) + b);
+    context.register_global_function(
+        "join",
+        0,
+        |parts: Vec<String>, separator: Option<String>| {
+            parts.join(separator.as_deref().unwrap_or(","))
+        },
+    );
+    context.register_global_function(
+        "getKey",
+        0,
+        |This(this): This<JsObject>, context: &mut Context| this.get("key", context),
+    );
+    context.register_global_function("check", 0, |valid: bool| -> Result<(), JsError> {
+        if valid {
+            Ok(())
+        } else {
+            Err(JsNativeError::range().with_message("invalid").into())
+        }
+    });
+
+    assert_eq!(forward(&mut context, "add(1, 0.5)"), "1.5");
+    assert_eq!(
+        forward(&mut context, "add('1', 0.5)"),
+        "Uncaught \"TypeError\": \"expected a number, got string\""
+    );
+    assert_eq!(
+        forward(&mut context, "add(1.5, 0.5)"),
+        "Uncaught \"RangeError\": \"1.5 is not an integer in the range of `i32`\""
+    );
+    assert_eq!(forward(&mut context, "join(['a', 'b'])"), "\"a,b\"");
+    assert_eq!(forward(&mut context, "join(['a', 'b'], '-')"), "\"a-b\"");
+    assert_eq!(forward(&mut context, "getKey.call({ key: 42 })"), "42");
+    assert_eq!(forward(&mut context, "check(true)"), "undefined");
+    assert_eq!(
+        forward(
+            &mut context,
+            "try { check(false) } catch (e) { e instanceof RangeError }"
+        ),
+        "true"
+    );
+
+    let function = FunctionBuilder::typed(&mut context, |a: i32, b: i32, c: i32| a * b * c)
+        .name("mul")
+        .build();
+    context.register_global_property("mul", function, Attribute::all());
+    assert_eq!(forward(&mut context, "mul.length"), "3");
+    assert_eq!(forward(&mut context, "mul(2, 3, 4)"), "24");
+    assert_eq!(
+        JsValue::undefined(),
+        forward_val(&mut context, "getKey.call({})").unwrap()
+    );
+}
+
+#[test]
+fn typed_function_storage() {
+    use crate::{
+        builtins::function::{Function, NativeFunctionSignature},
+        object::JsObject,
+        JsResult, JsValue,
+    };
+
+    #[allow(clippy::unnecessary_wraps)]
+    fn answer(_: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<JsValue> {
+        Ok(JsValue::new(42))
+    }
+
+    fn is_native(function: &JsObject) -> bool {
+        matches!(
+            function.borrow().as_function(),
+            Some(Function::Native { .. })
+        )
+    }
+
+    let mut context = Context::default();
+
+    let signature: NativeFunctionSignature = answer;
+    let function = FunctionBuilder::typed(&mut context, signature).build();
+    assert!(is_native(&function));
+    context.register_global_property("native", function, Attribute::all());
+    assert_eq!(forward(&mut context, "native()"), "42");
+
+    let function = FunctionBuilder::typed(&mut context, answer).build();
+    assert!(is_native(&function));
+    context.register_global_property("item", function, Attribute::all());
+    assert_eq!(forward(&mut context, "item()"), "42");
+
+    let function = FunctionBuilder::typed(
+        &mut context,
+        |_: &JsValue, args: &[JsValue], _: &mut Context| Ok(JsValue::new(args.len())),
+    )
+    .build();
+    assert!(is_native(&function));
+    context.register_global_property("count", function, Attribute::all());
+    assert_eq!(forward(&mut context, "count(1, 2)"), "2");
+
+    let offset = 10;
+    let function = FunctionBuilder::typed(
+        &mut context,
+        move |_: &JsValue, args: &[JsValue], _: &mut Context| Ok(JsValue::new(args.len() + offset)),
+    )
+    .build();
+    assert!(!is_native(&function));
+    context.register_global_property("shift", function, Attribute::all());
+    assert_eq!(forward(&mut context, "shift(1)"), "11");
+
+    // Functions with typed parameters are boxed, so they can read `this` when they are
+    // constructors.
+    let function = FunctionBuilder::typed(&mut context, |a: i32| a + 1).build();
+    assert!(!is_native(&function));
+}
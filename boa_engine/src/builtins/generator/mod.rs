@@ -255,6 +255,7 @@ impl Generator {
         );
         std::mem::swap(&mut context.vm.stack, &mut generator_context.stack);
         context.vm.push_frame(generator_context.call_frame.clone());
+        context.save_caller_environments(&generator_context.environments);
         if !first_execution {
             context.vm.push(value);
         }
@@ -371,6 +372,7 @@ impl Generator {
         );
         std::mem::swap(&mut context.vm.stack, &mut generator_context.stack);
         context.vm.push_frame(generator_context.call_frame.clone());
+        context.save_caller_environments(&generator_context.environments);
 
         let result = match abrupt_completion {
             Ok(value) => {
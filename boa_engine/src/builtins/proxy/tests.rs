@@ -0,0 +1,42 @@
+use crate::{forward, Context};
+
+#[test]
+fn set_trap_receives_key_and_receiver() {
+    let mut context = Context::default();
+    let init = r#"
+        var args;
+        var target = {};
+        var proxy = new Proxy(target, {
+            set(t, key, value, receiver) {
+                args = [t, key, value, receiver];
+                t[key] = value;
+                return true;
+            }
+        });
+        proxy.answer = 42;
+        "#;
+    forward(&mut context, init);
+    assert_eq!(forward(&mut context, "args[0] === target"), "true");
+    assert_eq!(forward(&mut context, "args[1]"), "\"answer\"");
+    assert_eq!(forward(&mut context, "args[2]"), "42");
+    assert_eq!(forward(&mut context, "args[3] === proxy"), "true");
+    assert_eq!(forward(&mut context, "target.answer"), "42");
+}
+
+#[test]
+fn set_trap_receives_explicit_receiver() {
+    let mut context = Context::default();
+    let init = r#"
+        var receiver;
+        var other = {};
+        var proxy = new Proxy({}, {
+            set(t, key, value, r) {
+                receiver = r;
+                return true;
+            }
+        });
+        Reflect.set(proxy, "answer", 42, other);
+        "#;
+    forward(&mut context, init);
+    assert_eq!(forward(&mut context, "receiver === other"), "true");
+}
@@ -1,6 +1,6 @@
 use crate::{
     builtins::function::ThisMode,
-    environments::BindingLocator,
+    environments::{BindingLocator, CompileTimeEnvironment},
     syntax::ast::{
         node::{
             declaration::{
@@ -11,12 +11,12 @@ use crate::{
             object::{MethodDefinition, PropertyDefinition, PropertyName},
             operator::assign::AssignTarget,
             template::TemplateElement,
-            Class, Declaration, GetConstField, GetField,
+            Class, Declaration, GetConstField, GetField, StatementList,
         },
         op::{AssignOp, BinOp, BitOp, CompOp, LogOp, NumOp, UnaryOp},
-        Const, Node,
+        Const, Node, Position,
     },
-    vm::{BindingOpcode, CodeBlock, Opcode},
+    vm::{BindingOpcode, CodeBlock, Opcode, ScopeInfo},
     Context, JsBigInt, JsResult, JsString, JsValue,
 };
 use boa_gc::Gc;
@@ -71,6 +71,8 @@ pub struct ByteCompiler<'b> {
     names_map: FxHashMap<Sym, u32>,
     bindings_map: FxHashMap<BindingLocator, u32>,
     jump_info: Vec<JumpControlInfo>,
+    /// Start of the scope of each compile time environment pushed by this compiler.
+    scope_starts: Vec<u32>,
     context: &'b mut Context,
 }
 
@@ -86,15 +88,49 @@ impl<'b> ByteCompiler<'b> {
             names_map: FxHashMap::default(),
             bindings_map: FxHashMap::default(),
             jump_info: Vec::new(),
+            scope_starts: Vec::new(),
             context,
         }
     }
 
+    /// Sets the name of the compiled source, which is inherited by the functions it contains.
+    #[inline]
+    pub(crate) fn set_source(&mut self, source: JsString) {
+        self.code_block.source = source;
+    }
+
     #[inline]
     fn interner(&self) -> &Interner {
         self.context.interner()
     }
 
+    /// Pushes a compile time environment, recording where its scope starts.
+    #[inline]
+    fn push_compile_time_environment(&mut self, function_scope: bool) {
+        let start = self.next_opcode_location();
+        self.scope_starts.push(start);
+        self.context.push_compile_time_environment(function_scope);
+    }
+
+    /// Pops a compile time environment, recording its bindings for debuggers.
+    fn pop_compile_time_environment(&mut self) -> CompileTimeEnvironment {
+        let environment_index = self.context.realm.compile_env.len() - 1;
+        let environment = self.context.pop_compile_time_environment();
+        let start = self
+            .scope_starts
+            .pop()
+            .expect("environment must have been pushed by this compiler");
+        let end = self.next_opcode_location();
+        self.code_block.scopes.push(ScopeInfo {
+            start,
+            end,
+            environment_index,
+            function_scope: environment.is_function_scope(),
+            bindings: environment.binding_names(),
+        });
+        environment
+    }
+
     #[inline]
     fn get_or_insert_literal(&mut self, literal: Literal) -> u32 {
         if let Some(index) = self.literals_map.get(&literal) {
@@ -256,9 +292,10 @@ impl<'b> ByteCompiler<'b> {
             return self.emit_opcode(Opcode::PushNegativeInfinity);
         }
 
-        // Check if the f64 value can fit in an i32.
+        // Check if the f64 value can fit in an i32. Negative zero can not be represented as an
+        // integer, so it has to be pushed as a rational.
         #[allow(clippy::float_cmp)]
-        if f64::from(value as i32) == value {
+        if f64::from(value as i32) == value && !(value == 0.0 && value.is_sign_negative()) {
             self.emit_push_integer(value as i32);
         } else {
             self.emit_opcode(Opcode::PushRational);
@@ -541,16 +578,29 @@ impl<'b> ByteCompiler<'b> {
     }
 
     #[inline]
-    pub fn compile_statement_list(&mut self, list: &[Node], use_expr: bool) -> JsResult<()> {
-        if let Some((last, items)) = list.split_last() {
-            for node in items {
-                self.compile_stmt(node, false)?;
-            }
-            self.compile_stmt(last, use_expr)?;
+    pub fn compile_statement_list(&mut self, list: &StatementList, use_expr: bool) -> JsResult<()> {
+        let positions = list.positions();
+        let count = list.items().len();
+        for (index, node) in list.items().iter().enumerate() {
+            if let Some(position) = positions.get(index) {
+                let pc = self.next_opcode_location();
+                self.code_block.add_position(pc, *position);
+            }
+            self.compile_stmt(node, use_expr && index + 1 == count)?;
         }
         Ok(())
     }
 
+    /// Compiles the body of a conditional or a loop. The position of a body that is not a block
+    /// is recorded here, since it is not part of a statement list.
+    fn compile_body(&mut self, body: &Node, position: Option<Position>) -> JsResult<()> {
+        if let Some(position) = position.filter(|_| !matches!(body, Node::Block(_))) {
+            let pc = self.next_opcode_location();
+            self.code_block.add_position(pc, position);
+        }
+        self.compile_stmt(body, false)
+    }
+
     #[inline]
     pub fn compile_expr(&mut self, expr: &Node, use_expr: bool) -> JsResult<()> {
         match expr {
@@ -1241,7 +1291,7 @@ impl<'b> ByteCompiler<'b> {
                     self.create_decls_from_stmt(node.body())?;
                 }
 
-                self.compile_stmt(node.body(), false)?;
+                self.compile_body(node.body(), node.body_position())?;
 
                 match node.else_node() {
                     None => {
@@ -1253,13 +1303,13 @@ impl<'b> ByteCompiler<'b> {
                         if !matches!(else_body, Node::Block(_)) {
                             self.create_decls_from_stmt(else_body)?;
                         }
-                        self.compile_stmt(else_body, false)?;
+                        self.compile_body(else_body, node.else_position())?;
                         self.patch_jump(exit);
                     }
                 }
             }
             Node::ForLoop(for_loop) => {
-                self.context.push_compile_time_environment(false);
+                self.push_compile_time_environment(false);
                 let push_env = self.jump_with_custom_opcode(Opcode::PushDeclarativeEnvironment);
 
                 if let Some(init) = for_loop.init() {
@@ -1290,7 +1340,7 @@ impl<'b> ByteCompiler<'b> {
                 if !matches!(for_loop.body(), Node::Block(_)) {
                     self.create_decls_from_stmt(for_loop.body())?;
                 }
-                self.compile_stmt(for_loop.body(), false)?;
+                self.compile_body(for_loop.body(), for_loop.body_position())?;
 
                 self.emit(Opcode::Jump, &[start_address]);
 
@@ -1298,7 +1348,7 @@ impl<'b> ByteCompiler<'b> {
                 self.pop_loop_control_info();
                 self.emit_opcode(Opcode::LoopEnd);
 
-                let num_bindings = self.context.pop_compile_time_environment().num_bindings();
+                let num_bindings = self.pop_compile_time_environment().num_bindings();
                 self.patch_jump_with_target(push_env, num_bindings as u32);
                 self.emit_opcode(Opcode::PopEnvironment);
             }
@@ -1311,7 +1361,7 @@ impl<'b> ByteCompiler<'b> {
                 self.push_loop_control_info_for_of_in_loop(for_in_loop.label(), start_address);
                 self.emit_opcode(Opcode::LoopContinue);
 
-                self.context.push_compile_time_environment(false);
+                self.push_compile_time_environment(false);
                 let push_env = self.jump_with_custom_opcode(Opcode::PushDeclarativeEnvironment);
                 let exit = self.jump_with_custom_opcode(Opcode::ForInLoopNext);
 
@@ -1366,9 +1416,9 @@ impl<'b> ByteCompiler<'b> {
                     }
                 }
 
-                self.compile_stmt(for_in_loop.body(), false)?;
+                self.compile_body(for_in_loop.body(), for_in_loop.body_position())?;
 
-                let num_bindings = self.context.pop_compile_time_environment().num_bindings();
+                let num_bindings = self.pop_compile_time_environment().num_bindings();
                 self.patch_jump_with_target(push_env, num_bindings as u32);
                 self.emit_opcode(Opcode::PopEnvironment);
 
@@ -1391,7 +1441,7 @@ impl<'b> ByteCompiler<'b> {
                 self.push_loop_control_info_for_of_in_loop(for_of_loop.label(), start_address);
                 self.emit_opcode(Opcode::LoopContinue);
 
-                self.context.push_compile_time_environment(false);
+                self.push_compile_time_environment(false);
                 let push_env = self.jump_with_custom_opcode(Opcode::PushDeclarativeEnvironment);
                 let exit = self.jump_with_custom_opcode(Opcode::ForInLoopNext);
 
@@ -1446,9 +1496,9 @@ impl<'b> ByteCompiler<'b> {
                     }
                 }
 
-                self.compile_stmt(for_of_loop.body(), false)?;
+                self.compile_body(for_of_loop.body(), for_of_loop.body_position())?;
 
-                let num_bindings = self.context.pop_compile_time_environment().num_bindings();
+                let num_bindings = self.pop_compile_time_environment().num_bindings();
                 self.patch_jump_with_target(push_env, num_bindings as u32);
                 self.emit_opcode(Opcode::PopEnvironment);
 
@@ -1468,7 +1518,7 @@ impl<'b> ByteCompiler<'b> {
 
                 self.compile_expr(while_.cond(), true)?;
                 let exit = self.jump_if_false();
-                self.compile_stmt(while_.body(), false)?;
+                self.compile_body(while_.body(), while_.body_position())?;
                 self.emit(Opcode::Jump, &[start_address]);
                 self.patch_jump(exit);
 
@@ -1483,14 +1533,13 @@ impl<'b> ByteCompiler<'b> {
                 self.push_loop_control_info(do_while.label(), start_address);
                 self.emit_opcode(Opcode::LoopContinue);
 
-                let condition_label_address = self.next_opcode_location();
                 self.compile_expr(do_while.cond(), true)?;
                 let exit = self.jump_if_false();
 
                 self.patch_jump(initial_label);
 
-                self.compile_stmt(do_while.body(), false)?;
-                self.emit(Opcode::Jump, &[condition_label_address]);
+                self.compile_body(do_while.body(), do_while.body_position())?;
+                self.emit(Opcode::Jump, &[start_address]);
                 self.patch_jump(exit);
 
                 self.pop_loop_control_info();
@@ -1622,11 +1671,11 @@ impl<'b> ByteCompiler<'b> {
                 }
             }
             Node::Block(block) => {
-                self.context.push_compile_time_environment(false);
+                self.push_compile_time_environment(false);
                 let push_env = self.jump_with_custom_opcode(Opcode::PushDeclarativeEnvironment);
                 self.create_declarations(block.items())?;
-                self.compile_statement_list(block.items(), use_expr)?;
-                let num_bindings = self.context.pop_compile_time_environment().num_bindings();
+                self.compile_statement_list(block.statement_list(), use_expr)?;
+                let num_bindings = self.pop_compile_time_environment().num_bindings();
                 self.patch_jump_with_target(push_env, num_bindings as u32);
                 self.emit_opcode(Opcode::PopEnvironment);
             }
@@ -1635,7 +1684,7 @@ impl<'b> ByteCompiler<'b> {
                 self.emit(Opcode::Throw, &[]);
             }
             Node::Switch(switch) => {
-                self.context.push_compile_time_environment(false);
+                self.push_compile_time_environment(false);
                 let push_env = self.jump_with_custom_opcode(Opcode::PushDeclarativeEnvironment);
                 for case in switch.cases() {
                     self.create_declarations(case.body().items())?;
@@ -1656,19 +1705,19 @@ impl<'b> ByteCompiler<'b> {
 
                 for (label, case) in labels.into_iter().zip(switch.cases()) {
                     self.patch_jump(label);
-                    self.compile_statement_list(case.body().items(), false)?;
+                    self.compile_statement_list(case.body(), false)?;
                 }
 
                 self.patch_jump(exit);
-                if let Some(body) = switch.default() {
-                    self.create_declarations(body)?;
+                if let Some(body) = switch.default_statement_list() {
+                    self.create_declarations(body.items())?;
                     self.compile_statement_list(body, false)?;
                 }
 
                 self.pop_switch_control_info();
 
                 self.emit_opcode(Opcode::LoopEnd);
-                let num_bindings = self.context.pop_compile_time_environment().num_bindings();
+                let num_bindings = self.pop_compile_time_environment().num_bindings();
                 self.patch_jump_with_target(push_env, num_bindings as u32);
                 self.emit_opcode(Opcode::PopEnvironment);
             }
@@ -1685,13 +1734,13 @@ impl<'b> ByteCompiler<'b> {
                 self.push_try_control_info(t.finally().is_some());
                 let try_start = self.next_opcode_location();
                 self.emit(Opcode::TryStart, &[Self::DUMMY_ADDRESS, 0]);
-                self.context.push_compile_time_environment(false);
+                self.push_compile_time_environment(false);
                 let push_env = self.jump_with_custom_opcode(Opcode::PushDeclarativeEnvironment);
 
                 self.create_declarations(t.block().items())?;
-                self.compile_statement_list(t.block().items(), use_expr)?;
+                self.compile_statement_list(t.block().statement_list(), use_expr)?;
 
-                let num_bindings = self.context.pop_compile_time_environment().num_bindings();
+                let num_bindings = self.pop_compile_time_environment().num_bindings();
                 self.patch_jump_with_target(push_env, num_bindings as u32);
                 self.emit_opcode(Opcode::PopEnvironment);
                 self.emit_opcode(Opcode::TryEnd);
@@ -1706,7 +1755,7 @@ impl<'b> ByteCompiler<'b> {
                     } else {
                         None
                     };
-                    self.context.push_compile_time_environment(false);
+                    self.push_compile_time_environment(false);
                     let push_env = self.jump_with_custom_opcode(Opcode::PushDeclarativeEnvironment);
                     if let Some(decl) = catch.parameter() {
                         match decl {
@@ -1726,9 +1775,9 @@ impl<'b> ByteCompiler<'b> {
                     }
 
                     self.create_declarations(catch.block().items())?;
-                    self.compile_statement_list(catch.block().items(), use_expr)?;
+                    self.compile_statement_list(catch.block().statement_list(), use_expr)?;
 
-                    let num_bindings = self.context.pop_compile_time_environment().num_bindings();
+                    let num_bindings = self.pop_compile_time_environment().num_bindings();
                     self.patch_jump_with_target(push_env, num_bindings as u32);
                     self.emit_opcode(Opcode::PopEnvironment);
                     if let Some(catch_start) = catch_start {
@@ -1754,13 +1803,13 @@ impl<'b> ByteCompiler<'b> {
                         finally_start_address,
                     );
 
-                    self.context.push_compile_time_environment(false);
+                    self.push_compile_time_environment(false);
                     let push_env = self.jump_with_custom_opcode(Opcode::PushDeclarativeEnvironment);
 
                     self.create_declarations(finally.items())?;
-                    self.compile_statement_list(finally.items(), false)?;
+                    self.compile_statement_list(finally.statement_list(), false)?;
 
-                    let num_bindings = self.context.pop_compile_time_environment().num_bindings();
+                    let num_bindings = self.pop_compile_time_environment().num_bindings();
                     self.patch_jump_with_target(push_env, num_bindings as u32);
                     self.emit_opcode(Opcode::PopEnvironment);
 
@@ -1833,6 +1882,7 @@ impl<'b> ByteCompiler<'b> {
         let strict = body.strict() || self.code_block.strict;
         let length = parameters.length();
         let mut code = CodeBlock::new(name.unwrap_or(Sym::EMPTY_STRING), length, strict, true);
+        code.source = self.code_block.source.clone();
 
         if let FunctionKind::Arrow = kind {
             code.constructor = false;
@@ -1849,10 +1899,11 @@ impl<'b> ByteCompiler<'b> {
             names_map: FxHashMap::default(),
             bindings_map: FxHashMap::default(),
             jump_info: Vec::new(),
+            scope_starts: Vec::new(),
             context: self.context,
         };
 
-        compiler.context.push_compile_time_environment(true);
+        compiler.push_compile_time_environment(true);
 
         // An arguments object is added when all of the following conditions are met
         // - If not in an arrow function (10.2.11.16)
@@ -1900,7 +1951,7 @@ impl<'b> ByteCompiler<'b> {
 
         let env_label = if parameters.has_expressions() {
             compiler.code_block.num_bindings = compiler.context.get_binding_number();
-            compiler.context.push_compile_time_environment(true);
+            compiler.push_compile_time_environment(true);
             Some(compiler.jump_with_custom_opcode(Opcode::PushFunctionEnvironment))
         } else {
             None
@@ -1913,20 +1964,15 @@ impl<'b> ByteCompiler<'b> {
         }
 
         compiler.create_declarations(body.items())?;
-        compiler.compile_statement_list(body.items(), false)?;
+        compiler.compile_statement_list(body, false)?;
 
         if let Some(env_label) = env_label {
-            let num_bindings = compiler
-                .context
-                .pop_compile_time_environment()
-                .num_bindings();
+            let num_bindings = compiler.pop_compile_time_environment().num_bindings();
             compiler.patch_jump_with_target(env_label, num_bindings as u32);
-            compiler.context.pop_compile_time_environment();
+            compiler.pop_compile_time_environment();
         } else {
-            compiler.code_block.num_bindings = compiler
-                .context
-                .pop_compile_time_environment()
-                .num_bindings();
+            compiler.code_block.num_bindings =
+                compiler.pop_compile_time_environment().num_bindings();
         }
 
         compiler.code_block.params = parameters.clone();
@@ -2390,16 +2436,18 @@ impl<'b> ByteCompiler<'b> {
     /// A class expression leaves the resulting class object on the stack for following operations.
     fn class(&mut self, class: &Class, expression: bool) -> JsResult<()> {
         let mut code = CodeBlock::new(class.name(), 0, true, true);
-        code.computed_field_names = Some(gc::GcCell::new(vec![]));
+        code.source = self.code_block.source.clone();
+        code.computed_field_names = Some(boa_gc::Cell::new(vec![]));
         let mut compiler = ByteCompiler {
             code_block: code,
             literals_map: FxHashMap::default(),
             names_map: FxHashMap::default(),
             bindings_map: FxHashMap::default(),
             jump_info: Vec::new(),
+            scope_starts: Vec::new(),
             context: self.context,
         };
-        compiler.context.push_compile_time_environment(true);
+        compiler.push_compile_time_environment(true);
 
         for element in class.elements() {
             match element {
@@ -2481,31 +2529,25 @@ impl<'b> ByteCompiler<'b> {
             }
             let env_label = if expr.parameters().has_expressions() {
                 compiler.code_block.num_bindings = compiler.context.get_binding_number();
-                compiler.context.push_compile_time_environment(true);
+                compiler.push_compile_time_environment(true);
                 Some(compiler.jump_with_custom_opcode(Opcode::PushFunctionEnvironment))
             } else {
                 None
             };
             compiler.create_declarations(expr.body().items())?;
-            compiler.compile_statement_list(expr.body().items(), false)?;
+            compiler.compile_statement_list(expr.body(), false)?;
             if let Some(env_label) = env_label {
-                let num_bindings = compiler
-                    .context
-                    .pop_compile_time_environment()
-                    .num_bindings();
+                let num_bindings = compiler.pop_compile_time_environment().num_bindings();
                 compiler.patch_jump_with_target(env_label, num_bindings as u32);
-                compiler.context.pop_compile_time_environment();
+                compiler.pop_compile_time_environment();
             } else {
-                compiler.code_block.num_bindings = compiler
-                    .context
-                    .pop_compile_time_environment()
-                    .num_bindings();
+                compiler.code_block.num_bindings =
+                    compiler.pop_compile_time_environment().num_bindings();
             }
         } else {
-            compiler.code_block.num_bindings = compiler
-                .context
-                .pop_compile_time_environment()
-                .num_bindings();
+            compiler.code_block.num_bindings =
+                compiler.pop_compile_time_environment().num_bindings();
+            compiler.code_block.derived_default_constructor = class.super_ref().is_some();
         }
 
         compiler.emit_opcode(Opcode::PushUndefined);
@@ -2652,13 +2694,12 @@ impl<'b> ByteCompiler<'b> {
                 ClassElement::StaticBlock(statement_list) => {
                     self.emit_opcode(Opcode::Dup);
                     let mut compiler = ByteCompiler::new(Sym::EMPTY_STRING, true, self.context);
-                    compiler.context.push_compile_time_environment(true);
+                    compiler.code_block.source = self.code_block.source.clone();
+                    compiler.push_compile_time_environment(true);
                     compiler.create_declarations(statement_list.items())?;
-                    compiler.compile_statement_list(statement_list.items(), false)?;
-                    compiler.code_block.num_bindings = compiler
-                        .context
-                        .pop_compile_time_environment()
-                        .num_bindings();
+                    compiler.compile_statement_list(statement_list, false)?;
+                    compiler.code_block.num_bindings =
+                        compiler.pop_compile_time_environment().num_bindings();
 
                     let code = Gc::new(compiler.finish());
                     let index = self.code_block.functions.len() as u32;
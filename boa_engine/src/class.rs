@@ -5,8 +5,9 @@
 //!# use boa_engine::{
 //!#    property::Attribute,
 //!#    class::{Class, ClassBuilder},
+//!#    object::JsObject,
 //!#    Context, JsResult, JsValue,
-//!#    builtins::JsArgs,
+//!#    builtins::{function::This, JsArgs},
 //!# };
 //!# use boa_gc::{Finalize, Trace};
 //!#
@@ -41,17 +42,15 @@
 //!
 //!     /// This is where the object is intitialized.
 //!     fn init(class: &mut ClassBuilder) -> JsResult<()> {
-//!         class.method("speak", 0, |this, _args, _ctx| {
-//!             if let Some(object) = this.as_object() {
-//!                 if let Some(animal) = object.downcast_ref::<Animal>() {
-//!                     match &*animal {
-//!                         Self::Cat => println!("meow"),
-//!                         Self::Dog => println!("woof"),
-//!                         Self::Other => println!(r"¯\_(ツ)_/¯"),
-//!                     }
+//!         // The `this` of the call is converted to an object, or a `TypeError` is thrown.
+//!         class.method("speak", 0, |This(this): This<JsObject>| {
+//!             if let Some(animal) = this.downcast_ref::<Animal>() {
+//!                 match &*animal {
+//!                     Self::Cat => println!("meow"),
+//!                     Self::Dog => println!("woof"),
+//!                     Self::Other => println!(r"¯\_(ツ)_/¯"),
 //!                 }
 //!             }
-//!             Ok(JsValue::undefined())
 //!         });
 //!
 //!         Ok(())
@@ -62,13 +61,123 @@
 //! [class-trait]: ./trait.Class.html
 
 use crate::{
-    builtins::function::NativeFunctionSignature,
-    object::{ConstructorBuilder, JsFunction, JsObject, NativeObject, ObjectData, PROTOTYPE},
+    builtins::function::IntoJsFunction,
+    context::intrinsics::StandardConstructor,
+    object::{
+        internal_methods::get_prototype_from_constructor_or_else, ConstructorBuilder,
+        FunctionBuilder, InternalObjectMethods, JsFunction, JsObject, NativeObject, ObjectData,
+        ORDINARY_INTERNAL_METHODS, PROTOTYPE,
+    },
     property::{Attribute, PropertyDescriptor, PropertyKey},
     Context, JsResult, JsValue,
 };
+use std::any::TypeId;
+
+/// Implements [`Class`] for the type of an `impl` block, exposing its functions to JavaScript.
+///
+/// The functions are converted like typed native functions, see [`IntoJsFunction`]: their
+/// parameters are converted from the arguments of the call, the `&mut Context` can be taken as
+/// the last parameter, and their return value is converted to a `JsValue`.
+///
+/// - `#[constructor]` marks the constructor of the class, which returns `Self` or a `Result` of
+///   `Self`. A class without constructor throws a `TypeError` when constructed.
+/// - `#[method]` marks a method of the prototype if the function takes `&self` or `&mut self`,
+///   or a static method otherwise.
+/// - `#[getter]` and `#[setter]` mark the functions of an accessor property of the prototype.
+///   The name of a setter must start with `set_`, which is removed from the name of its
+///   property.
+/// - `#[boa(rename = "name")]` sets the name of a method or accessor, which is the name of the
+///   function otherwise, and `#[boa(skip)]` doesn't expose a function.
+///
+/// Functions without any of these attributes are not exposed.
+///
+/// The name of the class is the name of the type, or the one set with
+/// `#[boa_class(name = "name")]`. `#[boa_class(extends = "Parent")]` sets the
+/// [`PARENT`][Class::PARENT] of the class to the native class `Parent`. The type must then
+/// implement `AsRef<Parent>` and `AsMut<Parent>`, which implement [`Class::upcast`] so the
+/// methods of `Parent` can be called on the instances of the class.
+///
+/// Methods and accessors throw a `TypeError` if `this` is not an object of the class. The native
+/// data of `this` stays borrowed while they run, so they can't take the `&mut Context`, which
+/// could call back into a method of the same object:
+///
+/// ```compile_fail
+/// # use boa_engine::{class::boa_class, Context};
+/// # use boa_gc::{Finalize, Trace};
+/// # #[derive(Debug, Trace, Finalize)]
+/// # struct Counter;
+/// #[boa_class]
+/// impl Counter {
+///     #[method]
+///     fn log(&self, context: &mut Context) {}
+/// }
+/// ```
+///
+/// # Examples
+///
+/// ```
+/// # use boa_engine::{class::boa_class, Context, JsValue};
+/// # use boa_gc::{Finalize, Trace};
+/// #[derive(Debug, Trace, Finalize)]
+/// struct Counter {
+///     count: u32,
+/// }
+///
+/// #[boa_class]
+/// impl Counter {
+///     #[constructor]
+///     fn new(start: Option<u32>) -> Self {
+///         Self {
+///             count: start.unwrap_or_default(),
+///         }
+///     }
+///
+///     #[getter]
+///     fn count(&self) -> u32 {
+///         self.count
+///     }
+///
+///     #[setter]
+///     fn set_count(&mut self, count: u32) {
+///         self.count = count;
+///     }
+///
+///     #[method]
+///     fn increment(&mut self, by: u32) -> u32 {
+///         self.count += by;
+///         self.count
+///     }
+///
+///     #[method]
+///     #[boa(rename = "isCounter")]
+///     fn is_counter(value: JsValue) -> bool {
+///         value.as_object().map_or(false, |object| object.is::<Self>())
+///     }
+/// }
+///
+/// let mut context = Context::default();
+/// context.register_global_class::<Counter>().unwrap();
+///
+/// let count = context
+///     .eval("let counter = new Counter(1); counter.increment(2); counter.count *= 2; counter.count")
+///     .unwrap();
+/// assert_eq!(count, JsValue::new(6));
+/// assert_eq!(context.eval("Counter.isCounter(counter)").unwrap(), JsValue::new(true));
+/// assert!(context.eval("Counter.prototype.increment.call({}, 1)").is_err());
+/// ```
+pub use boa_engine_derive::boa_class;
+
+/// The class a native class inherits from, as a function returning its constructor.
+///
+/// This is usually [`Context::class_constructor::<T>`][Context::class_constructor] for a native
+/// class `T`.
+pub type ClassParent = fn(&mut Context) -> JsResult<JsFunction>;
 
 /// Native class.
+///
+/// Native classes can be extended by JavaScript classes: the native data of the instances of
+/// `class Dog extends Animal {}` is created by the `constructor` of `Animal`, and their prototype
+/// is `Dog.prototype`.
 pub trait Class: NativeObject + Sized {
     /// The binding name of the object.
     const NAME: &'static str;
@@ -76,12 +185,119 @@ pub trait Class: NativeObject + Sized {
     const LENGTH: usize = 0;
     /// The attibutes the class will be binded with, default is `writable`, `enumerable`, `configurable`.
     const ATTRIBUTES: Attribute = Attribute::all();
+    /// The class this class inherits from, default is `None`.
+    ///
+    /// The prototype of the class inherits from the prototype of the parent, and the class
+    /// inherits the static methods of the parent. The native data of the instances is still of
+    /// this type, so the methods of a native parent only work on them if the class gives access to
+    /// the native data of the parent with [`upcast`][Class::upcast].
+    ///
+    /// ```
+    /// # use boa_engine::{
+    /// #     builtins::function::This,
+    /// #     class::{Class, ClassBuilder, ClassParent},
+    /// #     object::{JsObject, NativeObject},
+    /// #     Context, JsResult, JsValue,
+    /// # };
+    /// # use boa_gc::{Finalize, Trace};
+    /// # use std::any::TypeId;
+    /// # #[derive(Debug, Trace, Finalize)]
+    /// # struct Animal {
+    /// #     legs: u32,
+    /// # }
+    /// # impl Class for Animal {
+    /// #     const NAME: &'static str = "Animal";
+    /// #     fn constructor(_: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<Self> {
+    /// #         Ok(Self { legs: 2 })
+    /// #     }
+    /// #     fn init(class: &mut ClassBuilder<'_>) -> JsResult<()> {
+    /// #         class.method("legs", 0, |This(this): This<JsObject>| {
+    /// #             this.downcast_ref::<Animal>().map(|animal| animal.legs)
+    /// #         });
+    /// #         Ok(())
+    /// #     }
+    /// # }
+    /// #[derive(Debug, Trace, Finalize)]
+    /// struct Dog {
+    ///     animal: Animal,
+    /// }
+    ///
+    /// impl Class for Dog {
+    ///     const NAME: &'static str = "Dog";
+    ///     const PARENT: Option<ClassParent> = Some(Context::class_constructor::<Animal>);
+    ///
+    ///     fn upcast(&self, ancestor: TypeId) -> Option<&dyn NativeObject> {
+    ///         if ancestor == TypeId::of::<Animal>() {
+    ///             Some(&self.animal)
+    ///         } else {
+    ///             None
+    ///         }
+    ///     }
+    ///
+    ///     fn upcast_mut(&mut self, ancestor: TypeId) -> Option<&mut dyn NativeObject> {
+    ///         if ancestor == TypeId::of::<Animal>() {
+    ///             Some(&mut self.animal)
+    ///         } else {
+    ///             None
+    ///         }
+    ///     }
+    ///
+    ///     // ...
+    /// #     fn constructor(_: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<Self> {
+    /// #         Ok(Self { animal: Animal { legs: 4 } })
+    /// #     }
+    /// #     fn init(_: &mut ClassBuilder<'_>) -> JsResult<()> {
+    /// #         Ok(())
+    /// #     }
+    /// }
+    ///
+    /// let mut context = Context::default();
+    /// context.register_global_class::<Animal>().unwrap();
+    /// context.register_global_class::<Dog>().unwrap();
+    ///
+    /// let value = context.eval("new Dog() instanceof Animal").unwrap();
+    /// assert_eq!(value, JsValue::new(true));
+    /// let legs = context.eval("new Dog().legs()").unwrap();
+    /// assert_eq!(legs, JsValue::new(4));
+    /// ```
+    const PARENT: Option<ClassParent> = None;
 
     /// The constructor of the class.
     fn constructor(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<Self>;
 
     /// Initializes the internals and the methods of the class.
     fn init(class: &mut ClassBuilder<'_>) -> JsResult<()>;
+
+    /// The internal methods of the instances of the class, default is
+    /// [`ORDINARY_INTERNAL_METHODS`].
+    ///
+    /// Overriding methods such as `__get_own_property__` and `__own_property_keys__` lets the
+    /// instances expose their native data as properties, see [`InternalObjectMethods`].
+    #[inline]
+    fn internal_methods() -> &'static InternalObjectMethods {
+        &ORDINARY_INTERNAL_METHODS
+    }
+
+    /// Returns the native data of the ancestor class of type `ancestor`, default is `None`.
+    ///
+    /// [`JsObject::downcast_ref`] and [`JsObject::is`] use this on the instances of the class, so
+    /// the methods of its native ancestors can access their own native data. A class should
+    /// return its [`PARENT`][Class::PARENT] data, or the result of the `upcast` of its parent
+    /// data for the other ancestors.
+    #[inline]
+    fn upcast(&self, _ancestor: TypeId) -> Option<&dyn NativeObject> {
+        None
+    }
+
+    /// Returns the native data of the ancestor class of type `ancestor` mutably, default is
+    /// `None`.
+    ///
+    /// This is used by [`JsObject::downcast_mut`], and must be consistent with
+    /// [`upcast`][Class::upcast].
+    #[inline]
+    fn upcast_mut(&mut self, _ancestor: TypeId) -> Option<&mut dyn NativeObject> {
+        None
+    }
 }
 
 /// This is a wrapper around `Class::constructor` that sets the internal data of a class.
@@ -110,41 +326,17 @@ impl<T: Class> ClassConstructor for T {
             ));
         }
 
-        let class_constructor = context.global_object().clone().get(T::NAME, context)?;
-        let class_constructor = if let JsValue::Object(ref obj) = class_constructor {
-            obj
-        } else {
-            return context.throw_type_error(format!(
-                "invalid constructor for native class `{}` ",
-                T::NAME
-            ));
-        };
-        let class_prototype =
-            if let JsValue::Object(ref obj) = class_constructor.get(PROTOTYPE, context)? {
-                obj.clone()
-            } else {
-                return context.throw_type_error(format!(
-                    "invalid default prototype for native class `{}`",
-                    T::NAME
-                ));
-            };
-
-        let prototype = this
-            .as_object()
-            .cloned()
-            .map(|obj| {
-                obj.get(PROTOTYPE, context)
-                    .map(|val| val.as_object().cloned())
-            })
-            .transpose()?
-            .flatten()
-            .unwrap_or(class_prototype);
+        // `this` is the `new.target` of the call, which is a subclass of this class when
+        // constructing an instance of a JavaScript class that extends it.
+        let prototype = get_prototype_from_constructor_or_else(
+            this,
+            |context| Ok(context.native_class::<T>()?.prototype()),
+            context,
+        )?;
 
         let native_instance = Self::constructor(this, args, context)?;
-        let object_instance = JsObject::from_proto_and_data(
-            prototype,
-            ObjectData::native_object(Box::new(native_instance)),
-        );
+        let object_instance =
+            JsObject::from_proto_and_data(prototype, ObjectData::class_instance(native_instance));
         Ok(object_instance.into())
     }
 }
@@ -156,15 +348,44 @@ pub struct ClassBuilder<'context> {
 }
 
 impl<'context> ClassBuilder<'context> {
+    /// Creates the builder of the class `T`, whose constructor and prototype are the objects of
+    /// `class`.
     #[inline]
-    pub(crate) fn new<T>(context: &'context mut Context) -> Self
+    pub(crate) fn new<T>(
+        context: &'context mut Context,
+        class: StandardConstructor,
+    ) -> JsResult<Self>
     where
         T: ClassConstructor,
     {
-        let mut builder = ConstructorBuilder::new(context, T::raw_constructor);
+        let parent = match T::PARENT {
+            Some(parent) => {
+                let parent = parent(context)?;
+                let prototype = parent.get(PROTOTYPE, context)?;
+                let prototype = match prototype {
+                    JsValue::Object(ref prototype) => Some(prototype.clone()),
+                    JsValue::Null => None,
+                    _ => {
+                        return context.throw_type_error(format!(
+                            "the parent of native class `{}` has no prototype",
+                            T::NAME
+                        ))
+                    }
+                };
+                Some((JsObject::from(parent), prototype))
+            }
+            None => None,
+        };
+
+        let mut builder =
+            ConstructorBuilder::with_standard_constructor(context, T::raw_constructor, class);
         builder.name(T::NAME);
         builder.length(T::LENGTH);
-        Self { builder }
+        if let Some((constructor, prototype)) = parent {
+            builder.custom_prototype(constructor);
+            builder.inherit(prototype);
+        }
+        Ok(Self { builder })
     }
 
     #[inline]
@@ -174,35 +395,44 @@ impl<'context> ClassBuilder<'context> {
 
     /// Add a method to the class.
     ///
-    /// It is added to `prototype`.
+    /// It is added to `prototype`. The method can have typed parameters, see [`IntoJsFunction`].
     #[inline]
-    pub fn method<N>(
-        &mut self,
-        name: N,
-        length: usize,
-        function: NativeFunctionSignature,
-    ) -> &mut Self
+    pub fn method<N, F, Args>(&mut self, name: N, length: usize, function: F) -> &mut Self
     where
         N: AsRef<str>,
+        F: IntoJsFunction<Args>,
     {
-        self.builder.method(function, name.as_ref(), length);
+        let function = FunctionBuilder::typed(self.builder.context(), function)
+            .name(name.as_ref())
+            .length(length)
+            .build();
+        self.builder.property(
+            name.as_ref(),
+            function,
+            Attribute::WRITABLE | Attribute::NON_ENUMERABLE | Attribute::CONFIGURABLE,
+        );
         self
     }
 
     /// Add a static method to the class.
     ///
-    /// It is added to class object itself.
+    /// It is added to class object itself. The method can have typed parameters, see
+    /// [`IntoJsFunction`].
     #[inline]
-    pub fn static_method<N>(
-        &mut self,
-        name: N,
-        length: usize,
-        function: NativeFunctionSignature,
-    ) -> &mut Self
+    pub fn static_method<N, F, Args>(&mut self, name: N, length: usize, function: F) -> &mut Self
     where
         N: AsRef<str>,
+        F: IntoJsFunction<Args>,
     {
-        self.builder.static_method(function, name.as_ref(), length);
+        let function = FunctionBuilder::typed(self.builder.context(), function)
+            .name(name.as_ref())
+            .length(length)
+            .build();
+        self.builder.static_property(
+            name.as_ref(),
+            function,
+            Attribute::WRITABLE | Attribute::NON_ENUMERABLE | Attribute::CONFIGURABLE,
+        );
         self
     }
 
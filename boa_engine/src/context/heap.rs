@@ -0,0 +1,130 @@
+//! Heap limit and garbage collector statistics of a `Context`.
+
+use crate::{object::Object, vm::TerminationReason, Context, JsResult, JsString, JsValue};
+use boa_gc::GcStatistics;
+use std::collections::BTreeMap;
+
+/// What happens when a script exceeds the heap limit set with [`Context::set_heap_limit`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeapLimitAction {
+    /// Throw a `RangeError`, which can be caught by the script.
+    Throw,
+    /// Throw a `RangeError` that cannot be caught by the script, terminating the execution.
+    Terminate,
+}
+
+impl Default for HeapLimitAction {
+    #[inline]
+    fn default() -> Self {
+        Self::Throw
+    }
+}
+
+/// Statistics about the garbage-collected heap, returned by [`Context::gc_stats`].
+///
+/// The heap is shared by all the `Context`s of a thread, so these statistics include the
+/// objects of every `Context` of the current thread.
+#[derive(Debug, Clone, Default)]
+pub struct GcStats {
+    /// Collections, live bytes and pause times reported by the collector.
+    pub heap: GcStatistics,
+    /// Number of live JavaScript objects, by the name of their `ObjectKind`.
+    pub objects: BTreeMap<&'static str, usize>,
+}
+
+/// Installs the heap limit of a `Context` in the collector for the duration of an execution,
+/// restoring the previous limit when dropped.
+#[derive(Debug)]
+pub(crate) struct HeapLimitScope {
+    previous: Option<usize>,
+}
+
+impl HeapLimitScope {
+    pub(crate) fn enter(limit: Option<usize>) -> Self {
+        let previous = boa_gc::heap_limit();
+        if previous != limit {
+            boa_gc::set_heap_limit(limit);
+        }
+        Self { previous }
+    }
+}
+
+impl Drop for HeapLimitScope {
+    fn drop(&mut self) {
+        if boa_gc::heap_limit() != self.previous {
+            boa_gc::set_heap_limit(self.previous);
+        }
+    }
+}
+
+impl Context {
+    /// Sets the maximum number of bytes the garbage-collected heap can use, or removes the
+    /// limit if `limit` is `None`.
+    ///
+    /// When an allocation exceeds the limit even after a collection, the running script throws
+    /// a `RangeError`, which can be caught or not depending on the
+    /// [`HeapLimitAction`](Self::set_heap_limit_action). String concatenations and array
+    /// buffers are checked before they allocate; other allocations are checked between
+    /// instructions, so a single instruction can still go past the limit.
+    ///
+    /// The limit only applies while this `Context` runs a script, but it is compared with the
+    /// size of the whole heap, which is shared by all the `Context`s of a thread.
+    ///
+    /// The garbage-collected objects, the contents of strings and the bytes of array buffers are
+    /// counted, but not the other memory the objects own outside of the heap, such as their
+    /// property storage. The limit is therefore not a hard bound on the memory used by scripts.
+    /// See [`boa_gc::set_heap_limit`] for how the limit is checked.
+    #[inline]
+    pub fn set_heap_limit(&mut self, limit: Option<usize>) {
+        self.heap_limit = limit;
+    }
+
+    /// Returns the heap limit, if any.
+    #[inline]
+    pub fn heap_limit(&self) -> Option<usize> {
+        self.heap_limit
+    }
+
+    /// Sets what happens when a script exceeds the heap limit.
+    #[inline]
+    pub fn set_heap_limit_action(&mut self, action: HeapLimitAction) {
+        self.heap_limit_action = action;
+    }
+
+    /// Returns statistics about the garbage-collected heap of the current thread.
+    pub fn gc_stats(&self) -> GcStats {
+        let mut objects = BTreeMap::new();
+        boa_gc::walk_heap(|value| {
+            if let Some(object) = value
+                .downcast_ref::<boa_gc::Cell<Object>>()
+                .and_then(|cell| cell.try_borrow().ok())
+            {
+                *objects.entry(object.kind().as_str()).or_default() += 1;
+            }
+        });
+
+        GcStats {
+            heap: boa_gc::statistics(),
+            objects,
+        }
+    }
+
+    /// Concatenates `strings`, throwing the error of an exceeded heap limit instead of
+    /// allocating the result if it doesn't fit under the limit.
+    pub(crate) fn concat_strings(&mut self, strings: &[&str]) -> JsResult<JsString> {
+        JsString::try_concat_array(strings).ok_or_else(|| self.heap_limit_exceeded())
+    }
+
+    /// Constructs the `RangeError` of an exceeded heap limit, terminating the execution if the
+    /// error must not be caught.
+    pub(crate) fn heap_limit_exceeded(&mut self) -> JsValue {
+        let error = match self.heap_limit_action {
+            HeapLimitAction::Throw => self.construct_range_error("heap limit exceeded"),
+            HeapLimitAction::Terminate => self.terminate(TerminationReason::HeapLimit),
+        };
+        // Constructing the error allocates, which must not count as a new violation of the
+        // limit, or the error would be thrown again from the `catch` block handling it.
+        boa_gc::take_heap_limit_exceeded();
+        error
+    }
+}
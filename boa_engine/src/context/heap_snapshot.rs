@@ -0,0 +1,421 @@
+//! Export of the garbage-collected heap in the heap snapshot format of V8.
+
+use crate::{
+    builtins::function::Function,
+    environments::DeclarativeEnvironment,
+    object::{Object, ObjectKind},
+    property::PropertyKey,
+    vm::CodeBlock,
+    Context, JsValue,
+};
+use boa_interner::Sym;
+use rustc_hash::{FxHashMap, FxHashSet};
+use serde_json::json;
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    mem::size_of,
+    path::Path,
+};
+
+/// The kinds of nodes of the V8 format used by the snapshot.
+const NODE_TYPES: [&str; 6] = ["hidden", "object", "closure", "regexp", "code", "synthetic"];
+
+/// The kinds of edges of the V8 format used by the snapshot.
+const EDGE_TYPES: [&str; 4] = ["context", "element", "property", "internal"];
+
+/// Returns the address of a value of the heap, which identifies its node.
+#[inline]
+fn address<T>(value: &T) -> *const () {
+    (value as *const T).cast()
+}
+
+/// The name of an edge, which is an index for `element` edges.
+#[derive(Debug)]
+enum EdgeName {
+    Name(String),
+    Index(usize),
+}
+
+/// A reference from a node to another.
+#[derive(Debug)]
+struct Edge {
+    kind: usize,
+    name: EdgeName,
+    to: *const (),
+}
+
+impl Edge {
+    fn new(kind: &str, name: impl Into<String>, to: *const ()) -> Self {
+        Self {
+            kind: edge_type(kind),
+            name: EdgeName::Name(name.into()),
+            to,
+        }
+    }
+}
+
+/// A value of the heap.
+#[derive(Debug)]
+struct Node {
+    address: *const (),
+    kind: usize,
+    name: String,
+    self_size: usize,
+    edges: Vec<Edge>,
+}
+
+/// What is needed to find the name of the constructor of an object, once the whole heap is
+/// walked.
+#[derive(Debug, Default)]
+struct ObjectInfo {
+    prototype: Option<*const ()>,
+    constructor: Option<*const ()>,
+    /// The value of the `prototype` property, for constructors.
+    prototype_property: Option<*const ()>,
+    name: Option<String>,
+}
+
+fn node_type(kind: &str) -> usize {
+    NODE_TYPES
+        .iter()
+        .position(|name| *name == kind)
+        .expect("unknown node type")
+}
+
+fn edge_type(kind: &str) -> usize {
+    EDGE_TYPES
+        .iter()
+        .position(|name| *name == kind)
+        .expect("unknown edge type")
+}
+
+/// Adds an edge to `value` if it's an object.
+fn value_edge(edges: &mut Vec<Edge>, kind: &str, name: impl Into<String>, value: &JsValue) {
+    if let Some(object) = value.as_object() {
+        edges.push(Edge::new(kind, name, address(object.as_ref())));
+    }
+}
+
+/// Describes an object of the heap.
+fn object_node(object: &Object, info: &mut ObjectInfo) -> (usize, Vec<Edge>) {
+    let mut edges = Vec::new();
+
+    for (key, property) in object.properties().iter() {
+        let (kind, name) = match &key {
+            PropertyKey::Index(index) => ("element", EdgeName::Index(*index as usize)),
+            key => ("property", EdgeName::Name(key.to_string())),
+        };
+        if let Some(value) = property.value() {
+            if let Some(target) = value.as_object() {
+                edges.push(Edge {
+                    kind: edge_type(kind),
+                    name,
+                    to: address(target.as_ref()),
+                });
+            } else if let (PropertyKey::String(key), Some(name)) = (&key, value.as_string()) {
+                if key.as_str() == "name" {
+                    info.name = Some(name.to_string());
+                }
+            }
+            if let (PropertyKey::String(key), Some(target)) = (&key, value.as_object()) {
+                match key.as_str() {
+                    "constructor" => info.constructor = Some(address(target.as_ref())),
+                    "prototype" => info.prototype_property = Some(address(target.as_ref())),
+                    _ => {}
+                }
+            }
+        }
+        if let Some(get) = property.get() {
+            value_edge(&mut edges, "internal", format!("get {key}"), get);
+        }
+        if let Some(set) = property.set() {
+            value_edge(&mut edges, "internal", format!("set {key}"), set);
+        }
+    }
+
+    if let Some(prototype) = object.prototype() {
+        let prototype = address(prototype.as_ref());
+        info.prototype = Some(prototype);
+        edges.push(Edge::new("property", "__proto__", prototype));
+    }
+
+    let kind = match object.kind() {
+        ObjectKind::Function(function) | ObjectKind::GeneratorFunction(function) => {
+            if let Function::Ordinary { code, environments }
+            | Function::Generator { code, environments } = function
+            {
+                edges.push(Edge::new("internal", "code", address(&**code)));
+                for environment in environments.iter() {
+                    edges.push(Edge::new("context", "context", address(environment)));
+                }
+            }
+            "closure"
+        }
+        ObjectKind::BoundFunction(bound) => {
+            let target = bound.target_function();
+            edges.push(Edge::new(
+                "internal",
+                "bound_function",
+                address(target.as_ref()),
+            ));
+            value_edge(&mut edges, "internal", "bound_this", bound.this());
+            for (index, arg) in bound.args().iter().enumerate() {
+                value_edge(
+                    &mut edges,
+                    "internal",
+                    format!("bound_argument_{index}"),
+                    arg,
+                );
+            }
+            "closure"
+        }
+        ObjectKind::Map(map) => {
+            for (key, value) in map.iter() {
+                value_edge(&mut edges, "internal", "key", key);
+                value_edge(&mut edges, "internal", "value", value);
+            }
+            "object"
+        }
+        ObjectKind::Set(set) => {
+            for value in set.iter() {
+                value_edge(&mut edges, "internal", "value", value);
+            }
+            "object"
+        }
+        ObjectKind::RegExp(_) => "regexp",
+        _ => "object",
+    };
+
+    (node_type(kind), edges)
+}
+
+/// Walks the heap of the current thread, returning its nodes.
+fn walk(context: &Context) -> (Vec<Node>, FxHashMap<*const (), ObjectInfo>) {
+    let mut nodes = Vec::new();
+    let mut objects = FxHashMap::default();
+
+    boa_gc::walk_heap(|value| {
+        if let Some(cell) = value.downcast_ref::<boa_gc::Cell<Object>>() {
+            let object = match cell.try_borrow() {
+                Ok(object) => object,
+                Err(_) => return,
+            };
+            let mut info = ObjectInfo::default();
+            let (kind, edges) = object_node(&object, &mut info);
+            nodes.push(Node {
+                address: address(cell),
+                kind,
+                name: object.kind().as_str().to_owned(),
+                self_size: size_of::<Object>()
+                    + object.properties().iter().count() * size_of::<(PropertyKey, JsValue)>(),
+                edges,
+            });
+            objects.insert(address(cell), info);
+        } else if let Some(environment) = value.downcast_ref::<DeclarativeEnvironment>() {
+            let mut edges = Vec::new();
+            environment.for_each_binding(|index, value| {
+                value_edge(&mut edges, "context", index.to_string(), value);
+            });
+            if let Some(this) = environment.this() {
+                value_edge(&mut edges, "context", "this", this);
+            }
+            nodes.push(Node {
+                address: address(environment),
+                kind: node_type("hidden"),
+                name: "system / Context".to_owned(),
+                self_size: size_of::<DeclarativeEnvironment>()
+                    + environment.len() * size_of::<Option<JsValue>>(),
+                edges,
+            });
+        } else if let Some(code) = value.downcast_ref::<CodeBlock>() {
+            let edges = code
+                .functions
+                .iter()
+                .map(|function| Edge::new("internal", "function", address(&**function)))
+                .collect();
+            let name = if code.name == Sym::MAIN {
+                "<main>"
+            } else {
+                context.interner().resolve_expect(code.name)
+            };
+            nodes.push(Node {
+                address: address(code),
+                kind: node_type("code"),
+                name: format!("(code {name})"),
+                self_size: size_of::<CodeBlock>() + code.code.len(),
+                edges,
+            });
+        }
+    });
+
+    (nodes, objects)
+}
+
+impl Context {
+    /// Writes a snapshot of the garbage-collected heap to `path`, in the `.heapsnapshot` format
+    /// that can be loaded in the memory panel of the Chrome DevTools.
+    ///
+    /// The snapshot contains the objects, environments and compiled functions of the heap, and
+    /// the references between them. Objects are named by the name of their constructor, or by
+    /// their kind. Sizes are estimates of the memory used by each value itself.
+    ///
+    /// A garbage collection runs before taking the snapshot. The heap is shared by all the
+    /// `Context`s of a thread, so the snapshot includes the values of every `Context` of the
+    /// current thread.
+    pub fn write_heap_snapshot<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        boa_gc::force_collect();
+        let (mut nodes, objects) = walk(self);
+
+        // Name the objects by their constructor, found through their prototype. Prototypes
+        // without a `constructor` property fall back to the function whose `prototype` property
+        // references them.
+        let constructors: FxHashMap<_, _> = objects
+            .iter()
+            .filter_map(|(constructor, info)| Some((info.prototype_property?, *constructor)))
+            .collect();
+        for node in &mut nodes {
+            let constructor_name = objects
+                .get(&node.address)
+                .and_then(|info| info.prototype)
+                .and_then(|prototype| {
+                    objects
+                        .get(&prototype)?
+                        .constructor
+                        .or_else(|| constructors.get(&prototype).copied())
+                })
+                .and_then(|constructor| objects.get(&constructor)?.name.clone());
+            let own_name = objects
+                .get(&node.address)
+                .and_then(|info| info.name.clone());
+            if node.kind == node_type("closure") {
+                if let Some(name) = own_name {
+                    node.name = name;
+                }
+            } else if let Some(name) = constructor_name.filter(|name| !name.is_empty()) {
+                node.name = name;
+            }
+        }
+
+        // The root references the global object, the global environment, and the values that
+        // are not referenced by other values of the heap.
+        let referenced: FxHashSet<_> = nodes
+            .iter()
+            .flat_map(|node| node.edges.iter().map(|edge| edge.to))
+            .collect();
+        let mut root_edges = vec![Edge::new(
+            "property",
+            "global",
+            address(self.global_object().as_ref()),
+        )];
+        if let Some(environment) = self.realm.environments.iter().next() {
+            root_edges.push(Edge::new(
+                "context",
+                "global_environment",
+                address(environment),
+            ));
+        }
+        root_edges.extend(
+            nodes
+                .iter()
+                .filter(|node| !referenced.contains(&node.address))
+                .enumerate()
+                .map(|(index, node)| Edge {
+                    kind: edge_type("element"),
+                    name: EdgeName::Index(index),
+                    to: node.address,
+                }),
+        );
+        nodes.insert(
+            0,
+            Node {
+                address: std::ptr::null(),
+                kind: node_type("synthetic"),
+                name: "(root)".to_owned(),
+                self_size: 0,
+                edges: root_edges,
+            },
+        );
+
+        let file = File::create(path)?;
+        let mut writer = BufWriter::new(file);
+        serde_json::to_writer(&mut writer, &serialize(&nodes))?;
+        writer.flush()
+    }
+}
+
+/// Number of fields of each node in the `nodes` array.
+const NODE_FIELD_COUNT: usize = 6;
+
+/// Serializes the nodes to the V8 heap snapshot format.
+fn serialize(nodes: &[Node]) -> serde_json::Value {
+    let indices: FxHashMap<*const (), usize> = nodes
+        .iter()
+        .enumerate()
+        .map(|(index, node)| (node.address, index))
+        .collect();
+
+    let mut strings: Vec<String> = Vec::new();
+    let mut string_indices: FxHashMap<String, usize> = FxHashMap::default();
+    let mut intern = |string: &str| match string_indices.get(string) {
+        Some(index) => *index,
+        None => {
+            strings.push(string.to_owned());
+            string_indices.insert(string.to_owned(), strings.len() - 1);
+            strings.len() - 1
+        }
+    };
+
+    let mut node_fields = Vec::with_capacity(nodes.len() * NODE_FIELD_COUNT);
+    let mut edge_fields = Vec::new();
+    for (index, node) in nodes.iter().enumerate() {
+        let mut edge_count = 0;
+        for edge in &node.edges {
+            let target = match indices.get(&edge.to) {
+                Some(target) => *target,
+                None => continue,
+            };
+            let name = match &edge.name {
+                EdgeName::Name(name) => intern(name),
+                EdgeName::Index(index) => *index,
+            };
+            edge_fields.extend([edge.kind, name, target * NODE_FIELD_COUNT]);
+            edge_count += 1;
+        }
+        let name = intern(&node.name);
+        // Ids are odd, like the ids of the objects of V8.
+        node_fields.extend([
+            node.kind,
+            name,
+            index * 2 + 1,
+            node.self_size,
+            edge_count,
+            0,
+        ]);
+    }
+
+    json!({
+        "snapshot": {
+            "meta": {
+                "node_fields": ["type", "name", "id", "self_size", "edge_count", "trace_node_id"],
+                "node_types": [NODE_TYPES, "string", "number", "number", "number", "number"],
+                "edge_fields": ["type", "name_or_index", "to_node"],
+                "edge_types": [EDGE_TYPES, "string_or_number", "node"],
+                "trace_function_info_fields": [],
+                "trace_node_fields": [],
+                "sample_fields": [],
+                "location_fields": [],
+            },
+            "node_count": nodes.len(),
+            "edge_count": edge_fields.len() / 3,
+            "trace_function_count": 0,
+        },
+        "nodes": node_fields,
+        "edges": edge_fields,
+        "trace_function_infos": [],
+        "trace_tree": [],
+        "samples": [],
+        "locations": [],
+        "strings": strings,
+    })
+}
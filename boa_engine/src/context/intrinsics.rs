@@ -92,6 +92,7 @@ pub struct StandardConstructors {
     eval_error: StandardConstructor,
     uri_error: StandardConstructor,
     aggregate_error: StandardConstructor,
+    date: StandardConstructor,
     map: StandardConstructor,
     set: StandardConstructor,
     typed_array: StandardConstructor,
@@ -146,6 +147,7 @@ impl Default for StandardConstructors {
             eval_error: StandardConstructor::default(),
             uri_error: StandardConstructor::default(),
             aggregate_error: StandardConstructor::default(),
+            date: StandardConstructor::default(),
             map: StandardConstructor::default(),
             set: StandardConstructor::default(),
             typed_array: StandardConstructor::default(),
@@ -281,6 +283,11 @@ impl StandardConstructors {
         &self.aggregate_error
     }
 
+    #[inline]
+    pub fn date(&self) -> &StandardConstructor {
+        &self.date
+    }
+
     #[inline]
     pub fn map(&self) -> &StandardConstructor {
         &self.map
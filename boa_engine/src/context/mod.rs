@@ -2,22 +2,35 @@
 
 pub mod intrinsics;
 
-use intrinsics::{IntrinsicObjects, Intrinsics};
+mod heap;
+mod heap_snapshot;
+
+pub use heap::{GcStats, HeapLimitAction};
+
+pub(crate) use heap::HeapLimitScope;
+
+use intrinsics::{IntrinsicObjects, Intrinsics, StandardConstructor};
 
 use crate::{
-    builtins::{self, function::NativeFunctionSignature},
+    builtins::{self, function::IntoJsFunction},
     bytecompiler::ByteCompiler,
     class::{Class, ClassBuilder},
-    object::{FunctionBuilder, GlobalPropertyMap, JsObject, ObjectData},
+    object::{FunctionBuilder, GlobalPropertyMap, JsFunction, JsObject, ObjectData},
     property::{Attribute, PropertyDescriptor, PropertyKey},
     realm::Realm,
-    syntax::{ast::node::StatementList, parser::ParseError, Parser},
-    vm::{CallFrame, CodeBlock, FinallyReturn, GeneratorResumeKind, Vm},
-    JsResult, JsValue,
+    syntax::{ast::node::StatementList, optimizer::Optimizer, parser::ParseError, Parser},
+    vm::{
+        CallFrame, CodeBlock, ConsoleTracer, CoverageState, CpuProfilerState, DebuggerState,
+        FinallyReturn, GeneratorResumeKind, InterruptHandle, RuntimeLimits, TerminationReason,
+        TracerState, Vm, VmStatsState,
+    },
+    JsError, JsNativeError, JsResult, JsValue,
 };
 use boa_gc::Gc;
 use boa_interner::{Interner, Sym};
 use boa_profiler::Profiler;
+use rustc_hash::FxHashMap;
+use std::any::TypeId;
 
 #[cfg(feature = "console")]
 use crate::builtins::console::Console;
@@ -85,6 +98,18 @@ pub struct Context {
     /// Whether or not global strict mode is active.
     strict: bool,
 
+    /// Whether or not the AST optimizer runs before compilation.
+    optimize: bool,
+
+    /// Maximum size of the garbage-collected heap, in bytes.
+    heap_limit: Option<usize>,
+
+    /// What happens when the heap limit is exceeded.
+    heap_limit_action: HeapLimitAction,
+
+    /// The constructors and prototypes of the native classes, by the `TypeId` of their type.
+    native_classes: FxHashMap<TypeId, StandardConstructor>,
+
     pub(crate) vm: Vm,
 }
 
@@ -97,11 +122,26 @@ impl Default for Context {
             console: Console::default(),
             intrinsics: Intrinsics::default(),
             strict: false,
+            optimize: false,
+            heap_limit: None,
+            heap_limit_action: HeapLimitAction::default(),
+            native_classes: FxHashMap::default(),
             vm: Vm {
                 frame: None,
                 stack: Vec::with_capacity(1024),
-                trace: false,
-                stack_size_limit: 1024,
+                tracer: TracerState::default(),
+                runtime_limits: RuntimeLimits::default(),
+                termination: None,
+                call_depth: 0,
+                instruction_count: 0,
+                loop_iteration_count: 0,
+                interrupt: InterruptHandle::default(),
+                debugger: DebuggerState::default(),
+                coverage: CoverageState::default(),
+                cpu_profiler: CpuProfilerState::default(),
+                stats: VmStatsState::default(),
+                #[cfg(feature = "jit")]
+                jit_threshold: crate::vm::DEFAULT_JIT_THRESHOLD,
             },
         };
 
@@ -161,6 +201,21 @@ impl Context {
         self.strict = strict;
     }
 
+    /// Returns if the AST optimizer is enabled.
+    #[inline]
+    pub fn optimize(&self) -> bool {
+        self.optimize
+    }
+
+    /// Enables or disables the AST [`Optimizer`](crate::syntax::optimizer::Optimizer).
+    ///
+    /// When enabled, [`Context::eval`] folds constant expressions and removes unreachable `if`
+    /// branches before compiling the script.
+    #[inline]
+    pub fn set_optimize(&mut self, optimize: bool) {
+        self.optimize = optimize;
+    }
+
     /// Sets up the default global objects within Global
     #[inline]
     fn create_intrinsics(&mut self) {
@@ -404,8 +459,8 @@ impl Context {
 
     /// Register a global native function.
     ///
-    /// This is more efficient that creating a closure function, since this does not allocate,
-    /// it is just a function pointer.
+    /// The function can have the [`NativeFunctionSignature`][sig], or typed parameters converted
+    /// from the arguments of the call, see [`IntoJsFunction`].
     ///
     /// The function will be both `constructable` (call with `new`).
     ///
@@ -416,17 +471,17 @@ impl Context {
     ///
     /// If you want to make a function only `constructable`, or wish to bind it differently
     /// to the global object, you can create the function object with
-    /// [`FunctionBuilder`](crate::object::FunctionBuilder::native). And bind it to the global
+    /// [`FunctionBuilder`](crate::object::FunctionBuilder::typed). And bind it to the global
     /// object with [`Context::register_global_property`](Context::register_global_property)
     /// method.
+    ///
+    /// [sig]: crate::builtins::function::NativeFunctionSignature
     #[inline]
-    pub fn register_global_function(
-        &mut self,
-        name: &str,
-        length: usize,
-        body: NativeFunctionSignature,
-    ) {
-        let function = FunctionBuilder::native(self, body)
+    pub fn register_global_function<F, Args>(&mut self, name: &str, length: usize, body: F)
+    where
+        F: IntoJsFunction<Args>,
+    {
+        let function = FunctionBuilder::typed(self, body)
             .name(name)
             .length(length)
             .constructor(true)
@@ -445,8 +500,8 @@ impl Context {
 
     /// Register a global native function that is not a constructor.
     ///
-    /// This is more efficient that creating a closure function, since this does not allocate,
-    /// it is just a function pointer.
+    /// The function can have the [`NativeFunctionSignature`][sig], or typed parameters converted
+    /// from the arguments of the call, see [`IntoJsFunction`].
     ///
     /// The function will be bound to the global object with `writable`, `non-enumerable`
     /// and `configurable` attributes. The same as when you create a function in JavaScript.
@@ -456,14 +511,14 @@ impl Context {
     /// The difference to [`Context::register_global_function`](Context::register_global_function) is,
     /// that the function will not be `constructable`.
     /// Usage of the function as a constructor will produce a `TypeError`.
+    ///
+    /// [sig]: crate::builtins::function::NativeFunctionSignature
     #[inline]
-    pub fn register_global_builtin_function(
-        &mut self,
-        name: &str,
-        length: usize,
-        body: NativeFunctionSignature,
-    ) {
-        let function = FunctionBuilder::native(self, body)
+    pub fn register_global_builtin_function<F, Args>(&mut self, name: &str, length: usize, body: F)
+    where
+        F: IntoJsFunction<Args>,
+    {
+        let function = FunctionBuilder::typed(self, body)
             .name(name)
             .length(length)
             .constructor(false)
@@ -554,10 +609,7 @@ impl Context {
     where
         T: Class,
     {
-        let mut class_builder = ClassBuilder::new::<T>(self);
-        T::init(&mut class_builder)?;
-
-        let class = class_builder.build();
+        let class = self.class_constructor::<T>()?;
         let property = PropertyDescriptor::builder()
             .value(class)
             .writable(T::ATTRIBUTES.writable())
@@ -569,6 +621,39 @@ impl Context {
         Ok(())
     }
 
+    /// Returns the constructor of the native class of type `T`.
+    ///
+    /// The class is created the first time it is needed, such as when it is registered or when it
+    /// is the [`PARENT`][Class::PARENT] of another class, and is then shared by the whole context.
+    #[inline]
+    pub fn class_constructor<T>(&mut self) -> JsResult<JsFunction>
+    where
+        T: Class,
+    {
+        self.native_class::<T>()
+            .map(|class| JsFunction::from_object_unchecked(class.constructor()))
+    }
+
+    /// Returns the constructor and the prototype of the native class of type `T`, creating the
+    /// class if needed.
+    pub(crate) fn native_class<T>(&mut self) -> JsResult<StandardConstructor>
+    where
+        T: Class,
+    {
+        let type_id = TypeId::of::<T>();
+        if let Some(class) = self.native_classes.get(&type_id) {
+            return Ok(class.clone());
+        }
+
+        let class = StandardConstructor::default();
+        let mut class_builder = ClassBuilder::new::<T>(self, class.clone())?;
+        T::init(&mut class_builder)?;
+        class_builder.build();
+
+        self.native_classes.insert(type_id, class.clone());
+        Ok(class)
+    }
+
     /// Register a global property.
     ///
     /// # Example
@@ -634,23 +719,34 @@ impl Context {
     /// assert!(value.is_number());
     /// assert_eq!(value.as_number().unwrap(), 4.0);
     /// ```
+    #[inline]
+    pub fn eval<S>(&mut self, src: S) -> JsResult<JsValue, JsError>
+    where
+        S: AsRef<[u8]>,
+    {
+        self.eval_with_name(src, "")
+    }
+
+    /// Evaluates the given code like [`Context::eval`], compiling it as the source `name`.
+    ///
+    /// The name identifies the source in debuggers, for example in
+    /// [`Context::set_breakpoint`].
     #[allow(clippy::unit_arg, clippy::drop_copy)]
-    pub fn eval<S>(&mut self, src: S) -> JsResult<JsValue>
+    pub fn eval_with_name<S>(&mut self, src: S, name: &str) -> JsResult<JsValue, JsError>
     where
         S: AsRef<[u8]>,
     {
         let main_timer = Profiler::global().start_event("Evaluation", "Main");
 
-        let parsing_result = Parser::new(src.as_ref(), false)
+        let mut statement_list = Parser::new(src.as_ref(), false)
             .parse_all(self)
-            .map_err(|e| e.to_string());
+            .map_err(|e| JsNativeError::syntax().with_message(e.to_string()))?;
 
-        let statement_list = match parsing_result {
-            Ok(statement_list) => statement_list,
-            Err(e) => return self.throw_syntax_error(e),
-        };
+        if self.optimize {
+            Optimizer::new(self).apply(&mut statement_list);
+        }
 
-        let code_block = self.compile(&statement_list)?;
+        let code_block = self.compile_with_name(&statement_list, name)?;
         let result = self.execute(code_block);
 
         // The main_timer needs to be dropped before the Profiler is.
@@ -662,11 +758,21 @@ impl Context {
 
     /// Compile the AST into a `CodeBlock` ready to be executed by the VM.
     #[inline]
-    pub fn compile(&mut self, statement_list: &StatementList) -> JsResult<Gc<CodeBlock>> {
+    pub fn compile(&mut self, statement_list: &StatementList) -> JsResult<Gc<CodeBlock>, JsError> {
+        self.compile_with_name(statement_list, "")
+    }
+
+    /// Compile the AST into a `CodeBlock` like [`Context::compile`], as the source `name`.
+    pub fn compile_with_name(
+        &mut self,
+        statement_list: &StatementList,
+        name: &str,
+    ) -> JsResult<Gc<CodeBlock>, JsError> {
         let _timer = Profiler::global().start_event("Compilation", "Main");
         let mut compiler = ByteCompiler::new(Sym::MAIN, statement_list.strict(), self);
+        compiler.set_source(name.into());
         compiler.create_declarations(statement_list.items())?;
-        compiler.compile_statement_list(statement_list.items(), true)?;
+        compiler.compile_statement_list(statement_list, true)?;
         Ok(Gc::new(compiler.finish()))
     }
 
@@ -677,7 +783,7 @@ impl Context {
     /// times, there is no need to re-compile it, and you can just call `clone()` on the
     /// `Gc<CodeBlock>` returned by the [`Self::compile()`] function.
     #[inline]
-    pub fn execute(&mut self, code_block: Gc<CodeBlock>) -> JsResult<JsValue> {
+    pub fn execute(&mut self, code_block: Gc<CodeBlock>) -> JsResult<JsValue, JsError> {
         let _timer = Profiler::global().start_event("Execution", "Main");
         let global_object = self.global_object().clone().into();
 
@@ -698,12 +804,16 @@ impl Context {
             param_count: 0,
             arg_count: 0,
             generator_resume_kind: GeneratorResumeKind::Normal,
+            caller_environments: None,
         });
 
         self.realm.set_global_binding_number();
         let result = self.run();
         self.vm.pop_frame();
-        let (result, _) = result?;
+        let (result, _) = result.map_err(|error| match self.vm.termination {
+            Some(reason) => JsError::Termination(reason),
+            None => JsError::from_opaque(error),
+        })?;
         Ok(result)
     }
 
@@ -714,7 +824,73 @@ impl Context {
     }
 
     /// Set the value of trace on the context
+    ///
+    /// Enabling it attaches a [`ConsoleTracer`](crate::vm::ConsoleTracer), and disabling it
+    /// detaches the tracer, see [`Context::set_tracer`].
     pub fn set_trace(&mut self, trace: bool) {
-        self.vm.trace = trace;
+        if trace {
+            self.set_tracer(ConsoleTracer);
+        } else {
+            self.remove_tracer();
+        }
+    }
+
+    /// Returns the limits checked by the VM while executing code.
+    #[inline]
+    pub fn runtime_limits(&self) -> RuntimeLimits {
+        self.vm.runtime_limits
+    }
+
+    /// Sets the limits checked by the VM while executing code.
+    #[inline]
+    pub fn set_runtime_limits(&mut self, runtime_limits: RuntimeLimits) {
+        self.vm.runtime_limits = runtime_limits;
+    }
+
+    /// Returns the number of calls or loop iterations after which a code block is hot.
+    #[cfg(feature = "jit")]
+    #[inline]
+    pub fn jit_threshold(&self) -> u32 {
+        self.vm.jit_threshold
+    }
+
+    /// Sets the number of calls or loop iterations after which a code block is hot, see
+    /// [`CodeBlock::is_hot`](crate::vm::CodeBlock::is_hot).
+    #[cfg(feature = "jit")]
+    #[inline]
+    pub fn set_jit_threshold(&mut self, threshold: u32) {
+        self.vm.jit_threshold = threshold;
+    }
+
+    /// Returns a handle that can terminate the execution of this context from another thread.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use boa_engine::{vm::TerminationReason, Context};
+    ///
+    /// let mut context = Context::default();
+    /// let handle = context.interrupt_handle();
+    ///
+    /// std::thread::spawn(move || {
+    ///     std::thread::sleep(std::time::Duration::from_millis(10));
+    ///     handle.terminate();
+    /// });
+    ///
+    /// let error = context
+    ///     .eval("try { while (true) {} } finally { while (true) {} }")
+    ///     .unwrap_err();
+    /// assert_eq!(error.as_termination(), Some(TerminationReason::Interrupted));
+    /// ```
+    #[inline]
+    pub fn interrupt_handle(&self) -> InterruptHandle {
+        self.vm.interrupt.clone()
+    }
+
+    /// Returns the reason why the last execution was terminated with an uncatchable error, if
+    /// it was.
+    #[inline]
+    pub fn termination_reason(&self) -> Option<TerminationReason> {
+        self.vm.termination
     }
 }
@@ -28,6 +28,23 @@ impl CompileTimeEnvironment {
     pub(crate) fn num_bindings(&self) -> usize {
         self.bindings.len()
     }
+
+    /// Returns `true` if this environment is a function environment.
+    #[inline]
+    pub(crate) fn is_function_scope(&self) -> bool {
+        self.function_scope
+    }
+
+    /// Returns the name and index of every binding in this environment, ordered by index.
+    pub(crate) fn binding_names(&self) -> Vec<(Sym, usize)> {
+        let mut names: Vec<_> = self
+            .bindings
+            .iter()
+            .map(|(name, binding)| (*name, binding.index))
+            .collect();
+        names.sort_unstable_by_key(|(_, index)| *index);
+        names
+    }
 }
 
 /// The compile time environment stack contains a stack of all environments at bytecode compile time.
@@ -66,6 +83,20 @@ impl CompileTimeEnvironmentStack {
             .expect("global environment must always exist")
             .num_bindings()
     }
+
+    /// Returns the number of environments on the stack.
+    #[inline]
+    pub(crate) fn len(&self) -> usize {
+        self.stack.len()
+    }
+
+    /// Returns the global environment.
+    #[inline]
+    pub(crate) fn global(&self) -> &CompileTimeEnvironment {
+        self.stack
+            .first()
+            .expect("global environment must always exist")
+    }
 }
 
 impl Context {
@@ -45,6 +45,33 @@ impl DeclarativeEnvironment {
         assert!(!binding.is_none(), "binding must be initialized");
         *binding = Some(value);
     }
+
+    /// Get the `this` value of the environment, if it is a function environment.
+    #[inline]
+    pub(crate) fn this(&self) -> Option<&JsValue> {
+        self.this.as_ref()
+    }
+
+    /// Calls `f` with the index and the value of each initialized binding.
+    ///
+    /// Does nothing if the bindings are being modified.
+    pub(crate) fn for_each_binding(&self, mut f: impl FnMut(usize, &JsValue)) {
+        if let Ok(bindings) = self.bindings.try_borrow() {
+            for (index, value) in bindings.iter().enumerate() {
+                if let Some(value) = value {
+                    f(index, value);
+                }
+            }
+        }
+    }
+
+    /// Get the number of bindings of the environment.
+    #[inline]
+    pub(crate) fn len(&self) -> usize {
+        self.bindings
+            .try_borrow()
+            .map_or(0, |bindings| bindings.len())
+    }
 }
 
 /// A declarative environment stack holds all declarative environments at runtime.
@@ -96,6 +123,12 @@ impl DeclarativeEnvironmentStack {
         None
     }
 
+    /// Iterate over the environments of the stack, from the global environment.
+    #[inline]
+    pub(crate) fn iter(&self) -> impl Iterator<Item = &DeclarativeEnvironment> {
+        self.stack.iter().map(|environment| &**environment)
+    }
+
     /// Push a declarative environment on the environments stack.
     #[inline]
     pub(crate) fn push_declarative(&mut self, num_bindings: usize) {
@@ -155,6 +188,44 @@ impl DeclarativeEnvironmentStack {
             .clone()
     }
 
+    /// Get the value of a binding, or `None` if the binding does not exist on this stack or is
+    /// not initialized.
+    #[inline]
+    pub(crate) fn try_get_value(
+        &self,
+        environment_index: usize,
+        binding_index: usize,
+    ) -> Option<JsValue> {
+        self.stack
+            .get(environment_index)?
+            .bindings
+            .borrow()
+            .get(binding_index)?
+            .clone()
+    }
+
+    /// Set the value of a binding if it exists on this stack and is initialized.
+    /// Return `true` if the value has been set.
+    #[inline]
+    pub(crate) fn try_put_value(
+        &self,
+        environment_index: usize,
+        binding_index: usize,
+        value: JsValue,
+    ) -> bool {
+        let environment = match self.stack.get(environment_index) {
+            Some(environment) => environment,
+            None => return false,
+        };
+        match environment.bindings.borrow_mut().get_mut(binding_index) {
+            Some(binding @ Some(_)) => {
+                *binding = Some(value);
+                true
+            }
+            _ => false,
+        }
+    }
+
     /// Set the value of a binding.
     ///
     /// # Panics
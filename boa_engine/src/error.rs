@@ -0,0 +1,510 @@
+//! Errors that can be handled from Rust without a [`Context`].
+//!
+//! A [`JsError`] is either a native error, created from Rust with a kind and a message, or any
+//! value thrown by a script. Native errors are only converted to JavaScript error objects when
+//! they are thrown into a script, with [`Context::throw`] or [`JsError::to_opaque`].
+//!
+//! Only the entry points that run scripts, like [`Context::eval`], return a `JsError` so far. The
+//! rest of the API still fails with the thrown [`JsValue`], see [`JsResult`].
+//!
+//! An execution that was terminated by the host or by a runtime limit returns
+//! [`JsError::Termination`] instead, since the script couldn't catch it.
+//!
+//! Thrown values are garbage collected, so a `JsError` can't leave the thread of its `Context`.
+//! [`JsError::into_erased`] converts it to a [`JsErasedError`], which is `Send` and `Sync` and can
+//! be used with error handling crates like `anyhow`.
+
+use crate::{vm::TerminationReason, Context, JsResult, JsValue};
+use std::{error, fmt};
+
+/// An error returned or thrown by the engine, or created by native code.
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsError {
+    /// An error created from Rust, which is not yet a JavaScript object.
+    Native(JsNativeError),
+    /// A value thrown by JavaScript code, which can be any value.
+    Opaque(JsValue),
+    /// The execution was terminated, and the script could not catch the error.
+    Termination(TerminationReason),
+}
+
+impl JsError {
+    /// Creates an error from a value thrown by JavaScript code.
+    #[inline]
+    pub fn from_opaque(value: JsValue) -> Self {
+        Self::Opaque(value)
+    }
+
+    /// Returns the native error, if this error was created from Rust.
+    #[inline]
+    pub fn as_native(&self) -> Option<&JsNativeError> {
+        match self {
+            Self::Native(native) => Some(native),
+            Self::Opaque(_) | Self::Termination(_) => None,
+        }
+    }
+
+    /// Returns the thrown value, if this error was thrown by JavaScript code.
+    #[inline]
+    pub fn as_opaque(&self) -> Option<&JsValue> {
+        match self {
+            Self::Native(_) | Self::Termination(_) => None,
+            Self::Opaque(value) => Some(value),
+        }
+    }
+
+    /// Returns the reason why the execution was terminated, if it was.
+    #[inline]
+    pub fn as_termination(&self) -> Option<TerminationReason> {
+        match self {
+            Self::Termination(reason) => Some(*reason),
+            Self::Native(_) | Self::Opaque(_) => None,
+        }
+    }
+
+    /// Converts the error to the value thrown in JavaScript, creating the error object of a native
+    /// error.
+    ///
+    /// A termination is converted to a `RangeError` with the termination reason as its message.
+    pub fn to_opaque(&self, context: &mut Context) -> JsValue {
+        match self {
+            Self::Native(native) => native.to_opaque(context),
+            Self::Opaque(value) => value.clone(),
+            Self::Termination(reason) => JsNativeError::range()
+                .with_message(reason.to_string())
+                .to_opaque(context),
+        }
+    }
+
+    /// Converts the error to a native error, reading the `name`, `message` and `cause` of a thrown
+    /// error object.
+    ///
+    /// Returns `None` for a termination, if the thrown value is not an error object, or if its
+    /// `name` is not the name of a native error constructor.
+    pub fn try_native(&self, context: &mut Context) -> Option<JsNativeError> {
+        let value = match self {
+            Self::Native(native) => return Some(native.clone()),
+            Self::Opaque(value) => value,
+            Self::Termination(_) => return None,
+        };
+
+        let object = value.as_object()?;
+        if !object.is_error() {
+            return None;
+        }
+        let name = object.get("name", context).ok()?.to_string(context).ok()?;
+        let kind = JsNativeErrorKind::from_name(name.as_str())?;
+        let message = object
+            .get("message", context)
+            .ok()?
+            .to_string(context)
+            .ok()?;
+        let cause = if object.has_own_property("cause", context).ok()? {
+            let cause = object.get("cause", context).ok()?;
+            Some(Box::new(Self::Opaque(cause)))
+        } else {
+            None
+        };
+
+        Some(JsNativeError {
+            kind,
+            message: message.as_str().into(),
+            cause,
+        })
+    }
+
+    /// Converts the error to a [`JsErasedError`], which is `Send` and `Sync`.
+    ///
+    /// Thrown error objects of a native kind are read like in [`try_native`][Self::try_native],
+    /// and other thrown values are rendered to a string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use boa_engine::{Context, JsErasedError, JsNativeErrorKind};
+    /// let mut context = Context::default();
+    /// let error = context.eval("null.answer").unwrap_err();
+    ///
+    /// let error: Box<dyn std::error::Error + Send + Sync> = error.into_erased(&mut context).into();
+    /// let error = error.downcast::<JsErasedError>().unwrap();
+    /// assert_eq!(error.as_native().unwrap().kind(), JsNativeErrorKind::Type);
+    /// ```
+    pub fn into_erased(self, context: &mut Context) -> JsErasedError {
+        self.into_erased_inner(context, 0)
+    }
+
+    fn into_erased_inner(self, context: &mut Context, depth: usize) -> JsErasedError {
+        if let Self::Termination(reason) = self {
+            return JsErasedError::Termination(reason);
+        }
+        match self.try_native(context) {
+            Some(native) => JsErasedError::Native(JsErasedNativeError {
+                kind: native.kind,
+                message: native.message,
+                // The causes of error objects can form a cycle.
+                cause: native
+                    .cause
+                    .filter(|_| depth < MAX_ERASED_CAUSE_DEPTH)
+                    .map(|cause| Box::new(cause.into_erased_inner(context, depth + 1))),
+            }),
+            None => JsErasedError::Opaque(self.to_string()),
+        }
+    }
+}
+
+impl From<JsValue> for JsError {
+    #[inline]
+    fn from(value: JsValue) -> Self {
+        Self::Opaque(value)
+    }
+}
+
+impl From<JsNativeError> for JsError {
+    #[inline]
+    fn from(native: JsNativeError) -> Self {
+        Self::Native(native)
+    }
+}
+
+impl fmt::Display for JsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Native(native) => fmt::Display::fmt(native, f),
+            Self::Opaque(value) => write!(f, "{}", value.display()),
+            Self::Termination(reason) => write!(f, "execution terminated: {reason}"),
+        }
+    }
+}
+
+impl error::Error for JsError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::Native(native) => error::Error::source(native),
+            Self::Opaque(_) | Self::Termination(_) => None,
+        }
+    }
+}
+
+/// The number of causes kept by [`JsError::into_erased`].
+const MAX_ERASED_CAUSE_DEPTH: usize = 32;
+
+/// The kind of a [`JsNativeError`], which is the constructor of its error object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum JsNativeErrorKind {
+    /// An `Error`.
+    Error,
+    /// An `EvalError`.
+    Eval,
+    /// A `RangeError`.
+    Range,
+    /// A `ReferenceError`.
+    Reference,
+    /// A `SyntaxError`.
+    Syntax,
+    /// A `TypeError`.
+    Type,
+    /// A `URIError`.
+    Uri,
+}
+
+impl JsNativeErrorKind {
+    /// Returns the name of the constructor of the errors of this kind.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Error => "Error",
+            Self::Eval => "EvalError",
+            Self::Range => "RangeError",
+            Self::Reference => "ReferenceError",
+            Self::Syntax => "SyntaxError",
+            Self::Type => "TypeError",
+            Self::Uri => "URIError",
+        }
+    }
+
+    /// Returns the kind of the errors created by the constructor named `name`.
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "Error" => Self::Error,
+            "EvalError" => Self::Eval,
+            "RangeError" => Self::Range,
+            "ReferenceError" => Self::Reference,
+            "SyntaxError" => Self::Syntax,
+            "TypeError" => Self::Type,
+            "URIError" => Self::Uri,
+            _ => return None,
+        })
+    }
+}
+
+impl fmt::Display for JsNativeErrorKind {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// An error created from Rust, with a kind, a message and an optional cause.
+///
+/// # Examples
+///
+/// ```
+/// # use boa_engine::{JsError, JsNativeError, JsNativeErrorKind};
+/// let error = JsNativeError::typ()
+///     .with_message("not a function")
+///     .with_cause(JsNativeError::range().with_message("out of range"));
+///
+/// assert_eq!(error.kind(), JsNativeErrorKind::Type);
+/// assert_eq!(error.to_string(), "TypeError: not a function");
+///
+/// let error: JsError = error.into();
+/// assert!(error.as_native().is_some());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsNativeError {
+    kind: JsNativeErrorKind,
+    message: Box<str>,
+    cause: Option<Box<JsError>>,
+}
+
+impl JsNativeError {
+    /// Creates an error of the given kind, without message.
+    #[inline]
+    pub fn new(kind: JsNativeErrorKind) -> Self {
+        Self {
+            kind,
+            message: "".into(),
+            cause: None,
+        }
+    }
+
+    /// Creates an `Error`.
+    #[inline]
+    pub fn error() -> Self {
+        Self::new(JsNativeErrorKind::Error)
+    }
+
+    /// Creates an `EvalError`.
+    #[inline]
+    pub fn eval() -> Self {
+        Self::new(JsNativeErrorKind::Eval)
+    }
+
+    /// Creates a `RangeError`.
+    #[inline]
+    pub fn range() -> Self {
+        Self::new(JsNativeErrorKind::Range)
+    }
+
+    /// Creates a `ReferenceError`.
+    #[inline]
+    pub fn reference() -> Self {
+        Self::new(JsNativeErrorKind::Reference)
+    }
+
+    /// Creates a `SyntaxError`.
+    #[inline]
+    pub fn syntax() -> Self {
+        Self::new(JsNativeErrorKind::Syntax)
+    }
+
+    /// Creates a `TypeError`.
+    #[inline]
+    pub fn typ() -> Self {
+        Self::new(JsNativeErrorKind::Type)
+    }
+
+    /// Creates a `URIError`.
+    #[inline]
+    pub fn uri() -> Self {
+        Self::new(JsNativeErrorKind::Uri)
+    }
+
+    /// Sets the message of the error.
+    #[inline]
+    pub fn with_message<M>(mut self, message: M) -> Self
+    where
+        M: Into<Box<str>>,
+    {
+        self.message = message.into();
+        self
+    }
+
+    /// Sets the cause of the error, which becomes the `cause` property of its error object.
+    #[inline]
+    pub fn with_cause<C>(mut self, cause: C) -> Self
+    where
+        C: Into<JsError>,
+    {
+        self.cause = Some(Box::new(cause.into()));
+        self
+    }
+
+    /// Returns the kind of the error.
+    #[inline]
+    pub fn kind(&self) -> JsNativeErrorKind {
+        self.kind
+    }
+
+    /// Returns the message of the error.
+    #[inline]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Returns the cause of the error.
+    #[inline]
+    pub fn cause(&self) -> Option<&JsError> {
+        self.cause.as_deref()
+    }
+
+    /// Creates the error object of this error, and of its cause.
+    pub fn to_opaque(&self, context: &mut Context) -> JsValue {
+        let message = self.message.clone();
+        let error = match self.kind {
+            JsNativeErrorKind::Error => context.construct_error(message),
+            JsNativeErrorKind::Eval => context.construct_eval_error(message),
+            JsNativeErrorKind::Range => context.construct_range_error(message),
+            JsNativeErrorKind::Reference => context.construct_reference_error(message),
+            JsNativeErrorKind::Syntax => context.construct_syntax_error(message),
+            JsNativeErrorKind::Type => context.construct_type_error(message),
+            JsNativeErrorKind::Uri => context.construct_uri_error(message),
+        };
+
+        if let (Some(cause), Some(object)) = (&self.cause, error.as_object()) {
+            let cause = cause.to_opaque(context);
+            object.create_non_enumerable_data_property_or_throw("cause", cause, context);
+        }
+        error
+    }
+}
+
+impl fmt::Display for JsNativeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.message.is_empty() {
+            write!(f, "{}", self.kind)
+        } else {
+            write!(f, "{}: {}", self.kind, self.message)
+        }
+    }
+}
+
+impl error::Error for JsNativeError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        self.cause
+            .as_deref()
+            .map(|cause| cause as &(dyn error::Error + 'static))
+    }
+}
+
+/// A [`JsError`] detached from its `Context`, returned by [`JsError::into_erased`].
+///
+/// Unlike `JsError`, it doesn't hold garbage collected values, so it's `Send` and `Sync`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JsErasedError {
+    /// A native error, or a thrown error object of a native kind.
+    Native(JsErasedNativeError),
+    /// Any other thrown value, rendered to a string.
+    Opaque(String),
+    /// A terminated execution.
+    Termination(TerminationReason),
+}
+
+impl JsErasedError {
+    /// Returns the native error, if the error was native or a thrown error object of a native
+    /// kind.
+    #[inline]
+    pub fn as_native(&self) -> Option<&JsErasedNativeError> {
+        match self {
+            Self::Native(native) => Some(native),
+            Self::Opaque(_) | Self::Termination(_) => None,
+        }
+    }
+
+    /// Returns the reason why the execution was terminated, if it was.
+    #[inline]
+    pub fn as_termination(&self) -> Option<TerminationReason> {
+        match self {
+            Self::Termination(reason) => Some(*reason),
+            Self::Native(_) | Self::Opaque(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for JsErasedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Native(native) => fmt::Display::fmt(native, f),
+            Self::Opaque(value) => f.write_str(value),
+            Self::Termination(reason) => write!(f, "execution terminated: {reason}"),
+        }
+    }
+}
+
+impl error::Error for JsErasedError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::Native(native) => error::Error::source(native),
+            Self::Opaque(_) | Self::Termination(_) => None,
+        }
+    }
+}
+
+/// The native error of a [`JsErasedError`], with a kind, a message and an optional cause.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsErasedNativeError {
+    kind: JsNativeErrorKind,
+    message: Box<str>,
+    cause: Option<Box<JsErasedError>>,
+}
+
+impl JsErasedNativeError {
+    /// Returns the kind of the error.
+    #[inline]
+    pub fn kind(&self) -> JsNativeErrorKind {
+        self.kind
+    }
+
+    /// Returns the message of the error.
+    #[inline]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Returns the cause of the error.
+    #[inline]
+    pub fn cause(&self) -> Option<&JsErasedError> {
+        self.cause.as_deref()
+    }
+}
+
+impl fmt::Display for JsErasedNativeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.message.is_empty() {
+            write!(f, "{}", self.kind)
+        } else {
+            write!(f, "{}: {}", self.kind, self.message)
+        }
+    }
+}
+
+impl error::Error for JsErasedNativeError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        self.cause
+            .as_deref()
+            .map(|cause| cause as &(dyn error::Error + 'static))
+    }
+}
+
+impl Context {
+    /// Throws `error` into JavaScript, converting it to its error object.
+    ///
+    /// This is the point where native errors become JavaScript values, so native functions can
+    /// build [`JsError`]s and only pay for the error object when the error is thrown.
+    #[inline]
+    pub fn throw<E, R>(&mut self, error: E) -> JsResult<R>
+    where
+        E: Into<JsError>,
+    {
+        Err(error.into().to_opaque(self))
+    }
+}
@@ -67,15 +67,22 @@
     rustdoc::missing_doc_code_examples
 )]
 
+// Allows the macros of `boa_macros` and `boa_engine_derive`, which refer to `::boa_engine`, to be
+// used in the tests of this crate.
+#[cfg(test)]
+extern crate self as boa_engine;
+
 pub mod bigint;
 pub mod builtins;
 pub mod bytecompiler;
 pub mod class;
 pub mod context;
 pub mod environments;
+pub mod error;
 pub mod object;
 pub mod property;
 pub mod realm;
+pub mod serde;
 pub mod string;
 pub mod symbol;
 pub mod syntax;
@@ -87,7 +94,11 @@ mod tests;
 
 /// A convenience module that re-exports the most commonly-used Boa APIs
 pub mod prelude {
-    pub use crate::{object::JsObject, Context, JsBigInt, JsResult, JsString, JsValue};
+    pub use crate::{
+        error::{JsError, JsNativeError},
+        object::JsObject,
+        Context, JsBigInt, JsResult, JsString, JsValue,
+    };
 }
 
 use std::result::Result as StdResult;
@@ -95,12 +106,27 @@ use std::result::Result as StdResult;
 // Export things to root level
 #[doc(inline)]
 pub use crate::{
-    bigint::JsBigInt, context::Context, string::JsString, symbol::JsSymbol, value::JsValue,
+    bigint::JsBigInt,
+    context::Context,
+    error::{JsErasedError, JsErasedNativeError, JsError, JsNativeError, JsNativeErrorKind},
+    string::JsString,
+    symbol::JsSymbol,
+    value::JsValue,
 };
 
 /// The result of a Javascript expression is represented like this so it can succeed (`Ok`) or fail (`Err`)
+///
+/// The error is the thrown value by default. Only the entry points that run scripts
+/// ([`Context::eval`], [`Context::eval_with_name`], [`Context::compile`],
+/// [`Context::compile_with_name`] and [`Context::execute`]) return a [`JsError`], which can be
+/// inspected without a `Context`.
+///
+/// The other APIs, like the methods of [`JsObject`](object::JsObject), native functions and
+/// [`TryFromJs`](value::TryFromJs) conversions, still fail with the thrown [`JsValue`], which
+/// doesn't implement [`std::error::Error`]. It converts to a `JsError` with `?` or
+/// [`JsError::from`].
 #[must_use]
-pub type JsResult<T> = StdResult<T, JsValue>;
+pub type JsResult<T, E = JsValue> = StdResult<T, E>;
 
 /// Execute the code using an existing `Context`.
 ///
@@ -110,10 +136,10 @@ pub(crate) fn forward<S>(context: &mut Context, src: S) -> String
 where
     S: AsRef<[u8]>,
 {
-    context.eval(src.as_ref()).map_or_else(
-        |e| format!("Uncaught {}", e.display()),
-        |v| v.display().to_string(),
-    )
+    match context.eval(src.as_ref()) {
+        Ok(value) => value.display().to_string(),
+        Err(error) => format!("Uncaught {}", error.to_opaque(context).display()),
+    }
 }
 
 /// Execute the code using an existing Context.
@@ -128,7 +154,9 @@ pub(crate) fn forward_val<T: AsRef<[u8]>>(context: &mut Context, src: T) -> JsRe
     let main_timer = Profiler::global().start_event("Main", "Main");
 
     let src_bytes: &[u8] = src.as_ref();
-    let result = context.eval(src_bytes);
+    let result = context
+        .eval(src_bytes)
+        .map_err(|error| error.to_opaque(context));
 
     // The main_timer needs to be dropped before the Profiler is.
     drop(main_timer);
@@ -142,9 +170,10 @@ pub(crate) fn forward_val<T: AsRef<[u8]>>(context: &mut Context, src: T) -> JsRe
 pub(crate) fn exec<T: AsRef<[u8]>>(src: T) -> String {
     let src_bytes: &[u8] = src.as_ref();
 
-    match Context::default().eval(src_bytes) {
+    let mut context = Context::default();
+    match context.eval(src_bytes) {
         Ok(value) => value.display().to_string(),
-        Err(error) => error.display().to_string(),
+        Err(error) => error.to_opaque(&mut context).display().to_string(),
     }
 }
 
@@ -14,6 +14,7 @@ use crate::{
     Context, JsResult,
 };
 use boa_profiler::Profiler;
+use std::fmt;
 
 pub(super) mod arguments;
 pub(super) mod array;
@@ -281,7 +282,10 @@ impl JsObject {
 /// Then, reference this static in the creation phase of an `ObjectData`.
 ///
 /// E.g. `ObjectData::string`
-pub(crate) static ORDINARY_INTERNAL_METHODS: InternalObjectMethods = InternalObjectMethods {
+///
+/// Overriding methods can call the ordinary ones through this static, for example
+/// `(ORDINARY_INTERNAL_METHODS.__get__)(obj, key, receiver, context)`.
+pub static ORDINARY_INTERNAL_METHODS: InternalObjectMethods = InternalObjectMethods {
     __get_prototype_of__: ordinary_get_prototype_of,
     __set_prototype_of__: ordinary_set_prototype_of,
     __is_extensible__: ordinary_is_extensible,
@@ -303,30 +307,55 @@ pub(crate) static ORDINARY_INTERNAL_METHODS: InternalObjectMethods = InternalObj
 /// exclusive definitions of the internal methods, without having to
 /// resort to `dyn Object`.
 ///
+/// Native objects can also be given their own internal methods with
+/// [`ObjectData::native_object_with_internal_methods`][crate::object::ObjectData::native_object_with_internal_methods]
+/// or [`Class::internal_methods`][crate::class::Class::internal_methods], for example to expose
+/// the elements of a Rust collection as properties without using a `Proxy`.
+///
 /// For a guide on how to implement exotic internal methods, see `ORDINARY_INTERNAL_METHODS`.
 #[derive(Clone, Copy)]
 #[allow(clippy::type_complexity)]
-pub(crate) struct InternalObjectMethods {
-    pub(crate) __get_prototype_of__: fn(&JsObject, &mut Context) -> JsResult<JsPrototype>,
-    pub(crate) __set_prototype_of__: fn(&JsObject, JsPrototype, &mut Context) -> JsResult<bool>,
-    pub(crate) __is_extensible__: fn(&JsObject, &mut Context) -> JsResult<bool>,
-    pub(crate) __prevent_extensions__: fn(&JsObject, &mut Context) -> JsResult<bool>,
-    pub(crate) __get_own_property__:
+pub struct InternalObjectMethods {
+    /// Internal method `[[GetPrototypeOf]]`.
+    pub __get_prototype_of__: fn(&JsObject, &mut Context) -> JsResult<JsPrototype>,
+    /// Internal method `[[SetPrototypeOf]]`.
+    pub __set_prototype_of__: fn(&JsObject, JsPrototype, &mut Context) -> JsResult<bool>,
+    /// Internal method `[[IsExtensible]]`.
+    pub __is_extensible__: fn(&JsObject, &mut Context) -> JsResult<bool>,
+    /// Internal method `[[PreventExtensions]]`.
+    pub __prevent_extensions__: fn(&JsObject, &mut Context) -> JsResult<bool>,
+    /// Internal method `[[GetOwnProperty]]`.
+    pub __get_own_property__:
         fn(&JsObject, &PropertyKey, &mut Context) -> JsResult<Option<PropertyDescriptor>>,
-    pub(crate) __define_own_property__:
+    /// Internal method `[[DefineOwnProperty]]`.
+    pub __define_own_property__:
         fn(&JsObject, PropertyKey, PropertyDescriptor, &mut Context) -> JsResult<bool>,
-    pub(crate) __has_property__: fn(&JsObject, &PropertyKey, &mut Context) -> JsResult<bool>,
-    pub(crate) __get__: fn(&JsObject, &PropertyKey, JsValue, &mut Context) -> JsResult<JsValue>,
-    pub(crate) __set__:
-        fn(&JsObject, PropertyKey, JsValue, JsValue, &mut Context) -> JsResult<bool>,
-    pub(crate) __delete__: fn(&JsObject, &PropertyKey, &mut Context) -> JsResult<bool>,
-    pub(crate) __own_property_keys__: fn(&JsObject, &mut Context) -> JsResult<Vec<PropertyKey>>,
-    pub(crate) __call__:
-        Option<fn(&JsObject, &JsValue, &[JsValue], &mut Context) -> JsResult<JsValue>>,
-    pub(crate) __construct__:
+    /// Internal method `[[HasProperty]]`.
+    pub __has_property__: fn(&JsObject, &PropertyKey, &mut Context) -> JsResult<bool>,
+    /// Internal method `[[Get]]`, with the key and the receiver.
+    pub __get__: fn(&JsObject, &PropertyKey, JsValue, &mut Context) -> JsResult<JsValue>,
+    /// Internal method `[[Set]]`, with the key, the value and the receiver.
+    pub __set__: fn(&JsObject, PropertyKey, JsValue, JsValue, &mut Context) -> JsResult<bool>,
+    /// Internal method `[[Delete]]`.
+    pub __delete__: fn(&JsObject, &PropertyKey, &mut Context) -> JsResult<bool>,
+    /// Internal method `[[OwnPropertyKeys]]`.
+    pub __own_property_keys__: fn(&JsObject, &mut Context) -> JsResult<Vec<PropertyKey>>,
+    /// Internal method `[[Call]]`, only present on callable objects.
+    pub __call__: Option<fn(&JsObject, &JsValue, &[JsValue], &mut Context) -> JsResult<JsValue>>,
+    /// Internal method `[[Construct]]`, only present on constructors.
+    pub __construct__:
         Option<fn(&JsObject, &[JsValue], &JsValue, &mut Context) -> JsResult<JsValue>>,
 }
 
+impl fmt::Debug for InternalObjectMethods {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InternalObjectMethods")
+            .field("callable", &self.__call__.is_some())
+            .field("constructor", &self.__construct__.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
 /// Abstract operation `OrdinaryGetPrototypeOf`.
 ///
 /// More information:
@@ -953,11 +982,32 @@ pub(crate) fn get_prototype_from_constructor<F>(
 where
     F: FnOnce(&StandardConstructors) -> &StandardConstructor,
 {
-    let _timer = Profiler::global().start_event("Object::get_prototype_from_constructor", "object");
     // 1. Assert: intrinsicDefaultProto is this specification's name of an intrinsic
     // object.
     // The corresponding object must be an intrinsic that is intended to be used
     // as the [[Prototype]] value of an object.
+    get_prototype_from_constructor_or_else(
+        constructor,
+        |context| Ok(default(context.intrinsics().constructors()).prototype()),
+        context,
+    )
+}
+
+/// Abstract operation `GetPrototypeFromConstructor`, with a default prototype that is not one of
+/// the intrinsics, such as the prototype of a native class.
+///
+/// `default` is only called if the `prototype` of `constructor` is not an object.
+#[inline]
+#[track_caller]
+pub(crate) fn get_prototype_from_constructor_or_else<F>(
+    constructor: &JsValue,
+    default: F,
+    context: &mut Context,
+) -> JsResult<JsObject>
+where
+    F: FnOnce(&mut Context) -> JsResult<JsObject>,
+{
+    let _timer = Profiler::global().start_event("Object::get_prototype_from_constructor", "object");
     // 2. Let proto be ? Get(constructor, "prototype").
     if let Some(object) = constructor.as_object() {
         if let Some(proto) = object.get(PROTOTYPE, context)?.as_object() {
@@ -968,5 +1018,5 @@ where
     // TODO: handle realms
     // a. Let realm be ? GetFunctionRealm(constructor).
     // b. Set proto to realm's intrinsic object named intrinsicDefaultProto.
-    Ok(default(context.intrinsics().constructors()).prototype())
+    default(context)
 }
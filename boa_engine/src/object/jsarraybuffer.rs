@@ -0,0 +1,120 @@
+use crate::{
+    builtins::array_buffer::ArrayBuffer,
+    object::{JsObject, JsObjectType, ObjectData},
+    Context, JsResult, JsValue,
+};
+use boa_gc::{ExternalMemory, Finalize, Trace};
+use std::ops::Deref;
+
+/// JavaScript `ArrayBuffer` rust object.
+#[derive(Debug, Clone, Trace, Finalize)]
+pub struct JsArrayBuffer {
+    inner: JsObject,
+}
+
+impl JsArrayBuffer {
+    /// Create a new array buffer of `byte_length` bytes initialized to zero, throwing a
+    /// `RangeError` if the memory can't be allocated.
+    ///
+    /// Same as `new ArrayBuffer(byteLength)` in JavaScript.
+    #[inline]
+    pub fn new(byte_length: usize, context: &mut Context) -> JsResult<Self> {
+        let constructor = context
+            .intrinsics()
+            .constructors()
+            .array_buffer()
+            .constructor()
+            .into();
+        let inner = ArrayBuffer::allocate(&constructor, byte_length, context)?;
+
+        Ok(Self { inner })
+    }
+
+    /// Create an array buffer that owns the bytes of `block`.
+    #[inline]
+    pub fn from_byte_block(block: Vec<u8>, context: &mut Context) -> Self {
+        let prototype = context
+            .intrinsics()
+            .constructors()
+            .array_buffer()
+            .prototype();
+        let inner = JsObject::from_proto_and_data(
+            prototype,
+            ObjectData::array_buffer(ArrayBuffer {
+                array_buffer_byte_length: block.len(),
+                external_memory: ExternalMemory::reserve(block.len()),
+                array_buffer_data: Some(block),
+                array_buffer_detach_key: JsValue::undefined(),
+            }),
+        );
+
+        Self { inner }
+    }
+
+    /// Create a [`JsArrayBuffer`] from a [`JsObject`], if the object is not an array buffer throw
+    /// a `TypeError`.
+    ///
+    /// This does not clone the bytes of the buffer, it only does a shallow clone of the object.
+    #[inline]
+    pub fn from_object(object: JsObject, context: &mut Context) -> JsResult<Self> {
+        if object.is_array_buffer() {
+            Ok(Self { inner: object })
+        } else {
+            context.throw_type_error("object is not an ArrayBuffer")
+        }
+    }
+
+    /// Get the length of the buffer in bytes.
+    ///
+    /// Same as `buffer.byteLength` in JavaScript.
+    #[inline]
+    pub fn byte_length(&self) -> usize {
+        self.inner
+            .borrow()
+            .as_array_buffer()
+            .map_or(0, |buffer| buffer.array_buffer_byte_length)
+    }
+
+    /// Check if the buffer has been detached from its bytes.
+    #[inline]
+    pub fn is_detached(&self) -> bool {
+        self.inner
+            .borrow()
+            .as_array_buffer()
+            .map_or(true, |buffer| buffer.array_buffer_data.is_none())
+    }
+
+    /// Copy the bytes of the buffer, or return `None` if the buffer is detached.
+    #[inline]
+    pub fn to_vec(&self) -> Option<Vec<u8>> {
+        self.inner
+            .borrow()
+            .as_array_buffer()
+            .and_then(|buffer| buffer.array_buffer_data.clone())
+    }
+}
+
+impl From<JsArrayBuffer> for JsObject {
+    #[inline]
+    fn from(o: JsArrayBuffer) -> Self {
+        o.inner.clone()
+    }
+}
+
+impl From<JsArrayBuffer> for JsValue {
+    #[inline]
+    fn from(o: JsArrayBuffer) -> Self {
+        o.inner.clone().into()
+    }
+}
+
+impl Deref for JsArrayBuffer {
+    type Target = JsObject;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl JsObjectType for JsArrayBuffer {}
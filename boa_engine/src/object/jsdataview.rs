@@ -0,0 +1,247 @@
+use crate::{
+    builtins::DataView,
+    object::{JsArrayBuffer, JsObject, JsObjectType},
+    value::{IntoOrUndefined, TryFromJs},
+    Context, JsBigInt, JsResult, JsValue,
+};
+use boa_gc::{Finalize, Trace};
+use std::ops::Deref;
+
+/// JavaScript `DataView` rust object.
+#[derive(Debug, Clone, Trace, Finalize)]
+pub struct JsDataView {
+    inner: JsObject,
+}
+
+macro_rules! data_view_accessors {
+    ($($get:ident, $set:ident, $ty:ty, $name:literal;)*) => {
+        $(
+            #[doc = concat!("Read the `", $name, "` at `byte_offset`, throwing a `RangeError` if it is out of bounds.")]
+            #[inline]
+            pub fn $get(
+                &self,
+                byte_offset: usize,
+                little_endian: bool,
+                context: &mut Context,
+            ) -> JsResult<$ty> {
+                let value = DataView::$get(
+                    &self.inner.clone().into(),
+                    &[byte_offset.into(), little_endian.into()],
+                    context,
+                )?;
+                <$ty>::try_from_js(&value, context)
+            }
+
+            #[doc = concat!("Write a `", $name, "` at `byte_offset`, throwing a `RangeError` if it is out of bounds.")]
+            #[inline]
+            pub fn $set(
+                &self,
+                byte_offset: usize,
+                value: $ty,
+                little_endian: bool,
+                context: &mut Context,
+            ) -> JsResult<()> {
+                DataView::$set(
+                    &self.inner.clone().into(),
+                    &[byte_offset.into(), value.into(), little_endian.into()],
+                    context,
+                )?;
+                Ok(())
+            }
+        )*
+    };
+}
+
+impl JsDataView {
+    /// Create a new view over `buffer`, starting at `byte_offset` and spanning `byte_length`
+    /// bytes, or the rest of the buffer if `byte_length` is `None`.
+    ///
+    /// Same as `new DataView(buffer, byteOffset, byteLength)` in JavaScript.
+    #[inline]
+    pub fn new(
+        buffer: &JsArrayBuffer,
+        byte_offset: Option<usize>,
+        byte_length: Option<usize>,
+        context: &mut Context,
+    ) -> JsResult<Self> {
+        let new_target = context
+            .intrinsics()
+            .constructors()
+            .data_view()
+            .constructor()
+            .into();
+        let object = DataView::constructor(
+            &new_target,
+            &[
+                buffer.clone().into(),
+                byte_offset.into_or_undefined(),
+                byte_length.into_or_undefined(),
+            ],
+            context,
+        )?
+        .as_object()
+        .cloned()
+        .expect("DataView constructor should always return object");
+
+        Ok(Self { inner: object })
+    }
+
+    /// Create a [`JsDataView`] from a [`JsObject`], if the object is not a data view throw a
+    /// `TypeError`.
+    #[inline]
+    pub fn from_object(object: JsObject, context: &mut Context) -> JsResult<Self> {
+        if object.borrow().is_data_view() {
+            Ok(Self { inner: object })
+        } else {
+            context.throw_type_error("object is not a DataView")
+        }
+    }
+
+    /// Get the array buffer viewed by this data view.
+    ///
+    /// Same as `view.buffer` in JavaScript.
+    #[inline]
+    pub fn buffer(&self, context: &mut Context) -> JsResult<JsArrayBuffer> {
+        let buffer = DataView::get_buffer(&self.inner.clone().into(), &[], context)?
+            .as_object()
+            .cloned()
+            .expect("DataView.prototype.buffer always returns object");
+        JsArrayBuffer::from_object(buffer, context)
+    }
+
+    /// Get the length of the view in bytes.
+    ///
+    /// Same as `view.byteLength` in JavaScript.
+    #[inline]
+    pub fn byte_length(&self, context: &mut Context) -> JsResult<usize> {
+        let length = DataView::get_byte_length(&self.inner.clone().into(), &[], context)?;
+        usize::try_from_js(&length, context)
+    }
+
+    /// Get the offset of the view from the start of its buffer in bytes.
+    ///
+    /// Same as `view.byteOffset` in JavaScript.
+    #[inline]
+    pub fn byte_offset(&self, context: &mut Context) -> JsResult<usize> {
+        let offset = DataView::get_byte_offset(&self.inner.clone().into(), &[], context)?;
+        usize::try_from_js(&offset, context)
+    }
+
+    data_view_accessors! {
+        get_int8, set_int8, i8, "i8";
+        get_uint8, set_uint8, u8, "u8";
+        get_int16, set_int16, i16, "i16";
+        get_uint16, set_uint16, u16, "u16";
+        get_int32, set_int32, i32, "i32";
+        get_uint32, set_uint32, u32, "u32";
+        get_float32, set_float32, f32, "f32";
+        get_float64, set_float64, f64, "f64";
+    }
+
+    /// Read the `i64` at `byte_offset`, throwing a `RangeError` if it is out of bounds.
+    #[inline]
+    pub fn get_big_int64(
+        &self,
+        byte_offset: usize,
+        little_endian: bool,
+        context: &mut Context,
+    ) -> JsResult<i64> {
+        let value = DataView::get_big_int64(
+            &self.inner.clone().into(),
+            &[byte_offset.into(), little_endian.into()],
+            context,
+        )?;
+        Ok(value
+            .as_bigint()
+            .and_then(JsBigInt::to_i128)
+            .and_then(|x| i64::try_from(x).ok())
+            .expect("DataView.prototype.getBigInt64 always returns a 64-bit bigint"))
+    }
+
+    /// Write an `i64` at `byte_offset`, throwing a `RangeError` if it is out of bounds.
+    #[inline]
+    pub fn set_big_int64(
+        &self,
+        byte_offset: usize,
+        value: i64,
+        little_endian: bool,
+        context: &mut Context,
+    ) -> JsResult<()> {
+        DataView::set_big_int64(
+            &self.inner.clone().into(),
+            &[
+                byte_offset.into(),
+                JsBigInt::new(value).into(),
+                little_endian.into(),
+            ],
+            context,
+        )?;
+        Ok(())
+    }
+
+    /// Read the `u64` at `byte_offset`, throwing a `RangeError` if it is out of bounds.
+    #[inline]
+    pub fn get_big_uint64(
+        &self,
+        byte_offset: usize,
+        little_endian: bool,
+        context: &mut Context,
+    ) -> JsResult<u64> {
+        let value = DataView::get_big_uint64(
+            &self.inner.clone().into(),
+            &[byte_offset.into(), little_endian.into()],
+            context,
+        )?;
+        Ok(value
+            .as_bigint()
+            .and_then(JsBigInt::to_u128)
+            .and_then(|x| u64::try_from(x).ok())
+            .expect("DataView.prototype.getBigUint64 always returns a 64-bit bigint"))
+    }
+
+    /// Write a `u64` at `byte_offset`, throwing a `RangeError` if it is out of bounds.
+    #[inline]
+    pub fn set_big_uint64(
+        &self,
+        byte_offset: usize,
+        value: u64,
+        little_endian: bool,
+        context: &mut Context,
+    ) -> JsResult<()> {
+        DataView::set_big_uint64(
+            &self.inner.clone().into(),
+            &[
+                byte_offset.into(),
+                JsBigInt::new(value).into(),
+                little_endian.into(),
+            ],
+            context,
+        )?;
+        Ok(())
+    }
+}
+
+impl From<JsDataView> for JsObject {
+    #[inline]
+    fn from(o: JsDataView) -> Self {
+        o.inner.clone()
+    }
+}
+
+impl From<JsDataView> for JsValue {
+    #[inline]
+    fn from(o: JsDataView) -> Self {
+        o.inner.clone().into()
+    }
+}
+
+impl Deref for JsDataView {
+    type Target = JsObject;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl JsObjectType for JsDataView {}
@@ -0,0 +1,140 @@
+use crate::{
+    builtins::Date,
+    object::{JsObject, JsObjectType},
+    Context, JsResult, JsString, JsValue,
+};
+use boa_gc::{Finalize, Trace};
+use std::ops::Deref;
+
+/// JavaScript `Date` rust object.
+#[derive(Debug, Clone, Trace, Finalize)]
+pub struct JsDate {
+    inner: JsObject,
+}
+
+impl JsDate {
+    /// Create a new date representing the current date and time.
+    ///
+    /// Same as `new Date()` in JavaScript.
+    #[inline]
+    pub fn new(context: &mut Context) -> Self {
+        let new_target = context
+            .intrinsics()
+            .constructors()
+            .date()
+            .constructor()
+            .into();
+        let object = Date::constructor(&new_target, &[], context)
+            .expect("creating a date with the default prototype must not fail")
+            .as_object()
+            .cloned()
+            .expect("Date constructor should always return object");
+
+        Self { inner: object }
+    }
+
+    /// Create a new date from a number of milliseconds since the UNIX epoch.
+    ///
+    /// Same as `new Date(time)` in JavaScript.
+    #[inline]
+    pub fn from_time(time: f64, context: &mut Context) -> Self {
+        let new_target = context
+            .intrinsics()
+            .constructors()
+            .date()
+            .constructor()
+            .into();
+        let object = Date::constructor(&new_target, &[time.into()], context)
+            .expect("creating a date from a number must not fail")
+            .as_object()
+            .cloned()
+            .expect("Date constructor should always return object");
+
+        Self { inner: object }
+    }
+
+    /// Create a [`JsDate`] from a [`JsObject`], if the object is not a date throw a `TypeError`.
+    #[inline]
+    pub fn from_object(object: JsObject, context: &mut Context) -> JsResult<Self> {
+        if object.borrow().is_date() {
+            Ok(Self { inner: object })
+        } else {
+            context.throw_type_error("object is not a Date")
+        }
+    }
+
+    /// Get the number of milliseconds since the UNIX epoch, or `NaN` for an invalid date.
+    ///
+    /// Same as `date.getTime()` in JavaScript.
+    #[inline]
+    pub fn get_time(&self) -> f64 {
+        self.inner
+            .borrow()
+            .as_date()
+            .map_or(f64::NAN, Date::get_time)
+    }
+
+    /// Set the number of milliseconds since the UNIX epoch, returning the new time value.
+    ///
+    /// Same as `date.setTime(time)` in JavaScript.
+    #[inline]
+    pub fn set_time(&self, time: f64, context: &mut Context) -> JsResult<f64> {
+        Date::set_time(&self.inner.clone().into(), &[time.into()], context)?.to_number(context)
+    }
+
+    /// Convert the date to a string in the simplified ISO 8601 format, throwing a `RangeError`
+    /// for an invalid date.
+    #[inline]
+    pub fn to_iso_string(&self, context: &mut Context) -> JsResult<JsString> {
+        Date::to_iso_string(&self.inner.clone().into(), &[], context).map(|x| {
+            x.as_string()
+                .cloned()
+                .expect("Date.prototype.toISOString always returns string")
+        })
+    }
+
+    /// Convert the date to a string in the local time zone.
+    #[inline]
+    pub fn to_string(&self, context: &mut Context) -> JsResult<JsString> {
+        Date::to_string(&self.inner.clone().into(), &[], context).map(|x| {
+            x.as_string()
+                .cloned()
+                .expect("Date.prototype.toString always returns string")
+        })
+    }
+
+    /// Convert the date to a string in the UTC time zone.
+    #[inline]
+    pub fn to_utc_string(&self) -> String {
+        self.inner
+            .borrow()
+            .as_date()
+            .copied()
+            .map_or_else(|| "Invalid Date".to_owned(), Date::to_utc_string)
+    }
+}
+
+impl From<JsDate> for JsObject {
+    #[inline]
+    fn from(o: JsDate) -> Self {
+        o.inner.clone()
+    }
+}
+
+impl From<JsDate> for JsValue {
+    #[inline]
+    fn from(o: JsDate) -> Self {
+        o.inner.clone().into()
+    }
+}
+
+impl Deref for JsDate {
+    type Target = JsObject;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl JsObjectType for JsDate {}
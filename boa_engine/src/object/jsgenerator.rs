@@ -0,0 +1,87 @@
+use crate::{
+    builtins::generator::Generator,
+    object::{JsObject, JsObjectType},
+    Context, JsResult, JsValue,
+};
+use boa_gc::{Finalize, Trace};
+use std::ops::Deref;
+
+/// JavaScript `Generator` rust object.
+#[derive(Debug, Clone, Trace, Finalize)]
+pub struct JsGenerator {
+    inner: JsObject,
+}
+
+impl JsGenerator {
+    /// Create a [`JsGenerator`] from a [`JsObject`], if the object is not a generator throw a
+    /// `TypeError`.
+    #[inline]
+    pub fn from_object(object: JsObject, context: &mut Context) -> JsResult<Self> {
+        if object.is_generator() {
+            Ok(Self { inner: object })
+        } else {
+            context.throw_type_error("object is not a Generator")
+        }
+    }
+
+    /// Resume the generator with `value`, returning an iterator result object with the `value`
+    /// and `done` properties.
+    ///
+    /// Same as `generator.next(value)` in JavaScript.
+    #[inline]
+    pub fn next<T>(&self, value: T, context: &mut Context) -> JsResult<JsValue>
+    where
+        T: Into<JsValue>,
+    {
+        Generator::next(&self.inner.clone().into(), &[value.into()], context)
+    }
+
+    /// Finish the generator as if a `return value;` statement was executed at its current
+    /// position, returning an iterator result object.
+    ///
+    /// Same as `generator.return(value)` in JavaScript.
+    #[inline]
+    pub fn r#return<T>(&self, value: T, context: &mut Context) -> JsResult<JsValue>
+    where
+        T: Into<JsValue>,
+    {
+        Generator::r#return(&self.inner.clone().into(), &[value.into()], context)
+    }
+
+    /// Resume the generator by throwing `error` at its current position, returning an iterator
+    /// result object if the generator catches the error.
+    ///
+    /// Same as `generator.throw(error)` in JavaScript.
+    #[inline]
+    pub fn throw<T>(&self, error: T, context: &mut Context) -> JsResult<JsValue>
+    where
+        T: Into<JsValue>,
+    {
+        Generator::throw(&self.inner.clone().into(), &[error.into()], context)
+    }
+}
+
+impl From<JsGenerator> for JsObject {
+    #[inline]
+    fn from(o: JsGenerator) -> Self {
+        o.inner.clone()
+    }
+}
+
+impl From<JsGenerator> for JsValue {
+    #[inline]
+    fn from(o: JsGenerator) -> Self {
+        o.inner.clone().into()
+    }
+}
+
+impl Deref for JsGenerator {
+    type Target = JsObject;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl JsObjectType for JsGenerator {}
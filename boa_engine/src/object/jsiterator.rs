@@ -0,0 +1,103 @@
+use crate::{builtins::iterable::IteratorRecord, Context, JsResult, JsValue};
+
+/// Rust iterator over the values of a JavaScript iterable.
+///
+/// This is the `for (const value of iterable)` loop of JavaScript: every call to
+/// [`next`][Iterator::next] calls the `next` method of the JavaScript iterator, and errors thrown
+/// by it are returned as `Some(Err(error))`, after which the iteration stops.
+///
+/// If the iterator is dropped before it's exhausted, the `return` method of the JavaScript
+/// iterator is called, like when breaking out of a `for..of` loop. Use [`close`][Self::close] to
+/// get the error thrown by `return`, if any.
+///
+/// The iterator borrows the context mutably until it's dropped, so the context can't be used
+/// directly while iterating. Use [`context`][Self::context] to work with the values inside the
+/// loop, and drop the iterator (or let it go out of scope) before using the context again.
+///
+/// ```
+/// # use boa_engine::{object::JsIterator, Context, JsValue};
+/// let mut context = Context::default();
+/// let set = context.eval("new Set([1, 2, 3])").unwrap();
+///
+/// let values = JsIterator::new(&set, &mut context)
+///     .unwrap()
+///     .collect::<Result<Vec<_>, _>>()
+///     .unwrap();
+/// assert_eq!(values, [JsValue::new(1), JsValue::new(2), JsValue::new(3)]);
+/// ```
+#[derive(Debug)]
+pub struct JsIterator<'context> {
+    record: IteratorRecord,
+    done: bool,
+    context: &'context mut Context,
+}
+
+impl<'context> JsIterator<'context> {
+    /// Get an iterator of `iterable` by calling its `@@iterator` method, throwing a `TypeError`
+    /// if it's not iterable.
+    #[inline]
+    pub fn new(iterable: &JsValue, context: &'context mut Context) -> JsResult<Self> {
+        let record = iterable.get_iterator(context, None, None)?;
+
+        Ok(Self {
+            record,
+            done: false,
+            context,
+        })
+    }
+
+    /// Get the context used by the iterator, to work with the values between iterations.
+    #[inline]
+    pub fn context(&mut self) -> &mut Context {
+        self.context
+    }
+
+    /// Stop the iteration, calling the `return` method of the JavaScript iterator if it was not
+    /// exhausted.
+    #[inline]
+    pub fn close(mut self) -> JsResult<()> {
+        self.close_inner()
+    }
+
+    fn close_inner(&mut self) -> JsResult<()> {
+        if self.done {
+            return Ok(());
+        }
+        self.done = true;
+        self.record
+            .close(Ok(JsValue::undefined()), self.context)
+            .map(|_| ())
+    }
+}
+
+impl Iterator for JsIterator<'_> {
+    type Item = JsResult<JsValue>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let value = match self.record.step(self.context) {
+            Ok(Some(result)) => result.value(self.context),
+            Ok(None) => {
+                self.done = true;
+                return None;
+            }
+            Err(error) => Err(error),
+        };
+
+        // An iterator that throws is not closed, so the iteration just stops.
+        if value.is_err() {
+            self.done = true;
+        }
+        Some(value)
+    }
+}
+
+impl Drop for JsIterator<'_> {
+    fn drop(&mut self) {
+        // Errors can't be reported from `drop`, `close` must be used to observe them.
+        self.close_inner().ok();
+    }
+}
@@ -0,0 +1,191 @@
+use crate::{
+    builtins::{map::ordered_map::OrderedMap, Map},
+    object::{JsFunction, JsObject, JsObjectType},
+    value::IntoOrUndefined,
+    Context, JsResult, JsValue,
+};
+use boa_gc::{Finalize, Trace};
+use std::ops::Deref;
+
+/// JavaScript `Map` rust object.
+#[derive(Debug, Clone, Trace, Finalize)]
+pub struct JsMap {
+    inner: JsObject,
+}
+
+impl JsMap {
+    /// Create a new empty map.
+    #[inline]
+    pub fn new(context: &mut Context) -> Self {
+        let new_target = context
+            .intrinsics()
+            .constructors()
+            .map()
+            .constructor()
+            .into();
+        let object = Map::constructor(&new_target, &[], context)
+            .expect("creating an empty map with the default prototype must not fail")
+            .as_object()
+            .cloned()
+            .expect("Map constructor should always return object");
+
+        Self { inner: object }
+    }
+
+    /// Create a map from an `IntoIterator<Item = (JsValue, JsValue)>` of entries.
+    #[inline]
+    pub fn from_entries<I>(entries: I, context: &mut Context) -> JsResult<Self>
+    where
+        I: IntoIterator<Item = (JsValue, JsValue)>,
+    {
+        let map = Self::new(context);
+        for (key, value) in entries {
+            map.set(key, value, context)?;
+        }
+        Ok(map)
+    }
+
+    /// Create a [`JsMap`] from a [`JsObject`], if the object is not a map throw a `TypeError`.
+    ///
+    /// This does not clone the entries of the map, it only does a shallow clone of the object.
+    #[inline]
+    pub fn from_object(object: JsObject, context: &mut Context) -> JsResult<Self> {
+        if object.is_map() {
+            Ok(Self { inner: object })
+        } else {
+            context.throw_type_error("object is not a Map")
+        }
+    }
+
+    /// Get the number of entries of the map.
+    ///
+    /// Same as `map.size` in JavaScript.
+    #[inline]
+    pub fn size(&self) -> usize {
+        self.inner.borrow().as_map_ref().map_or(0, OrderedMap::len)
+    }
+
+    /// Check if the map has no entries.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.size() == 0
+    }
+
+    /// Get the value associated with `key`, or `undefined` if the map has no such key.
+    #[inline]
+    pub fn get<K>(&self, key: K, context: &mut Context) -> JsResult<JsValue>
+    where
+        K: Into<JsValue>,
+    {
+        Map::get(&self.inner.clone().into(), &[key.into()], context)
+    }
+
+    /// Set the value associated with `key`.
+    #[inline]
+    pub fn set<K, V>(&self, key: K, value: V, context: &mut Context) -> JsResult<JsValue>
+    where
+        K: Into<JsValue>,
+        V: Into<JsValue>,
+    {
+        Map::set(
+            &self.inner.clone().into(),
+            &[key.into(), value.into()],
+            context,
+        )
+    }
+
+    /// Check if the map has an entry for `key`.
+    #[inline]
+    pub fn has<K>(&self, key: K, context: &mut Context) -> JsResult<bool>
+    where
+        K: Into<JsValue>,
+    {
+        Map::has(&self.inner.clone().into(), &[key.into()], context).map(|x| x.to_boolean())
+    }
+
+    /// Remove the entry for `key`, returning `true` if the map had one.
+    #[inline]
+    pub fn delete<K>(&self, key: K, context: &mut Context) -> JsResult<bool>
+    where
+        K: Into<JsValue>,
+    {
+        Map::delete(&self.inner.clone().into(), &[key.into()], context).map(|x| x.to_boolean())
+    }
+
+    /// Remove all the entries of the map.
+    #[inline]
+    pub fn clear(&self, context: &mut Context) -> JsResult<JsValue> {
+        Map::clear(&self.inner.clone().into(), &[], context)
+    }
+
+    /// Get an iterator object over the `[key, value]` pairs of the map.
+    #[inline]
+    pub fn entries(&self, context: &mut Context) -> JsResult<JsObject> {
+        Map::entries(&self.inner.clone().into(), &[], context).map(|x| {
+            x.as_object()
+                .cloned()
+                .expect("Map.prototype.entries always returns object")
+        })
+    }
+
+    /// Get an iterator object over the keys of the map.
+    #[inline]
+    pub fn keys(&self, context: &mut Context) -> JsResult<JsObject> {
+        Map::keys(&self.inner.clone().into(), &[], context).map(|x| {
+            x.as_object()
+                .cloned()
+                .expect("Map.prototype.keys always returns object")
+        })
+    }
+
+    /// Get an iterator object over the values of the map.
+    #[inline]
+    pub fn values(&self, context: &mut Context) -> JsResult<JsObject> {
+        Map::values(&self.inner.clone().into(), &[], context).map(|x| {
+            x.as_object()
+                .cloned()
+                .expect("Map.prototype.values always returns object")
+        })
+    }
+
+    /// Call `callback` with the value, the key and the map for each entry of the map.
+    #[inline]
+    pub fn for_each(
+        &self,
+        callback: JsFunction,
+        this_arg: Option<JsValue>,
+        context: &mut Context,
+    ) -> JsResult<()> {
+        Map::for_each(
+            &self.inner.clone().into(),
+            &[callback.into(), this_arg.into_or_undefined()],
+            context,
+        )?;
+        Ok(())
+    }
+}
+
+impl From<JsMap> for JsObject {
+    #[inline]
+    fn from(o: JsMap) -> Self {
+        o.inner.clone()
+    }
+}
+
+impl From<JsMap> for JsValue {
+    #[inline]
+    fn from(o: JsMap) -> Self {
+        o.inner.clone().into()
+    }
+}
+
+impl Deref for JsMap {
+    type Target = JsObject;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl JsObjectType for JsMap {}
@@ -0,0 +1,376 @@
+use crate::{
+    builtins::{
+        function::{Captures, ClosureFunctionSignature},
+        Proxy,
+    },
+    object::{FunctionBuilder, JsObject, JsObjectType, NativeObject},
+    Context, JsResult, JsValue,
+};
+use boa_gc::{Finalize, Trace};
+use std::{fmt, ops::Deref};
+
+/// JavaScript `Proxy` rust object.
+#[derive(Debug, Clone, Trace, Finalize)]
+pub struct JsProxy {
+    inner: JsObject,
+}
+
+impl JsProxy {
+    /// Create a new proxy for `target`, with the traps defined by `handler`.
+    ///
+    /// Same as `new Proxy(target, handler)` in JavaScript.
+    #[inline]
+    pub fn new(target: &JsObject, handler: &JsObject, context: &mut Context) -> Self {
+        let object = Proxy::create(&target.clone().into(), &handler.clone().into(), context)
+            .expect("ProxyCreate with an object target and handler must not fail")
+            .as_object()
+            .cloned()
+            .expect("ProxyCreate always returns object");
+
+        Self { inner: object }
+    }
+
+    /// Create a [`JsProxy`] from a [`JsObject`], if the object is not a proxy throw a
+    /// `TypeError`.
+    #[inline]
+    pub fn from_object(object: JsObject, context: &mut Context) -> JsResult<Self> {
+        if object.borrow().is_proxy() {
+            Ok(Self { inner: object })
+        } else {
+            context.throw_type_error("object is not a Proxy")
+        }
+    }
+
+    /// Get the target of the proxy, throwing a `TypeError` if the proxy has been revoked.
+    #[inline]
+    pub fn target(&self, context: &mut Context) -> JsResult<JsObject> {
+        self.data(context).map(|(target, _)| target)
+    }
+
+    /// Get the handler of the proxy, throwing a `TypeError` if the proxy has been revoked.
+    #[inline]
+    pub fn handler(&self, context: &mut Context) -> JsResult<JsObject> {
+        self.data(context).map(|(_, handler)| handler)
+    }
+
+    fn data(&self, context: &mut Context) -> JsResult<(JsObject, JsObject)> {
+        self.inner
+            .borrow()
+            .as_proxy()
+            .expect("JsProxy must always wrap a proxy")
+            .try_data(context)
+    }
+}
+
+impl From<JsProxy> for JsObject {
+    #[inline]
+    fn from(o: JsProxy) -> Self {
+        o.inner.clone()
+    }
+}
+
+impl From<JsProxy> for JsValue {
+    #[inline]
+    fn from(o: JsProxy) -> Self {
+        o.inner.clone().into()
+    }
+}
+
+impl Deref for JsProxy {
+    type Target = JsObject;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl JsObjectType for JsProxy {}
+
+/// Builder for creating a [`JsProxy`] whose traps are Rust closures.
+///
+/// The handler object of the proxy is created by [`build`][Self::build], with a method for each
+/// trap that was set. Traps are called like the methods of a handler defined in JavaScript: `this`
+/// is the handler, and the arguments are the target followed by the arguments of the trapped
+/// operation. Operations without a trap are forwarded to the target.
+///
+/// Like [`FunctionBuilder::closure`], traps must be `Copy`, so they can't capture values that
+/// hold garbage collected objects. The `*_with_captures` variants of the trap setters pass
+/// `captures` to the trap instead, like [`FunctionBuilder::closure_with_captures`].
+///
+/// ```
+/// # use boa_engine::{object::JsProxyBuilder, Context, JsValue};
+/// let mut context = Context::default();
+/// let target = context.construct_object();
+/// let proxy = JsProxyBuilder::new(target)
+///     .get(|_, args, context| {
+///         let key = args.get(1).cloned().unwrap_or_default().to_string(context)?;
+///         Ok(format!("config.{}", key.as_str()).into())
+///     })
+///     .has(|_, _, _| Ok(true.into()))
+///     .build(&mut context);
+///
+/// assert_eq!(
+///     proxy.get("port", &mut context).unwrap(),
+///     JsValue::new("config.port")
+/// );
+/// ```
+#[derive(Clone)]
+pub struct JsProxyBuilder {
+    target: JsObject,
+    traps: Vec<(&'static str, Box<dyn ClosureFunctionSignature>, Captures)>,
+}
+
+impl JsProxyBuilder {
+    /// Create a builder for a proxy of `target`, without any traps.
+    #[inline]
+    pub fn new(target: JsObject) -> Self {
+        Self {
+            target,
+            traps: Vec::new(),
+        }
+    }
+
+    /// Set the trap called `name`, replacing a previous trap with the same name.
+    fn trap<F>(mut self, name: &'static str, trap: F) -> Self
+    where
+        F: Fn(&JsValue, &[JsValue], &mut Context) -> JsResult<JsValue> + Copy + 'static,
+    {
+        let trap: Box<dyn ClosureFunctionSignature> =
+            Box::new(move |this, args, _, context| trap(this, args, context));
+        self.traps.retain(|(trap_name, _, _)| *trap_name != name);
+        self.traps.push((name, trap, Captures::new(())));
+        self
+    }
+
+    /// Set the trap called `name` with `captures`, replacing a previous trap with the same name.
+    fn trap_with_captures<F, C>(mut self, name: &'static str, trap: F, captures: C) -> Self
+    where
+        F: Fn(&JsValue, &[JsValue], &mut C, &mut Context) -> JsResult<JsValue> + Copy + 'static,
+        C: NativeObject,
+    {
+        let trap: Box<dyn ClosureFunctionSignature> =
+            Box::new(move |this, args, captures: Captures, context| {
+                let mut captures = captures.as_mut_any();
+                let captures = captures.downcast_mut::<C>().ok_or_else(|| {
+                    context.construct_type_error("cannot downcast `Captures` to given type")
+                })?;
+                trap(this, args, captures, context)
+            });
+        self.traps.retain(|(trap_name, _, _)| *trap_name != name);
+        self.traps.push((name, trap, Captures::new(captures)));
+        self
+    }
+
+    /// Set the `get` trap, called with the target, the property key and the receiver when a
+    /// property is read. It returns the value of the property.
+    #[inline]
+    #[must_use]
+    pub fn get<F>(self, trap: F) -> Self
+    where
+        F: Fn(&JsValue, &[JsValue], &mut Context) -> JsResult<JsValue> + Copy + 'static,
+    {
+        self.trap("get", trap)
+    }
+
+    /// Set the `get` trap like [`get`][Self::get], passing `captures` to the trap.
+    #[inline]
+    #[must_use]
+    pub fn get_with_captures<F, C>(self, trap: F, captures: C) -> Self
+    where
+        F: Fn(&JsValue, &[JsValue], &mut C, &mut Context) -> JsResult<JsValue> + Copy + 'static,
+        C: NativeObject,
+    {
+        self.trap_with_captures("get", trap, captures)
+    }
+
+    /// Set the `set` trap, called with the target, the property key, the value and the receiver
+    /// when a property is assigned. It returns whether the assignment succeeded.
+    #[inline]
+    #[must_use]
+    pub fn set<F>(self, trap: F) -> Self
+    where
+        F: Fn(&JsValue, &[JsValue], &mut Context) -> JsResult<JsValue> + Copy + 'static,
+    {
+        self.trap("set", trap)
+    }
+
+    /// Set the `set` trap like [`set`][Self::set], passing `captures` to the trap.
+    #[inline]
+    #[must_use]
+    pub fn set_with_captures<F, C>(self, trap: F, captures: C) -> Self
+    where
+        F: Fn(&JsValue, &[JsValue], &mut C, &mut Context) -> JsResult<JsValue> + Copy + 'static,
+        C: NativeObject,
+    {
+        self.trap_with_captures("set", trap, captures)
+    }
+
+    /// Set the `has` trap, called with the target and the property key by the `in` operator. It
+    /// returns whether the property exists.
+    #[inline]
+    #[must_use]
+    pub fn has<F>(self, trap: F) -> Self
+    where
+        F: Fn(&JsValue, &[JsValue], &mut Context) -> JsResult<JsValue> + Copy + 'static,
+    {
+        self.trap("has", trap)
+    }
+
+    /// Set the `has` trap like [`has`][Self::has], passing `captures` to the trap.
+    #[inline]
+    #[must_use]
+    pub fn has_with_captures<F, C>(self, trap: F, captures: C) -> Self
+    where
+        F: Fn(&JsValue, &[JsValue], &mut C, &mut Context) -> JsResult<JsValue> + Copy + 'static,
+        C: NativeObject,
+    {
+        self.trap_with_captures("has", trap, captures)
+    }
+
+    /// Set the `deleteProperty` trap, called with the target and the property key by the
+    /// `delete` operator. It returns whether the property was deleted.
+    #[inline]
+    #[must_use]
+    pub fn delete_property<F>(self, trap: F) -> Self
+    where
+        F: Fn(&JsValue, &[JsValue], &mut Context) -> JsResult<JsValue> + Copy + 'static,
+    {
+        self.trap("deleteProperty", trap)
+    }
+
+    /// Set the `deleteProperty` trap like [`delete_property`][Self::delete_property],
+    /// passing `captures` to the trap.
+    #[inline]
+    #[must_use]
+    pub fn delete_property_with_captures<F, C>(self, trap: F, captures: C) -> Self
+    where
+        F: Fn(&JsValue, &[JsValue], &mut C, &mut Context) -> JsResult<JsValue> + Copy + 'static,
+        C: NativeObject,
+    {
+        self.trap_with_captures("deleteProperty", trap, captures)
+    }
+
+    /// Set the `ownKeys` trap, called with the target by `Reflect.ownKeys` and by the functions
+    /// that enumerate properties. It returns an array of strings and symbols.
+    #[inline]
+    #[must_use]
+    pub fn own_keys<F>(self, trap: F) -> Self
+    where
+        F: Fn(&JsValue, &[JsValue], &mut Context) -> JsResult<JsValue> + Copy + 'static,
+    {
+        self.trap("ownKeys", trap)
+    }
+
+    /// Set the `ownKeys` trap like [`own_keys`][Self::own_keys], passing `captures` to the trap.
+    #[inline]
+    #[must_use]
+    pub fn own_keys_with_captures<F, C>(self, trap: F, captures: C) -> Self
+    where
+        F: Fn(&JsValue, &[JsValue], &mut C, &mut Context) -> JsResult<JsValue> + Copy + 'static,
+        C: NativeObject,
+    {
+        self.trap_with_captures("ownKeys", trap, captures)
+    }
+
+    /// Set the `getOwnPropertyDescriptor` trap, called with the target and the property key. It
+    /// returns a property descriptor object, or `undefined` if the property doesn't exist.
+    #[inline]
+    #[must_use]
+    pub fn get_own_property_descriptor<F>(self, trap: F) -> Self
+    where
+        F: Fn(&JsValue, &[JsValue], &mut Context) -> JsResult<JsValue> + Copy + 'static,
+    {
+        self.trap("getOwnPropertyDescriptor", trap)
+    }
+
+    /// Set the `getOwnPropertyDescriptor` trap like
+    /// [`get_own_property_descriptor`][Self::get_own_property_descriptor], passing `captures` to
+    /// the trap.
+    #[inline]
+    #[must_use]
+    pub fn get_own_property_descriptor_with_captures<F, C>(self, trap: F, captures: C) -> Self
+    where
+        F: Fn(&JsValue, &[JsValue], &mut C, &mut Context) -> JsResult<JsValue> + Copy + 'static,
+        C: NativeObject,
+    {
+        self.trap_with_captures("getOwnPropertyDescriptor", trap, captures)
+    }
+
+    /// Set the `apply` trap, called with the target, the `this` value and an array of the
+    /// arguments when the proxy is called. It is only used if the target is callable.
+    #[inline]
+    #[must_use]
+    pub fn apply<F>(self, trap: F) -> Self
+    where
+        F: Fn(&JsValue, &[JsValue], &mut Context) -> JsResult<JsValue> + Copy + 'static,
+    {
+        self.trap("apply", trap)
+    }
+
+    /// Set the `apply` trap like [`apply`][Self::apply], passing `captures` to the trap.
+    #[inline]
+    #[must_use]
+    pub fn apply_with_captures<F, C>(self, trap: F, captures: C) -> Self
+    where
+        F: Fn(&JsValue, &[JsValue], &mut C, &mut Context) -> JsResult<JsValue> + Copy + 'static,
+        C: NativeObject,
+    {
+        self.trap_with_captures("apply", trap, captures)
+    }
+
+    /// Set the `construct` trap, called with the target, an array of the arguments and the new
+    /// target when the proxy is called with `new`. It returns the new object, and is only used if
+    /// the target is a constructor.
+    #[inline]
+    #[must_use]
+    pub fn construct<F>(self, trap: F) -> Self
+    where
+        F: Fn(&JsValue, &[JsValue], &mut Context) -> JsResult<JsValue> + Copy + 'static,
+    {
+        self.trap("construct", trap)
+    }
+
+    /// Set the `construct` trap like [`construct`][Self::construct],
+    /// passing `captures` to the trap.
+    #[inline]
+    #[must_use]
+    pub fn construct_with_captures<F, C>(self, trap: F, captures: C) -> Self
+    where
+        F: Fn(&JsValue, &[JsValue], &mut C, &mut Context) -> JsResult<JsValue> + Copy + 'static,
+        C: NativeObject,
+    {
+        self.trap_with_captures("construct", trap, captures)
+    }
+
+    /// Create the handler object and the proxy.
+    pub fn build(self, context: &mut Context) -> JsProxy {
+        let handler = context.construct_object();
+        for (name, trap, captures) in self.traps {
+            let function = FunctionBuilder::boxed_closure(context, trap, captures)
+                .name(name)
+                .build();
+            handler
+                .create_data_property_or_throw(name, function, context)
+                .expect("defining a property of a new object must not fail");
+        }
+
+        JsProxy::new(&self.target, &handler, context)
+    }
+}
+
+impl fmt::Debug for JsProxyBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JsProxyBuilder")
+            .field("target", &self.target)
+            .field(
+                "traps",
+                &self
+                    .traps
+                    .iter()
+                    .map(|(name, _, _)| name)
+                    .collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
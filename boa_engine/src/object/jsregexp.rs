@@ -0,0 +1,129 @@
+use crate::{
+    builtins::RegExp,
+    object::{JsArray, JsObject, JsObjectType},
+    Context, JsResult, JsString, JsValue,
+};
+use boa_gc::{Finalize, Trace};
+use std::ops::Deref;
+
+/// JavaScript `RegExp` rust object.
+#[derive(Debug, Clone, Trace, Finalize)]
+pub struct JsRegExp {
+    inner: JsObject,
+}
+
+impl JsRegExp {
+    /// Create a new regular expression from a pattern and flags, throwing a `SyntaxError` if the
+    /// pattern or the flags are invalid.
+    ///
+    /// Same as `new RegExp(pattern, flags)` in JavaScript.
+    #[inline]
+    pub fn new<P, F>(pattern: P, flags: F, context: &mut Context) -> JsResult<Self>
+    where
+        P: Into<JsString>,
+        F: Into<JsString>,
+    {
+        let object = RegExp::create(&pattern.into().into(), &flags.into().into(), context)?
+            .as_object()
+            .cloned()
+            .expect("RegExpCreate always returns object");
+
+        Ok(Self { inner: object })
+    }
+
+    /// Create a [`JsRegExp`] from a [`JsObject`], if the object is not a regular expression throw
+    /// a `TypeError`.
+    #[inline]
+    pub fn from_object(object: JsObject, context: &mut Context) -> JsResult<Self> {
+        if object.is_regexp() {
+            Ok(Self { inner: object })
+        } else {
+            context.throw_type_error("object is not a RegExp")
+        }
+    }
+
+    /// Get the source text of the regular expression.
+    ///
+    /// Same as `regexp.source` in JavaScript.
+    #[inline]
+    pub fn source(&self, context: &mut Context) -> JsResult<JsString> {
+        RegExp::get_source(&self.inner.clone().into(), &[], context).map(|x| {
+            x.as_string()
+                .cloned()
+                .expect("RegExp.prototype.source always returns string")
+        })
+    }
+
+    /// Get the flags of the regular expression.
+    ///
+    /// Same as `regexp.flags` in JavaScript.
+    #[inline]
+    pub fn flags(&self, context: &mut Context) -> JsResult<JsString> {
+        RegExp::get_flags(&self.inner.clone().into(), &[], context).map(|x| {
+            x.as_string()
+                .cloned()
+                .expect("RegExp.prototype.flags always returns string")
+        })
+    }
+
+    /// Check if the regular expression matches `input`, updating `lastIndex` for global and
+    /// sticky regular expressions.
+    #[inline]
+    pub fn test<S>(&self, input: S, context: &mut Context) -> JsResult<bool>
+    where
+        S: Into<JsString>,
+    {
+        RegExp::test(&self.inner.clone().into(), &[input.into().into()], context)
+            .map(|x| x.to_boolean())
+    }
+
+    /// Search for a match in `input`, returning the match array or `None` if there is no match.
+    ///
+    /// Same as `regexp.exec(input)` in JavaScript.
+    #[inline]
+    pub fn exec<S>(&self, input: S, context: &mut Context) -> JsResult<Option<JsArray>>
+    where
+        S: Into<JsString>,
+    {
+        let result = RegExp::exec(&self.inner.clone().into(), &[input.into().into()], context)?;
+        match result.as_object() {
+            Some(object) => JsArray::from_object(object.clone(), context).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Convert the regular expression to a string of the form `/source/flags`.
+    #[inline]
+    pub fn to_string(&self, context: &mut Context) -> JsResult<JsString> {
+        RegExp::to_string(&self.inner.clone().into(), &[], context).map(|x| {
+            x.as_string()
+                .cloned()
+                .expect("RegExp.prototype.toString always returns string")
+        })
+    }
+}
+
+impl From<JsRegExp> for JsObject {
+    #[inline]
+    fn from(o: JsRegExp) -> Self {
+        o.inner.clone()
+    }
+}
+
+impl From<JsRegExp> for JsValue {
+    #[inline]
+    fn from(o: JsRegExp) -> Self {
+        o.inner.clone().into()
+    }
+}
+
+impl Deref for JsRegExp {
+    type Target = JsObject;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl JsObjectType for JsRegExp {}
@@ -0,0 +1,167 @@
+use crate::{
+    builtins::{set::ordered_set::OrderedSet, Set},
+    object::{JsFunction, JsObject, JsObjectType},
+    value::IntoOrUndefined,
+    Context, JsResult, JsValue,
+};
+use boa_gc::{Finalize, Trace};
+use std::ops::Deref;
+
+/// JavaScript `Set` rust object.
+#[derive(Debug, Clone, Trace, Finalize)]
+pub struct JsSet {
+    inner: JsObject,
+}
+
+impl JsSet {
+    /// Create a new empty set.
+    #[inline]
+    pub fn new(context: &mut Context) -> Self {
+        let new_target = context
+            .intrinsics()
+            .constructors()
+            .set()
+            .constructor()
+            .into();
+        let object = Set::constructor(&new_target, &[], context)
+            .expect("creating an empty set with the default prototype must not fail")
+            .as_object()
+            .cloned()
+            .expect("Set constructor should always return object");
+
+        Self { inner: object }
+    }
+
+    /// Create a set from an `IntoIterator<Item = JsValue>` convertable object.
+    #[inline]
+    pub fn from_iter<I>(values: I, context: &mut Context) -> JsResult<Self>
+    where
+        I: IntoIterator<Item = JsValue>,
+    {
+        let set = Self::new(context);
+        for value in values {
+            set.add(value, context)?;
+        }
+        Ok(set)
+    }
+
+    /// Create a [`JsSet`] from a [`JsObject`], if the object is not a set throw a `TypeError`.
+    ///
+    /// This does not clone the values of the set, it only does a shallow clone of the object.
+    #[inline]
+    pub fn from_object(object: JsObject, context: &mut Context) -> JsResult<Self> {
+        if object.borrow().is_set() {
+            Ok(Self { inner: object })
+        } else {
+            context.throw_type_error("object is not a Set")
+        }
+    }
+
+    /// Get the number of values of the set.
+    ///
+    /// Same as `set.size` in JavaScript.
+    #[inline]
+    pub fn size(&self) -> usize {
+        self.inner.borrow().as_set_ref().map_or(0, OrderedSet::size)
+    }
+
+    /// Check if the set has no values.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.size() == 0
+    }
+
+    /// Add a value to the set.
+    #[inline]
+    pub fn add<T>(&self, value: T, context: &mut Context) -> JsResult<JsValue>
+    where
+        T: Into<JsValue>,
+    {
+        Set::add(&self.inner.clone().into(), &[value.into()], context)
+    }
+
+    /// Check if the set contains `value`.
+    #[inline]
+    pub fn has<T>(&self, value: T, context: &mut Context) -> JsResult<bool>
+    where
+        T: Into<JsValue>,
+    {
+        Set::has(&self.inner.clone().into(), &[value.into()], context).map(|x| x.to_boolean())
+    }
+
+    /// Remove `value` from the set, returning `true` if the set contained it.
+    #[inline]
+    pub fn delete<T>(&self, value: T, context: &mut Context) -> JsResult<bool>
+    where
+        T: Into<JsValue>,
+    {
+        Set::delete(&self.inner.clone().into(), &[value.into()], context).map(|x| x.to_boolean())
+    }
+
+    /// Remove all the values of the set.
+    #[inline]
+    pub fn clear(&self, context: &mut Context) -> JsResult<JsValue> {
+        Set::clear(&self.inner.clone().into(), &[], context)
+    }
+
+    /// Get an iterator object over the `[value, value]` pairs of the set.
+    #[inline]
+    pub fn entries(&self, context: &mut Context) -> JsResult<JsObject> {
+        Set::entries(&self.inner.clone().into(), &[], context).map(|x| {
+            x.as_object()
+                .cloned()
+                .expect("Set.prototype.entries always returns object")
+        })
+    }
+
+    /// Get an iterator object over the values of the set.
+    #[inline]
+    pub fn values(&self, context: &mut Context) -> JsResult<JsObject> {
+        Set::values(&self.inner.clone().into(), &[], context).map(|x| {
+            x.as_object()
+                .cloned()
+                .expect("Set.prototype.values always returns object")
+        })
+    }
+
+    /// Call `callback` with the value, the value again and the set for each value of the set.
+    #[inline]
+    pub fn for_each(
+        &self,
+        callback: JsFunction,
+        this_arg: Option<JsValue>,
+        context: &mut Context,
+    ) -> JsResult<()> {
+        Set::for_each(
+            &self.inner.clone().into(),
+            &[callback.into(), this_arg.into_or_undefined()],
+            context,
+        )?;
+        Ok(())
+    }
+}
+
+impl From<JsSet> for JsObject {
+    #[inline]
+    fn from(o: JsSet) -> Self {
+        o.inner.clone()
+    }
+}
+
+impl From<JsSet> for JsValue {
+    #[inline]
+    fn from(o: JsSet) -> Self {
+        o.inner.clone().into()
+    }
+}
+
+impl Deref for JsSet {
+    type Target = JsObject;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl JsObjectType for JsSet {}
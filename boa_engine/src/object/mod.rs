@@ -1,7 +1,8 @@
 //! This module implements the Rust representation of a JavaScript object.
 
+pub use internal_methods::{InternalObjectMethods, ORDINARY_INTERNAL_METHODS};
 pub use jsobject::{JsObject, RecursionLimiter, Ref, RefMut};
-pub use operations::IntegrityLevel;
+pub use operations::{IntegrityLevel, PrototypeChain};
 pub use property_map::*;
 
 use self::internal_methods::{
@@ -18,7 +19,6 @@ use self::internal_methods::{
         PROXY_EXOTIC_INTERNAL_METHODS_WITH_CALL,
     },
     string::STRING_EXOTIC_INTERNAL_METHODS,
-    InternalObjectMethods, ORDINARY_INTERNAL_METHODS,
 };
 use crate::{
     builtins::{
@@ -26,7 +26,8 @@ use crate::{
         array_buffer::ArrayBuffer,
         function::arguments::Arguments,
         function::{
-            arguments::ParameterMap, BoundFunction, Captures, Function, NativeFunctionSignature,
+            arguments::ParameterMap, typed_function, BoundFunction, Captures,
+            ClosureFunctionSignature, Function, IntoJsFunction, NativeFunctionSignature,
         },
         generator::Generator,
         intl::date_time_format::DateTimeFormat,
@@ -41,17 +42,18 @@ use crate::{
         typed_array::integer_indexed_object::IntegerIndexed,
         DataView, Date, RegExp,
     },
+    class::Class,
     context::intrinsics::StandardConstructor,
     property::{Attribute, PropertyDescriptor, PropertyKey},
     Context, JsBigInt, JsResult, JsString, JsSymbol, JsValue,
 };
-use boa_gc::{Finalize, Trace};
+use boa_gc::{unsafe_empty_trace, Finalize, Trace};
 use boa_interner::Sym;
 use rustc_hash::FxHashMap;
 use std::{
-    any::Any,
+    any::{Any, TypeId},
     fmt::{self, Debug, Display},
-    ops::{Deref, DerefMut},
+    ops::Deref,
 };
 
 #[cfg(test)]
@@ -59,14 +61,32 @@ mod tests;
 
 pub(crate) mod internal_methods;
 mod jsarray;
+mod jsarraybuffer;
+mod jsdataview;
+mod jsdate;
 mod jsfunction;
+mod jsgenerator;
+mod jsiterator;
+mod jsmap;
 mod jsobject;
+mod jsproxy;
+mod jsregexp;
+mod jsset;
 mod jstypedarray;
 mod operations;
 mod property_map;
 
 pub use jsarray::*;
+pub use jsarraybuffer::*;
+pub use jsdataview::*;
+pub use jsdate::*;
 pub use jsfunction::*;
+pub use jsgenerator::*;
+pub use jsiterator::*;
+pub use jsmap::*;
+pub use jsproxy::*;
+pub use jsregexp::*;
+pub use jsset::*;
 pub use jstypedarray::*;
 
 pub(crate) trait JsObjectType:
@@ -102,6 +122,46 @@ impl<T: Any + Debug + Trace> NativeObject for T {
     }
 }
 
+/// Finds the native data of the ancestors of a native class in the native data of its
+/// instances, see [`Class::upcast`].
+#[derive(Clone, Copy, Debug, Finalize)]
+pub struct NativeUpcast {
+    upcast: fn(&dyn NativeObject, TypeId) -> Option<&dyn NativeObject>,
+    upcast_mut: fn(&mut dyn NativeObject, TypeId) -> Option<&mut dyn NativeObject>,
+}
+
+// SAFETY: `NativeUpcast` only contains function pointers, which hold no `Gc`.
+unsafe impl Trace for NativeUpcast {
+    unsafe_empty_trace!();
+}
+
+impl NativeUpcast {
+    /// The upcasts of the instances of the native class `T`.
+    fn of<T: Class>() -> Self {
+        fn upcast<T: Class>(
+            object: &dyn NativeObject,
+            ancestor: TypeId,
+        ) -> Option<&dyn NativeObject> {
+            object.as_any().downcast_ref::<T>()?.upcast(ancestor)
+        }
+
+        fn upcast_mut<T: Class>(
+            object: &mut dyn NativeObject,
+            ancestor: TypeId,
+        ) -> Option<&mut dyn NativeObject> {
+            object
+                .as_mut_any()
+                .downcast_mut::<T>()?
+                .upcast_mut(ancestor)
+        }
+
+        Self {
+            upcast: upcast::<T>,
+            upcast_mut: upcast_mut::<T>,
+        }
+    }
+}
+
 /// The internal representation of a JavaScript object.
 #[derive(Debug, Trace, Finalize)]
 pub struct Object {
@@ -164,7 +224,7 @@ pub enum ObjectKind {
     Date(Date),
     Global,
     Arguments(Arguments),
-    NativeObject(Box<dyn NativeObject>),
+    NativeObject(Box<dyn NativeObject>, Option<NativeUpcast>),
     IntegerIndexed(IntegerIndexed),
     DateTimeFormat(Box<DateTimeFormat>),
 }
@@ -411,11 +471,37 @@ impl ObjectData {
     /// Create the `NativeObject` object data
     pub fn native_object(native_object: Box<dyn NativeObject>) -> Self {
         Self {
-            kind: ObjectKind::NativeObject(native_object),
+            kind: ObjectKind::NativeObject(native_object, None),
             internal_methods: &ORDINARY_INTERNAL_METHODS,
         }
     }
 
+    /// Create the `NativeObject` object data with custom internal methods.
+    ///
+    /// This allows a native object to behave like an exotic object, e.g. to answer `[[Get]]`
+    /// and `[[OwnPropertyKeys]]` for properties backed by its Rust data. Methods that are not
+    /// overridden should be taken from [`ORDINARY_INTERNAL_METHODS`].
+    pub fn native_object_with_internal_methods(
+        native_object: Box<dyn NativeObject>,
+        internal_methods: &'static InternalObjectMethods,
+    ) -> Self {
+        Self {
+            kind: ObjectKind::NativeObject(native_object, None),
+            internal_methods,
+        }
+    }
+
+    /// Create the `NativeObject` object data of an instance of the native class `T`.
+    ///
+    /// The methods of the ancestors of `T` can downcast the instance to their own native data,
+    /// see [`Class::upcast`].
+    pub(crate) fn class_instance<T: Class>(instance: T) -> Self {
+        Self {
+            kind: ObjectKind::NativeObject(Box::new(instance), Some(NativeUpcast::of::<T>())),
+            internal_methods: T::internal_methods(),
+        }
+    }
+
     /// Creates the `IntegerIndexed` object data
     pub fn integer_indexed(integer_indexed: IntegerIndexed) -> Self {
         Self {
@@ -433,9 +519,10 @@ impl ObjectData {
     }
 }
 
-impl Display for ObjectKind {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.write_str(match self {
+impl ObjectKind {
+    /// Returns the name of the kind of the object.
+    pub fn as_str(&self) -> &'static str {
+        match self {
             Self::Array => "Array",
             Self::ArrayIterator(_) => "ArrayIterator",
             Self::ArrayBuffer(_) => "ArrayBuffer",
@@ -462,11 +549,17 @@ impl Display for ObjectKind {
             Self::Date(_) => "Date",
             Self::Global => "Global",
             Self::Arguments(_) => "Arguments",
-            Self::NativeObject(_) => "NativeObject",
+            Self::NativeObject(..) => "NativeObject",
             Self::IntegerIndexed(_) => "TypedArray",
             Self::DataView(_) => "DataView",
             Self::DateTimeFormat(_) => "DateTimeFormat",
-        })
+        }
+    }
+}
+
+impl Display for ObjectKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
     }
 }
 
@@ -1179,7 +1272,7 @@ impl Object {
         matches!(
             self.data,
             ObjectData {
-                kind: ObjectKind::NativeObject(_),
+                kind: ObjectKind::NativeObject(..),
                 ..
             }
         )
@@ -1189,7 +1282,7 @@ impl Object {
     pub fn as_native_object(&self) -> Option<&dyn NativeObject> {
         match self.data {
             ObjectData {
-                kind: ObjectKind::NativeObject(ref object),
+                kind: ObjectKind::NativeObject(ref object, _),
                 ..
             } => Some(object.as_ref()),
             _ => None,
@@ -1197,18 +1290,15 @@ impl Object {
     }
 
     /// Return `true` if it is a native object and the native type is `T`.
+    ///
+    /// This is also `true` for the instances of a native class whose ancestor is the native class
+    /// `T`, see [`Class::upcast`].
     #[inline]
     pub fn is<T>(&self) -> bool
     where
         T: NativeObject,
     {
-        match self.data {
-            ObjectData {
-                kind: ObjectKind::NativeObject(ref object),
-                ..
-            } => object.deref().as_any().is::<T>(),
-            _ => false,
-        }
+        self.downcast_ref::<T>().is_some()
     }
 
     /// Downcast a reference to the object,
@@ -1220,9 +1310,17 @@ impl Object {
     {
         match self.data {
             ObjectData {
-                kind: ObjectKind::NativeObject(ref object),
+                kind: ObjectKind::NativeObject(ref object, upcast),
                 ..
-            } => object.deref().as_any().downcast_ref::<T>(),
+            } => {
+                let object = object.as_ref();
+                match object.as_any().downcast_ref::<T>() {
+                    Some(object) => Some(object),
+                    None => (upcast?.upcast)(object, TypeId::of::<T>())?
+                        .as_any()
+                        .downcast_ref::<T>(),
+                }
+            }
             _ => None,
         }
     }
@@ -1236,9 +1334,18 @@ impl Object {
     {
         match self.data {
             ObjectData {
-                kind: ObjectKind::NativeObject(ref mut object),
+                kind: ObjectKind::NativeObject(ref mut object, upcast),
                 ..
-            } => object.deref_mut().as_mut_any().downcast_mut::<T>(),
+            } => {
+                let object = object.as_mut();
+                if object.as_any().is::<T>() {
+                    object.as_mut_any().downcast_mut::<T>()
+                } else {
+                    (upcast?.upcast_mut)(object, TypeId::of::<T>())?
+                        .as_mut_any()
+                        .downcast_mut::<T>()
+                }
+            }
             _ => None,
         }
     }
@@ -1433,6 +1540,23 @@ impl<'context> FunctionBuilder<'context> {
         }
     }
 
+    /// Create a new `FunctionBuilder` for creating a function with typed parameters.
+    ///
+    /// The length of the function is the number of arguments it takes. See [`IntoJsFunction`]
+    /// for the accepted functions.
+    #[inline]
+    pub fn typed<F, Args>(context: &'context mut Context, function: F) -> Self
+    where
+        F: IntoJsFunction<Args>,
+    {
+        Self {
+            context,
+            function: typed_function(function),
+            name: JsString::default(),
+            length: F::LENGTH,
+        }
+    }
+
     /// Create a new closure function with additional captures.
     ///
     /// # Note
@@ -1467,6 +1591,26 @@ impl<'context> FunctionBuilder<'context> {
         }
     }
 
+    /// Create a new `FunctionBuilder` for creating a closure function from an already boxed
+    /// closure and its captures.
+    #[inline]
+    pub(crate) fn boxed_closure(
+        context: &'context mut Context,
+        function: Box<dyn ClosureFunctionSignature>,
+        captures: Captures,
+    ) -> Self {
+        Self {
+            context,
+            function: Function::Closure {
+                function,
+                constructor: false,
+                captures,
+            },
+            name: JsString::default(),
+            length: 0,
+        }
+    }
+
     /// Specify the name property of object function object.
     ///
     /// The default is `""` (empty string).
@@ -7,6 +7,7 @@ use crate::{
     value::Type,
     Context, JsResult, JsValue,
 };
+use std::iter::FusedIterator;
 
 /// Object integrity level.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -285,6 +286,59 @@ impl JsObject {
         Ok(desc.is_some())
     }
 
+    /// Get the own property descriptor of the object for `key`, or `None` if the object doesn't
+    /// have such property.
+    ///
+    /// Same as `Reflect.getOwnPropertyDescriptor(object, key)` in JavaScript, but returning the
+    /// descriptor without converting it to an object.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-ordinary-object-internal-methods-and-internal-slots-getownproperty-p
+    #[inline]
+    pub fn get_own_property<K>(
+        &self,
+        key: K,
+        context: &mut Context,
+    ) -> JsResult<Option<PropertyDescriptor>>
+    where
+        K: Into<PropertyKey>,
+    {
+        self.__get_own_property__(&key.into(), context)
+    }
+
+    /// Get the keys of the own properties of the object.
+    ///
+    /// The keys are in the same order as `Reflect.ownKeys(object)` in JavaScript. For ordinary
+    /// objects that is the array indices in ascending order, then the string keys and then the
+    /// symbol keys, both in the order they were created.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-ordinary-object-internal-methods-and-internal-slots-ownpropertykeys
+    #[inline]
+    pub fn own_property_keys(&self, context: &mut Context) -> JsResult<Vec<PropertyKey>> {
+        self.__own_property_keys__(context)
+    }
+
+    /// Get an iterator over the prototype chain of the object, starting with its prototype.
+    ///
+    /// Each prototype is obtained with `[[GetPrototypeOf]]`, like `Object.getPrototypeOf` in
+    /// JavaScript, so proxies in the chain can run traps and throw errors. The chain of an
+    /// object with a proxy in it can also be infinite.
+    #[inline]
+    pub fn prototype_chain<'context>(
+        &self,
+        context: &'context mut Context,
+    ) -> PrototypeChain<'context> {
+        PrototypeChain {
+            next: Some(self.clone()),
+            context,
+        }
+    }
+
     /// Call this object.
     ///
     /// # Panics
@@ -508,13 +562,19 @@ impl JsObject {
         }
     }
 
-    /// It is used to iterate over names of object's keys.
+    /// Abstract operation `EnumerableOwnPropertyNames ( O, kind )`
+    ///
+    /// Get the keys, the values or the `[key, value]` arrays of the enumerable own properties of
+    /// the object that have string keys, in the order of
+    /// [`own_property_keys`][Self::own_property_keys].
+    ///
+    /// Same as `Object.keys`, `Object.values` and `Object.entries` in JavaScript.
     ///
     /// More information:
     /// - [ECMAScript reference][spec]
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-enumerableownpropertynames
-    pub(crate) fn enumerable_own_property_names(
+    pub fn enumerable_own_property_names(
         &self,
         kind: PropertyNameKind,
         context: &mut Context,
@@ -832,3 +892,29 @@ impl JsValue {
         }
     }
 }
+
+/// Iterator over the prototype chain of an object, created by [`JsObject::prototype_chain`].
+///
+/// The iteration stops after the first error thrown by `[[GetPrototypeOf]]`.
+#[derive(Debug)]
+pub struct PrototypeChain<'context> {
+    next: Option<JsObject>,
+    context: &'context mut Context,
+}
+
+impl Iterator for PrototypeChain<'_> {
+    type Item = JsResult<JsObject>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let object = self.next.take()?;
+        match object.__get_prototype_of__(self.context) {
+            Ok(prototype) => {
+                self.next.clone_from(&prototype);
+                prototype.map(Ok)
+            }
+            Err(error) => Some(Err(error)),
+        }
+    }
+}
+
+impl FusedIterator for PrototypeChain<'_> {}
@@ -88,6 +88,9 @@ impl PropertyMap {
     /// An iterator visiting all keys in arbitrary order. The iterator element type is `PropertyKey`.
     ///
     /// This iterator does not recurse down the prototype chain.
+    ///
+    /// To get the keys in the order of `Reflect.ownKeys`, use
+    /// [`JsObject::own_property_keys`][super::JsObject::own_property_keys].
     #[inline]
     pub fn keys(&self) -> Keys<'_> {
         Keys(self.iter())
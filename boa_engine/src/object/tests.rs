@@ -1,4 +1,16 @@
-use crate::{check_output, exec, TestAction};
+use crate::{
+    check_output,
+    class::{Class, ClassBuilder},
+    exec, forward, forward_val,
+    object::{
+        InternalObjectMethods, JsArray, JsArrayBuffer, JsDataView, JsDate, JsGenerator, JsIterator,
+        JsMap, JsObject, JsProxy, JsProxyBuilder, JsRegExp, JsSet, ORDINARY_INTERNAL_METHODS,
+    },
+    property::{Attribute, PropertyDescriptor, PropertyKey, PropertyNameKind},
+    value::{IntoJs, TryFromJs},
+    Context, JsResult, JsValue, TestAction,
+};
+use boa_gc::{Finalize, Trace};
 
 #[test]
 fn ordinary_has_instance_nonobject_prototype() {
@@ -44,3 +56,407 @@ fn object_properties_return_order() {
         ),
     ]);
 }
+
+#[test]
+fn builtin_wrappers() {
+    let mut context = Context::default();
+
+    let map = forward_val(&mut context, "new Map([[1, 'a']])").unwrap();
+    let map = JsMap::from_object(map.as_object().unwrap().clone(), &mut context).unwrap();
+    assert_eq!(map.get(1, &mut context).unwrap(), JsValue::new("a"));
+    assert!(JsSet::from_object(map.into(), &mut context).is_err());
+
+    let date = JsDate::from_time(86_400_000.0, &mut context);
+    context.register_global_property("date", date, Attribute::all());
+    assert_eq!(
+        forward(&mut context, "date instanceof Date && date.toISOString()"),
+        "\"1970-01-02T00:00:00.000Z\""
+    );
+
+    let generator = forward_val(&mut context, "(function* () { yield (yield 1) * 2; })()").unwrap();
+    let generator =
+        JsGenerator::from_object(generator.as_object().unwrap().clone(), &mut context).unwrap();
+    context.register_global_property("generator", generator.clone(), Attribute::all());
+    let result = generator.next(JsValue::undefined(), &mut context).unwrap();
+    context.register_global_property("result", result, Attribute::all());
+    assert_eq!(forward(&mut context, "result.value"), "1");
+    let result = generator.next(21, &mut context).unwrap();
+    context.register_global_property("result", result, Attribute::all());
+    assert_eq!(forward(&mut context, "result.value"), "42");
+
+    let target = context.construct_object();
+    let handler = forward_val(&mut context, "({ get: () => 'trapped' })").unwrap();
+    let handler = handler.as_object().unwrap().clone();
+    let proxy = JsProxy::new(&target, &handler, &mut context);
+    assert_eq!(
+        proxy.get("x", &mut context).unwrap(),
+        JsValue::new("trapped")
+    );
+    assert_eq!(proxy.target(&mut context).unwrap(), target);
+}
+
+#[test]
+fn set_wrapper() {
+    let mut context = Context::default();
+
+    let set = JsSet::from_iter([1.into(), 2.into(), 1.into()], &mut context).unwrap();
+    assert_eq!(set.size(), 2);
+    assert!(set.has(2, &mut context).unwrap());
+    assert!(set.delete(2, &mut context).unwrap());
+    assert!(!set.has(2, &mut context).unwrap());
+    set.add("a", &mut context).unwrap();
+
+    let value = set.into_js(&mut context);
+    context.register_global_property("set", value.clone(), Attribute::all());
+    assert_eq!(forward(&mut context, "[...set].join()"), "\"1,a\"");
+    let set = JsSet::try_from_js(&value, &mut context).unwrap();
+    set.clear(&mut context).unwrap();
+    assert!(set.is_empty());
+
+    let value = forward_val(&mut context, "new Map()").unwrap();
+    assert!(JsSet::try_from_js(&value, &mut context).is_err());
+}
+
+#[test]
+fn regexp_wrapper() {
+    let mut context = Context::default();
+
+    let regexp = JsRegExp::new("a(b+)", "g", &mut context).unwrap();
+    assert_eq!(regexp.source(&mut context).unwrap().as_str(), "a(b+)");
+    assert_eq!(regexp.flags(&mut context).unwrap().as_str(), "g");
+    assert_eq!(regexp.to_string(&mut context).unwrap().as_str(), "/a(b+)/g");
+    assert!(regexp.test("xabb", &mut context).unwrap());
+
+    let regexp = JsRegExp::new("a(b+)", "", &mut context).unwrap();
+    let matched = regexp.exec("xabb", &mut context).unwrap().unwrap();
+    assert_eq!(matched.get(1, &mut context).unwrap(), JsValue::new("bb"));
+    assert!(regexp.exec("xyz", &mut context).unwrap().is_none());
+    assert!(JsRegExp::new("(", "", &mut context).is_err());
+
+    let value = forward_val(&mut context, "/x/i").unwrap();
+    let regexp = JsRegExp::try_from_js(&value, &mut context).unwrap();
+    assert_eq!(regexp.flags(&mut context).unwrap().as_str(), "i");
+    let value = regexp.into_js(&mut context);
+    context.register_global_property("regexp", value, Attribute::all());
+    assert_eq!(forward(&mut context, "regexp.test('X')"), "true");
+    assert!(JsRegExp::try_from_js(&JsValue::new("x"), &mut context).is_err());
+}
+
+#[test]
+fn array_buffer_and_data_view_wrappers() {
+    let mut context = Context::default();
+
+    let buffer = JsArrayBuffer::from_byte_block(vec![1, 2, 3, 4], &mut context);
+    assert_eq!(buffer.byte_length(), 4);
+    assert!(!buffer.is_detached());
+
+    let view = JsDataView::new(&buffer, Some(1), None, &mut context).unwrap();
+    assert_eq!(view.byte_offset(&mut context).unwrap(), 1);
+    assert_eq!(view.byte_length(&mut context).unwrap(), 3);
+    assert_eq!(view.get_uint8(0, false, &mut context).unwrap(), 2);
+    assert_eq!(view.get_uint16(1, false, &mut context).unwrap(), 0x0304);
+    assert_eq!(view.get_uint16(1, true, &mut context).unwrap(), 0x0403);
+    view.set_int8(2, -1, false, &mut context).unwrap();
+    assert!(view.get_uint32(0, false, &mut context).is_err());
+    assert_eq!(buffer.to_vec(), Some(vec![1, 2, 3, 255]));
+
+    let value = view.into_js(&mut context);
+    context.register_global_property("view", value, Attribute::all());
+    assert_eq!(forward(&mut context, "view.getUint8(0)"), "2");
+    let value = forward_val(&mut context, "view.buffer").unwrap();
+    let buffer = JsArrayBuffer::try_from_js(&value, &mut context).unwrap();
+    assert_eq!(buffer.byte_length(), 4);
+    assert!(JsDataView::new(&buffer, Some(5), None, &mut context).is_err());
+
+    let value = forward_val(&mut context, "new DataView(new ArrayBuffer(8), 2)").unwrap();
+    let view = JsDataView::try_from_js(&value, &mut context).unwrap();
+    assert_eq!(view.buffer(&mut context).unwrap().byte_length(), 8);
+    assert_eq!(view.byte_length(&mut context).unwrap(), 6);
+    assert!(JsArrayBuffer::try_from_js(&value, &mut context).is_err());
+}
+
+#[test]
+fn proxy_builder() {
+    let mut context = Context::default();
+
+    let assigned = JsArray::new(&mut context);
+    let target = context.construct_object();
+    let proxy = JsProxyBuilder::new(target)
+        .get(|_, args, context| {
+            let key = args.get(1).cloned().unwrap_or_default();
+            Ok(format!("<{}>", key.to_string(context)?.as_str()).into())
+        })
+        .has(|_, args, _| Ok(args.get(1).map_or(false, JsValue::is_string).into()))
+        .own_keys(|_, _, context| Ok(JsArray::from_iter(["a".into(), "b".into()], context).into()))
+        .set_with_captures(
+            |_, args, assigned, context| {
+                assigned.push(args.get(1).cloned().unwrap_or_default(), context)?;
+                Ok(true.into())
+            },
+            assigned.clone(),
+        )
+        .delete_property(|_, _, _| Ok(false.into()))
+        .build(&mut context);
+    context.register_global_property("proxy", proxy, Attribute::all());
+
+    assert_eq!(forward(&mut context, "proxy.name"), "\"<name>\"");
+    assert_eq!(forward(&mut context, "'x' in proxy"), "true");
+    assert_eq!(
+        forward(&mut context, "Reflect.ownKeys(proxy).join()"),
+        "\"a,b\""
+    );
+    assert_eq!(forward(&mut context, "proxy.answer = 42"), "42");
+    assert_eq!(
+        assigned.get(0, &mut context).unwrap(),
+        JsValue::new("answer")
+    );
+    assert_eq!(
+        forward(&mut context, "Reflect.deleteProperty(proxy, 'a')"),
+        "false"
+    );
+
+    let target = forward_val(&mut context, "(function (a, b) { return a + b; })").unwrap();
+    let proxy = JsProxyBuilder::new(target.as_object().unwrap().clone())
+        .apply(|_, args, context| {
+            let arguments = args.get(2).cloned().unwrap_or_default();
+            let arguments = JsArray::from_object(arguments.as_object().unwrap().clone(), context)?;
+            Ok(arguments.length(context)?.into())
+        })
+        .build(&mut context);
+    context.register_global_property("count", proxy, Attribute::all());
+    assert_eq!(forward(&mut context, "count(1, 2, 3)"), "3");
+
+    // The trap reads the captured object when it runs, not when the proxy is built.
+    let settings = context.construct_object();
+    let proxy = JsProxyBuilder::new(context.construct_object())
+        .get_with_captures(
+            |_, args, settings, context| {
+                let key = args.get(1).cloned().unwrap_or_default();
+                settings.get(key.to_property_key(context)?, context)
+            },
+            settings.clone(),
+        )
+        .build(&mut context);
+    context.register_global_property("settings", proxy, Attribute::all());
+    assert_eq!(forward(&mut context, "settings.depth"), "undefined");
+    settings.set("depth", 3, false, &mut context).unwrap();
+    assert_eq!(forward(&mut context, "settings.depth"), "3");
+}
+
+#[test]
+fn iterator() {
+    let mut context = Context::default();
+
+    let map = forward_val(&mut context, "new Map([['a', 1], ['b', 2]])").unwrap();
+    let mut entries = JsIterator::new(&map, &mut context).unwrap();
+    let mut keys = Vec::new();
+    while let Some(entry) = entries.next() {
+        let entry = entry.unwrap();
+        keys.push(entry.get_v(0, entries.context()).unwrap());
+    }
+    drop(entries);
+    assert_eq!(keys, [JsValue::new("a"), JsValue::new("b")]);
+
+    let generator = forward_val(
+        &mut context,
+        "var closed = false; (function* () { try { yield 1; yield 2; } finally { closed = true; } })()",
+    )
+    .unwrap();
+    let mut values = JsIterator::new(&generator, &mut context).unwrap();
+    assert_eq!(values.next().unwrap().unwrap(), JsValue::new(1));
+    drop(values);
+    assert_eq!(forward(&mut context, "closed"), "true");
+
+    let throwing = forward_val(
+        &mut context,
+        "({ [Symbol.iterator]() { return { next() { throw 'boom'; } }; } })",
+    )
+    .unwrap();
+    let mut values = JsIterator::new(&throwing, &mut context).unwrap();
+    assert_eq!(values.next().unwrap().unwrap_err(), JsValue::new("boom"));
+    assert!(values.next().is_none());
+    drop(values);
+
+    assert!(JsIterator::new(&JsValue::new(1), &mut context).is_err());
+}
+
+#[test]
+fn reflection() {
+    let mut context = Context::default();
+
+    let object = forward_val(
+        &mut context,
+        r#"
+        var proto = { inherited: true };
+        var object = Object.create(proto);
+        object.b = 1;
+        object[2] = 'two';
+        object[Symbol.iterator] = null;
+        object.a = 'a';
+        object[0] = 'zero';
+        Object.defineProperty(object, 'hidden', { value: 0, enumerable: false });
+        object
+        "#,
+    )
+    .unwrap();
+    let object = object.as_object().unwrap();
+
+    let keys: Vec<_> = object
+        .own_property_keys(&mut context)
+        .unwrap()
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    assert_eq!(
+        keys,
+        ["0", "2", "b", "a", "hidden", "Symbol(Symbol.iterator)"]
+    );
+
+    let keys = object
+        .enumerable_own_property_names(PropertyNameKind::Key, &mut context)
+        .unwrap();
+    assert_eq!(
+        keys,
+        ["0", "2", "b", "a"].map(JsValue::new),
+        "symbol and non-enumerable keys are skipped"
+    );
+    let values = object
+        .enumerable_own_property_names(PropertyNameKind::Value, &mut context)
+        .unwrap();
+    assert_eq!(values[1], JsValue::new("two"));
+    let entries = object
+        .enumerable_own_property_names(PropertyNameKind::KeyAndValue, &mut context)
+        .unwrap();
+    let entry =
+        JsArray::from_object(entries[1].as_object().unwrap().clone(), &mut context).unwrap();
+    assert_eq!(entry.get(0, &mut context).unwrap(), JsValue::new("2"));
+    assert_eq!(entry.get(1, &mut context).unwrap(), JsValue::new("two"));
+
+    let hidden = object
+        .get_own_property("hidden", &mut context)
+        .unwrap()
+        .unwrap();
+    assert!(!hidden.expect_enumerable());
+    assert!(object
+        .get_own_property("inherited", &mut context)
+        .unwrap()
+        .is_none());
+
+    let proto = forward_val(&mut context, "proto").unwrap();
+    let object_prototype = context.intrinsics().constructors().object().prototype();
+    let chain = object
+        .prototype_chain(&mut context)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(
+        chain,
+        [proto.as_object().unwrap().clone(), object_prototype]
+    );
+}
+
+#[derive(Debug, Trace, Finalize)]
+struct NativeVec(Vec<i32>);
+
+static NATIVE_VEC_INTERNAL_METHODS: InternalObjectMethods = InternalObjectMethods {
+    __get_own_property__: native_vec_get_own_property,
+    __get__: native_vec_get,
+    __own_property_keys__: native_vec_own_property_keys,
+    ..ORDINARY_INTERNAL_METHODS
+};
+
+fn native_vec_element(obj: &JsObject, key: &PropertyKey) -> Option<i32> {
+    match key {
+        PropertyKey::Index(index) => obj
+            .downcast_ref::<NativeVec>()
+            .and_then(|vec| vec.0.get(*index as usize).copied()),
+        _ => None,
+    }
+}
+
+fn native_vec_get_own_property(
+    obj: &JsObject,
+    key: &PropertyKey,
+    context: &mut Context,
+) -> JsResult<Option<PropertyDescriptor>> {
+    if let Some(element) = native_vec_element(obj, key) {
+        return Ok(Some(
+            PropertyDescriptor::builder()
+                .value(element)
+                .writable(false)
+                .enumerable(true)
+                .configurable(true)
+                .build(),
+        ));
+    }
+    (ORDINARY_INTERNAL_METHODS.__get_own_property__)(obj, key, context)
+}
+
+fn native_vec_get(
+    obj: &JsObject,
+    key: &PropertyKey,
+    receiver: JsValue,
+    context: &mut Context,
+) -> JsResult<JsValue> {
+    if let Some(element) = native_vec_element(obj, key) {
+        return Ok(element.into());
+    }
+    (ORDINARY_INTERNAL_METHODS.__get__)(obj, key, receiver, context)
+}
+
+fn native_vec_own_property_keys(
+    obj: &JsObject,
+    context: &mut Context,
+) -> JsResult<Vec<PropertyKey>> {
+    let len = obj.downcast_ref::<NativeVec>().map_or(0, |vec| vec.0.len());
+    let mut keys: Vec<_> = (0..len).map(PropertyKey::from).collect();
+    keys.extend((ORDINARY_INTERNAL_METHODS.__own_property_keys__)(
+        obj, context,
+    )?);
+    Ok(keys)
+}
+
+impl Class for NativeVec {
+    const NAME: &'static str = "NativeVec";
+
+    fn constructor(_: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<Self> {
+        let elements = args
+            .iter()
+            .map(|arg| arg.to_i32(context))
+            .collect::<JsResult<_>>()?;
+        Ok(Self(elements))
+    }
+
+    fn init(_: &mut ClassBuilder<'_>) -> JsResult<()> {
+        Ok(())
+    }
+
+    fn internal_methods() -> &'static InternalObjectMethods {
+        &NATIVE_VEC_INTERNAL_METHODS
+    }
+}
+
+#[test]
+fn native_object_internal_methods() {
+    let mut context = Context::default();
+    context.register_global_class::<NativeVec>().unwrap();
+
+    forward(
+        &mut context,
+        "var vec = new NativeVec(1, 2, 3); vec.name = 'vec';",
+    );
+    assert_eq!(forward(&mut context, "vec[1]"), "2");
+    assert_eq!(forward(&mut context, "vec[3]"), "undefined");
+    assert_eq!(forward(&mut context, "2 in vec"), "true");
+    assert_eq!(
+        forward(&mut context, "Object.keys(vec).join()"),
+        "\"0,1,2,name\""
+    );
+    assert_eq!(
+        forward(
+            &mut context,
+            "Object.getOwnPropertyDescriptor(vec, 0).writable"
+        ),
+        "false"
+    );
+    assert_eq!(forward(&mut context, "vec.name"), "\"vec\"");
+}
@@ -673,10 +673,15 @@ impl PartialEq<&str> for PropertyKey {
     }
 }
 
+/// What [`JsObject::enumerable_own_property_names`][crate::object::JsObject::enumerable_own_property_names]
+/// returns for each property.
 #[derive(Debug, Clone, Copy, Finalize)]
-pub(crate) enum PropertyNameKind {
+pub enum PropertyNameKind {
+    /// The key of the property.
     Key,
+    /// The value of the property.
     Value,
+    /// A `[key, value]` array.
     KeyAndValue,
 }
 
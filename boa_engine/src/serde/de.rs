@@ -0,0 +1,354 @@
+//! A serde [`Deserializer`][de::Deserializer] that reads `JsValue`s.
+
+use crate::{
+    object::JsObject, property::PropertyNameKind, Context, JsError, JsNativeError, JsString,
+    JsValue,
+};
+use serde::de::{self, DeserializeSeed, IntoDeserializer, Visitor};
+use std::vec;
+
+/// A serde `Deserializer` that converts a `JsValue` to Rust values, reading the properties of
+/// objects in a [`Context`].
+///
+/// See the [module documentation][super] for how values are converted.
+#[derive(Debug)]
+pub struct Deserializer<'context> {
+    value: JsValue,
+    context: &'context mut Context,
+}
+
+impl<'context> Deserializer<'context> {
+    /// Creates a deserializer that reads `value`.
+    #[inline]
+    pub fn new(value: JsValue, context: &'context mut Context) -> Self {
+        Self { value, context }
+    }
+}
+
+/// Visits a number as an integer if it is one, so that it can be deserialized to integer types.
+#[allow(clippy::float_cmp)]
+fn visit_number<'de, V: Visitor<'de>>(number: f64, visitor: V) -> Result<V::Value, JsError> {
+    if number.fract() != 0.0 || (number == 0.0 && number.is_sign_negative()) {
+        visitor.visit_f64(number)
+    } else if number >= i64::MIN as f64 && number < i64::MAX as f64 {
+        visitor.visit_i64(number as i64)
+    } else if number >= 0.0 && number < u64::MAX as f64 {
+        visitor.visit_u64(number as u64)
+    } else {
+        visitor.visit_f64(number)
+    }
+}
+
+impl<'de> de::Deserializer<'de> for Deserializer<'_> {
+    type Error = JsError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, JsError> {
+        match self.value {
+            JsValue::Undefined | JsValue::Null => visitor.visit_unit(),
+            JsValue::Boolean(boolean) => visitor.visit_bool(boolean),
+            JsValue::Integer(integer) => visitor.visit_i32(integer),
+            JsValue::Rational(number) => visit_number(number, visitor),
+            JsValue::String(ref string) => visitor.visit_str(string.as_str()),
+            JsValue::BigInt(ref bigint) => {
+                if let Some(integer) = bigint.to_i128() {
+                    if let Ok(integer) = i64::try_from(integer) {
+                        visitor.visit_i64(integer)
+                    } else if let Ok(integer) = u64::try_from(integer) {
+                        visitor.visit_u64(integer)
+                    } else {
+                        visitor.visit_i128(integer)
+                    }
+                } else if let Some(integer) = bigint.to_u128() {
+                    visitor.visit_u128(integer)
+                } else {
+                    Err(JsNativeError::range()
+                        .with_message(format!("bigint {bigint} is too large to deserialize"))
+                        .into())
+                }
+            }
+            JsValue::Symbol(_) => Err(JsNativeError::typ()
+                .with_message("cannot deserialize a symbol")
+                .into()),
+            JsValue::Object(ref object) if object.is_callable() => Err(JsNativeError::typ()
+                .with_message("cannot deserialize a function")
+                .into()),
+            JsValue::Object(ref object) if object.is_array() || object.is_typed_array() => {
+                let length = object.length_of_array_like(self.context)?;
+                visitor.visit_seq(ArrayAccess {
+                    object: object.clone(),
+                    index: 0,
+                    length,
+                    context: self.context,
+                })
+            }
+            JsValue::Object(ref object) => {
+                let keys =
+                    object.enumerable_own_property_names(PropertyNameKind::Key, self.context)?;
+                visitor.visit_map(ObjectAccess {
+                    object: object.clone(),
+                    keys: keys.into_iter(),
+                    value: None,
+                    context: self.context,
+                })
+            }
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, JsError> {
+        if self.value.is_null_or_undefined() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    #[inline]
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, JsError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, JsError> {
+        match self.value {
+            JsValue::String(ref variant) => visitor.visit_enum(
+                IntoDeserializer::<JsError>::into_deserializer(variant.as_str().to_owned()),
+            ),
+            JsValue::Object(ref object) => {
+                let keys =
+                    object.enumerable_own_property_names(PropertyNameKind::Key, self.context)?;
+                let variant = match keys.as_slice() {
+                    [variant] => variant.to_string(self.context)?,
+                    _ => {
+                        return Err(JsNativeError::typ()
+                            .with_message("expected an object with a single property for an enum")
+                            .into())
+                    }
+                };
+                let value = object.get(variant.clone(), self.context)?;
+                visitor.visit_enum(VariantAccess {
+                    variant: variant.as_str().to_owned(),
+                    value,
+                    context: self.context,
+                })
+            }
+            _ => Err(JsNativeError::typ()
+                .with_message(format!(
+                    "expected a string or an object for an enum, got {}",
+                    self.value.type_of()
+                ))
+                .into()),
+        }
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf unit
+        unit_struct seq tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+/// Visits the elements of an array or a typed array.
+struct ArrayAccess<'context> {
+    object: JsObject,
+    index: usize,
+    length: usize,
+    context: &'context mut Context,
+}
+
+impl<'de> de::SeqAccess<'de> for ArrayAccess<'_> {
+    type Error = JsError;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, JsError>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        if self.index >= self.length {
+            return Ok(None);
+        }
+        let value = self.object.get(self.index, self.context)?;
+        self.index += 1;
+        seed.deserialize(Deserializer::new(value, self.context))
+            .map(Some)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.length - self.index)
+    }
+}
+
+/// Visits the own enumerable string-keyed properties of an object.
+struct ObjectAccess<'context> {
+    object: JsObject,
+    keys: vec::IntoIter<JsValue>,
+    value: Option<JsValue>,
+    context: &'context mut Context,
+}
+
+impl<'de> de::MapAccess<'de> for ObjectAccess<'_> {
+    type Error = JsError;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, JsError>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        let key = match self.keys.next() {
+            Some(key) => key,
+            None => return Ok(None),
+        };
+        let key = key.to_string(self.context)?;
+        self.value = Some(self.object.get(key.clone(), self.context)?);
+        seed.deserialize(MapKey { key }).map(Some)
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, JsError>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let value = self
+            .value
+            .take()
+            .expect("`next_value_seed` must be called after `next_key_seed`");
+        seed.deserialize(Deserializer::new(value, self.context))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.keys.len())
+    }
+}
+
+/// Deserializes the name of a property as the key of a map.
+///
+/// Property names are always strings, so keys of other types are parsed from them, like the
+/// integer keys of a `BTreeMap<u32, _>` that were converted to property names.
+struct MapKey {
+    key: JsString,
+}
+
+/// Implements the methods of `MapKey` that parse the key to a primitive type.
+macro_rules! deserialize_parsed_key {
+    ($($method:ident => $visit:ident,)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, JsError> {
+                match self.key.parse() {
+                    Ok(key) => visitor.$visit(key),
+                    Err(_) => Err(de::Error::invalid_value(
+                        de::Unexpected::Str(self.key.as_str()),
+                        &visitor,
+                    )),
+                }
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for MapKey {
+    type Error = JsError;
+
+    #[inline]
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, JsError> {
+        visitor.visit_str(self.key.as_str())
+    }
+
+    deserialize_parsed_key! {
+        deserialize_bool => visit_bool,
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_i128 => visit_i128,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_u128 => visit_u128,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+    }
+
+    #[inline]
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, JsError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    #[inline]
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, JsError> {
+        visitor.visit_enum(IntoDeserializer::<JsError>::into_deserializer(
+            self.key.as_str().to_owned(),
+        ))
+    }
+
+    serde::forward_to_deserialize_any! {
+        char str string bytes byte_buf option unit unit_struct seq tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
+
+/// Visits an enum variant stored as an object with a single property.
+struct VariantAccess<'context> {
+    variant: String,
+    value: JsValue,
+    context: &'context mut Context,
+}
+
+impl<'de, 'context> de::EnumAccess<'de> for VariantAccess<'context> {
+    type Error = JsError;
+    type Variant = Deserializer<'context>;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Deserializer<'context>), JsError>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let variant =
+            seed.deserialize(IntoDeserializer::<JsError>::into_deserializer(self.variant))?;
+        Ok((variant, Deserializer::new(self.value, self.context)))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for Deserializer<'_> {
+    type Error = JsError;
+
+    #[inline]
+    fn unit_variant(self) -> Result<(), JsError> {
+        de::Deserialize::deserialize(self)
+    }
+
+    #[inline]
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, JsError>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        seed.deserialize(self)
+    }
+
+    #[inline]
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value, JsError> {
+        de::Deserializer::deserialize_seq(self, visitor)
+    }
+
+    #[inline]
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, JsError> {
+        de::Deserializer::deserialize_map(self, visitor)
+    }
+}
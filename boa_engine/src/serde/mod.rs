@@ -0,0 +1,210 @@
+//! Conversions between Rust types implementing serde's traits and `JsValue`s.
+//!
+//! [`to_value`] and [`from_value`] convert any type implementing [`Serialize`] or
+//! [`Deserialize`][serde::Deserialize] directly to and from JavaScript values, without going
+//! through an intermediate format like [`serde_json::Value`]:
+//!
+//!  - `bool`s, strings and `char`s are converted to booleans and strings.
+//!  - Numbers are converted to numbers. Integers that aren't safe integers are converted to
+//!    bigints, and integers can be deserialized from bigints.
+//!  - `None`, `()` and unit structs are converted to `undefined`, and can be deserialized from
+//!    `undefined` or `null`.
+//!  - Sequences and tuples are converted to arrays, and can be deserialized from arrays or typed
+//!    arrays. Byte slices are converted to `Uint8Array`s.
+//!  - Maps and structs are converted to objects. Their keys must be strings or numbers, and are
+//!    deserialized from the own enumerable string-keyed properties of an object. Numeric keys
+//!    are parsed from the names of the properties.
+//!  - Enums are externally tagged, like in `serde_json`: unit variants are converted to strings
+//!    and other variants to an object with a single property named after the variant.
+//!
+//! # Example
+//!
+//! ```
+//! use boa_engine::{serde::{from_value, to_value}, Context};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Debug, PartialEq, Serialize, Deserialize)]
+//! struct Person {
+//!     name: String,
+//!     age: u8,
+//!     phones: Vec<String>,
+//! }
+//!
+//! let mut context = Context::default();
+//! let value = context
+//!     .eval(r#"({ name: "John Doe", age: 43, phones: ["+44 1234567"] })"#)
+//!     .unwrap();
+//!
+//! let person: Person = from_value(&value, &mut context).unwrap();
+//! assert_eq!(person.name, "John Doe");
+//!
+//! let value = to_value(&person, &mut context).unwrap();
+//! assert_eq!(from_value::<Person>(&value, &mut context).unwrap(), person);
+//! ```
+
+mod de;
+mod ser;
+
+pub use de::Deserializer;
+pub use ser::{SerializeArray, SerializeObject, SerializeVariant, Serializer};
+
+use crate::{Context, JsError, JsNativeError, JsResult, JsValue};
+use serde::{de::DeserializeOwned, Serialize};
+use std::fmt::Display;
+
+/// Converts a Rust value to a `JsValue`, creating objects in `context`.
+///
+/// Throws a `TypeError` if the value can't be converted, such as a map with keys that aren't
+/// strings or numbers.
+pub fn to_value<T>(value: &T, context: &mut Context) -> JsResult<JsValue>
+where
+    T: Serialize + ?Sized,
+{
+    value
+        .serialize(Serializer::new(context))
+        .map_err(|error| error.to_opaque(context))
+}
+
+/// Converts a `JsValue` to a Rust value, reading the properties of objects in `context`.
+///
+/// Throws a `TypeError` if the value doesn't have the shape expected by `T`, and rethrows any
+/// error thrown by a getter.
+pub fn from_value<T>(value: &JsValue, context: &mut Context) -> JsResult<T>
+where
+    T: DeserializeOwned,
+{
+    T::deserialize(Deserializer::new(value.clone(), context))
+        .map_err(|error| error.to_opaque(context))
+}
+
+impl serde::ser::Error for JsError {
+    #[inline]
+    fn custom<T: Display>(msg: T) -> Self {
+        JsNativeError::typ().with_message(msg.to_string()).into()
+    }
+}
+
+impl serde::de::Error for JsError {
+    #[inline]
+    fn custom<T: Display>(msg: T) -> Self {
+        JsNativeError::typ().with_message(msg.to_string()).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{from_value, to_value};
+    use crate::{forward, property::Attribute, Context, JsBigInt, JsValue};
+    use serde::{Deserialize, Serialize};
+    use std::collections::BTreeMap;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    enum Shape {
+        Empty,
+        Circle(f64),
+        Rectangle { width: u32, height: u32 },
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Drawing {
+        name: String,
+        layer: Option<u8>,
+        shapes: Vec<Shape>,
+        tags: BTreeMap<String, bool>,
+        origin: (i32, i32),
+        id: u64,
+    }
+
+    #[test]
+    fn serde_roundtrip() {
+        let mut context = Context::default();
+        let drawing = Drawing {
+            name: "sketch".to_owned(),
+            layer: None,
+            shapes: vec![
+                Shape::Empty,
+                Shape::Circle(1.5),
+                Shape::Rectangle {
+                    width: 2,
+                    height: 3,
+                },
+            ],
+            tags: BTreeMap::from([("draft".to_owned(), true)]),
+            origin: (-1, 1),
+            id: u64::MAX,
+        };
+
+        let value = to_value(&drawing, &mut context).unwrap();
+        context.register_global_property("drawing", value.clone(), Attribute::all());
+        assert_eq!(forward(&mut context, "drawing.name"), "\"sketch\"");
+        assert_eq!(forward(&mut context, "drawing.layer"), "undefined");
+        assert_eq!(forward(&mut context, "drawing.shapes[0]"), "\"Empty\"");
+        assert_eq!(forward(&mut context, "drawing.shapes[1].Circle"), "1.5");
+        assert_eq!(
+            forward(&mut context, "drawing.shapes[2].Rectangle.height"),
+            "3"
+        );
+        assert_eq!(forward(&mut context, "drawing.tags.draft"), "true");
+        assert_eq!(forward(&mut context, "drawing.origin[0]"), "-1");
+        assert_eq!(forward(&mut context, "typeof drawing.id"), "\"bigint\"");
+
+        assert_eq!(
+            from_value::<Drawing>(&value, &mut context).unwrap(),
+            drawing
+        );
+    }
+
+    #[test]
+    fn serde_from_script() {
+        let mut context = Context::default();
+        let value = context
+            .eval(
+                r#"({
+                    name: "script",
+                    layer: null,
+                    shapes: ["Empty", { Circle: 2 }],
+                    tags: {},
+                    origin: new Int32Array([4, 5]),
+                    id: 7n,
+                })"#,
+            )
+            .unwrap();
+        let drawing = from_value::<Drawing>(&value, &mut context).unwrap();
+        assert_eq!(drawing.layer, None);
+        assert_eq!(drawing.shapes, vec![Shape::Empty, Shape::Circle(2.0)]);
+        assert_eq!(drawing.origin, (4, 5));
+        assert_eq!(drawing.id, 7);
+
+        assert_eq!(
+            to_value(&(1_i64 << 53), &mut context).unwrap(),
+            JsValue::new(JsBigInt::new(1_i64 << 53))
+        );
+
+        let value = context.eval("({ name: 1 })").unwrap();
+        assert!(from_value::<Drawing>(&value, &mut context).is_err());
+        let value = context.eval("({ Circle: 1, Empty: null })").unwrap();
+        assert!(from_value::<Shape>(&value, &mut context).is_err());
+    }
+
+    #[test]
+    fn serde_numeric_map_keys() {
+        let mut context = Context::default();
+        let layers = BTreeMap::from([(1_u32, "background".to_owned()), (10, "text".to_owned())]);
+
+        let value = to_value(&layers, &mut context).unwrap();
+        context.register_global_property("layers", value.clone(), Attribute::all());
+        assert_eq!(forward(&mut context, "layers[10]"), "\"text\"");
+        assert_eq!(
+            from_value::<BTreeMap<u32, String>>(&value, &mut context).unwrap(),
+            layers
+        );
+
+        let value = context.eval("({ 1: 1, '-2': 2 })").unwrap();
+        assert_eq!(
+            from_value::<BTreeMap<i8, u8>>(&value, &mut context).unwrap(),
+            BTreeMap::from([(-2, 2), (1, 1)])
+        );
+        let value = context.eval("({ one: 1 })").unwrap();
+        assert!(from_value::<BTreeMap<u32, u8>>(&value, &mut context).is_err());
+    }
+}
@@ -0,0 +1,467 @@
+//! A serde [`Serializer`][ser::Serializer] that creates `JsValue`s.
+
+use crate::{
+    object::{JsArray, JsObject, JsUint8Array},
+    property::PropertyKey,
+    Context, JsBigInt, JsError, JsNativeError, JsValue,
+};
+use serde::ser::{self, Serialize};
+
+/// The biggest integer that can be represented exactly by a number.
+const MAX_SAFE_INTEGER: i64 = (1 << 53) - 1;
+
+/// A serde `Serializer` that converts Rust values to `JsValue`s, creating objects in a
+/// [`Context`].
+///
+/// See the [module documentation][super] for how values are converted.
+#[derive(Debug)]
+pub struct Serializer<'context> {
+    context: &'context mut Context,
+}
+
+impl<'context> Serializer<'context> {
+    /// Creates a serializer that creates objects in `context`.
+    #[inline]
+    pub fn new(context: &'context mut Context) -> Self {
+        Self { context }
+    }
+
+    /// Converts an integer to a number if it is a safe integer, or to a bigint otherwise.
+    fn integer<T>(value: T) -> JsValue
+    where
+        T: TryInto<i64> + Into<JsBigInt> + Copy,
+    {
+        match value.try_into() {
+            Ok(integer) if (-MAX_SAFE_INTEGER..=MAX_SAFE_INTEGER).contains(&integer) => {
+                integer.into()
+            }
+            _ => JsBigInt::new(value).into(),
+        }
+    }
+
+    /// Creates an object with a single property, named after an enum variant.
+    fn variant(
+        variant: &'static str,
+        value: JsValue,
+        context: &mut Context,
+    ) -> Result<JsValue, JsError> {
+        let object = context.construct_object();
+        object.create_data_property_or_throw(variant, value, context)?;
+        Ok(object.into())
+    }
+}
+
+impl<'context> ser::Serializer for Serializer<'context> {
+    type Ok = JsValue;
+    type Error = JsError;
+
+    type SerializeSeq = SerializeArray<'context>;
+    type SerializeTuple = SerializeArray<'context>;
+    type SerializeTupleStruct = SerializeArray<'context>;
+    type SerializeTupleVariant = SerializeVariant<SerializeArray<'context>>;
+    type SerializeMap = SerializeObject<'context>;
+    type SerializeStruct = SerializeObject<'context>;
+    type SerializeStructVariant = SerializeVariant<SerializeObject<'context>>;
+
+    #[inline]
+    fn serialize_bool(self, v: bool) -> Result<JsValue, JsError> {
+        Ok(v.into())
+    }
+
+    #[inline]
+    fn serialize_i8(self, v: i8) -> Result<JsValue, JsError> {
+        Ok(v.into())
+    }
+
+    #[inline]
+    fn serialize_i16(self, v: i16) -> Result<JsValue, JsError> {
+        Ok(v.into())
+    }
+
+    #[inline]
+    fn serialize_i32(self, v: i32) -> Result<JsValue, JsError> {
+        Ok(v.into())
+    }
+
+    #[inline]
+    fn serialize_i64(self, v: i64) -> Result<JsValue, JsError> {
+        Ok(Self::integer(v))
+    }
+
+    #[inline]
+    fn serialize_i128(self, v: i128) -> Result<JsValue, JsError> {
+        Ok(Self::integer(v))
+    }
+
+    #[inline]
+    fn serialize_u8(self, v: u8) -> Result<JsValue, JsError> {
+        Ok(v.into())
+    }
+
+    #[inline]
+    fn serialize_u16(self, v: u16) -> Result<JsValue, JsError> {
+        Ok(v.into())
+    }
+
+    #[inline]
+    fn serialize_u32(self, v: u32) -> Result<JsValue, JsError> {
+        Ok(v.into())
+    }
+
+    #[inline]
+    fn serialize_u64(self, v: u64) -> Result<JsValue, JsError> {
+        Ok(Self::integer(v))
+    }
+
+    #[inline]
+    fn serialize_u128(self, v: u128) -> Result<JsValue, JsError> {
+        Ok(Self::integer(v))
+    }
+
+    #[inline]
+    fn serialize_f32(self, v: f32) -> Result<JsValue, JsError> {
+        Ok(v.into())
+    }
+
+    #[inline]
+    fn serialize_f64(self, v: f64) -> Result<JsValue, JsError> {
+        Ok(v.into())
+    }
+
+    #[inline]
+    fn serialize_char(self, v: char) -> Result<JsValue, JsError> {
+        Ok(v.into())
+    }
+
+    #[inline]
+    fn serialize_str(self, v: &str) -> Result<JsValue, JsError> {
+        Ok(v.into())
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<JsValue, JsError> {
+        let array = JsUint8Array::from_iter(v.iter().copied(), self.context)?;
+        Ok(JsObject::from(array).into())
+    }
+
+    #[inline]
+    fn serialize_none(self) -> Result<JsValue, JsError> {
+        Ok(JsValue::undefined())
+    }
+
+    #[inline]
+    fn serialize_some<T>(self, value: &T) -> Result<JsValue, JsError>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self)
+    }
+
+    #[inline]
+    fn serialize_unit(self) -> Result<JsValue, JsError> {
+        Ok(JsValue::undefined())
+    }
+
+    #[inline]
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<JsValue, JsError> {
+        Ok(JsValue::undefined())
+    }
+
+    #[inline]
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<JsValue, JsError> {
+        Ok(variant.into())
+    }
+
+    #[inline]
+    fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T) -> Result<JsValue, JsError>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<JsValue, JsError>
+    where
+        T: Serialize + ?Sized,
+    {
+        let value = value.serialize(Serializer::new(self.context))?;
+        Self::variant(variant, value, self.context)
+    }
+
+    #[inline]
+    fn serialize_seq(self, len: Option<usize>) -> Result<SerializeArray<'context>, JsError> {
+        Ok(SerializeArray {
+            context: self.context,
+            elements: Vec::with_capacity(len.unwrap_or_default()),
+        })
+    }
+
+    #[inline]
+    fn serialize_tuple(self, len: usize) -> Result<SerializeArray<'context>, JsError> {
+        self.serialize_seq(Some(len))
+    }
+
+    #[inline]
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<SerializeArray<'context>, JsError> {
+        self.serialize_seq(Some(len))
+    }
+
+    #[inline]
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleVariant, JsError> {
+        Ok(SerializeVariant {
+            variant,
+            inner: self.serialize_seq(Some(len))?,
+        })
+    }
+
+    #[inline]
+    fn serialize_map(self, _len: Option<usize>) -> Result<SerializeObject<'context>, JsError> {
+        Ok(SerializeObject {
+            object: self.context.construct_object(),
+            key: None,
+            context: self.context,
+        })
+    }
+
+    #[inline]
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<SerializeObject<'context>, JsError> {
+        self.serialize_map(Some(len))
+    }
+
+    #[inline]
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStructVariant, JsError> {
+        Ok(SerializeVariant {
+            variant,
+            inner: self.serialize_map(Some(len))?,
+        })
+    }
+}
+
+/// Serializes sequences and tuples to arrays.
+#[derive(Debug)]
+pub struct SerializeArray<'context> {
+    context: &'context mut Context,
+    elements: Vec<JsValue>,
+}
+
+impl<'context> SerializeArray<'context> {
+    fn push<T>(&mut self, value: &T) -> Result<(), JsError>
+    where
+        T: Serialize + ?Sized,
+    {
+        let value = value.serialize(Serializer::new(self.context))?;
+        self.elements.push(value);
+        Ok(())
+    }
+
+    fn finish(self) -> (JsValue, &'context mut Context) {
+        let array = JsArray::from_iter(self.elements, self.context);
+        (array.into(), self.context)
+    }
+}
+
+impl ser::SerializeSeq for SerializeArray<'_> {
+    type Ok = JsValue;
+    type Error = JsError;
+
+    #[inline]
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), JsError>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.push(value)
+    }
+
+    #[inline]
+    fn end(self) -> Result<JsValue, JsError> {
+        Ok(self.finish().0)
+    }
+}
+
+impl ser::SerializeTuple for SerializeArray<'_> {
+    type Ok = JsValue;
+    type Error = JsError;
+
+    #[inline]
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), JsError>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.push(value)
+    }
+
+    #[inline]
+    fn end(self) -> Result<JsValue, JsError> {
+        Ok(self.finish().0)
+    }
+}
+
+impl ser::SerializeTupleStruct for SerializeArray<'_> {
+    type Ok = JsValue;
+    type Error = JsError;
+
+    #[inline]
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), JsError>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.push(value)
+    }
+
+    #[inline]
+    fn end(self) -> Result<JsValue, JsError> {
+        Ok(self.finish().0)
+    }
+}
+
+/// Serializes maps and structs to objects.
+#[derive(Debug)]
+pub struct SerializeObject<'context> {
+    context: &'context mut Context,
+    object: JsObject,
+    key: Option<PropertyKey>,
+}
+
+impl<'context> SerializeObject<'context> {
+    fn insert<T>(&mut self, key: PropertyKey, value: &T) -> Result<(), JsError>
+    where
+        T: Serialize + ?Sized,
+    {
+        let value = value.serialize(Serializer::new(self.context))?;
+        self.object
+            .create_data_property_or_throw(key, value, self.context)?;
+        Ok(())
+    }
+
+    #[inline]
+    fn finish(self) -> (JsValue, &'context mut Context) {
+        (self.object.into(), self.context)
+    }
+}
+
+impl ser::SerializeMap for SerializeObject<'_> {
+    type Ok = JsValue;
+    type Error = JsError;
+
+    fn serialize_key<T>(&mut self, key: &T) -> Result<(), JsError>
+    where
+        T: Serialize + ?Sized,
+    {
+        let key = key.serialize(Serializer::new(self.context))?;
+        if key.is_object() {
+            return Err(JsNativeError::typ()
+                .with_message("map keys must be strings or numbers")
+                .into());
+        }
+        self.key = Some(key.to_property_key(self.context)?);
+        Ok(())
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> Result<(), JsError>
+    where
+        T: Serialize + ?Sized,
+    {
+        let key = self
+            .key
+            .take()
+            .expect("`serialize_value` must be called after `serialize_key`");
+        self.insert(key, value)
+    }
+
+    #[inline]
+    fn end(self) -> Result<JsValue, JsError> {
+        Ok(self.finish().0)
+    }
+}
+
+impl ser::SerializeStruct for SerializeObject<'_> {
+    type Ok = JsValue;
+    type Error = JsError;
+
+    #[inline]
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), JsError>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.insert(key.into(), value)
+    }
+
+    #[inline]
+    fn end(self) -> Result<JsValue, JsError> {
+        Ok(self.finish().0)
+    }
+}
+
+/// Serializes the fields of an enum variant, and wraps them in an object with a single property
+/// named after the variant.
+#[derive(Debug)]
+pub struct SerializeVariant<S> {
+    variant: &'static str,
+    inner: S,
+}
+
+impl ser::SerializeTupleVariant for SerializeVariant<SerializeArray<'_>> {
+    type Ok = JsValue;
+    type Error = JsError;
+
+    #[inline]
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), JsError>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.inner.push(value)
+    }
+
+    fn end(self) -> Result<JsValue, JsError> {
+        let (value, context) = self.inner.finish();
+        Serializer::variant(self.variant, value, context)
+    }
+}
+
+impl ser::SerializeStructVariant for SerializeVariant<SerializeObject<'_>> {
+    type Ok = JsValue;
+    type Error = JsError;
+
+    #[inline]
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), JsError>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.inner.insert(key.into(), value)
+    }
+
+    fn end(self) -> Result<JsValue, JsError> {
+        let (value, context) = self.inner.finish();
+        Serializer::variant(self.variant, value, context)
+    }
+}
@@ -518,15 +518,21 @@ struct Inner {
 }
 
 impl Inner {
-    /// Create a new `Inner` from `&str`.
+    /// Get the layout of an `Inner` holding `len` bytes, and the offset of the string data.
     #[inline]
-    fn new(s: &str) -> NonNull<Self> {
+    fn layout(len: usize) -> (Layout, usize) {
         // We get the layout of the `Inner` type and we extend by the size
         // of the string array.
-        let inner_layout = Layout::new::<Self>();
-        let (layout, offset) = inner_layout
-            .extend(Layout::array::<u8>(s.len()).expect("failed to create memory layout"))
-            .expect("failed to extend memory layout");
+        Layout::new::<Self>()
+            .extend(Layout::array::<u8>(len).expect("failed to create memory layout"))
+            .expect("failed to extend memory layout")
+    }
+
+    /// Create a new `Inner` from `&str`.
+    #[inline]
+    fn new(s: &str) -> NonNull<Self> {
+        let (layout, offset) = Self::layout(s.len());
+        boa_gc::reserve_external_memory(layout.size());
 
         let inner = unsafe {
             let inner = try_alloc(layout).cast::<Self>();
@@ -556,58 +562,75 @@ impl Inner {
     /// Concatenate array of strings.
     #[inline]
     fn concat_array(strings: &[&str]) -> NonNull<Self> {
-        let mut total_string_size = 0;
-        for string in strings {
-            total_string_size += string.len();
-        }
-
-        // We get the layout of the `Inner` type and we extend by the size
-        // of the string array.
-        let inner_layout = Layout::new::<Self>();
-        let (layout, offset) = inner_layout
-            .extend(Layout::array::<u8>(total_string_size).expect("failed to create memory layout"))
-            .expect("failed to extend memory layout");
-
-        let inner = unsafe {
-            let inner = try_alloc(layout).cast::<Self>();
+        let total_string_size = strings.iter().map(|string| string.len()).sum();
+        let (layout, offset) = Self::layout(total_string_size);
+        boa_gc::reserve_external_memory(layout.size());
 
-            // Write the first part, the Inner.
-            inner.write(Self {
-                len: total_string_size,
-                refcount: Cell::new(1),
-                data: [0; 0],
-            });
-
-            // Get offset into the string data.
-            let data = (*inner).data.as_mut_ptr();
+        // Safety: The memory of the layout was counted against the heap limit.
+        unsafe { Self::write_concat(strings, total_string_size, layout, offset) }
+    }
 
-            debug_assert!(std::ptr::eq(inner.cast::<u8>().add(offset), data));
+    /// Concatenate array of strings, or return `None` if the result doesn't fit under the heap
+    /// limit, before allocating it.
+    #[inline]
+    fn try_concat_array(strings: &[&str]) -> Option<NonNull<Self>> {
+        let total_string_size = strings.iter().map(|string| string.len()).sum();
+        let (layout, offset) = Self::layout(total_string_size);
+        if !boa_gc::try_reserve_external_memory(layout.size()) {
+            return None;
+        }
 
-            // Copy the two string data into data offset.
-            let mut offset = 0;
-            for string in strings {
-                copy_nonoverlapping(string.as_ptr(), data.add(offset), string.len());
-                offset += string.len();
-            }
+        // Safety: The memory of the layout was counted against the heap limit.
+        Some(unsafe { Self::write_concat(strings, total_string_size, layout, offset) })
+    }
 
-            inner
-        };
+    /// Allocate an `Inner` with the given layout and copy the strings into it.
+    ///
+    /// # Safety
+    ///
+    /// `layout` and `offset` must be the ones returned by [`Inner::layout`] for
+    /// `total_string_size`, the sum of the lengths of `strings`, and the size of the layout must
+    /// have been counted as external memory, since [`Inner::dealloc`] releases it.
+    #[inline]
+    unsafe fn write_concat(
+        strings: &[&str],
+        total_string_size: usize,
+        layout: Layout,
+        offset: usize,
+    ) -> NonNull<Self> {
+        let inner = try_alloc(layout).cast::<Self>();
+
+        // Write the first part, the Inner.
+        inner.write(Self {
+            len: total_string_size,
+            refcount: Cell::new(1),
+            data: [0; 0],
+        });
+
+        // Get offset into the string data.
+        let data = (*inner).data.as_mut_ptr();
+
+        debug_assert!(std::ptr::eq(inner.cast::<u8>().add(offset), data));
+
+        // Copy the two string data into data offset.
+        let mut offset = 0;
+        for string in strings {
+            copy_nonoverlapping(string.as_ptr(), data.add(offset), string.len());
+            offset += string.len();
+        }
 
-        // Safety: We already know it's not null, so this is safe.
-        unsafe { NonNull::new_unchecked(inner) }
+        // We already know it's not null, so this is safe.
+        NonNull::new_unchecked(inner)
     }
 
     /// Deallocate inner type with string data.
     #[inline]
     unsafe fn dealloc(x: NonNull<Self>) {
         let len = (*x.as_ptr()).len;
-
-        let inner_layout = Layout::new::<Self>();
-        let (layout, _offset) = inner_layout
-            .extend(Layout::array::<u8>(len).expect("failed to create memory layout"))
-            .expect("failed to extend memory layout");
+        let (layout, _offset) = Self::layout(len);
 
         dealloc(x.as_ptr().cast::<_>(), layout);
+        boa_gc::release_external_memory(layout.size());
     }
 
     #[inline]
@@ -764,29 +787,25 @@ impl JsString {
         T: AsRef<str>,
         U: AsRef<str>,
     {
-        let x = x.as_ref();
-        let y = y.as_ref();
-
-        let inner = Inner::concat_array(&[x, y]);
-        let s = unsafe { inner.as_ref() }.as_str();
-
-        if s.len() <= MAX_CONSTANT_STRING_LENGTH {
-            if let Some(constant) = CONSTANTS.with(|c| c.get(s).cloned()) {
-                unsafe { Inner::dealloc(inner) };
-                return constant;
-            }
-        }
-
-        Self {
-            // Safety: We already know it's a valid heap pointer.
-            inner: unsafe { TaggedInner::new_heap(inner) },
-            _marker: PhantomData,
-        }
+        Self::concat_array(&[x.as_ref(), y.as_ref()])
     }
 
     /// Concatenate array of string.
     pub fn concat_array(strings: &[&str]) -> Self {
-        let inner = Inner::concat_array(strings);
+        Self::from_concatenated(Inner::concat_array(strings))
+    }
+
+    /// Concatenate array of string, or return `None` without allocating if the result doesn't
+    /// fit under the heap limit of the current thread.
+    ///
+    /// See [`boa_gc::set_heap_limit`] for how the limit is checked.
+    pub(crate) fn try_concat_array(strings: &[&str]) -> Option<Self> {
+        Inner::try_concat_array(strings).map(Self::from_concatenated)
+    }
+
+    /// Create a string from a newly concatenated `Inner`, replacing it with a constant string
+    /// if there is one.
+    fn from_concatenated(inner: NonNull<Inner>) -> Self {
         let s = unsafe { inner.as_ref() }.as_str();
 
         if s.len() <= MAX_CONSTANT_STRING_LENGTH {
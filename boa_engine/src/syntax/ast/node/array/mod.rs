@@ -45,6 +45,11 @@ impl ArrayDecl {
         }
     }
 
+    /// Gets a mutable reference to the elements of the array.
+    pub(crate) fn elements_mut(&mut self) -> &mut [Node] {
+        &mut self.arr
+    }
+
     /// Indicates if a spread operator in the array literal has a trailing comma.
     /// This is a syntax error in some cases.
     pub(crate) fn has_trailing_comma_spread(&self) -> bool {
@@ -25,6 +25,13 @@ pub struct AwaitExpr {
     expr: Box<Node>,
 }
 
+impl AwaitExpr {
+    /// Gets a mutable reference to the awaited expression.
+    pub(crate) fn expr_mut(&mut self) -> &mut Node {
+        &mut self.expr
+    }
+}
+
 impl<T> From<T> for AwaitExpr
 where
     T: Into<Box<Node>>,
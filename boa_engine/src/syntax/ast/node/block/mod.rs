@@ -34,11 +34,21 @@ pub struct Block {
 }
 
 impl Block {
+    /// Gets the statement list of this block.
+    pub(crate) fn statement_list(&self) -> &StatementList {
+        &self.statements
+    }
+
     /// Gets the list of statements and declarations in this block.
     pub(crate) fn items(&self) -> &[Node] {
         self.statements.items()
     }
 
+    /// Gets a mutable reference to the list of statements and declarations in this block.
+    pub(crate) fn items_mut(&mut self) -> &mut [Node] {
+        self.statements.items_mut()
+    }
+
     /// Get the lexically declared names of the block.
     pub(crate) fn lexically_declared_names(&self) -> Vec<(Sym, bool)> {
         self.statements.lexically_declared_names()
@@ -47,10 +47,20 @@ impl Call {
         &self.expr
     }
 
+    /// Gets a mutable reference to the target function.
+    pub(crate) fn expr_mut(&mut self) -> &mut Node {
+        &mut self.expr
+    }
+
     /// Retrieves the arguments passed to the function.
     pub fn args(&self) -> &[Node] {
         &self.args
     }
+
+    /// Retrieves a mutable reference to the arguments passed to the function.
+    pub(crate) fn args_mut(&mut self) -> &mut [Node] {
+        &mut self.args
+    }
 }
 
 impl ToInternedString for Call {
@@ -40,6 +40,18 @@ impl ConditionalOp {
         &self.if_false
     }
 
+    pub(crate) fn cond_mut(&mut self) -> &mut Node {
+        &mut self.condition
+    }
+
+    pub(crate) fn if_true_mut(&mut self) -> &mut Node {
+        &mut self.if_true
+    }
+
+    pub(crate) fn if_false_mut(&mut self) -> &mut Node {
+        &mut self.if_false
+    }
+
     /// Creates a `ConditionalOp` AST node.
     pub fn new<C, T, F>(condition: C, if_true: T, if_false: F) -> Self
     where
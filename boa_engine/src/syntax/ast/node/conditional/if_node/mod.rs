@@ -1,4 +1,4 @@
-use crate::syntax::ast::node::Node;
+use crate::syntax::ast::{node::Node, Position};
 use boa_gc::{Finalize, Trace};
 use boa_interner::{Interner, ToInternedString};
 
@@ -22,11 +22,16 @@ use serde::{Deserialize, Serialize};
 /// [falsy]: https://developer.mozilla.org/en-US/docs/Glossary/falsy
 /// [expression]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Guide/Expressions_and_Operators#Expressions
 #[cfg_attr(feature = "deser", derive(Serialize, Deserialize))]
-#[derive(Clone, Debug, Trace, Finalize, PartialEq)]
+#[derive(Clone, Debug, Trace, Finalize)]
 pub struct If {
     cond: Box<Node>,
     body: Box<Node>,
     else_node: Option<Box<Node>>,
+    /// Start positions of the body and the else node, used for debugging information. Only
+    /// known if the node was created by the parser.
+    #[unsafe_ignore_trace]
+    #[cfg_attr(feature = "deser", serde(skip))]
+    positions: (Option<Position>, Option<Position>),
 }
 
 impl If {
@@ -42,6 +47,18 @@ impl If {
         self.else_node.as_ref().map(Box::as_ref)
     }
 
+    pub(crate) fn cond_mut(&mut self) -> &mut Node {
+        &mut self.cond
+    }
+
+    pub(crate) fn body_mut(&mut self) -> &mut Node {
+        &mut self.body
+    }
+
+    pub(crate) fn else_node_mut(&mut self) -> Option<&mut Node> {
+        self.else_node.as_mut().map(Box::as_mut)
+    }
+
     /// Creates an `If` AST node.
     pub fn new<C, B, E, OE>(condition: C, body: B, else_node: OE) -> Self
     where
@@ -54,9 +71,25 @@ impl If {
             cond: Box::new(condition.into()),
             body: Box::new(body.into()),
             else_node: else_node.into().map(E::into).map(Box::new),
+            positions: (None, None),
         }
     }
 
+    /// Gets the start position of the body, if it is known.
+    pub(crate) fn body_position(&self) -> Option<Position> {
+        self.positions.0
+    }
+
+    /// Gets the start position of the else node, if it is known.
+    pub(crate) fn else_position(&self) -> Option<Position> {
+        self.positions.1
+    }
+
+    /// Sets the start positions of the body and the else node.
+    pub(crate) fn set_positions(&mut self, body: Position, else_node: Option<Position>) {
+        self.positions = (Some(body), else_node);
+    }
+
     pub(in crate::syntax::ast::node) fn to_indented_string(
         &self,
         interner: &Interner,
@@ -85,6 +118,12 @@ impl ToInternedString for If {
     }
 }
 
+impl PartialEq for If {
+    fn eq(&self, other: &Self) -> bool {
+        self.cond == other.cond && self.body == other.body && self.else_node == other.else_node
+    }
+}
+
 impl From<If> for Node {
     fn from(if_stm: If) -> Self {
         Self::If(if_stm)
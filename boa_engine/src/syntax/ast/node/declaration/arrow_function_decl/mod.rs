@@ -61,6 +61,11 @@ impl ArrowFunctionDecl {
         &self.body
     }
 
+    /// Gets a mutable reference to the body of the arrow function.
+    pub(crate) fn body_mut(&mut self) -> &mut StatementList {
+        &mut self.body
+    }
+
     /// Implements the display formatting with indentation.
     pub(in crate::syntax::ast::node) fn to_indented_string(
         &self,
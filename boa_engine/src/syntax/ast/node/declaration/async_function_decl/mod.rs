@@ -52,6 +52,11 @@ impl AsyncFunctionDecl {
         self.body.items()
     }
 
+    /// Gets a mutable reference to the body of the async function declaration.
+    pub(crate) fn body_mut(&mut self) -> &mut StatementList {
+        &mut self.body
+    }
+
     /// Implements the display formatting with indentation.
     pub(in crate::syntax::ast::node) fn to_indented_string(
         &self,
@@ -50,6 +50,11 @@ impl AsyncGeneratorDecl {
         self.body.items()
     }
 
+    /// Gets a mutable reference to the body of the async generator declaration.
+    pub(crate) fn body_mut(&mut self) -> &mut StatementList {
+        &mut self.body
+    }
+
     /// Implements the display formatting with indentation.
     pub(in crate::syntax::ast::node) fn to_indented_string(
         &self,
@@ -53,6 +53,11 @@ impl AsyncGeneratorExpr {
         &self.body
     }
 
+    /// Gets a mutable reference to the body of the async generator expression.
+    pub(crate) fn body_mut(&mut self) -> &mut StatementList {
+        &mut self.body
+    }
+
     pub(in crate::syntax::ast::node) fn to_indented_string(
         &self,
         interner: &Interner,
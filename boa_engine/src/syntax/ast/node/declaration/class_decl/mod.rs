@@ -73,6 +73,21 @@ impl Class {
         &self.elements
     }
 
+    /// Gets a mutable reference to the expression of the superclass, if any.
+    pub(crate) fn super_ref_mut(&mut self) -> Option<&mut Node> {
+        self.super_ref.as_deref_mut()
+    }
+
+    /// Gets a mutable reference to the constructor of the class, if any.
+    pub(crate) fn constructor_mut(&mut self) -> Option<&mut FunctionExpr> {
+        self.constructor.as_mut()
+    }
+
+    /// Gets a mutable reference to the elements of the class.
+    pub(crate) fn elements_mut(&mut self) -> &mut [ClassElement] {
+        &mut self.elements
+    }
+
     /// Implements the display formatting with indentation.
     pub(in crate::syntax::ast::node) fn to_indented_string(
         &self,
@@ -61,6 +61,11 @@ impl FunctionExpr {
         &self.body
     }
 
+    /// Gets a mutable reference to the body of the function expression.
+    pub(crate) fn body_mut(&mut self) -> &mut StatementList {
+        &mut self.body
+    }
+
     /// Implements the display formatting with indentation.
     pub(in crate::syntax::ast::node) fn to_indented_string(
         &self,
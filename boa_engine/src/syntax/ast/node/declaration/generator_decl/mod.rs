@@ -51,6 +51,11 @@ impl GeneratorDecl {
         &self.body
     }
 
+    /// Gets a mutable reference to the body of the generator declaration.
+    pub(crate) fn body_mut(&mut self) -> &mut StatementList {
+        &mut self.body
+    }
+
     /// Implements the display formatting with indentation.
     pub(in crate::syntax::ast::node) fn to_indented_string(
         &self,
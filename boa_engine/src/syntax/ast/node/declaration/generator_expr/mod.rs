@@ -53,6 +53,11 @@ impl GeneratorExpr {
         &self.body
     }
 
+    /// Gets a mutable reference to the body of the generator expression.
+    pub(crate) fn body_mut(&mut self) -> &mut StatementList {
+        &mut self.body
+    }
+
     /// Converts the generator expresion node to a string with indentation.
     pub(in crate::syntax::ast::node) fn to_indented_string(
         &self,
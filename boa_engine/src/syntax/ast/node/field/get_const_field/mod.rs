@@ -50,6 +50,11 @@ impl GetConstField {
         &self.obj
     }
 
+    /// Gets a mutable reference to the original object from where to get the field from.
+    pub(crate) fn obj_mut(&mut self) -> &mut Node {
+        &mut self.obj
+    }
+
     /// Gets the name of the field to retrieve.
     pub fn field(&self) -> Sym {
         self.field
@@ -39,10 +39,20 @@ impl GetField {
         &self.obj
     }
 
+    /// Gets a mutable reference to the object to get the field from.
+    pub(crate) fn obj_mut(&mut self) -> &mut Node {
+        &mut self.obj
+    }
+
     pub fn field(&self) -> &Node {
         &self.field
     }
 
+    /// Gets a mutable reference to the expression of the field name.
+    pub(crate) fn field_mut(&mut self) -> &mut Node {
+        &mut self.field
+    }
+
     /// Creates a `GetField` AST node.
     pub fn new<V, F>(value: V, field: F) -> Self
     where
@@ -1,4 +1,4 @@
-use crate::syntax::ast::node::Node;
+use crate::syntax::ast::{node::Node, Position};
 use boa_gc::{Finalize, Trace};
 use boa_interner::{Interner, Sym, ToInternedString};
 
@@ -18,11 +18,16 @@ use serde::{Deserialize, Serialize};
 /// [spec]: https://tc39.es/ecma262/#sec-do-while-statement
 /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Statements/do...while
 #[cfg_attr(feature = "deser", derive(Serialize, Deserialize))]
-#[derive(Clone, Debug, Trace, Finalize, PartialEq)]
+#[derive(Clone, Debug, Trace, Finalize)]
 pub struct DoWhileLoop {
     body: Box<Node>,
     cond: Box<Node>,
     label: Option<Sym>,
+    /// Start position of the body, used for debugging information. Only known if the node was
+    /// created by the parser.
+    #[unsafe_ignore_trace]
+    #[cfg_attr(feature = "deser", serde(skip))]
+    body_position: Option<Position>,
 }
 
 impl DoWhileLoop {
@@ -34,6 +39,14 @@ impl DoWhileLoop {
         &self.cond
     }
 
+    pub(crate) fn body_mut(&mut self) -> &mut Node {
+        &mut self.body
+    }
+
+    pub(crate) fn cond_mut(&mut self) -> &mut Node {
+        &mut self.cond
+    }
+
     pub fn label(&self) -> Option<Sym> {
         self.label
     }
@@ -52,9 +65,20 @@ impl DoWhileLoop {
             body: Box::new(body.into()),
             cond: Box::new(condition.into()),
             label: None,
+            body_position: None,
         }
     }
 
+    /// Gets the start position of the body, if it is known.
+    pub(crate) fn body_position(&self) -> Option<Position> {
+        self.body_position
+    }
+
+    /// Sets the start position of the body.
+    pub(crate) fn set_body_position(&mut self, position: Position) {
+        self.body_position = Some(position);
+    }
+
     /// Converts the "do while" loop to a string with the given indentation.
     pub(in crate::syntax::ast::node) fn to_indented_string(
         &self,
@@ -82,6 +106,12 @@ impl ToInternedString for DoWhileLoop {
     }
 }
 
+impl PartialEq for DoWhileLoop {
+    fn eq(&self, other: &Self) -> bool {
+        self.body == other.body && self.cond == other.cond && self.label == other.label
+    }
+}
+
 impl From<DoWhileLoop> for Node {
     fn from(do_while: DoWhileLoop) -> Self {
         Self::DoWhileLoop(do_while)
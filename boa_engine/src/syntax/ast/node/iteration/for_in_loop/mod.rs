@@ -1,4 +1,7 @@
-use crate::syntax::ast::node::{iteration::IterableLoopInitializer, Node};
+use crate::syntax::ast::{
+    node::{iteration::IterableLoopInitializer, Node},
+    Position,
+};
 use boa_gc::{Finalize, Trace};
 use boa_interner::{Interner, Sym, ToInternedString};
 
@@ -6,12 +9,17 @@ use boa_interner::{Interner, Sym, ToInternedString};
 use serde::{Deserialize, Serialize};
 
 #[cfg_attr(feature = "deser", derive(Serialize, Deserialize))]
-#[derive(Clone, Debug, Trace, Finalize, PartialEq)]
+#[derive(Clone, Debug, Trace, Finalize)]
 pub struct ForInLoop {
     init: Box<IterableLoopInitializer>,
     expr: Box<Node>,
     body: Box<Node>,
     label: Option<Sym>,
+    /// Start position of the body, used for debugging information. Only known if the node was
+    /// created by the parser.
+    #[unsafe_ignore_trace]
+    #[cfg_attr(feature = "deser", serde(skip))]
+    body_position: Option<Position>,
 }
 
 impl ForInLoop {
@@ -25,9 +33,20 @@ impl ForInLoop {
             expr: Box::new(expr.into()),
             body: Box::new(body.into()),
             label: None,
+            body_position: None,
         }
     }
 
+    /// Gets the start position of the body, if it is known.
+    pub(crate) fn body_position(&self) -> Option<Position> {
+        self.body_position
+    }
+
+    /// Sets the start position of the body.
+    pub(crate) fn set_body_position(&mut self, position: Position) {
+        self.body_position = Some(position);
+    }
+
     pub fn init(&self) -> &IterableLoopInitializer {
         &self.init
     }
@@ -40,6 +59,16 @@ impl ForInLoop {
         &self.body
     }
 
+    /// Gets a mutable reference to the object whose keys are iterated.
+    pub(crate) fn expr_mut(&mut self) -> &mut Node {
+        &mut self.expr
+    }
+
+    /// Gets a mutable reference to the body of the loop.
+    pub(crate) fn body_mut(&mut self) -> &mut Node {
+        &mut self.body
+    }
+
     pub fn label(&self) -> Option<Sym> {
         self.label
     }
@@ -76,6 +105,15 @@ impl ToInternedString for ForInLoop {
     }
 }
 
+impl PartialEq for ForInLoop {
+    fn eq(&self, other: &Self) -> bool {
+        self.init == other.init
+            && self.expr == other.expr
+            && self.body == other.body
+            && self.label == other.label
+    }
+}
+
 impl From<ForInLoop> for Node {
     fn from(for_in: ForInLoop) -> Self {
         Self::ForInLoop(for_in)
@@ -1,4 +1,4 @@
-use crate::syntax::ast::node::Node;
+use crate::syntax::ast::{node::Node, Position};
 use boa_gc::{Finalize, Trace};
 use boa_interner::{Interner, Sym, ToInternedString};
 
@@ -17,11 +17,16 @@ use serde::{Deserialize, Serialize};
 /// [spec]: https://tc39.es/ecma262/#prod-ForDeclaration
 /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Statements/for
 #[cfg_attr(feature = "deser", derive(Serialize, Deserialize))]
-#[derive(Clone, Debug, Trace, Finalize, PartialEq)]
+#[derive(Clone, Debug, Trace, Finalize)]
 pub struct ForLoop {
     #[cfg_attr(feature = "deser", serde(flatten))]
     inner: Box<InnerForLoop>,
     label: Option<Sym>,
+    /// Start position of the body, used for debugging information. Only known if the node was
+    /// created by the parser.
+    #[unsafe_ignore_trace]
+    #[cfg_attr(feature = "deser", serde(skip))]
+    body_position: Option<Position>,
 }
 
 impl ForLoop {
@@ -36,9 +41,20 @@ impl ForLoop {
         Self {
             inner: Box::new(InnerForLoop::new(init, condition, final_expr, body)),
             label: None,
+            body_position: None,
         }
     }
 
+    /// Gets the start position of the body, if it is known.
+    pub(crate) fn body_position(&self) -> Option<Position> {
+        self.body_position
+    }
+
+    /// Sets the start position of the body.
+    pub(crate) fn set_body_position(&mut self, position: Position) {
+        self.body_position = Some(position);
+    }
+
     /// Gets the initialization node.
     pub fn init(&self) -> Option<&Node> {
         self.inner.init()
@@ -54,11 +70,31 @@ impl ForLoop {
         self.inner.final_expr()
     }
 
+    /// Gets a mutable reference to the initialization node.
+    pub(crate) fn init_mut(&mut self) -> Option<&mut Node> {
+        self.inner.init.as_mut()
+    }
+
+    /// Gets a mutable reference to the final expression node.
+    pub(crate) fn final_expr_mut(&mut self) -> Option<&mut Node> {
+        self.inner.final_expr.as_mut()
+    }
+
     /// Gets the body of the for loop.
     pub fn body(&self) -> &Node {
         self.inner.body()
     }
 
+    /// Gets a mutable reference to the loop condition node.
+    pub(crate) fn condition_mut(&mut self) -> Option<&mut Node> {
+        self.inner.condition.as_mut()
+    }
+
+    /// Gets a mutable reference to the body of the for loop.
+    pub(crate) fn body_mut(&mut self) -> &mut Node {
+        &mut self.inner.body
+    }
+
     /// Converts the for loop to a string with the given indentation.
     pub(in crate::syntax::ast::node) fn to_indented_string(
         &self,
@@ -105,6 +141,12 @@ impl ToInternedString for ForLoop {
     }
 }
 
+impl PartialEq for ForLoop {
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner && self.label == other.label
+    }
+}
+
 impl From<ForLoop> for Node {
     fn from(for_loop: ForLoop) -> Self {
         Self::ForLoop(for_loop)
@@ -1,4 +1,7 @@
-use crate::syntax::ast::node::{iteration::IterableLoopInitializer, Node};
+use crate::syntax::ast::{
+    node::{iteration::IterableLoopInitializer, Node},
+    Position,
+};
 use boa_gc::{Finalize, Trace};
 use boa_interner::{Interner, Sym, ToInternedString};
 
@@ -6,12 +9,17 @@ use boa_interner::{Interner, Sym, ToInternedString};
 use serde::{Deserialize, Serialize};
 
 #[cfg_attr(feature = "deser", derive(Serialize, Deserialize))]
-#[derive(Clone, Debug, Trace, Finalize, PartialEq)]
+#[derive(Clone, Debug, Trace, Finalize)]
 pub struct ForOfLoop {
     init: Box<IterableLoopInitializer>,
     iterable: Box<Node>,
     body: Box<Node>,
     label: Option<Sym>,
+    /// Start position of the body, used for debugging information. Only known if the node was
+    /// created by the parser.
+    #[unsafe_ignore_trace]
+    #[cfg_attr(feature = "deser", serde(skip))]
+    body_position: Option<Position>,
 }
 
 impl ForOfLoop {
@@ -26,9 +34,20 @@ impl ForOfLoop {
             iterable: Box::new(iterable.into()),
             body: Box::new(body.into()),
             label: None,
+            body_position: None,
         }
     }
 
+    /// Gets the start position of the body, if it is known.
+    pub(crate) fn body_position(&self) -> Option<Position> {
+        self.body_position
+    }
+
+    /// Sets the start position of the body.
+    pub(crate) fn set_body_position(&mut self, position: Position) {
+        self.body_position = Some(position);
+    }
+
     pub fn init(&self) -> &IterableLoopInitializer {
         &self.init
     }
@@ -41,6 +60,16 @@ impl ForOfLoop {
         &self.body
     }
 
+    /// Gets a mutable reference to the iterated value.
+    pub(crate) fn iterable_mut(&mut self) -> &mut Node {
+        &mut self.iterable
+    }
+
+    /// Gets a mutable reference to the body of the loop.
+    pub(crate) fn body_mut(&mut self) -> &mut Node {
+        &mut self.body
+    }
+
     pub fn label(&self) -> Option<Sym> {
         self.label
     }
@@ -77,6 +106,15 @@ impl ToInternedString for ForOfLoop {
     }
 }
 
+impl PartialEq for ForOfLoop {
+    fn eq(&self, other: &Self) -> bool {
+        self.init == other.init
+            && self.iterable == other.iterable
+            && self.body == other.body
+            && self.label == other.label
+    }
+}
+
 impl From<ForOfLoop> for Node {
     fn from(for_of: ForOfLoop) -> Self {
         Self::ForOfLoop(for_of)
@@ -1,4 +1,4 @@
-use crate::syntax::ast::node::Node;
+use crate::syntax::ast::{node::Node, Position};
 use boa_gc::{Finalize, Trace};
 use boa_interner::{Interner, Sym, ToInternedString};
 
@@ -17,11 +17,16 @@ use serde::{Deserialize, Serialize};
 /// [spec]: https://tc39.es/ecma262/#prod-grammar-notation-WhileStatement
 /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Statements/while
 #[cfg_attr(feature = "deser", derive(Serialize, Deserialize))]
-#[derive(Clone, Debug, Trace, Finalize, PartialEq)]
+#[derive(Clone, Debug, Trace, Finalize)]
 pub struct WhileLoop {
     cond: Box<Node>,
     body: Box<Node>,
     label: Option<Sym>,
+    /// Start position of the body, used for debugging information. Only known if the node was
+    /// created by the parser.
+    #[unsafe_ignore_trace]
+    #[cfg_attr(feature = "deser", serde(skip))]
+    body_position: Option<Position>,
 }
 
 impl WhileLoop {
@@ -33,6 +38,14 @@ impl WhileLoop {
         &self.body
     }
 
+    pub(crate) fn cond_mut(&mut self) -> &mut Node {
+        &mut self.cond
+    }
+
+    pub(crate) fn body_mut(&mut self) -> &mut Node {
+        &mut self.body
+    }
+
     pub fn label(&self) -> Option<Sym> {
         self.label
     }
@@ -51,9 +64,20 @@ impl WhileLoop {
             cond: Box::new(condition.into()),
             body: Box::new(body.into()),
             label: None,
+            body_position: None,
         }
     }
 
+    /// Gets the start position of the body, if it is known.
+    pub(crate) fn body_position(&self) -> Option<Position> {
+        self.body_position
+    }
+
+    /// Sets the start position of the body.
+    pub(crate) fn set_body_position(&mut self, position: Position) {
+        self.body_position = Some(position);
+    }
+
     /// Converts the while loop to a string with the given indentation.
     pub(in crate::syntax::ast::node) fn to_indented_string(
         &self,
@@ -81,6 +105,12 @@ impl ToInternedString for WhileLoop {
     }
 }
 
+impl PartialEq for WhileLoop {
+    fn eq(&self, other: &Self) -> bool {
+        self.cond == other.cond && self.body == other.body && self.label == other.label
+    }
+}
+
 impl From<WhileLoop> for Node {
     fn from(while_loop: WhileLoop) -> Self {
         Self::WhileLoop(while_loop)
@@ -40,6 +40,11 @@ impl New {
         self.call.args()
     }
 
+    /// Gets a mutable reference to the call of the constructor.
+    pub(crate) fn call_mut(&mut self) -> &mut Call {
+        &mut self.call
+    }
+
     /// Returns the inner call
     pub(crate) fn call(&self) -> &Call {
         &self.call
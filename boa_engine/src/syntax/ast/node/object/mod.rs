@@ -47,6 +47,11 @@ impl Object {
         &self.properties
     }
 
+    /// Gets a mutable reference to the property definitions of the object.
+    pub(crate) fn properties_mut(&mut self) -> &mut [PropertyDefinition] {
+        &mut self.properties
+    }
+
     /// Implements the display formatting with indentation.
     pub(in crate::syntax::ast::node) fn to_indented_string(
         &self,
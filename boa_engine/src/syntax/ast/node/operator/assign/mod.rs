@@ -53,6 +53,11 @@ impl Assign {
     pub fn rhs(&self) -> &Node {
         &self.rhs
     }
+
+    /// Gets a mutable reference to the right hand side of the assignment operation.
+    pub(crate) fn rhs_mut(&mut self) -> &mut Node {
+        &mut self.rhs
+    }
 }
 
 impl ToInternedString for Assign {
@@ -48,6 +48,16 @@ impl BinOp {
     pub fn rhs(&self) -> &Node {
         &self.rhs
     }
+
+    /// Gets a mutable reference to the left hand side of the binary operation.
+    pub(crate) fn lhs_mut(&mut self) -> &mut Node {
+        &mut self.lhs
+    }
+
+    /// Gets a mutable reference to the right hand side of the binary operation.
+    pub(crate) fn rhs_mut(&mut self) -> &mut Node {
+        &mut self.rhs
+    }
 }
 
 impl ToInternedString for BinOp {
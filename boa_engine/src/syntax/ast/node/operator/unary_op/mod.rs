@@ -41,6 +41,11 @@ impl UnaryOp {
     pub fn target(&self) -> &Node {
         self.target.as_ref()
     }
+
+    /// Gets a mutable reference to the target of this unary operator.
+    pub(crate) fn target_mut(&mut self) -> &mut Node {
+        self.target.as_mut()
+    }
 }
 
 impl ToInternedString for UnaryOp {
@@ -36,6 +36,11 @@ impl Spread {
         &self.val
     }
 
+    /// Gets a mutable reference to the spread value.
+    pub(crate) fn val_mut(&mut self) -> &mut Node {
+        &mut self.val
+    }
+
     /// Creates a `Spread` AST node.
     pub fn new<V>(val: V) -> Self
     where
@@ -1,6 +1,9 @@
 //! Statement list node.
 
-use crate::syntax::ast::node::{Declaration, Node};
+use crate::syntax::ast::{
+    node::{Declaration, Node},
+    Position,
+};
 use boa_gc::{unsafe_empty_trace, Finalize, Trace};
 use boa_interner::{Interner, Sym, ToInternedString};
 use std::{ops::Deref, rc::Rc};
@@ -21,10 +24,15 @@ mod tests;
 ///
 /// [spec]: https://tc39.es/ecma262/#prod-StatementList
 #[cfg_attr(feature = "deser", derive(Serialize, Deserialize))]
-#[derive(Clone, Debug, Trace, Finalize, PartialEq)]
+#[derive(Clone, Debug, Trace, Finalize)]
 pub struct StatementList {
     items: Box<[Node]>,
     strict: bool,
+    /// Start position of each item, used for debugging information. Empty if the list was not
+    /// created by the parser.
+    #[unsafe_ignore_trace]
+    #[cfg_attr(feature = "deser", serde(skip))]
+    positions: Box<[Position]>,
 }
 
 impl StatementList {
@@ -34,6 +42,27 @@ impl StatementList {
         &self.items
     }
 
+    /// Gets a mutable reference to the list of items.
+    #[inline]
+    pub(crate) fn items_mut(&mut self) -> &mut [Node] {
+        &mut self.items
+    }
+
+    /// Gets the start position of each item, in the same order as [`Self::items`].
+    ///
+    /// The list is empty if the positions are not known.
+    #[inline]
+    pub fn positions(&self) -> &[Position] {
+        &self.positions
+    }
+
+    /// Sets the start position of each item.
+    #[inline]
+    pub(crate) fn set_positions(&mut self, positions: Box<[Position]>) {
+        debug_assert_eq!(positions.len(), self.items.len());
+        self.positions = positions;
+    }
+
     /// Get the strict mode.
     #[inline]
     pub fn strict(&self) -> bool {
@@ -183,10 +212,18 @@ where
         Self {
             items: stm.into(),
             strict: false,
+            positions: Box::default(),
         }
     }
 }
 
+// Positions are debugging information, and are not part of the structure of the list.
+impl PartialEq for StatementList {
+    fn eq(&self, other: &Self) -> bool {
+        self.items == other.items && self.strict == other.strict
+    }
+}
+
 impl ToInternedString for StatementList {
     fn to_interned_string(&self, interner: &Interner) -> String {
         self.to_indented_string(interner, 0)
@@ -37,10 +37,20 @@ impl Case {
         &self.condition
     }
 
+    /// Gets a mutable reference to the condition of the case.
+    pub(crate) fn condition_mut(&mut self) -> &mut Node {
+        &mut self.condition
+    }
+
     /// Gets the statement listin the body of the case.
     pub fn body(&self) -> &StatementList {
         &self.body
     }
+
+    /// Gets a mutable reference to the statement list of the case.
+    pub(crate) fn body_mut(&mut self) -> &mut StatementList {
+        &mut self.body
+    }
 }
 
 /// The `switch` statement evaluates an expression, matching the expression's value to a case
@@ -97,6 +107,26 @@ impl Switch {
         self.default.as_ref().map(StatementList::items)
     }
 
+    /// Gets a mutable reference to the value to match on.
+    pub(crate) fn val_mut(&mut self) -> &mut Node {
+        &mut self.val
+    }
+
+    /// Gets a mutable reference to the cases of the switch.
+    pub(crate) fn cases_mut(&mut self) -> &mut [Case] {
+        &mut self.cases
+    }
+
+    /// Gets a mutable reference to the default statement list of the switch, if any.
+    pub(crate) fn default_mut(&mut self) -> Option<&mut StatementList> {
+        self.default.as_mut()
+    }
+
+    /// Gets the default statement list, if any.
+    pub(crate) fn default_statement_list(&self) -> Option<&StatementList> {
+        self.default.as_ref()
+    }
+
     /// Implements the display formatting with indentation.
     pub(in crate::syntax::ast::node) fn to_indented_string(
         &self,
@@ -37,6 +37,10 @@ impl TemplateLit {
     pub(crate) fn elements(&self) -> &[TemplateElement] {
         &self.elements
     }
+
+    pub(crate) fn elements_mut(&mut self) -> &mut [TemplateElement] {
+        &mut self.elements
+    }
 }
 
 impl ToInternedString for TemplateLit {
@@ -66,6 +66,21 @@ impl Try {
         self.finally.as_ref().map(Finally::block)
     }
 
+    /// Gets a mutable reference to the `try` block.
+    pub(crate) fn block_mut(&mut self) -> &mut Block {
+        &mut self.block
+    }
+
+    /// Gets a mutable reference to the `catch` block, if any.
+    pub(crate) fn catch_mut(&mut self) -> Option<&mut Catch> {
+        self.catch.as_mut()
+    }
+
+    /// Gets a mutable reference to the `finally` block, if any.
+    pub(crate) fn finally_mut(&mut self) -> Option<&mut Block> {
+        self.finally.as_mut().map(|finally| &mut finally.block)
+    }
+
     /// Implements the display formatting with indentation.
     pub(in crate::syntax::ast::node) fn to_indented_string(
         &self,
@@ -133,6 +148,11 @@ impl Catch {
         &self.block
     }
 
+    /// Gets a mutable reference to the block of the `catch`.
+    pub(crate) fn block_mut(&mut self) -> &mut Block {
+        &mut self.block
+    }
+
     /// Implements the display formatting with indentation.
     pub(super) fn to_indented_string(&self, interner: &Interner, indentation: usize) -> String {
         let mut buf = " catch".to_owned();
@@ -25,6 +25,11 @@ impl Yield {
         self.expr.as_ref().map(Box::as_ref)
     }
 
+    /// Gets a mutable reference to the yielded expression, if any.
+    pub(crate) fn expr_mut(&mut self) -> Option<&mut Node> {
+        self.expr.as_deref_mut()
+    }
+
     pub fn delegate(&self) -> bool {
         self.delegate
     }
@@ -4,6 +4,7 @@
 
 pub mod ast;
 pub mod lexer;
+pub mod optimizer;
 pub mod parser;
 
 pub use lexer::Lexer;
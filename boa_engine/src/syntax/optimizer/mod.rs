@@ -0,0 +1,510 @@
+//! AST optimizations that run before bytecode generation.
+//!
+//! The [`Optimizer`] performs constant folding of operators applied to literals, simplifies
+//! template literals with constant substitutions and removes `if` branches that can never be
+//! taken. All folding goes through the same [`JsValue`] operations the VM uses at runtime, so
+//! the optimized program is observably identical to the original one.
+
+use crate::{
+    builtins::Number,
+    syntax::ast::{
+        node::{
+            self,
+            declaration::class_decl::ClassElement,
+            object::{MethodDefinition, PropertyDefinition, PropertyName},
+            template::TemplateElement,
+            Class, Declaration, DeclarationList, Node, StatementList, TemplateLit,
+        },
+        op::{BinOp, BitOp, CompOp, LogOp, NumOp, UnaryOp},
+        Const,
+    },
+    value::Numeric,
+    Context, JsBigInt, JsString, JsValue,
+};
+use rustc_hash::FxHashSet;
+
+#[cfg(test)]
+mod tests;
+
+/// Folds constant expressions and prunes dead branches of a [`StatementList`].
+///
+/// # Examples
+///
+/// ```
+/// # use boa_engine::{
+/// #     syntax::{ast::{node::Node, Const}, optimizer::Optimizer},
+/// #     Context,
+/// # };
+/// let mut context = Context::default();
+/// let mut statement_list = context.parse("1 + 2 * 3").unwrap();
+///
+/// Optimizer::new(&mut context).apply(&mut statement_list);
+///
+/// assert_eq!(statement_list.items(), &[Node::Const(Const::Int(7))]);
+/// ```
+#[derive(Debug)]
+pub struct Optimizer<'context> {
+    context: &'context mut Context,
+}
+
+impl<'context> Optimizer<'context> {
+    /// Creates a new `Optimizer` that uses the given context to intern new strings.
+    #[inline]
+    pub fn new(context: &'context mut Context) -> Self {
+        Self { context }
+    }
+
+    /// Optimizes the given statement list in place.
+    pub fn apply(&mut self, statement_list: &mut StatementList) {
+        self.visit_statements(statement_list.items_mut());
+    }
+
+    fn visit_statements(&mut self, nodes: &mut [Node]) {
+        for node in nodes {
+            self.visit(node);
+        }
+    }
+
+    fn visit(&mut self, node: &mut Node) {
+        match node {
+            Node::BinOp(bin_op) => {
+                self.visit(bin_op.lhs_mut());
+                self.visit(bin_op.rhs_mut());
+                if let Some(folded) = self.fold_bin_op(bin_op) {
+                    *node = folded;
+                }
+            }
+            Node::UnaryOp(unary_op) => {
+                self.visit(unary_op.target_mut());
+                if let Some(folded) = self.fold_unary_op(unary_op) {
+                    *node = folded;
+                }
+            }
+            Node::TemplateLit(template) => {
+                for element in template.elements_mut() {
+                    if let TemplateElement::Expr(expr) = element {
+                        self.visit(expr);
+                    }
+                }
+                if let Some(folded) = self.fold_template(template) {
+                    *node = folded;
+                }
+            }
+            Node::ConditionalOp(conditional) => {
+                self.visit(conditional.cond_mut());
+                self.visit(conditional.if_true_mut());
+                self.visit(conditional.if_false_mut());
+
+                // Only literal results are inlined, since replacing `cond ? obj.f : g` with
+                // `obj.f` would change the `this` value of a call or the name of a function.
+                if let Node::Const(cond) = conditional.cond() {
+                    let taken = if self.to_value(cond).to_boolean() {
+                        conditional.if_true()
+                    } else {
+                        conditional.if_false()
+                    };
+                    if let Node::Const(_) = taken {
+                        *node = taken.clone();
+                    }
+                }
+            }
+            Node::If(if_node) => {
+                self.visit(if_node.cond_mut());
+                self.visit(if_node.body_mut());
+                if let Some(else_node) = if_node.else_node_mut() {
+                    self.visit(else_node);
+                }
+
+                let cond = if let Node::Const(cond) = if_node.cond() {
+                    self.to_value(cond).to_boolean()
+                } else {
+                    return;
+                };
+
+                let (taken, dropped) = if cond {
+                    (Some(if_node.body()), if_node.else_node())
+                } else {
+                    (if_node.else_node(), Some(if_node.body()))
+                };
+
+                // `var` declarations are hoisted out of the dropped branch, and function
+                // declarations as the direct body of an `if` get their own scope, so in those
+                // cases the statement has to be kept.
+                if dropped.map_or(false, has_var_declarations)
+                    || taken.map_or(false, |taken| {
+                        matches!(
+                            taken,
+                            Node::FunctionDecl(_)
+                                | Node::GeneratorDecl(_)
+                                | Node::AsyncFunctionDecl(_)
+                                | Node::AsyncGeneratorDecl(_)
+                        )
+                    })
+                {
+                    return;
+                }
+
+                *node = taken.cloned().unwrap_or(Node::Empty);
+            }
+            Node::Block(block) => self.visit_statements(block.items_mut()),
+            Node::VarDeclList(list) | Node::LetDeclList(list) | Node::ConstDeclList(list) => {
+                let declarations = match list {
+                    DeclarationList::Const(declarations)
+                    | DeclarationList::Let(declarations)
+                    | DeclarationList::Var(declarations) => declarations,
+                };
+                for declaration in declarations.iter_mut() {
+                    if let Declaration::Identifier {
+                        init: Some(init), ..
+                    } = declaration
+                    {
+                        self.visit(init);
+                    }
+                }
+            }
+            Node::Assign(assign) => self.visit(assign.rhs_mut()),
+            Node::Call(call) => {
+                self.visit(call.expr_mut());
+                self.visit_statements(call.args_mut());
+            }
+            Node::New(new) => {
+                self.visit(new.call_mut().expr_mut());
+                self.visit_statements(new.call_mut().args_mut());
+            }
+            Node::ArrayDecl(array) => self.visit_statements(array.elements_mut()),
+            Node::Object(object) => {
+                for property in object.properties_mut() {
+                    match property {
+                        PropertyDefinition::IdentifierReference(_) => {}
+                        PropertyDefinition::Property(name, value) => {
+                            self.visit_property_name(name);
+                            self.visit(value);
+                        }
+                        PropertyDefinition::MethodDefinition(method, name) => {
+                            self.visit_property_name(name);
+                            self.visit_method(method);
+                        }
+                        PropertyDefinition::SpreadObject(value) => self.visit(value),
+                    }
+                }
+            }
+            Node::Spread(spread) => self.visit(spread.val_mut()),
+            Node::GetConstField(get_const_field) => self.visit(get_const_field.obj_mut()),
+            Node::GetPrivateField(get_private_field) => self.visit(get_private_field.obj_mut()),
+            Node::GetField(get_field) => {
+                self.visit(get_field.obj_mut());
+                self.visit(get_field.field_mut());
+            }
+            Node::AwaitExpr(await_expr) => self.visit(await_expr.expr_mut()),
+            Node::Yield(yield_expr) => {
+                if let Some(expr) = yield_expr.expr_mut() {
+                    self.visit(expr);
+                }
+            }
+            Node::Return(ret) => {
+                if let Some(expr) = ret.expr_mut() {
+                    self.visit(expr);
+                }
+            }
+            Node::Throw(throw) => self.visit(throw.expr_mut()),
+            Node::WhileLoop(while_loop) => {
+                self.visit(while_loop.cond_mut());
+                self.visit(while_loop.body_mut());
+            }
+            Node::DoWhileLoop(do_while_loop) => {
+                self.visit(do_while_loop.body_mut());
+                self.visit(do_while_loop.cond_mut());
+            }
+            Node::ForLoop(for_loop) => {
+                if let Some(init) = for_loop.init_mut() {
+                    self.visit(init);
+                }
+                if let Some(condition) = for_loop.condition_mut() {
+                    self.visit(condition);
+                }
+                if let Some(final_expr) = for_loop.final_expr_mut() {
+                    self.visit(final_expr);
+                }
+                self.visit(for_loop.body_mut());
+            }
+            Node::ForInLoop(for_in_loop) => {
+                self.visit(for_in_loop.expr_mut());
+                self.visit(for_in_loop.body_mut());
+            }
+            Node::ForOfLoop(for_of_loop) => {
+                self.visit(for_of_loop.iterable_mut());
+                self.visit(for_of_loop.body_mut());
+            }
+            Node::Switch(switch) => {
+                self.visit(switch.val_mut());
+                for case in switch.cases_mut() {
+                    self.visit(case.condition_mut());
+                    self.apply(case.body_mut());
+                }
+                if let Some(default) = switch.default_mut() {
+                    self.apply(default);
+                }
+            }
+            Node::Try(try_node) => {
+                self.visit_statements(try_node.block_mut().items_mut());
+                if let Some(catch) = try_node.catch_mut() {
+                    self.visit_statements(catch.block_mut().items_mut());
+                }
+                if let Some(finally) = try_node.finally_mut() {
+                    self.visit_statements(finally.items_mut());
+                }
+            }
+            Node::FunctionDecl(function) => self.apply(function.body_mut()),
+            Node::FunctionExpr(function) => self.apply(function.body_mut()),
+            Node::ArrowFunctionDecl(function) => self.apply(function.body_mut()),
+            Node::GeneratorDecl(function) => self.apply(function.body_mut()),
+            Node::GeneratorExpr(function) => self.apply(function.body_mut()),
+            Node::AsyncFunctionDecl(function) => self.apply(function.body_mut()),
+            Node::AsyncFunctionExpr(function) => self.apply(function.body_mut()),
+            Node::AsyncGeneratorDecl(function) => self.apply(function.body_mut()),
+            Node::AsyncGeneratorExpr(function) => self.apply(function.body_mut()),
+            Node::ClassDecl(class) | Node::ClassExpr(class) => self.visit_class(class),
+            _ => {}
+        }
+    }
+
+    fn visit_property_name(&mut self, name: &mut PropertyName) {
+        if let PropertyName::Computed(name) = name {
+            self.visit(name);
+        }
+    }
+
+    fn visit_method(&mut self, method: &mut MethodDefinition) {
+        match method {
+            MethodDefinition::Get(function)
+            | MethodDefinition::Set(function)
+            | MethodDefinition::Ordinary(function) => self.apply(function.body_mut()),
+            MethodDefinition::Generator(function) => self.apply(function.body_mut()),
+            MethodDefinition::AsyncGenerator(function) => self.apply(function.body_mut()),
+            MethodDefinition::Async(function) => self.apply(function.body_mut()),
+        }
+    }
+
+    fn visit_class(&mut self, class: &mut Class) {
+        if let Some(super_ref) = class.super_ref_mut() {
+            self.visit(super_ref);
+        }
+        if let Some(constructor) = class.constructor_mut() {
+            self.apply(constructor.body_mut());
+        }
+        for element in class.elements_mut() {
+            match element {
+                ClassElement::MethodDefinition(name, method)
+                | ClassElement::StaticMethodDefinition(name, method) => {
+                    self.visit_property_name(name);
+                    self.visit_method(method);
+                }
+                ClassElement::FieldDefinition(name, init)
+                | ClassElement::StaticFieldDefinition(name, init) => {
+                    self.visit_property_name(name);
+                    if let Some(init) = init {
+                        self.visit(init);
+                    }
+                }
+                ClassElement::PrivateMethodDefinition(_, method)
+                | ClassElement::PrivateStaticMethodDefinition(_, method) => {
+                    self.visit_method(method);
+                }
+                ClassElement::PrivateFieldDefinition(_, Some(init))
+                | ClassElement::PrivateStaticFieldDefinition(_, Some(init)) => self.visit(init),
+                ClassElement::PrivateFieldDefinition(_, None)
+                | ClassElement::PrivateStaticFieldDefinition(_, None) => {}
+                ClassElement::StaticBlock(statement_list) => self.apply(statement_list),
+            }
+        }
+    }
+
+    /// Folds a binary operation whose operands are literals.
+    fn fold_bin_op(&mut self, bin_op: &node::BinOp) -> Option<Node> {
+        if let BinOp::Log(op) = bin_op.op() {
+            return self.fold_logical(op, bin_op.lhs(), bin_op.rhs());
+        }
+
+        let (lhs, rhs) = match (bin_op.lhs(), bin_op.rhs()) {
+            (Node::Const(lhs), Node::Const(rhs)) => (self.to_value(lhs), self.to_value(rhs)),
+            _ => return None,
+        };
+
+        let context = &mut *self.context;
+        let result = match bin_op.op() {
+            BinOp::Num(op) => match op {
+                NumOp::Add => lhs.add(&rhs, context),
+                NumOp::Sub => lhs.sub(&rhs, context),
+                NumOp::Div => lhs.div(&rhs, context),
+                NumOp::Mul => lhs.mul(&rhs, context),
+                NumOp::Exp => lhs.pow(&rhs, context),
+                NumOp::Mod => lhs.rem(&rhs, context),
+            },
+            BinOp::Bit(op) => match op {
+                BitOp::And => lhs.bitand(&rhs, context),
+                BitOp::Or => lhs.bitor(&rhs, context),
+                BitOp::Xor => lhs.bitxor(&rhs, context),
+                BitOp::Shl => lhs.shl(&rhs, context),
+                BitOp::Shr => lhs.shr(&rhs, context),
+                BitOp::UShr => lhs.ushr(&rhs, context),
+            },
+            BinOp::Comp(op) => match op {
+                CompOp::Equal => lhs.equals(&rhs, context).map(JsValue::new),
+                CompOp::NotEqual => lhs.equals(&rhs, context).map(|eq| JsValue::new(!eq)),
+                CompOp::StrictEqual => Ok(JsValue::new(lhs.strict_equals(&rhs))),
+                CompOp::StrictNotEqual => Ok(JsValue::new(!lhs.strict_equals(&rhs))),
+                CompOp::GreaterThan => lhs.gt(&rhs, context).map(JsValue::new),
+                CompOp::GreaterThanOrEqual => lhs.ge(&rhs, context).map(JsValue::new),
+                CompOp::LessThan => lhs.lt(&rhs, context).map(JsValue::new),
+                CompOp::LessThanOrEqual => lhs.le(&rhs, context).map(JsValue::new),
+                CompOp::In | CompOp::InstanceOf => return None,
+            },
+            BinOp::Log(_) | BinOp::Assign(_) | BinOp::Comma => return None,
+        };
+
+        // Operations that throw (e.g. mixing BigInt and Number) are left for the runtime.
+        let value = result.ok()?;
+        self.to_const(&value).map(Node::Const)
+    }
+
+    /// Folds a logical operation whose left hand side is a literal.
+    fn fold_logical(&mut self, op: LogOp, lhs: &Node, rhs: &Node) -> Option<Node> {
+        let lhs_value = match lhs {
+            Node::Const(lhs) => self.to_value(lhs),
+            _ => return None,
+        };
+
+        let short_circuit = match op {
+            LogOp::And => !lhs_value.to_boolean(),
+            LogOp::Or => lhs_value.to_boolean(),
+            LogOp::Coalesce => !lhs_value.is_null_or_undefined(),
+        };
+
+        if short_circuit {
+            Some(lhs.clone())
+        } else if let Node::Const(_) = rhs {
+            Some(rhs.clone())
+        } else {
+            None
+        }
+    }
+
+    /// Folds a unary operation whose operand is a literal.
+    fn fold_unary_op(&mut self, unary_op: &node::UnaryOp) -> Option<Node> {
+        let value = match unary_op.target() {
+            Node::Const(target) => self.to_value(target),
+            _ => return None,
+        };
+
+        let context = &mut *self.context;
+        let result = match unary_op.op() {
+            UnaryOp::Minus => value.to_numeric(context).map(|numeric| match numeric {
+                Numeric::Number(number) => JsValue::new(-number),
+                Numeric::BigInt(bigint) => JsValue::new(JsBigInt::neg(&bigint)),
+            }),
+            UnaryOp::Plus => value.to_number(context).map(JsValue::new),
+            UnaryOp::Not => Ok(JsValue::new(!value.to_boolean())),
+            UnaryOp::Tilde => value.to_numeric(context).map(|numeric| match numeric {
+                Numeric::Number(number) => JsValue::new(Number::not(number)),
+                Numeric::BigInt(bigint) => JsValue::new(JsBigInt::not(&bigint)),
+            }),
+            UnaryOp::TypeOf => Ok(value.type_of().into()),
+            UnaryOp::Void => Ok(JsValue::undefined()),
+            UnaryOp::IncrementPost
+            | UnaryOp::IncrementPre
+            | UnaryOp::DecrementPost
+            | UnaryOp::DecrementPre
+            | UnaryOp::Delete => return None,
+        };
+
+        let value = result.ok()?;
+        self.to_const(&value).map(Node::Const)
+    }
+
+    /// Inlines literal substitutions into the strings of a template literal.
+    ///
+    /// If every substitution is a literal, the whole template is replaced by a string literal.
+    fn fold_template(&mut self, template: &TemplateLit) -> Option<Node> {
+        let mut elements = Vec::with_capacity(template.elements().len());
+        let mut buffer: Option<String> = None;
+        let mut changed = false;
+
+        for element in template.elements() {
+            let string = match element {
+                TemplateElement::String(sym) => {
+                    self.context.interner().resolve_expect(*sym).to_owned()
+                }
+                TemplateElement::Expr(Node::Const(constant)) => {
+                    changed = true;
+                    let value = self.to_value(constant);
+                    match value.to_string(self.context) {
+                        Ok(string) => string.as_str().to_owned(),
+                        Err(_) => return None,
+                    }
+                }
+                TemplateElement::Expr(expr) => {
+                    if let Some(string) = buffer.take() {
+                        let sym = self.context.interner_mut().get_or_intern(string);
+                        elements.push(TemplateElement::String(sym));
+                    }
+                    elements.push(TemplateElement::Expr(expr.clone()));
+                    continue;
+                }
+            };
+            buffer.get_or_insert_with(String::new).push_str(&string);
+        }
+
+        if !changed {
+            return None;
+        }
+
+        let sym = self
+            .context
+            .interner_mut()
+            .get_or_intern(buffer.unwrap_or_default());
+        if elements.is_empty() {
+            return Some(Const::String(sym).into());
+        }
+        elements.push(TemplateElement::String(sym));
+
+        Some(Node::TemplateLit(TemplateLit::new(elements)))
+    }
+
+    /// Converts a literal into the value it evaluates to.
+    fn to_value(&self, constant: &Const) -> JsValue {
+        match constant {
+            Const::String(sym) => {
+                JsString::new(self.context.interner().resolve_expect(*sym)).into()
+            }
+            Const::Num(number) => JsValue::new(*number),
+            Const::Int(integer) => JsValue::new(*integer),
+            Const::BigInt(bigint) => JsBigInt::new(bigint.as_ref().clone()).into(),
+            Const::Bool(boolean) => JsValue::new(*boolean),
+            Const::Null => JsValue::null(),
+            Const::Undefined => JsValue::undefined(),
+        }
+    }
+
+    /// Converts a primitive value back into a literal, if possible.
+    fn to_const(&mut self, value: &JsValue) -> Option<Const> {
+        Some(match value {
+            JsValue::Null => Const::Null,
+            JsValue::Undefined => Const::Undefined,
+            JsValue::Boolean(boolean) => Const::Bool(*boolean),
+            JsValue::String(string) => {
+                Const::String(self.context.interner_mut().get_or_intern(string.as_str()))
+            }
+            JsValue::Rational(number) => Const::Num(*number),
+            JsValue::Integer(integer) => Const::Int(*integer),
+            JsValue::BigInt(bigint) => Const::BigInt(Box::new(bigint.as_inner().clone())),
+            JsValue::Object(_) | JsValue::Symbol(_) => return None,
+        })
+    }
+}
+
+/// Returns `true` if the given statement declares any `var` bindings.
+fn has_var_declarations(node: &Node) -> bool {
+    let mut vars = FxHashSet::default();
+    node.var_declared_names(&mut vars);
+    !vars.is_empty()
+}
@@ -0,0 +1,261 @@
+use super::Optimizer;
+use crate::{
+    forward,
+    syntax::ast::{
+        node::{template::TemplateElement, Identifier, Node, TemplateLit},
+        Const,
+    },
+    Context,
+};
+use boa_interner::ToInternedString;
+
+/// Parses and optimizes the given script, returning its top level statements.
+#[track_caller]
+fn optimize(context: &mut Context, src: &str) -> Box<[Node]> {
+    let mut statement_list = context.parse(src).expect("failed to parse");
+    Optimizer::new(context).apply(&mut statement_list);
+    statement_list.items().into()
+}
+
+#[test]
+fn fold_numeric_operations() {
+    let mut context = Context::default();
+
+    assert_eq!(
+        &*optimize(&mut context, "1 + 2 * 3 - 4"),
+        &[Node::Const(Const::Int(3))]
+    );
+    assert_eq!(
+        &*optimize(&mut context, "2 ** 10 % 1000"),
+        &[Node::Const(Const::Int(24))]
+    );
+    assert_eq!(
+        &*optimize(&mut context, "~5 | 1 << 4"),
+        &[Node::Const(Const::Int(-6))]
+    );
+}
+
+#[test]
+fn fold_preserves_negative_zero_and_nan() {
+    let mut context = Context::default();
+
+    match &*optimize(&mut context, "-0") {
+        [Node::Const(Const::Num(n))] => assert!(*n == 0.0 && n.is_sign_negative()),
+        other => panic!("unexpected folding result: {other:?}"),
+    }
+    match &*optimize(&mut context, "0 / 0") {
+        [Node::Const(Const::Num(n))] => assert!(n.is_nan()),
+        other => panic!("unexpected folding result: {other:?}"),
+    }
+
+    context.set_optimize(true);
+    assert_eq!(forward(&mut context, "Object.is(-0 * 1, -0)"), "true");
+    assert_eq!(forward(&mut context, "1 / -0"), "-Infinity");
+    assert_eq!(forward(&mut context, "NaN === 0 / 0"), "false");
+}
+
+#[test]
+fn fold_strings() {
+    let mut context = Context::default();
+
+    let folded = optimize(&mut context, "'a' + 1 + 2 + `b${3 + 4}c${'d'}`");
+    let expected = context.interner_mut().get_or_intern_static("a12b7cd");
+    assert_eq!(&*folded, &[Node::Const(Const::String(expected))]);
+
+    let folded = optimize(&mut context, "typeof 1n");
+    let expected = context.interner_mut().get_or_intern_static("bigint");
+    assert_eq!(&*folded, &[Node::Const(Const::String(expected))]);
+}
+
+#[test]
+fn fold_template_literals() {
+    let mut context = Context::default();
+
+    let folded = optimize(&mut context, "`a${1 + 1}b${x}c${'d'}`");
+    let interner = context.interner_mut();
+    let expected = TemplateLit::new(vec![
+        TemplateElement::String(interner.get_or_intern_static("a2b")),
+        TemplateElement::Expr(Identifier::new(interner.get_or_intern_static("x")).into()),
+        TemplateElement::String(interner.get_or_intern_static("cd")),
+    ]);
+    assert_eq!(&*folded, &[Node::TemplateLit(expected)]);
+
+    context.set_optimize(true);
+    assert_eq!(
+        forward(&mut context, "let x = 'X'; `a${1 + 1}b${x}c${'d'}`"),
+        "\"a2bXcd\""
+    );
+}
+
+#[test]
+fn fold_bigint() {
+    let mut context = Context::default();
+
+    assert_eq!(
+        &*optimize(&mut context, "2n ** 64n"),
+        &[Node::Const(Const::BigInt(Box::new(
+            num_bigint::BigInt::from(1u128 << 64)
+        )))]
+    );
+
+    // Mixing BigInt and Number throws, so it must be left for the runtime.
+    assert!(matches!(
+        &*optimize(&mut context, "1n + 1"),
+        [Node::BinOp(_)]
+    ));
+    context.set_optimize(true);
+    assert_eq!(
+        forward(&mut context, "try { 1n + 1 } catch (e) { e.name }"),
+        "\"TypeError\""
+    );
+}
+
+#[test]
+fn logical_operators_keep_references() {
+    let mut context = Context::default();
+
+    assert_eq!(
+        &*optimize(&mut context, "0 && f()"),
+        &[Node::Const(Const::Int(0))]
+    );
+    assert_eq!(
+        &*optimize(&mut context, "null ?? 'x'"),
+        &[Node::Const(Const::String(
+            context.interner_mut().get_or_intern_static("x")
+        ))]
+    );
+    assert!(matches!(
+        &*optimize(&mut context, "(true && o.f)()"),
+        [Node::Call(_)]
+    ));
+    assert!(matches!(
+        &*optimize(&mut context, "true ? o.f : 0"),
+        [Node::ConditionalOp(_)]
+    ));
+}
+
+#[test]
+fn prune_dead_branches() {
+    let mut context = Context::default();
+
+    assert_eq!(
+        &*optimize(&mut context, "if (1 > 2) { f(); }"),
+        &[Node::Empty]
+    );
+    assert!(matches!(
+        &*optimize(&mut context, "if (!0) { f(); } else { g(); }"),
+        [Node::Block(_)]
+    ));
+
+    // The `var` declaration must still be hoisted.
+    assert!(matches!(
+        &*optimize(&mut context, "if (false) { var x = 1; }"),
+        [Node::If(_)]
+    ));
+
+    context.set_optimize(true);
+    assert_eq!(
+        forward(&mut context, "if (false) { var x = 1; } typeof x"),
+        "\"undefined\""
+    );
+    assert_eq!(
+        forward(
+            &mut context,
+            "let r = 0; if ('' + 0) { r = 1; } else { r = 2; } r"
+        ),
+        "1"
+    );
+}
+
+/// Parses and optimizes the given script, returning it printed back as source code.
+#[track_caller]
+fn optimize_to_string(context: &mut Context, src: &str) -> String {
+    let mut statement_list = context.parse(src).expect("failed to parse");
+    Optimizer::new(context).apply(&mut statement_list);
+    statement_list.to_interned_string(context.interner())
+}
+
+#[test]
+fn fold_inside_literals_and_calls() {
+    let mut context = Context::default();
+
+    assert_eq!(
+        optimize_to_string(
+            &mut context,
+            "const config = { timeout: 60 * 1000, [1 + 1]: 'a' + 'b', get f() { return 2 * 3; } };"
+        ),
+        "const config = {\n    timeout: 60000,\n    2: \"ab\",\n    get f() {\n        return 6;\n    },\n};\n"
+    );
+    assert_eq!(
+        optimize_to_string(&mut context, "let a = [1 + 2, ...[3 * 4], 'x' + 1];"),
+        "let a = [3, ...[12], \"x1\"];\n"
+    );
+    assert_eq!(
+        optimize_to_string(&mut context, "new Foo(2 * 3)[1 + 1].bar(4 - 1);"),
+        "new Foo(6)[2].bar(3);\n"
+    );
+    assert_eq!(
+        optimize_to_string(
+            &mut context,
+            "class A { x = 1 + 1; static #y = 2 * 2; m() { return 3 - 1; } }"
+        ),
+        "class A {\n    x = 2;\n    static #y = 4;\n    m() {\n        return 2;\n    }\n};\n"
+    );
+
+    context.set_optimize(true);
+    assert_eq!(
+        forward(
+            &mut context,
+            "const config = { timeout: 60 * 1000 }; [config.timeout, ...[2 ** 3]].join()"
+        ),
+        "\"60000,8\""
+    );
+}
+
+#[test]
+fn prune_nested_dead_branches() {
+    let mut context = Context::default();
+
+    assert_eq!(
+        optimize_to_string(
+            &mut context,
+            "try { if (false) { f(); } } catch (e) { if (0) { g(); } } finally { if ('') { h(); } }"
+        ),
+        "try {\n    ;;\n} catch(e) {\n    ;;\n} finally {\n    ;;\n};\n"
+    );
+    assert_eq!(
+        optimize_to_string(
+            &mut context,
+            "switch (x) { case 1 + 1: if (false) { f(); } break; default: if (null) { g(); } }"
+        ),
+        "switch (x) {\n    case 2:\n        ;;\n        break;\n    default:\n        ;;\n}\n"
+    );
+    assert_eq!(
+        optimize_to_string(
+            &mut context,
+            "for (let i = 1 + 1; i < 2 * 5; i += 3 - 2) { for (const x of [1 + 1]) { if (false) { f(); } } }"
+        ),
+        "for (let i = 2; i < 10; i += 1) {\n    for (const x of [2]) {\n        ;;\n    };\n};\n"
+    );
+    assert_eq!(
+        optimize_to_string(
+            &mut context,
+            "function* g() { yield 1 + 1; } async function f() { await (2 * 2); }"
+        ),
+        "function* g() {\n    yield 2;\n};\nasync function f() {\n    await 4;\n};\n"
+    );
+
+    context.set_optimize(true);
+    assert_eq!(
+        forward(
+            &mut context,
+            r#"
+            let log = [];
+            try { if (1) { log.push('try'); } else { log.push('else'); } } finally { log.push('finally'); }
+            switch (2) { case 1 + 1: if (false) { log.push('dead'); } log.push('case'); }
+            log.join()
+            "#
+        ),
+        "\"try,finally,case\""
+    );
+}
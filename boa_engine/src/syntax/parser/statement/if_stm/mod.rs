@@ -73,6 +73,7 @@ where
 
         let strict = cursor.strict_mode();
         let token = cursor.peek(0, interner)?.ok_or(ParseError::AbruptEnd)?;
+        let body_position = token.span().start();
         let then_node = match token.kind() {
             TokenKind::Keyword((Keyword::Function, _)) if !strict => {
                 // FunctionDeclarations in IfStatement Statement Clauses
@@ -94,6 +95,7 @@ where
             }
         };
 
+        let mut else_position = None;
         let else_node = if let Some(token) = cursor.peek(0, interner)? {
             match token.kind() {
                 TokenKind::Keyword((Keyword::Else, true)) => {
@@ -107,6 +109,7 @@ where
 
                     let strict = cursor.strict_mode();
                     let token = cursor.peek(0, interner)?.ok_or(ParseError::AbruptEnd)?;
+                    else_position = Some(token.span().start());
                     match token.kind() {
                         TokenKind::Keyword((Keyword::Function, _)) if !strict => {
                             // FunctionDeclarations in IfStatement Statement Clauses
@@ -142,6 +145,8 @@ where
             None
         };
 
-        Ok(If::new::<_, _, Node, _>(condition, then_node, else_node))
+        let mut if_node = If::new::<_, _, Node, _>(condition, then_node, else_node);
+        if_node.set_positions(body_position, else_position);
+        Ok(if_node)
     }
 }
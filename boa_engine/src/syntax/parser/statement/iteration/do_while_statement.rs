@@ -72,6 +72,11 @@ where
             .span()
             .end();
 
+        let body_position = cursor
+            .peek(0, interner)?
+            .ok_or(ParseError::AbruptEnd)?
+            .span()
+            .start();
         let body = Statement::new(self.allow_yield, self.allow_await, self.allow_return)
             .parse(cursor, interner)?;
 
@@ -117,6 +122,8 @@ where
             }
         }
 
-        Ok(DoWhileLoop::new(body, cond))
+        let mut do_while_loop = DoWhileLoop::new(body, cond);
+        do_while_loop.set_body_position(body_position);
+        Ok(do_while_loop)
     }
 }
@@ -129,6 +129,11 @@ where
                     .span()
                     .end();
 
+                let body_position = cursor
+                    .peek(0, interner)?
+                    .ok_or(ParseError::AbruptEnd)?
+                    .span()
+                    .start();
                 let body = Statement::new(self.allow_yield, self.allow_await, self.allow_return)
                     .parse(cursor, interner)?;
 
@@ -164,7 +169,9 @@ where
                     }
                 }
 
-                return Ok(ForInLoop::new(init, expr, body).into());
+                let mut for_in_loop = ForInLoop::new(init, expr, body);
+                for_in_loop.set_body_position(body_position);
+                return Ok(for_in_loop.into());
             }
             (Some(init), TokenKind::Keyword((Keyword::Of, false))) => {
                 let init = node_to_iterable_loop_initializer(init, init_position)?;
@@ -178,6 +185,11 @@ where
                     .span()
                     .end();
 
+                let body_position = cursor
+                    .peek(0, interner)?
+                    .ok_or(ParseError::AbruptEnd)?
+                    .span()
+                    .start();
                 let body = Statement::new(self.allow_yield, self.allow_await, self.allow_return)
                     .parse(cursor, interner)?;
 
@@ -213,7 +225,9 @@ where
                     }
                 }
 
-                return Ok(ForOfLoop::new(init, iterable, body).into());
+                let mut for_of_loop = ForOfLoop::new(init, iterable, body);
+                for_of_loop.set_body_position(body_position);
+                return Ok(for_of_loop.into());
             }
             (Some(Node::ConstDeclList(list)), _) => {
                 // Reject const declarations without initializers inside for loops
@@ -269,7 +283,9 @@ where
         }
 
         // TODO: do not encapsulate the `for` in a block just to have an inner scope.
-        Ok(ForLoop::new(init, cond, step, body).into())
+        let mut for_loop = ForLoop::new(init, cond, step, body);
+        for_loop.set_body_position(position);
+        Ok(for_loop.into())
     }
 }
 
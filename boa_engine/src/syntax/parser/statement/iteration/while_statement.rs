@@ -68,6 +68,11 @@ where
             .span()
             .end();
 
+        let body_position = cursor
+            .peek(0, interner)?
+            .ok_or(ParseError::AbruptEnd)?
+            .span()
+            .start();
         let body = Statement::new(self.allow_yield, self.allow_await, self.allow_return)
             .parse(cursor, interner)?;
 
@@ -76,6 +81,8 @@ where
             return Err(ParseError::wrong_function_declaration_non_strict(position));
         }
 
-        Ok(WhileLoop::new(cond, body))
+        let mut while_loop = WhileLoop::new(cond, body);
+        while_loop.set_body_position(body_position);
+        Ok(while_loop)
     }
 }
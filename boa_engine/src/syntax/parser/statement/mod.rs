@@ -293,11 +293,11 @@ where
         let mut items = Vec::new();
 
         loop {
-            match cursor.peek(0, interner)? {
+            let position = match cursor.peek(0, interner)? {
                 Some(token) if self.break_nodes.contains(token.kind()) => break,
                 None => break,
-                _ => {}
-            }
+                Some(token) => token.span().start(),
+            };
 
             let item = StatementListItem::new(
                 self.allow_yield,
@@ -306,15 +306,18 @@ where
                 self.in_block,
             )
             .parse(cursor, interner)?;
-            items.push(item);
+            items.push((item, position));
 
             // move the cursor forward for any consecutive semicolon.
             while cursor.next_if(Punctuator::Semicolon, interner)?.is_some() {}
         }
 
-        items.sort_by(Node::hoistable_order);
+        items.sort_by(|(a, _), (b, _)| Node::hoistable_order(a, b));
 
-        Ok(items.into())
+        let (items, positions): (Vec<_>, Vec<_>) = items.into_iter().unzip();
+        let mut list = node::StatementList::from(items);
+        list.set_positions(positions.into());
+        Ok(list)
     }
 }
 
@@ -0,0 +1,111 @@
+//! Conversions from Rust types to `JsValue` that can't fail.
+
+use crate::{
+    object::{
+        JsArray, JsArrayBuffer, JsDataView, JsDate, JsFunction, JsGenerator, JsMap, JsObject,
+        JsProxy, JsRegExp, JsSet, JsTypedArray,
+    },
+    Context, JsBigInt, JsString, JsSymbol, JsValue,
+};
+use std::{collections::HashMap, hash::BuildHasher};
+
+/// A type that can be converted to a `JsValue`, creating objects in `context` if needed.
+///
+/// This can be derived for structs with named fields, which are converted to objects with a
+/// property for each field. See [`TryFromJs`][super::TryFromJs] for the attributes of the derive.
+pub trait IntoJs {
+    /// Converts this value to a `JsValue`.
+    fn into_js(self, context: &mut Context) -> JsValue;
+}
+
+macro_rules! impl_into_js_from {
+    ($($ty:ty),*) => {
+        $(
+            impl IntoJs for $ty {
+                #[inline]
+                fn into_js(self, _: &mut Context) -> JsValue {
+                    self.into()
+                }
+            }
+        )*
+    };
+}
+
+impl_into_js_from!(JsValue, bool, char, String, JsString, JsSymbol, JsBigInt);
+impl_into_js_from!(f32, f64, i8, u8, i16, u16, i32, u32, i64, u64, usize);
+impl_into_js_from!(JsObject, JsArray, JsFunction, JsTypedArray);
+impl_into_js_from!(JsMap, JsSet, JsDate, JsRegExp);
+impl_into_js_from!(JsArrayBuffer, JsDataView, JsGenerator, JsProxy);
+
+/// `()` is converted to `undefined`.
+impl IntoJs for () {
+    #[inline]
+    fn into_js(self, _: &mut Context) -> JsValue {
+        JsValue::undefined()
+    }
+}
+
+impl IntoJs for &str {
+    #[inline]
+    fn into_js(self, _: &mut Context) -> JsValue {
+        self.into()
+    }
+}
+
+/// `None` is converted to `undefined`.
+impl<T: IntoJs> IntoJs for Option<T> {
+    #[inline]
+    fn into_js(self, context: &mut Context) -> JsValue {
+        match self {
+            Some(value) => value.into_js(context),
+            None => JsValue::undefined(),
+        }
+    }
+}
+
+/// Converted to an array.
+impl<T: IntoJs> IntoJs for Vec<T> {
+    fn into_js(self, context: &mut Context) -> JsValue {
+        let elements = self
+            .into_iter()
+            .map(|element| element.into_js(context))
+            .collect::<Vec<_>>();
+        JsArray::from_iter(elements, context).into()
+    }
+}
+
+/// Converted to an object with a property for each entry.
+impl<T: IntoJs, S: BuildHasher> IntoJs for HashMap<String, T, S> {
+    fn into_js(self, context: &mut Context) -> JsValue {
+        let object = context.construct_object();
+        for (key, value) in self {
+            let value = value.into_js(context);
+            object
+                .create_data_property_or_throw(key, value, context)
+                .expect("defining a property of a new object must not fail");
+        }
+        object.into()
+    }
+}
+
+macro_rules! impl_into_js_for_tuple {
+    ($($param:ident $value:ident),*) => {
+        /// Converted to an array.
+        impl<$($param: IntoJs),*> IntoJs for ($($param,)*) {
+            fn into_js(self, context: &mut Context) -> JsValue {
+                let ($($value,)*) = self;
+                let elements = [$($value.into_js(context)),*];
+                JsArray::from_iter(elements, context).into()
+            }
+        }
+    };
+}
+
+impl_into_js_for_tuple!(A a);
+impl_into_js_for_tuple!(A a, B b);
+impl_into_js_for_tuple!(A a, B b, C c);
+impl_into_js_for_tuple!(A a, B b, C c, D d);
+impl_into_js_for_tuple!(A a, B b, C c, D d, E e);
+impl_into_js_for_tuple!(A a, B b, C c, D d, E e, F f);
+impl_into_js_for_tuple!(A a, B b, C c, D d, E e, F f, G g);
+impl_into_js_for_tuple!(A a, B b, C c, D d, E e, F f, G g, H h);
@@ -33,8 +33,11 @@ pub(crate) mod display;
 mod equality;
 mod hash;
 mod integer;
+mod into_js;
 mod operations;
 mod serde_json;
+mod try_from_js;
+mod try_into_js;
 mod r#type;
 
 pub use conversions::*;
@@ -42,8 +45,13 @@ pub use display::ValueDisplay;
 pub use equality::*;
 pub use hash::*;
 pub use integer::IntegerOrInfinity;
+pub use into_js::IntoJs;
 pub use operations::*;
 pub use r#type::Type;
+pub use try_from_js::TryFromJs;
+pub use try_into_js::TryIntoJs;
+
+pub use boa_engine_derive::{IntoJs, TryFromJs};
 
 static TWO_E_64: Lazy<BigInt> = Lazy::new(|| {
     const TWO_E_64: u128 = 2u128.pow(64);
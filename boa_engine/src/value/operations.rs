@@ -1,6 +1,5 @@
 use super::{
-    Context, FromStr, JsBigInt, JsResult, JsString, JsValue, Numeric, PreferredType,
-    WellKnownSymbols,
+    Context, FromStr, JsBigInt, JsResult, JsValue, Numeric, PreferredType, WellKnownSymbols,
 };
 use crate::builtins::number::{f64_to_int32, f64_to_uint32, Number};
 
@@ -19,9 +18,17 @@ impl JsValue {
             (Self::BigInt(ref x), Self::BigInt(ref y)) => Self::new(JsBigInt::add(x, y)),
 
             // String concat
-            (Self::String(ref x), Self::String(ref y)) => Self::from(JsString::concat(x, y)),
-            (Self::String(ref x), y) => Self::from(JsString::concat(x, y.to_string(context)?)),
-            (x, Self::String(ref y)) => Self::from(JsString::concat(x.to_string(context)?, y)),
+            (Self::String(ref x), Self::String(ref y)) => {
+                Self::from(context.concat_strings(&[x.as_str(), y.as_str()])?)
+            }
+            (Self::String(ref x), y) => {
+                let y = y.to_string(context)?;
+                Self::from(context.concat_strings(&[x.as_str(), y.as_str()])?)
+            }
+            (x, Self::String(ref y)) => {
+                let x = x.to_string(context)?;
+                Self::from(context.concat_strings(&[x.as_str(), y.as_str()])?)
+            }
 
             // Slow path:
             (_, _) => match (
@@ -29,10 +36,12 @@ impl JsValue {
                 other.to_primitive(context, PreferredType::Default)?,
             ) {
                 (Self::String(ref x), ref y) => {
-                    Self::from(JsString::concat(x, y.to_string(context)?))
+                    let y = y.to_string(context)?;
+                    Self::from(context.concat_strings(&[x.as_str(), y.as_str()])?)
                 }
                 (ref x, Self::String(ref y)) => {
-                    Self::from(JsString::concat(x.to_string(context)?, y))
+                    let x = x.to_string(context)?;
+                    Self::from(context.concat_strings(&[x.as_str(), y.as_str()])?)
                 }
                 (x, y) => match (x.to_numeric(context)?, y.to_numeric(context)?) {
                     (Numeric::Number(x), Numeric::Number(y)) => Self::new(x + y),
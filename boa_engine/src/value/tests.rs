@@ -1,7 +1,7 @@
 #![allow(clippy::float_cmp)]
 
 use super::*;
-use crate::{check_output, forward, forward_val, Context, TestAction};
+use crate::{check_output, forward, forward_val, property::Attribute, Context, TestAction};
 
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
@@ -611,6 +611,86 @@ fn to_primitive() {
     ]);
 }
 
+#[test]
+fn derived_conversions() {
+    use std::collections::HashMap;
+
+    #[derive(Debug, PartialEq, TryFromJs, IntoJs)]
+    struct Point {
+        x: i32,
+        #[boa(rename = "yCoord")]
+        y: i32,
+        #[boa(default)]
+        label: String,
+    }
+
+    let mut context = Context::default();
+
+    let value = forward_val(&mut context, "({ x: 1, yCoord: 2 })").unwrap();
+    let point = Point::try_from_js(&value, &mut context).unwrap();
+    assert_eq!(
+        point,
+        Point {
+            x: 1,
+            y: 2,
+            label: String::new()
+        }
+    );
+    assert!(Point::try_from_js(&JsValue::new(1), &mut context).is_err());
+    let value = forward_val(&mut context, "({ x: 1, y: 2 })").unwrap();
+    assert!(Point::try_from_js(&value, &mut context).is_err());
+
+    let value = point.into_js(&mut context);
+    context.register_global_property("point", value, Attribute::all());
+    assert_eq!(forward(&mut context, "point.yCoord"), "2");
+    assert_eq!(forward(&mut context, "point.label"), "\"\"");
+
+    let value = forward_val(&mut context, "({ a: 1, b: 2 })").unwrap();
+    let map = HashMap::<String, u8>::try_from_js(&value, &mut context).unwrap();
+    assert_eq!(map.len(), 2);
+    assert_eq!(map["b"], 2);
+
+    let value = forward_val(&mut context, "[1, 'one']").unwrap();
+    let tuple = <(f64, String)>::try_from_js(&value, &mut context).unwrap();
+    assert_eq!(tuple, (1.0, "one".to_owned()));
+    let value = tuple.into_js(&mut context);
+    context.register_global_property("tuple", value, Attribute::all());
+    assert_eq!(forward(&mut context, "tuple.length"), "2");
+}
+
+#[test]
+fn integer_conversion_bounds() {
+    let mut context = Context::default();
+
+    assert_eq!(u8::try_from_js(&JsValue::new(255), &mut context), Ok(255));
+    assert!(u8::try_from_js(&JsValue::new(256), &mut context).is_err());
+    assert!(u8::try_from_js(&JsValue::new(-1), &mut context).is_err());
+    assert_eq!(i8::try_from_js(&JsValue::new(-128), &mut context), Ok(-128));
+    assert!(i8::try_from_js(&JsValue::new(128), &mut context).is_err());
+    assert!(i32::try_from_js(&JsValue::new(1.5), &mut context).is_err());
+
+    let two_pow_63 = 2f64.powi(63);
+    let two_pow_64 = 2f64.powi(64);
+    assert_eq!(
+        i64::try_from_js(&JsValue::new(-two_pow_63), &mut context),
+        Ok(i64::MIN)
+    );
+    assert!(i64::try_from_js(&JsValue::new(two_pow_63), &mut context).is_err());
+    assert_eq!(
+        u64::try_from_js(&JsValue::new(two_pow_63), &mut context),
+        Ok(1 << 63)
+    );
+    assert!(u64::try_from_js(&JsValue::new(two_pow_64), &mut context).is_err());
+    assert!(usize::try_from_js(&JsValue::new(two_pow_64), &mut context).is_err());
+
+    // The largest `f64` below the bounds still converts.
+    let below_two_pow_64 = two_pow_64 - 2048.0;
+    assert_eq!(
+        u64::try_from_js(&JsValue::new(below_two_pow_64), &mut context),
+        Ok(u64::MAX - 2047)
+    );
+}
+
 /// Test cyclic conversions that previously caused stack overflows
 /// Relevant mitigations for these are in `JsObject::ordinary_to_primitive` and
 /// `JsObject::to_json`
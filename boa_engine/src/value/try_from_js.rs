@@ -0,0 +1,221 @@
+//! Conversions from `JsValue` to Rust types.
+
+use crate::{
+    object::{
+        JsArray, JsArrayBuffer, JsDataView, JsDate, JsFunction, JsGenerator, JsMap, JsObject,
+        JsProxy, JsRegExp, JsSet, JsTypedArray,
+    },
+    property::PropertyNameKind,
+    Context, JsResult, JsString, JsValue,
+};
+use std::{collections::HashMap, hash::BuildHasher};
+
+/// A type that can be created from a `JsValue`, throwing a `TypeError` if the value doesn't have
+/// the expected type, or a `RangeError` if a number doesn't fit in the expected number type.
+///
+/// The conversions don't coerce values: a `String` can only be created from a string, and an
+/// `i32` only from a number that is an integer in the range of `i32`.
+///
+/// # Deriving
+///
+/// `TryFromJs` and [`IntoJs`][super::IntoJs] can be derived for structs with named fields, which
+/// are converted from and to objects with a property for each field. The properties have the
+/// names of the fields, unless they are renamed:
+///
+/// - `#[boa(rename = "name")]` on a field sets the name of its property.
+/// - `#[boa(default)]` on a field uses `Default::default()` when its property is `undefined`,
+///   instead of converting `undefined`. It is ignored by `IntoJs`.
+///
+/// ```
+/// # use boa_engine::{value::{IntoJs, TryFromJs}, Context};
+/// #[derive(Debug, PartialEq, TryFromJs, IntoJs)]
+/// struct Options {
+///     #[boa(rename = "maxDepth")]
+///     max_depth: u32,
+///     #[boa(default)]
+///     verbose: bool,
+/// }
+///
+/// let mut context = Context::default();
+/// let value = context.eval("({ maxDepth: 4 })").unwrap();
+/// let options = Options::try_from_js(&value, &mut context).unwrap();
+/// assert_eq!(options, Options { max_depth: 4, verbose: false });
+///
+/// let value = options.into_js(&mut context);
+/// let object = value.as_object().unwrap();
+/// assert_eq!(object.get("maxDepth", &mut context).unwrap(), 4.into());
+/// ```
+pub trait TryFromJs: Sized {
+    /// Converts `value` to this type.
+    fn try_from_js(value: &JsValue, context: &mut Context) -> JsResult<Self>;
+}
+
+impl TryFromJs for JsValue {
+    #[inline]
+    fn try_from_js(value: &JsValue, _: &mut Context) -> JsResult<Self> {
+        Ok(value.clone())
+    }
+}
+
+impl TryFromJs for bool {
+    fn try_from_js(value: &JsValue, context: &mut Context) -> JsResult<Self> {
+        match value.as_boolean() {
+            Some(boolean) => Ok(boolean),
+            None => unexpected_type("a boolean", value, context),
+        }
+    }
+}
+
+impl TryFromJs for JsString {
+    fn try_from_js(value: &JsValue, context: &mut Context) -> JsResult<Self> {
+        match value.as_string() {
+            Some(string) => Ok(string.clone()),
+            None => unexpected_type("a string", value, context),
+        }
+    }
+}
+
+impl TryFromJs for String {
+    fn try_from_js(value: &JsValue, context: &mut Context) -> JsResult<Self> {
+        JsString::try_from_js(value, context).map(|string| string.as_str().to_owned())
+    }
+}
+
+impl TryFromJs for f64 {
+    fn try_from_js(value: &JsValue, context: &mut Context) -> JsResult<Self> {
+        match value.as_number() {
+            Some(number) => Ok(number),
+            None => unexpected_type("a number", value, context),
+        }
+    }
+}
+
+impl TryFromJs for f32 {
+    fn try_from_js(value: &JsValue, context: &mut Context) -> JsResult<Self> {
+        f64::try_from_js(value, context).map(|number| number as Self)
+    }
+}
+
+macro_rules! impl_try_from_js_for_integer {
+    ($($ty:ty),*) => {
+        $(
+            impl TryFromJs for $ty {
+                // `From<$ty> for f64` is not implemented for the 64-bit integers.
+                #[allow(clippy::float_cmp, clippy::cast_lossless)]
+                fn try_from_js(value: &JsValue, context: &mut Context) -> JsResult<Self> {
+                    let number = f64::try_from_js(value, context)?;
+                    // `Self::MAX` of the 64-bit integers rounds up to the next power of two as an
+                    // `f64`, where adding one has no effect, so the exclusive bound is exact for
+                    // every type.
+                    if number.fract() == 0.0
+                        && number >= Self::MIN as f64
+                        && number < Self::MAX as f64 + 1.0
+                    {
+                        Ok(number as Self)
+                    } else {
+                        context.throw_range_error(format!(
+                            "{number} is not an integer in the range of `{}`",
+                            stringify!($ty)
+                        ))
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_try_from_js_for_integer!(i8, u8, i16, u16, i32, u32, i64, u64, usize);
+
+impl TryFromJs for JsObject {
+    fn try_from_js(value: &JsValue, context: &mut Context) -> JsResult<Self> {
+        match value.as_object() {
+            Some(object) => Ok(object.clone()),
+            None => unexpected_type("an object", value, context),
+        }
+    }
+}
+
+macro_rules! impl_try_from_js_for_object_wrapper {
+    ($($ty:ty),*) => {
+        $(
+            impl TryFromJs for $ty {
+                fn try_from_js(value: &JsValue, context: &mut Context) -> JsResult<Self> {
+                    let object = JsObject::try_from_js(value, context)?;
+                    Self::from_object(object, context)
+                }
+            }
+        )*
+    };
+}
+
+impl_try_from_js_for_object_wrapper!(JsArray, JsTypedArray, JsFunction);
+impl_try_from_js_for_object_wrapper!(JsMap, JsSet, JsDate, JsRegExp);
+impl_try_from_js_for_object_wrapper!(JsArrayBuffer, JsDataView, JsGenerator, JsProxy);
+
+/// `undefined` and `null` are converted to `None`.
+impl<T: TryFromJs> TryFromJs for Option<T> {
+    fn try_from_js(value: &JsValue, context: &mut Context) -> JsResult<Self> {
+        if value.is_null_or_undefined() {
+            Ok(None)
+        } else {
+            T::try_from_js(value, context).map(Some)
+        }
+    }
+}
+
+/// Converts the elements of an array-like object.
+impl<T: TryFromJs> TryFromJs for Vec<T> {
+    fn try_from_js(value: &JsValue, context: &mut Context) -> JsResult<Self> {
+        let object = JsObject::try_from_js(value, context)?;
+        let length = object.length_of_array_like(context)?;
+        let mut vec = Self::with_capacity(length);
+        for index in 0..length {
+            let element = object.get(index, context)?;
+            vec.push(T::try_from_js(&element, context)?);
+        }
+        Ok(vec)
+    }
+}
+
+/// Converts the own enumerable string-keyed properties of an object.
+impl<T: TryFromJs, S: BuildHasher + Default> TryFromJs for HashMap<String, T, S> {
+    fn try_from_js(value: &JsValue, context: &mut Context) -> JsResult<Self> {
+        let object = JsObject::try_from_js(value, context)?;
+        let keys = object.enumerable_own_property_names(PropertyNameKind::Key, context)?;
+        let mut map = Self::with_capacity_and_hasher(keys.len(), S::default());
+        for key in keys {
+            let key = key.to_string(context)?;
+            let value = object.get(key.clone(), context)?;
+            map.insert(key.as_str().to_owned(), T::try_from_js(&value, context)?);
+        }
+        Ok(map)
+    }
+}
+
+macro_rules! impl_try_from_js_for_tuple {
+    ($($param:ident $index:literal),*) => {
+        /// Converts the first elements of an array-like object.
+        impl<$($param: TryFromJs),*> TryFromJs for ($($param,)*) {
+            fn try_from_js(value: &JsValue, context: &mut Context) -> JsResult<Self> {
+                let object = JsObject::try_from_js(value, context)?;
+                Ok(($(
+                    $param::try_from_js(&object.get($index, context)?, context)?,
+                )*))
+            }
+        }
+    };
+}
+
+impl_try_from_js_for_tuple!(A 0);
+impl_try_from_js_for_tuple!(A 0, B 1);
+impl_try_from_js_for_tuple!(A 0, B 1, C 2);
+impl_try_from_js_for_tuple!(A 0, B 1, C 2, D 3);
+impl_try_from_js_for_tuple!(A 0, B 1, C 2, D 3, E 4);
+impl_try_from_js_for_tuple!(A 0, B 1, C 2, D 3, E 4, F 5);
+impl_try_from_js_for_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+impl_try_from_js_for_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+
+/// Throws a `TypeError` for a value that isn't `expected`.
+fn unexpected_type<T>(expected: &str, value: &JsValue, context: &mut Context) -> JsResult<T> {
+    context.throw_type_error(format!("expected {expected}, got {}", value.type_of()))
+}
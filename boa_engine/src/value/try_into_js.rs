@@ -0,0 +1,37 @@
+//! Conversions from Rust types to `JsValue`.
+
+use crate::{value::IntoJs, Context, JsError, JsResult, JsValue};
+
+/// A type that can be converted to a `JsValue`, such as the return type of a typed native
+/// function.
+///
+/// Every [`IntoJs`] type can be converted, and converting a `Result` throws its error.
+pub trait TryIntoJs {
+    /// Converts this value to a `JsValue`.
+    fn try_into_js(self, context: &mut Context) -> JsResult<JsValue>;
+}
+
+impl<T: IntoJs> TryIntoJs for T {
+    #[inline]
+    fn try_into_js(self, context: &mut Context) -> JsResult<JsValue> {
+        Ok(self.into_js(context))
+    }
+}
+
+/// `Err` throws the error.
+impl<T: TryIntoJs> TryIntoJs for JsResult<T> {
+    #[inline]
+    fn try_into_js(self, context: &mut Context) -> JsResult<JsValue> {
+        self?.try_into_js(context)
+    }
+}
+
+/// `Err` throws the error, converted to a JavaScript value.
+impl<T: TryIntoJs> TryIntoJs for Result<T, JsError> {
+    fn try_into_js(self, context: &mut Context) -> JsResult<JsValue> {
+        match self {
+            Ok(value) => value.try_into_js(context),
+            Err(error) => context.throw(error),
+        }
+    }
+}
@@ -3,7 +3,7 @@
 //! This module will provides everything needed to implement the `CallFrame`
 
 use super::CodeBlock;
-use crate::JsValue;
+use crate::{environments::DeclarativeEnvironmentStack, JsValue};
 use boa_gc::{Finalize, Gc, Trace};
 
 #[derive(Clone, Debug, Finalize, Trace)]
@@ -32,6 +32,9 @@ pub struct CallFrame {
     pub(crate) arg_count: usize,
     #[unsafe_ignore_trace]
     pub(crate) generator_resume_kind: GeneratorResumeKind,
+
+    // The environments of the calling frame, saved while a debugger is attached.
+    pub(crate) caller_environments: Option<DeclarativeEnvironmentStack>,
 }
 
 impl CallFrame {
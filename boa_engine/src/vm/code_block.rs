@@ -14,15 +14,18 @@ use crate::{
     environments::{BindingLocator, DeclarativeEnvironmentStack},
     object::{internal_methods::get_prototype_from_constructor, JsObject, ObjectData},
     property::{PropertyDescriptor, PropertyKey},
-    syntax::ast::node::FormalParameterList,
+    syntax::ast::{node::FormalParameterList, Position},
     vm::call_frame::GeneratorResumeKind,
     vm::{call_frame::FinallyReturn, CallFrame, Opcode},
-    Context, JsResult, JsValue,
+    Context, JsResult, JsString, JsValue,
 };
 use boa_gc::{Cell, Finalize, Gc, Trace};
 use boa_interner::{Interner, Sym, ToInternedString};
 use boa_profiler::Profiler;
-use std::{convert::TryInto, mem::size_of};
+use std::{collections::BTreeSet, convert::TryInto, mem::size_of};
+
+#[cfg(feature = "jit")]
+use crate::vm::Hotness;
 
 /// This represents whether a value can be read from [`CodeBlock`] code.
 ///
@@ -100,6 +103,42 @@ pub struct CodeBlock {
     /// Similar to the `[[ClassFieldInitializerName]]` slot in the spec.
     /// Holds class field names that are computed at class declaration time.
     pub(crate) computed_field_names: Option<Cell<Vec<PropertyKey>>>,
+
+    /// Is this the default constructor of a derived class, which constructs its `this` by calling
+    /// the constructor of the superclass.
+    pub(crate) derived_default_constructor: bool,
+
+    /// Name of the source this function was compiled from, empty if the source has no name.
+    #[unsafe_ignore_trace]
+    pub(crate) source: JsString,
+
+    /// Source position of each statement, as `(pc, position)` pairs ordered by `pc`.
+    #[unsafe_ignore_trace]
+    pub(crate) positions: Vec<(u32, Position)>,
+
+    /// Lexical scopes of this function, used to inspect its bindings while debugging.
+    #[unsafe_ignore_trace]
+    pub(crate) scopes: Vec<ScopeInfo>,
+
+    /// How often this function was called and how many loop iterations it ran.
+    #[cfg(feature = "jit")]
+    #[unsafe_ignore_trace]
+    pub(crate) hotness: Hotness,
+}
+
+/// The bindings of a compile time environment, and the range of bytecode where they are in scope.
+#[derive(Clone, Debug)]
+pub(crate) struct ScopeInfo {
+    /// First instruction of the scope.
+    pub(crate) start: u32,
+    /// Instruction after the last instruction of the scope.
+    pub(crate) end: u32,
+    /// Index of the environment on the environment stack.
+    pub(crate) environment_index: usize,
+    /// Is this a function environment.
+    pub(crate) function_scope: bool,
+    /// Name and index of the bindings of the environment.
+    pub(crate) bindings: Vec<(Sym, usize)>,
 }
 
 impl CodeBlock {
@@ -121,9 +160,89 @@ impl CodeBlock {
             lexical_name_argument: false,
             arguments_binding: None,
             computed_field_names: None,
+            derived_default_constructor: false,
+            source: JsString::default(),
+            positions: Vec::new(),
+            scopes: Vec::new(),
+            #[cfg(feature = "jit")]
+            hotness: Hotness::default(),
         }
     }
 
+    /// Returns how many times this function was entered, counting the resumptions of
+    /// generators.
+    #[cfg(feature = "jit")]
+    #[inline]
+    pub fn invocation_count(&self) -> u32 {
+        self.hotness.invocations()
+    }
+
+    /// Returns how many loop iterations this function ran.
+    #[cfg(feature = "jit")]
+    #[inline]
+    pub fn loop_iteration_count(&self) -> u32 {
+        self.hotness.loop_iterations()
+    }
+
+    /// Returns `true` once this function was called, or ran loop iterations, as many times as
+    /// the [JIT threshold](crate::Context::set_jit_threshold) of a context running it.
+    #[cfg(feature = "jit")]
+    #[inline]
+    pub fn is_hot(&self) -> bool {
+        self.hotness.is_hot()
+    }
+
+    /// Records that the statement at `position` starts at `pc`.
+    ///
+    /// Statements that don't emit any code share their `pc` with the next statement, which
+    /// replaces them.
+    pub(crate) fn add_position(&mut self, pc: u32, position: Position) {
+        match self.positions.last_mut() {
+            Some(last) if last.0 == pc => last.1 = position,
+            _ => self.positions.push((pc, position)),
+        }
+    }
+
+    /// Returns the index in `positions` of the statement that starts at `pc`, if any.
+    pub(crate) fn statement_index(&self, pc: usize) -> Option<usize> {
+        self.positions
+            .binary_search_by_key(&pc, |(start, _)| *start as usize)
+            .ok()
+    }
+
+    /// Returns the line of the statement that starts at `pc`, if any.
+    pub(crate) fn statement_line(&self, pc: usize) -> Option<u32> {
+        self.statement_index(pc)
+            .map(|index| self.positions[index].1.line_number())
+    }
+
+    /// Returns the lines where the statements of this code block and of the functions it defines
+    /// start, which are the lines where the debugger can pause.
+    pub fn statement_lines(&self) -> BTreeSet<u32> {
+        let mut lines = self
+            .positions
+            .iter()
+            .map(|(_, position)| position.line_number())
+            .collect::<BTreeSet<_>>();
+        for function in &self.functions {
+            lines.extend(function.statement_lines());
+        }
+        lines
+    }
+
+    /// Returns the position of the statement containing the instruction at `pc`, if known.
+    pub(crate) fn position_at(&self, pc: usize) -> Option<Position> {
+        let index = self
+            .positions
+            .partition_point(|(start, _)| *start as usize <= pc);
+        index.checked_sub(1).map(|index| self.positions[index].1)
+    }
+
+    /// Returns the line of the statement containing the instruction at `pc`, if known.
+    pub(crate) fn line_at(&self, pc: usize) -> Option<u32> {
+        self.position_at(pc).map(Position::line_number)
+    }
+
     /// Read type T from code.
     ///
     /// # Safety
@@ -698,7 +817,9 @@ impl JsObject {
                     param_count,
                     arg_count,
                     generator_resume_kind: GeneratorResumeKind::Normal,
+                    caller_environments: None,
                 });
+                context.save_caller_environments(&environments);
 
                 let result = context.run();
                 context.vm.pop_frame().expect("must have frame");
@@ -793,11 +914,13 @@ impl JsObject {
                     param_count,
                     arg_count,
                     generator_resume_kind: GeneratorResumeKind::Normal,
+                    caller_environments: None,
                 };
                 let mut stack = args;
 
                 std::mem::swap(&mut context.vm.stack, &mut stack);
                 context.vm.push_frame(call_frame);
+                context.save_caller_environments(&environments);
 
                 let init_result = context.run();
 
@@ -880,19 +1003,50 @@ impl JsObject {
                 code,
                 mut environments,
             } => {
+                // The default constructor of a derived class is `constructor(...args) { super(...args); }`,
+                // so the superclass creates `this`, which lets classes extend native classes.
+                let derived_this = if code.derived_default_constructor {
+                    let superclass = this_function_object
+                        .__get_prototype_of__(context)?
+                        .filter(Self::is_constructor);
+                    let superclass = match superclass {
+                        Some(superclass) => superclass,
+                        None => {
+                            return context
+                                .throw_type_error("super constructor is not a constructor")
+                        }
+                    };
+                    match superclass
+                        .construct(args, this_target, context)?
+                        .as_object()
+                    {
+                        Some(this) => Some(this.clone()),
+                        None => {
+                            return context
+                                .throw_type_error("super constructor must return an object")
+                        }
+                    }
+                } else {
+                    None
+                };
+
                 std::mem::swap(&mut environments, &mut context.realm.environments);
 
                 let this = {
-                    // If the prototype of the constructor is not an object, then use the default object
-                    // prototype as prototype for the new object
-                    // see <https://tc39.es/ecma262/#sec-ordinarycreatefromconstructor>
-                    // see <https://tc39.es/ecma262/#sec-getprototypefromconstructor>
-                    let prototype = get_prototype_from_constructor(
-                        this_target,
-                        StandardConstructors::object,
-                        context,
-                    )?;
-                    let this = Self::from_proto_and_data(prototype, ObjectData::ordinary());
+                    let this = if let Some(this) = derived_this {
+                        this
+                    } else {
+                        // If the prototype of the constructor is not an object, then use the default object
+                        // prototype as prototype for the new object
+                        // see <https://tc39.es/ecma262/#sec-ordinarycreatefromconstructor>
+                        // see <https://tc39.es/ecma262/#sec-getprototypefromconstructor>
+                        let prototype = get_prototype_from_constructor(
+                            this_target,
+                            StandardConstructors::object,
+                            context,
+                        )?;
+                        Self::from_proto_and_data(prototype, ObjectData::ordinary())
+                    };
 
                     // Set computed class field names if they exist.
                     if let Some(fields) = &code.computed_field_names {
@@ -981,7 +1135,9 @@ impl JsObject {
                     param_count,
                     arg_count,
                     generator_resume_kind: GeneratorResumeKind::Normal,
+                    caller_environments: None,
                 });
+                context.save_caller_environments(&environments);
 
                 let result = context.run();
 
@@ -0,0 +1,357 @@
+//! Code coverage of the scripts running in the VM.
+
+use crate::{
+    syntax::ast::Position,
+    vm::{CodeBlock, Opcode},
+    Context, JsString,
+};
+use boa_gc::Gc;
+use boa_interner::{Interner, Sym};
+use rustc_hash::FxHashMap;
+use std::{collections::BTreeMap, convert::TryFrom};
+
+/// The coverage recorded since coverage was enabled with [`Context::set_coverage`], returned by
+/// [`Context::take_coverage`].
+///
+/// Functions, statements and branches are keyed by the position where their statement starts,
+/// so the statements sharing a line are counted separately. The coverage includes every function
+/// of a script once the script starts running, even the functions that were never called.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Coverage {
+    functions: Vec<FunctionCoverage>,
+    statements: BTreeMap<Position, u64>,
+    branches: Vec<BranchCoverage>,
+}
+
+/// The number of calls of a function.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionCoverage {
+    name: JsString,
+    position: Position,
+    count: u64,
+}
+
+impl FunctionCoverage {
+    /// Returns the name of the function.
+    #[inline]
+    pub fn name(&self) -> &JsString {
+        &self.name
+    }
+
+    /// Returns the position of the first statement of the function.
+    #[inline]
+    pub fn position(&self) -> Position {
+        self.position
+    }
+
+    /// Returns the line of the first statement of the function.
+    #[inline]
+    pub fn line(&self) -> u32 {
+        self.position.line_number()
+    }
+
+    /// Returns the number of times the function was called.
+    #[inline]
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+}
+
+/// The outcomes of a conditional jump, such as the condition of an `if` or a loop, a `case` test,
+/// or the left-hand side of `&&`, `||` and `??`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BranchCoverage {
+    position: Position,
+    taken: u64,
+    not_taken: u64,
+}
+
+impl BranchCoverage {
+    /// Returns the position of the statement containing the branch.
+    #[inline]
+    pub fn position(&self) -> Position {
+        self.position
+    }
+
+    /// Returns the line of the statement containing the branch.
+    #[inline]
+    pub fn line(&self) -> u32 {
+        self.position.line_number()
+    }
+
+    /// Returns the number of times the jump was taken.
+    #[inline]
+    pub fn taken(&self) -> u64 {
+        self.taken
+    }
+
+    /// Returns the number of times the execution fell through the jump.
+    #[inline]
+    pub fn not_taken(&self) -> u64 {
+        self.not_taken
+    }
+}
+
+impl Coverage {
+    /// Returns the functions, ordered by position.
+    #[inline]
+    pub fn functions(&self) -> &[FunctionCoverage] {
+        &self.functions
+    }
+
+    /// Returns the number of times each statement was executed, by the position where it starts.
+    #[inline]
+    pub fn statements(&self) -> &BTreeMap<Position, u64> {
+        &self.statements
+    }
+
+    /// Returns the number of times each line with statements was executed.
+    ///
+    /// A line with several statements counts the executions of its most executed statement.
+    pub fn lines(&self) -> BTreeMap<u32, u64> {
+        let mut lines = BTreeMap::<u32, u64>::new();
+        for (position, count) in &self.statements {
+            let entry = lines.entry(position.line_number()).or_default();
+            *entry = (*entry).max(*count);
+        }
+        lines
+    }
+
+    /// Returns the branches, ordered by position.
+    #[inline]
+    pub fn branches(&self) -> &[BranchCoverage] {
+        &self.branches
+    }
+
+    /// Formats the coverage as an LCOV tracefile, with a record for `source_file`.
+    ///
+    /// LCOV only has lines, so the statements are aggregated by line like in
+    /// [`lines`][Self::lines], and the branches of a line are numbered in order of position.
+    pub fn to_lcov(&self, source_file: &str) -> String {
+        let mut lcov = format!("TN:\nSF:{source_file}\n");
+
+        for function in &self.functions {
+            lcov.push_str(&format!("FN:{},{}\n", function.line(), function.name));
+        }
+        for function in &self.functions {
+            lcov.push_str(&format!("FNDA:{},{}\n", function.count, function.name));
+        }
+        let hit = self.functions.iter().filter(|f| f.count > 0).count();
+        lcov.push_str(&format!("FNF:{}\nFNH:{hit}\n", self.functions.len()));
+
+        let mut block = 0;
+        let mut previous_line = None;
+        for branch in &self.branches {
+            // Block numbers restart on every line.
+            block = if previous_line == Some(branch.line()) {
+                block + 1
+            } else {
+                0
+            };
+            previous_line = Some(branch.line());
+            let executed = branch.taken + branch.not_taken > 0;
+            for (index, count) in [branch.taken, branch.not_taken].into_iter().enumerate() {
+                let count = if executed {
+                    count.to_string()
+                } else {
+                    "-".to_owned()
+                };
+                lcov.push_str(&format!("BRDA:{},{block},{index},{count}\n", branch.line()));
+            }
+        }
+        let hit = self
+            .branches
+            .iter()
+            .map(|b| usize::from(b.taken > 0) + usize::from(b.not_taken > 0))
+            .sum::<usize>();
+        lcov.push_str(&format!("BRF:{}\nBRH:{hit}\n", self.branches.len() * 2));
+
+        let lines = self.lines();
+        for (line, count) in &lines {
+            lcov.push_str(&format!("DA:{line},{count}\n"));
+        }
+        let hit = lines.values().filter(|count| **count > 0).count();
+        lcov.push_str(&format!("LF:{}\nLH:{hit}\n", lines.len()));
+
+        lcov.push_str("end_of_record\n");
+        lcov
+    }
+}
+
+/// The coverage of a code block.
+#[derive(Debug)]
+struct BlockCoverage {
+    code: Gc<CodeBlock>,
+    calls: u64,
+    /// Executions of each statement, in the order of `code.positions`.
+    statements: Vec<u64>,
+    /// The conditional jumps of the code block, by `pc`, with their `(taken, not_taken)` counts.
+    branches: BTreeMap<u32, (u64, u64)>,
+}
+
+impl BlockCoverage {
+    fn new(code: Gc<CodeBlock>, interner: &Interner) -> Self {
+        let mut branches = BTreeMap::new();
+        let mut pc = 0;
+        while pc < code.code.len() {
+            if is_branch(code.code[pc]) {
+                branches.insert(pc as u32, (0, 0));
+            }
+            code.instruction_operands(&mut pc, interner);
+        }
+
+        Self {
+            statements: vec![0; code.positions.len()],
+            code,
+            calls: 0,
+            branches,
+        }
+    }
+}
+
+/// Returns `true` if `opcode` is a conditional jump.
+fn is_branch(opcode: u8) -> bool {
+    matches!(
+        Opcode::try_from(opcode),
+        Ok(Opcode::JumpIfFalse
+            | Opcode::JumpIfNotUndefined
+            | Opcode::LogicalAnd
+            | Opcode::LogicalOr
+            | Opcode::Coalesce
+            | Opcode::Case)
+    )
+}
+
+/// The coverage recording state of a `Context`.
+#[derive(Debug, Default)]
+pub(crate) struct CoverageState {
+    enabled: bool,
+    blocks: Vec<BlockCoverage>,
+    /// Index in `blocks` of each code block, by address.
+    indices: FxHashMap<*const CodeBlock, usize>,
+}
+
+impl CoverageState {
+    /// Returns `true` if coverage is being recorded.
+    #[inline]
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+}
+
+impl Context {
+    /// Enables or disables recording the coverage of the code run by this `Context`.
+    ///
+    /// Recording coverage slows the execution down.
+    #[inline]
+    pub fn set_coverage(&mut self, coverage: bool) {
+        self.vm.coverage.enabled = coverage;
+    }
+
+    /// Returns the coverage recorded so far, and starts recording it again from zero.
+    pub fn take_coverage(&mut self) -> Coverage {
+        let blocks = std::mem::take(&mut self.vm.coverage.blocks);
+        self.vm.coverage.indices.clear();
+
+        let mut coverage = Coverage::default();
+        for block in blocks {
+            let code = &block.code;
+            if code.name != Sym::MAIN {
+                if let Some((_, position)) = code.positions.first() {
+                    let name = match self.interner().resolve_expect(code.name) {
+                        "" => "<anonymous>",
+                        name => name,
+                    };
+                    coverage.functions.push(FunctionCoverage {
+                        name: name.into(),
+                        position: *position,
+                        count: block.calls,
+                    });
+                }
+            }
+
+            for ((_, position), count) in code.positions.iter().zip(&block.statements) {
+                let entry = coverage.statements.entry(*position).or_default();
+                *entry = (*entry).max(*count);
+            }
+
+            for (pc, (taken, not_taken)) in &block.branches {
+                // Branches before the first statement, like in default parameter values, are
+                // counted in the first statement.
+                let position = code
+                    .position_at(*pc as usize)
+                    .or_else(|| code.positions.first().map(|(_, position)| *position));
+                if let Some(position) = position {
+                    coverage.branches.push(BranchCoverage {
+                        position,
+                        taken: *taken,
+                        not_taken: *not_taken,
+                    });
+                }
+            }
+        }
+
+        coverage.functions.sort_by_key(FunctionCoverage::position);
+        coverage.branches.sort_by_key(BranchCoverage::position);
+        coverage
+    }
+
+    /// Returns the coverage of `code`, registering it and its inner functions if needed.
+    fn coverage_block(&mut self, code: &Gc<CodeBlock>) -> &mut BlockCoverage {
+        let address: *const CodeBlock = &**code;
+        if !self.vm.coverage.indices.contains_key(&address) {
+            let mut pending = vec![code.clone()];
+            while let Some(code) = pending.pop() {
+                let address: *const CodeBlock = &*code;
+                if self.vm.coverage.indices.contains_key(&address) {
+                    continue;
+                }
+                pending.extend(code.functions.iter().cloned());
+                let block = BlockCoverage::new(code, self.interner());
+                let coverage = &mut self.vm.coverage;
+                coverage.indices.insert(address, coverage.blocks.len());
+                coverage.blocks.push(block);
+            }
+        }
+
+        let index = self.vm.coverage.indices[&address];
+        &mut self.vm.coverage.blocks[index]
+    }
+
+    /// Records a call of the function of the current frame, if it starts executing.
+    pub(crate) fn coverage_call(&mut self) {
+        let frame = self.vm.frame();
+        if frame.pc == 0 {
+            let code = frame.code.clone();
+            self.coverage_block(&code).calls += 1;
+        }
+    }
+
+    /// Records the execution of the instruction at the current `pc`, returning its `pc` if it's a
+    /// conditional jump, whose outcome must be recorded with [`Context::coverage_branch`].
+    pub(crate) fn coverage_instruction(&mut self) -> Option<usize> {
+        let frame = self.vm.frame();
+        let pc = frame.pc;
+        let code = frame.code.clone();
+        let block = self.coverage_block(&code);
+
+        if let Some(index) = code.statement_index(pc) {
+            block.statements[index] += 1;
+        }
+        is_branch(code.code[pc]).then(|| pc)
+    }
+
+    /// Records the outcome of the conditional jump at `pc`, after executing it.
+    pub(crate) fn coverage_branch(&mut self, pc: usize) {
+        let frame = self.vm.frame();
+        let code = frame.code.clone();
+        let taken = frame.pc == code.read::<u32>(pc + 1) as usize;
+        if let Some(counts) = self.coverage_block(&code).branches.get_mut(&(pc as u32)) {
+            if taken {
+                counts.0 += 1;
+            } else {
+                counts.1 += 1;
+            }
+        }
+    }
+}
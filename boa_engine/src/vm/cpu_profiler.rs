@@ -0,0 +1,305 @@
+//! A sampling profiler of the JavaScript functions running in the VM.
+
+use crate::{
+    vm::{CallFrame, CodeBlock},
+    Context, JsString,
+};
+use boa_gc::Gc;
+use boa_interner::{Interner, Sym};
+use rustc_hash::FxHashMap;
+use serde_json::{json, Value};
+use std::time::{Duration, Instant};
+
+/// A profile recorded between [`Context::start_cpu_profile`] and [`Context::stop_cpu_profile`].
+///
+/// The profile is a tree of call stacks, where each node is a function called from the function
+/// of its parent node. The time between two samples is attributed to the call stack of the second
+/// sample, so the time spent in native functions is attributed to the function calling them.
+#[derive(Debug, Clone)]
+pub struct CpuProfile {
+    nodes: Vec<ProfileNode>,
+    /// The node sampled by each sample, with the time elapsed since the previous sample.
+    samples: Vec<(usize, Duration)>,
+    duration: Duration,
+}
+
+/// A function in a call stack of a [`CpuProfile`].
+#[derive(Debug, Clone)]
+pub struct ProfileNode {
+    name: JsString,
+    line: u32,
+    parent: Option<usize>,
+    children: Vec<usize>,
+    hit_count: u64,
+    self_time: Duration,
+}
+
+impl ProfileNode {
+    /// Returns the name of the function, `<main>` for scripts, or `(root)` for the root node.
+    #[inline]
+    pub fn name(&self) -> &JsString {
+        &self.name
+    }
+
+    /// Returns the line of the first statement of the function, or `0` if it's not known.
+    #[inline]
+    pub fn line(&self) -> u32 {
+        self.line
+    }
+
+    /// Returns the index of the node of the calling function, or `None` for the root node.
+    #[inline]
+    pub fn parent(&self) -> Option<usize> {
+        self.parent
+    }
+
+    /// Returns the indices of the nodes of the functions called by this function.
+    #[inline]
+    pub fn children(&self) -> &[usize] {
+        &self.children
+    }
+
+    /// Returns the number of samples taken while this function was on top of the call stack.
+    #[inline]
+    pub fn hit_count(&self) -> u64 {
+        self.hit_count
+    }
+
+    /// Returns the time spent in this function, excluding the functions it called.
+    #[inline]
+    pub fn self_time(&self) -> Duration {
+        self.self_time
+    }
+}
+
+impl CpuProfile {
+    /// Returns the nodes of the profile. The first node is the root of the tree.
+    #[inline]
+    pub fn nodes(&self) -> &[ProfileNode] {
+        &self.nodes
+    }
+
+    /// Returns the time between the start and the end of the profile.
+    #[inline]
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// Returns the names of the functions of the call stack of `node`, outermost first.
+    fn stack(&self, node: usize) -> Vec<String> {
+        let mut stack = Vec::new();
+        let mut current = Some(node);
+        while let Some(index) = current {
+            let node = &self.nodes[index];
+            if node.parent.is_some() {
+                stack.push(node.name.to_string());
+            }
+            current = node.parent;
+        }
+        stack.reverse();
+        stack
+    }
+
+    /// Converts the profile to the `.cpuprofile` format of the Chrome DevTools.
+    pub fn to_cpuprofile(&self) -> Value {
+        let nodes = self
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(index, node)| {
+                json!({
+                    "id": index + 1,
+                    "callFrame": {
+                        "functionName": node.name.as_str(),
+                        "scriptId": "0",
+                        "url": "",
+                        "lineNumber": i64::from(node.line) - 1,
+                        "columnNumber": -1,
+                    },
+                    "hitCount": node.hit_count,
+                    "children": node.children.iter().map(|child| child + 1).collect::<Vec<_>>(),
+                })
+            })
+            .collect::<Vec<_>>();
+        let samples = self
+            .samples
+            .iter()
+            .map(|(node, _)| node + 1)
+            .collect::<Vec<_>>();
+        let time_deltas = self
+            .samples
+            .iter()
+            .map(|(_, delta)| delta.as_micros() as u64)
+            .collect::<Vec<_>>();
+
+        json!({
+            "nodes": nodes,
+            "startTime": 0,
+            "endTime": self.duration.as_micros() as u64,
+            "samples": samples,
+            "timeDeltas": time_deltas,
+        })
+    }
+
+    /// Converts the profile to folded stacks, the input format of flame graph generators.
+    ///
+    /// Each line contains a call stack, with the functions separated by `;`, and the time spent
+    /// in its innermost function in microseconds.
+    pub fn to_folded(&self) -> String {
+        let mut folded = String::new();
+        for (index, node) in self.nodes.iter().enumerate() {
+            let micros = node.self_time.as_micros();
+            if node.parent.is_some() && micros > 0 {
+                folded.push_str(&format!("{} {micros}\n", self.stack(index).join(";")));
+            }
+        }
+        folded
+    }
+}
+
+/// The profiling state of a `Context`.
+#[derive(Debug, Default)]
+pub(crate) struct CpuProfilerState {
+    profile: Option<Recording>,
+}
+
+/// A profile being recorded.
+#[derive(Debug)]
+struct Recording {
+    interval: Duration,
+    start: Instant,
+    last_sample: Instant,
+    profile: CpuProfile,
+    /// The child of each node for a code block, by the index of the node and the code block
+    /// address. The code block is kept alive, so its address can't be reused by another one.
+    children: FxHashMap<(usize, *const CodeBlock), (usize, Gc<CodeBlock>)>,
+}
+
+impl CpuProfilerState {
+    /// Returns `true` if a profile is being recorded.
+    #[inline]
+    pub(crate) fn is_running(&self) -> bool {
+        self.profile.is_some()
+    }
+
+    /// Restarts the sampling interval when the host starts an execution, so the time spent by
+    /// the host between executions is not attributed to any function.
+    #[inline]
+    pub(crate) fn resume(&mut self) {
+        if let Some(recording) = self.profile.as_mut() {
+            recording.last_sample = Instant::now();
+        }
+    }
+}
+
+impl Context {
+    /// Starts recording a CPU profile, sampling the call stack every `interval` while code runs,
+    /// and discarding the profile being recorded, if any.
+    ///
+    /// The call stack is sampled between two instructions, so a shorter `interval` gives a more
+    /// precise profile at the cost of a slower execution.
+    ///
+    /// Profiles can't be recorded on `wasm32`, where `std::time::Instant` is not available, so
+    /// this does nothing there and [`Context::stop_cpu_profile`] returns `None`.
+    pub fn start_cpu_profile(&mut self, interval: Duration) {
+        if cfg!(target_arch = "wasm32") {
+            return;
+        }
+
+        let now = Instant::now();
+        let root = ProfileNode {
+            name: "(root)".into(),
+            line: 0,
+            parent: None,
+            children: Vec::new(),
+            hit_count: 0,
+            self_time: Duration::ZERO,
+        };
+        self.vm.cpu_profiler.profile = Some(Recording {
+            interval,
+            start: now,
+            last_sample: now,
+            profile: CpuProfile {
+                nodes: vec![root],
+                samples: Vec::new(),
+                duration: Duration::ZERO,
+            },
+            children: FxHashMap::default(),
+        });
+    }
+
+    /// Stops recording the CPU profile, returning it, or `None` if no profile was being
+    /// recorded.
+    pub fn stop_cpu_profile(&mut self) -> Option<CpuProfile> {
+        let recording = self.vm.cpu_profiler.profile.take()?;
+        let mut profile = recording.profile;
+        profile.duration = recording.start.elapsed();
+        Some(profile)
+    }
+
+    /// Samples the call stack if the sampling interval has elapsed since the last sample.
+    pub(crate) fn cpu_profiler_sample(&mut self) {
+        let mut recording = match self.vm.cpu_profiler.profile.take() {
+            Some(recording) => recording,
+            None => return,
+        };
+
+        let now = Instant::now();
+        let delta = now.saturating_duration_since(recording.last_sample);
+        if delta >= recording.interval {
+            let mut frames = Vec::new();
+            let mut frame = self.vm.frame.as_deref();
+            while let Some(current) = frame {
+                frames.push(current);
+                frame = current.prev.as_deref();
+            }
+
+            let mut node = 0;
+            for frame in frames.iter().rev() {
+                node = recording.child(node, frame, self.interner());
+            }
+
+            recording.last_sample = now;
+            recording.profile.samples.push((node, delta));
+            let node = &mut recording.profile.nodes[node];
+            node.hit_count += 1;
+            node.self_time += delta;
+        }
+
+        self.vm.cpu_profiler.profile = Some(recording);
+    }
+}
+
+impl Recording {
+    /// Returns the child of `parent` for the code block of `frame`, adding it if needed.
+    fn child(&mut self, parent: usize, frame: &CallFrame, interner: &Interner) -> usize {
+        let code = &frame.code;
+        let address: *const CodeBlock = &**code;
+        let nodes = &mut self.profile.nodes;
+        let (index, _) = self.children.entry((parent, address)).or_insert_with(|| {
+            let name = if code.name == Sym::MAIN {
+                "<main>"
+            } else {
+                match interner.resolve_expect(code.name) {
+                    "" => "(anonymous)",
+                    name => name,
+                }
+            };
+            let index = nodes.len();
+            nodes.push(ProfileNode {
+                name: name.into(),
+                line: code
+                    .positions
+                    .first()
+                    .map_or(0, |(_, position)| position.line_number()),
+                parent: Some(parent),
+                children: Vec::new(),
+                hit_count: 0,
+                self_time: Duration::ZERO,
+            });
+            nodes[parent].children.push(index);
+            (index, code.clone())
+        });
+        *index
+    }
+}
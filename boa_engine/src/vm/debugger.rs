@@ -0,0 +1,396 @@
+//! Hooks to debug the scripts running in the VM.
+
+use crate::{environments::DeclarativeEnvironmentStack, vm::CallFrame, Context, JsString, JsValue};
+use boa_interner::Sym;
+use rustc_hash::FxHashSet;
+use std::{cmp::Reverse, fmt};
+
+/// Hooks called by the VM while a debugger is attached with [`Context::set_debugger`].
+///
+/// All the methods have an empty default implementation. While a hook runs, the debugger is
+/// detached from the `Context`, so code evaluated by the hook is not debugged.
+pub trait Debugger {
+    /// Called before executing each statement, with the line where it starts.
+    fn on_statement(&mut self, _context: &mut Context, _line: u32) {}
+
+    /// Called when the execution pauses on a breakpoint or after a step, before executing the
+    /// statement at `line`.
+    ///
+    /// The execution resumes when this method returns, in the way given by the returned
+    /// [`StepAction`].
+    fn on_pause(&mut self, _context: &mut Context, _reason: PauseReason, _line: u32) -> StepAction {
+        StepAction::Continue
+    }
+
+    /// Called when a function starts executing, after its frame is pushed.
+    fn on_call(&mut self, _context: &mut Context) {}
+
+    /// Called when a function returns or yields `value`, before its frame is popped.
+    fn on_return(&mut self, _context: &mut Context, _value: &JsValue) {}
+
+    /// Called when `error` is thrown, before it is caught. An error is reported once, in the
+    /// frame that throws it, even if it unwinds through other frames.
+    fn on_exception(&mut self, _context: &mut Context, _error: &JsValue) {}
+}
+
+/// Why the execution paused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PauseReason {
+    /// A breakpoint was hit.
+    Breakpoint,
+    /// A step requested by [`Debugger::on_pause`] completed, or a pause was requested with
+    /// [`Context::debugger_pause`].
+    Step,
+}
+
+/// How the execution resumes after a pause.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StepAction {
+    /// Run until the next breakpoint.
+    Continue,
+    /// Pause on the next statement, entering called functions.
+    StepIn,
+    /// Pause on the next statement of the current function, or of its callers once it returns.
+    StepOver,
+    /// Pause on the next statement of the caller of the current function.
+    StepOut,
+}
+
+/// The state of the debugger of a `Context`.
+#[derive(Default)]
+pub(crate) struct DebuggerState {
+    debugger: Option<Box<dyn Debugger>>,
+    /// The breakpoints, as `(source, line)` pairs.
+    breakpoints: FxHashSet<(JsString, u32)>,
+    /// The pending step, with the call depth at which it was requested.
+    step: Option<(StepAction, usize)>,
+    /// The last error reported to `on_exception`, which is still unwinding.
+    last_exception: Option<JsValue>,
+}
+
+impl fmt::Debug for DebuggerState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DebuggerState")
+            .field("attached", &self.debugger.is_some())
+            .field("breakpoints", &self.breakpoints)
+            .field("step", &self.step)
+            .finish()
+    }
+}
+
+impl DebuggerState {
+    /// Returns `true` if a debugger is attached.
+    #[inline]
+    pub(crate) fn is_attached(&self) -> bool {
+        self.debugger.is_some()
+    }
+
+    /// Attaches back the debugger detached while running a hook, unless the hook attached
+    /// another one.
+    #[inline]
+    fn restore(&mut self, debugger: Box<dyn Debugger>) {
+        if self.debugger.is_none() {
+            self.debugger = Some(debugger);
+        }
+    }
+}
+
+/// A snapshot of a frame of the call stack, returned by [`Context::debug_frame`].
+#[derive(Debug, Clone)]
+pub struct DebugFrame {
+    name: JsString,
+    source: JsString,
+    line: Option<u32>,
+    this: JsValue,
+    locals: Vec<(JsString, Option<JsValue>)>,
+}
+
+impl DebugFrame {
+    /// Returns the name of the function of the frame, or `<main>` for scripts.
+    #[inline]
+    pub fn name(&self) -> &JsString {
+        &self.name
+    }
+
+    /// Returns the name of the source of the function, empty if the source has no name.
+    #[inline]
+    pub fn source(&self) -> &JsString {
+        &self.source
+    }
+
+    /// Returns the line being executed in the frame, if known.
+    #[inline]
+    pub fn line(&self) -> Option<u32> {
+        self.line
+    }
+
+    /// Returns the `this` value of the frame.
+    #[inline]
+    pub fn this(&self) -> &JsValue {
+        &self.this
+    }
+
+    /// Returns the bindings in scope in the frame, innermost first.
+    ///
+    /// The value of a binding is `None` if it's not initialized yet. The bindings of scripts
+    /// include the lexical bindings of the global scope, but not the properties of the global
+    /// object.
+    #[inline]
+    pub fn locals(&self) -> &[(JsString, Option<JsValue>)] {
+        &self.locals
+    }
+
+    /// Returns the value of the binding `name` in scope in the frame, or `None` if there is no
+    /// such binding or if it's not initialized yet.
+    pub fn local(&self, name: &str) -> Option<&JsValue> {
+        self.locals
+            .iter()
+            .find(|(local, _)| local.as_str() == name)
+            .and_then(|(_, value)| value.as_ref())
+    }
+}
+
+impl Context {
+    /// Attaches a debugger to this `Context`, replacing the previous one.
+    #[inline]
+    pub fn set_debugger<D>(&mut self, debugger: D)
+    where
+        D: Debugger + 'static,
+    {
+        self.vm.debugger.debugger = Some(Box::new(debugger));
+    }
+
+    /// Detaches the debugger of this `Context`, returning it.
+    #[inline]
+    pub fn remove_debugger(&mut self) -> Option<Box<dyn Debugger>> {
+        self.vm.debugger.step = None;
+        self.vm.debugger.debugger.take()
+    }
+
+    /// Sets a breakpoint on the statements starting at `line` of the source named `source`.
+    ///
+    /// Sources are named when they are compiled, with [`Context::eval_with_name`] or
+    /// [`Context::compile_with_name`]. Code evaluated with [`Context::eval`] has an empty name.
+    #[inline]
+    pub fn set_breakpoint(&mut self, source: &str, line: u32) {
+        self.vm.debugger.breakpoints.insert((source.into(), line));
+    }
+
+    /// Removes the breakpoint on `line` of `source`, returning `true` if there was one.
+    #[inline]
+    pub fn remove_breakpoint(&mut self, source: &str, line: u32) -> bool {
+        self.vm.debugger.breakpoints.remove(&(source.into(), line))
+    }
+
+    /// Removes all the breakpoints of `source`.
+    #[inline]
+    pub fn clear_source_breakpoints(&mut self, source: &str) {
+        self.vm
+            .debugger
+            .breakpoints
+            .retain(|(breakpoint_source, _)| breakpoint_source.as_str() != source);
+    }
+
+    /// Removes all the breakpoints.
+    #[inline]
+    pub fn clear_breakpoints(&mut self) {
+        self.vm.debugger.breakpoints.clear();
+    }
+
+    /// Pauses the execution on the next statement, as if a step in was requested.
+    #[inline]
+    pub fn debugger_pause(&mut self) {
+        self.vm.debugger.step = Some((StepAction::StepIn, self.vm.call_depth));
+    }
+
+    /// Returns the number of frames on the call stack.
+    #[inline]
+    pub fn frame_count(&self) -> usize {
+        self.vm.call_depth
+    }
+
+    /// Returns a snapshot of the frame at `depth` in the call stack, where `0` is the frame
+    /// being executed and `1` the frame that called it.
+    ///
+    /// The bindings of the frames other than the current one can only be read if a debugger was
+    /// attached when they called the next frame.
+    pub fn debug_frame(&self, depth: usize) -> Option<DebugFrame> {
+        let (frame, environments) = self.frame_at(depth)?;
+        let code = &frame.code;
+        let locals = environments
+            .map(|environments| {
+                self.frame_bindings(frame)
+                    .into_iter()
+                    .map(|(name, environment_index, binding_index)| {
+                        (
+                            self.interner().resolve_expect(name).into(),
+                            environments.try_get_value(environment_index, binding_index),
+                        )
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Some(DebugFrame {
+            name: self.interner().resolve_expect(code.name).into(),
+            source: code.source.clone(),
+            line: code.line_at(frame.pc),
+            this: frame.this.clone(),
+            locals,
+        })
+    }
+
+    /// Sets the value of the binding `name` in scope in the frame at `depth`, returning `true`
+    /// if it was set.
+    ///
+    /// Like with [`debug_frame`](Self::debug_frame), only the bindings that can be read can be
+    /// set. Bindings that are not initialized yet are not set.
+    pub fn set_frame_local(&mut self, depth: usize, name: &str, value: JsValue) -> bool {
+        let (frame, environments) = match self.frame_at(depth) {
+            Some((frame, Some(environments))) => (frame, environments),
+            _ => return false,
+        };
+        self.frame_bindings(frame)
+            .into_iter()
+            .find(|(local, _, _)| self.interner().resolve_expect(*local) == name)
+            .map_or(false, |(_, environment_index, binding_index)| {
+                environments.try_put_value(environment_index, binding_index, value)
+            })
+    }
+
+    /// Returns the frame at `depth`, with its environments if they were saved.
+    fn frame_at(&self, depth: usize) -> Option<(&CallFrame, Option<&DeclarativeEnvironmentStack>)> {
+        let mut frame = self.vm.frame.as_deref()?;
+        let mut environments = Some(&self.realm.environments);
+        for _ in 0..depth {
+            environments = frame.caller_environments.as_ref();
+            frame = frame.prev.as_deref()?;
+        }
+        Some((frame, environments))
+    }
+
+    /// Returns the bindings in scope at the current instruction of `frame`, innermost first, as
+    /// `(name, environment_index, binding_index)`.
+    fn frame_bindings(&self, frame: &CallFrame) -> Vec<(Sym, usize, usize)> {
+        // Scripts are not compiled in a function environment, and their bindings live in the
+        // global environment.
+        let script = !frame.code.scopes.iter().any(|scope| scope.function_scope);
+        let global = if script {
+            self.realm.compile_env.global().binding_names()
+        } else {
+            Vec::new()
+        };
+
+        let pc = frame.pc;
+        let mut scopes: Vec<_> = frame
+            .code
+            .scopes
+            .iter()
+            .filter(|scope| scope.start as usize <= pc && pc < scope.end as usize)
+            .map(|scope| (scope.environment_index, scope.bindings.as_slice()))
+            .collect();
+        if script {
+            scopes.push((0, global.as_slice()));
+        }
+        scopes.sort_by_key(|(environment_index, _)| Reverse(*environment_index));
+
+        let mut seen = FxHashSet::default();
+        let mut bindings = Vec::new();
+        for (environment_index, scope_bindings) in scopes {
+            for (name, binding_index) in scope_bindings {
+                if seen.insert(*name) {
+                    bindings.push((*name, environment_index, *binding_index));
+                }
+            }
+        }
+        bindings
+    }
+
+    /// Saves the environments of the frame calling the current frame, so the debugger can read
+    /// its bindings.
+    pub(crate) fn save_caller_environments(&mut self, environments: &DeclarativeEnvironmentStack) {
+        let attached = self.vm.debugger.is_attached();
+        if let Some(frame) = self.vm.frame.as_mut() {
+            frame.caller_environments = attached.then(|| environments.clone());
+        }
+    }
+
+    /// Resets the debugger state at the start of an execution started by the host.
+    pub(crate) fn debugger_start(&mut self) {
+        self.vm.debugger.last_exception = None;
+    }
+
+    /// Calls the hooks of the statement at `line`, pausing if needed.
+    pub(crate) fn debugger_statement(&mut self, line: u32) {
+        if let Some(mut debugger) = self.vm.debugger.debugger.take() {
+            debugger.on_statement(self, line);
+
+            let call_depth = self.vm.call_depth;
+            let source = self.vm.frame().code.source.clone();
+            let reason = if self.vm.debugger.breakpoints.contains(&(source, line)) {
+                Some(PauseReason::Breakpoint)
+            } else {
+                match self.vm.debugger.step {
+                    Some((StepAction::StepIn, _)) => Some(PauseReason::Step),
+                    Some((StepAction::StepOver, depth)) if call_depth <= depth => {
+                        Some(PauseReason::Step)
+                    }
+                    Some((StepAction::StepOut, depth)) if call_depth < depth => {
+                        Some(PauseReason::Step)
+                    }
+                    _ => None,
+                }
+            };
+
+            if let Some(reason) = reason {
+                self.vm.debugger.step = match debugger.on_pause(self, reason, line) {
+                    StepAction::Continue => None,
+                    action => Some((action, call_depth)),
+                };
+            }
+
+            self.vm.debugger.restore(debugger);
+        }
+    }
+
+    /// Calls the `on_call` hook.
+    pub(crate) fn debugger_call(&mut self) {
+        if let Some(mut debugger) = self.vm.debugger.debugger.take() {
+            debugger.on_call(self);
+            self.vm.debugger.restore(debugger);
+        }
+    }
+
+    /// Calls the `on_return` hook.
+    pub(crate) fn debugger_return(&mut self, value: &JsValue) {
+        if let Some(mut debugger) = self.vm.debugger.debugger.take() {
+            debugger.on_return(self, value);
+            self.vm.debugger.restore(debugger);
+        }
+    }
+
+    /// Calls the `on_exception` hook, unless `error` was already reported while unwinding.
+    pub(crate) fn debugger_exception(&mut self, error: &JsValue) {
+        let reported = self
+            .vm
+            .debugger
+            .last_exception
+            .as_ref()
+            .map_or(false, |last| last.strict_equals(error));
+        if reported {
+            return;
+        }
+        self.vm.debugger.last_exception = Some(error.clone());
+
+        if let Some(mut debugger) = self.vm.debugger.debugger.take() {
+            debugger.on_exception(self, error);
+            self.vm.debugger.restore(debugger);
+        }
+    }
+
+    /// Records that the error being unwound was caught.
+    #[inline]
+    pub(crate) fn debugger_exception_caught(&mut self) {
+        self.vm.debugger.last_exception = None;
+    }
+}
@@ -0,0 +1,49 @@
+//! Termination of a running script from another thread.
+
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+/// A handle to terminate the execution of a [`Context`](crate::Context) from any thread.
+///
+/// The VM polls the handle on every loop iteration, on backward jumps and on calls, and unwinds
+/// the execution with an uncatchable error, so neither `catch` nor `finally` blocks run. Native
+/// functions can't call back into JavaScript while the execution unwinds.
+///
+/// A termination requested while no script is running is kept, and terminates the next
+/// execution. Hosts that reuse a `Context` after a cancelled request can clear it with
+/// [`reset`](Self::reset).
+///
+/// Returned by [`Context::interrupt_handle`](crate::Context::interrupt_handle).
+#[derive(Debug, Clone, Default)]
+pub struct InterruptHandle {
+    requested: Arc<AtomicBool>,
+}
+
+impl InterruptHandle {
+    /// Requests the termination of the running script.
+    #[inline]
+    pub fn terminate(&self) {
+        self.requested.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns `true` if a termination was requested and not yet handled by the VM.
+    #[inline]
+    pub fn is_terminating(&self) -> bool {
+        self.requested.load(Ordering::Relaxed)
+    }
+
+    /// Clears a termination request that wasn't handled by the VM yet.
+    #[inline]
+    pub fn reset(&self) {
+        self.requested.store(false, Ordering::Relaxed);
+    }
+
+    /// Clears the termination request, returning `true` if one was pending.
+    #[inline]
+    pub(crate) fn take(&self) -> bool {
+        // Avoid a read-modify-write on the hot path, where no termination is pending.
+        self.requested.load(Ordering::Relaxed) && self.requested.swap(false, Ordering::Relaxed)
+    }
+}
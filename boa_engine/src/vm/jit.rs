@@ -0,0 +1,60 @@
+//! Hotness detection for the baseline JIT described in `docs/jit.md`.
+//!
+//! Only the counters that pick the code blocks worth compiling exist so far. There is no code
+//! generation yet, so hot code blocks are still run by the interpreter.
+
+use std::cell::Cell;
+
+/// The default number of calls or loop iterations after which a code block is hot.
+pub(crate) const DEFAULT_JIT_THRESHOLD: u32 = 1000;
+
+/// Counts how often a [`CodeBlock`](super::CodeBlock) runs.
+///
+/// The counters live in `Cell`s because code blocks are shared behind a `Gc`, and they don't
+/// need tracing.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Hotness {
+    invocations: Cell<u32>,
+    loop_iterations: Cell<u32>,
+    hot: Cell<bool>,
+}
+
+impl Hotness {
+    /// Records that the code block was entered.
+    #[inline]
+    pub(crate) fn record_invocation(&self, threshold: u32) {
+        let invocations = self.invocations.get().saturating_add(1);
+        self.invocations.set(invocations);
+        if invocations >= threshold {
+            self.hot.set(true);
+        }
+    }
+
+    /// Records an iteration of one of the loops of the code block.
+    #[inline]
+    pub(crate) fn record_loop_iteration(&self, threshold: u32) {
+        let loop_iterations = self.loop_iterations.get().saturating_add(1);
+        self.loop_iterations.set(loop_iterations);
+        if loop_iterations >= threshold {
+            self.hot.set(true);
+        }
+    }
+
+    /// Returns how many times the code block was entered.
+    #[inline]
+    pub(crate) fn invocations(&self) -> u32 {
+        self.invocations.get()
+    }
+
+    /// Returns how many loop iterations the code block ran.
+    #[inline]
+    pub(crate) fn loop_iterations(&self) -> u32 {
+        self.loop_iterations.get()
+    }
+
+    /// Returns `true` once either counter reached the threshold.
+    #[inline]
+    pub(crate) fn is_hot(&self) -> bool {
+        self.hot.get()
+    }
+}
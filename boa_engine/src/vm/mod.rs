@@ -2,32 +2,54 @@
 //! This module will provide an instruction set for the AST to use, various traits,
 //! plus an interpreter to execute those instructions
 
-use crate::{
-    builtins::{function::Function, iterable::IteratorRecord, Array, ForInIterator, Number},
-    object::PrivateElement,
-    property::{DescriptorKind, PropertyDescriptor, PropertyKey},
-    value::Numeric,
-    vm::{
-        call_frame::CatchAddresses,
-        code_block::{create_function_object, create_generator_function_object, Readable},
-    },
-    Context, JsBigInt, JsResult, JsString, JsValue,
-};
-use boa_interner::ToInternedString;
+use crate::{context::HeapLimitScope, vm::code_block::Readable, Context, JsResult, JsValue};
 use boa_profiler::Profiler;
-use std::{convert::TryInto, mem::size_of, ops::Neg, time::Instant};
+use std::{convert::TryInto, mem::size_of, time::Instant};
 
 mod call_frame;
 mod code_block;
+mod coverage;
+mod cpu_profiler;
+mod debugger;
+mod interrupt;
+#[cfg(feature = "jit")]
+mod jit;
 mod opcode;
-
-pub use {call_frame::CallFrame, code_block::CodeBlock, opcode::Opcode};
+mod ops;
+mod runtime_limits;
+mod stats;
+mod tracer;
+
+pub use {
+    call_frame::CallFrame,
+    code_block::CodeBlock,
+    coverage::{BranchCoverage, Coverage, FunctionCoverage},
+    cpu_profiler::{CpuProfile, ProfileNode},
+    debugger::{DebugFrame, Debugger, PauseReason, StepAction},
+    interrupt::InterruptHandle,
+    opcode::{Opcode, OpcodeCategory},
+    runtime_limits::{RuntimeLimits, TerminationReason},
+    stats::{CategoryStats, FunctionStats, InstructionStats, OpcodeStats, VmStats},
+    tracer::{
+        ConsoleTracer, FilteredTracer, FrameEnter, FrameExit, InstructionTrace, JsonLinesTracer,
+        Tracer,
+    },
+};
 
 pub(crate) use {
     call_frame::{FinallyReturn, GeneratorResumeKind, TryStackEntry},
+    code_block::ScopeInfo,
+    coverage::CoverageState,
+    cpu_profiler::CpuProfilerState,
+    debugger::DebuggerState,
     opcode::BindingOpcode,
+    stats::VmStatsState,
+    tracer::TracerState,
 };
 
+#[cfg(feature = "jit")]
+pub(crate) use jit::{Hotness, DEFAULT_JIT_THRESHOLD};
+
 #[cfg(test)]
 mod tests;
 /// Virtual Machine.
@@ -35,8 +57,24 @@ mod tests;
 pub struct Vm {
     pub(crate) frame: Option<Box<CallFrame>>,
     pub(crate) stack: Vec<JsValue>,
-    pub(crate) trace: bool,
-    pub(crate) stack_size_limit: usize,
+    pub(crate) tracer: TracerState,
+    pub(crate) runtime_limits: RuntimeLimits,
+    /// Set when the current error must not be caught, terminating the whole execution.
+    pub(crate) termination: Option<TerminationReason>,
+    /// Number of call frames on the frame stack.
+    pub(crate) call_depth: usize,
+    /// Number of instructions executed since the host started the execution.
+    pub(crate) instruction_count: u64,
+    /// Number of loop iterations since the host started the execution.
+    pub(crate) loop_iteration_count: u64,
+    pub(crate) interrupt: InterruptHandle,
+    pub(crate) debugger: DebuggerState,
+    pub(crate) coverage: CoverageState,
+    pub(crate) cpu_profiler: CpuProfilerState,
+    pub(crate) stats: VmStatsState,
+    /// Number of calls or loop iterations after which a code block is hot.
+    #[cfg(feature = "jit")]
+    pub(crate) jit_threshold: u32,
 }
 
 impl Vm {
@@ -93,12 +131,14 @@ impl Vm {
         let prev = self.frame.take();
         frame.prev = prev;
         self.frame = Some(Box::new(frame));
+        self.call_depth += 1;
     }
 
     #[inline]
     pub(crate) fn pop_frame(&mut self) -> Option<Box<CallFrame>> {
         let mut current = self.frame.take()?;
         self.frame = current.prev.take();
+        self.call_depth -= 1;
         Some(current)
     }
 }
@@ -120,15 +160,6 @@ pub(crate) enum ReturnType {
 
 impl Context {
     fn execute_instruction(&mut self) -> JsResult<ShouldExit> {
-        macro_rules! bin_op {
-            ($op:ident) => {{
-                let rhs = self.vm.pop();
-                let lhs = self.vm.pop();
-                let value = lhs.$op(&rhs, self)?;
-                self.vm.push(value)
-            }};
-        }
-
         let opcode: Opcode = {
             let _timer = Profiler::global().start_event("Opcode retrieval", "vm");
             let opcode = self.vm.frame().code.code[self.vm.frame().pc]
@@ -141,1637 +172,284 @@ impl Context {
         let _timer = Profiler::global().start_event(opcode.as_instruction_str(), "vm");
 
         match opcode {
-            Opcode::Nop => {}
-            Opcode::Pop => {
-                let _val = self.vm.pop();
-            }
-            Opcode::Dup => {
-                let value = self.vm.pop();
-                self.vm.push(value.clone());
-                self.vm.push(value);
-            }
-            Opcode::Swap => {
-                let first = self.vm.pop();
-                let second = self.vm.pop();
-
-                self.vm.push(first);
-                self.vm.push(second);
-            }
-            Opcode::PushUndefined => self.vm.push(JsValue::undefined()),
-            Opcode::PushNull => self.vm.push(JsValue::null()),
-            Opcode::PushTrue => self.vm.push(true),
-            Opcode::PushFalse => self.vm.push(false),
-            Opcode::PushZero => self.vm.push(0),
-            Opcode::PushOne => self.vm.push(1),
-            Opcode::PushInt8 => {
-                let value = self.vm.read::<i8>();
-                self.vm.push(i32::from(value));
-            }
-            Opcode::PushInt16 => {
-                let value = self.vm.read::<i16>();
-                self.vm.push(i32::from(value));
-            }
-            Opcode::PushInt32 => {
-                let value = self.vm.read::<i32>();
-                self.vm.push(value);
-            }
-            Opcode::PushRational => {
-                let value = self.vm.read::<f64>();
-                self.vm.push(value);
-            }
-            Opcode::PushNaN => self.vm.push(JsValue::nan()),
-            Opcode::PushPositiveInfinity => self.vm.push(JsValue::positive_infinity()),
-            Opcode::PushNegativeInfinity => self.vm.push(JsValue::negative_infinity()),
-            Opcode::PushLiteral => {
-                let index = self.vm.read::<u32>() as usize;
-                let value = self.vm.frame().code.literals[index].clone();
-                self.vm.push(value);
-            }
-            Opcode::PushEmptyObject => self.vm.push(self.construct_object()),
-            Opcode::PushClassPrototype => {
-                let superclass = self.vm.pop();
-                if superclass.is_null() {
-                    self.vm.push(JsValue::Null);
-                }
-                if let Some(superclass) = superclass.as_constructor() {
-                    let proto = superclass.get("prototype", self)?;
-                    if !proto.is_object() && !proto.is_null() {
-                        return self
-                            .throw_type_error("superclass prototype must be an object or null");
-                    }
-                    self.vm.push(proto);
-                } else {
-                    return self.throw_type_error("superclass must be a constructor");
-                }
-            }
-            Opcode::PushNewArray => {
-                let array = Array::array_create(0, None, self)
-                    .expect("Array creation with 0 length should never fail");
-                self.vm.push(array);
-            }
-            Opcode::PushValueToArray => {
-                let value = self.vm.pop();
-                let array = self.vm.pop();
-                let o = array.as_object().expect("should be an object");
-                let len = o
-                    .length_of_array_like(self)
-                    .expect("should have 'length' property");
-                o.create_data_property_or_throw(len, value, self)
-                    .expect("should be able to create new data property");
-                self.vm.push(array);
-            }
-            Opcode::PushElisionToArray => {
-                let array = self.vm.pop();
-                let o = array.as_object().expect("should always be an object");
-
-                let len = o
-                    .length_of_array_like(self)
-                    .expect("arrays should always have a 'length' property");
-
-                o.set("length", len + 1, true, self)?;
-                self.vm.push(array);
-            }
-            Opcode::PushIteratorToArray => {
-                let next_function = self.vm.pop();
-                let iterator = self.vm.pop();
-                let array = self.vm.pop();
-
-                let iterator = IteratorRecord::new(iterator, next_function);
-                while let Some(next) = iterator.step(self)? {
-                    Array::push(&array, &[next.value(self)?], self)?;
-                }
-
-                self.vm.push(array);
-            }
-            Opcode::Add => bin_op!(add),
-            Opcode::Sub => bin_op!(sub),
-            Opcode::Mul => bin_op!(mul),
-            Opcode::Div => bin_op!(div),
-            Opcode::Pow => bin_op!(pow),
-            Opcode::Mod => bin_op!(rem),
-            Opcode::BitAnd => bin_op!(bitand),
-            Opcode::BitOr => bin_op!(bitor),
-            Opcode::BitXor => bin_op!(bitxor),
-            Opcode::ShiftLeft => bin_op!(shl),
-            Opcode::ShiftRight => bin_op!(shr),
-            Opcode::UnsignedShiftRight => bin_op!(ushr),
-            Opcode::Eq => {
-                let rhs = self.vm.pop();
-                let lhs = self.vm.pop();
-                let value = lhs.equals(&rhs, self)?;
-                self.vm.push(value);
-            }
-            Opcode::NotEq => {
-                let rhs = self.vm.pop();
-                let lhs = self.vm.pop();
-                let value = !lhs.equals(&rhs, self)?;
-                self.vm.push(value);
-            }
-            Opcode::StrictEq => {
-                let rhs = self.vm.pop();
-                let lhs = self.vm.pop();
-                self.vm.push(lhs.strict_equals(&rhs));
-            }
-            Opcode::StrictNotEq => {
-                let rhs = self.vm.pop();
-                let lhs = self.vm.pop();
-                self.vm.push(!lhs.strict_equals(&rhs));
-            }
-            Opcode::GreaterThan => bin_op!(gt),
-            Opcode::GreaterThanOrEq => bin_op!(ge),
-            Opcode::LessThan => bin_op!(lt),
-            Opcode::LessThanOrEq => bin_op!(le),
-            Opcode::In => {
-                let rhs = self.vm.pop();
-                let lhs = self.vm.pop();
-
-                if !rhs.is_object() {
-                    return self.throw_type_error(format!(
-                        "right-hand side of 'in' should be an object, got {}",
-                        rhs.type_of()
-                    ));
-                }
-                let key = lhs.to_property_key(self)?;
-                let value = self.has_property(&rhs, &key)?;
-                self.vm.push(value);
-            }
-            Opcode::InstanceOf => {
-                let target = self.vm.pop();
-                let v = self.vm.pop();
-                let value = v.instance_of(&target, self)?;
-
-                self.vm.push(value);
-            }
-            Opcode::Void => {
-                let _old = self.vm.pop();
-                self.vm.push(JsValue::undefined());
-            }
-            Opcode::TypeOf => {
-                let value = self.vm.pop();
-                self.vm.push(value.type_of());
-            }
-            Opcode::Pos => {
-                let value = self.vm.pop();
-                let value = value.to_number(self)?;
-                self.vm.push(value);
-            }
-            Opcode::Neg => {
-                let value = self.vm.pop();
-                match value.to_numeric(self)? {
-                    Numeric::Number(number) => self.vm.push(number.neg()),
-                    Numeric::BigInt(bigint) => self.vm.push(JsBigInt::neg(&bigint)),
-                }
-            }
-            Opcode::Inc => {
-                let value = self.vm.pop();
-                match value.to_numeric(self)? {
-                    Numeric::Number(number) => self.vm.push(number + 1f64),
-                    Numeric::BigInt(bigint) => {
-                        self.vm.push(JsBigInt::add(&bigint, &JsBigInt::one()));
-                    }
-                }
-            }
-            Opcode::IncPost => {
-                let value = self.vm.pop();
-                let value = value.to_numeric(self)?;
-                self.vm.push(value.clone());
-                match value {
-                    Numeric::Number(number) => self.vm.push(number + 1f64),
-                    Numeric::BigInt(bigint) => {
-                        self.vm.push(JsBigInt::add(&bigint, &JsBigInt::one()));
-                    }
-                }
-            }
-            Opcode::Dec => {
-                let value = self.vm.pop();
-                match value.to_numeric(self)? {
-                    Numeric::Number(number) => self.vm.push(number - 1f64),
-                    Numeric::BigInt(bigint) => {
-                        self.vm.push(JsBigInt::sub(&bigint, &JsBigInt::one()));
-                    }
-                }
-            }
-            Opcode::DecPost => {
-                let value = self.vm.pop();
-                let value = value.to_numeric(self)?;
-                self.vm.push(value.clone());
-                match value {
-                    Numeric::Number(number) => self.vm.push(number - 1f64),
-                    Numeric::BigInt(bigint) => {
-                        self.vm.push(JsBigInt::sub(&bigint, &JsBigInt::one()));
-                    }
-                }
-            }
-            Opcode::LogicalNot => {
-                let value = self.vm.pop();
-                self.vm.push(!value.to_boolean());
-            }
-            Opcode::BitNot => {
-                let value = self.vm.pop();
-                match value.to_numeric(self)? {
-                    Numeric::Number(number) => self.vm.push(Number::not(number)),
-                    Numeric::BigInt(bigint) => self.vm.push(JsBigInt::not(&bigint)),
-                }
-            }
-            Opcode::DefVar => {
-                let index = self.vm.read::<u32>();
-                let binding_locator = self.vm.frame().code.bindings[index as usize];
-
-                if binding_locator.is_global() {
-                    let key = self
-                        .interner()
-                        .resolve_expect(binding_locator.name())
-                        .into();
-                    self.global_bindings_mut().entry(key).or_insert(
-                        PropertyDescriptor::builder()
-                            .value(JsValue::Undefined)
-                            .writable(true)
-                            .enumerable(true)
-                            .configurable(true)
-                            .build(),
-                    );
-                } else {
-                    self.realm.environments.put_value_if_uninitialized(
-                        binding_locator.environment_index(),
-                        binding_locator.binding_index(),
-                        JsValue::Undefined,
-                    );
-                }
-            }
-            Opcode::DefInitVar => {
-                let index = self.vm.read::<u32>();
-                let value = self.vm.pop();
-                let binding_locator = self.vm.frame().code.bindings[index as usize];
-                binding_locator.throw_mutate_immutable(self)?;
-
-                if binding_locator.is_global() {
-                    let key = self
-                        .interner()
-                        .resolve_expect(binding_locator.name())
-                        .into();
-                    crate::object::internal_methods::global::global_set_no_receiver(
-                        &key, value, self,
-                    )?;
-                } else {
-                    self.realm.environments.put_value(
-                        binding_locator.environment_index(),
-                        binding_locator.binding_index(),
-                        value,
-                    );
-                }
-            }
-            Opcode::DefLet => {
-                let index = self.vm.read::<u32>();
-                let binding_locator = self.vm.frame().code.bindings[index as usize];
-                self.realm.environments.put_value(
-                    binding_locator.environment_index(),
-                    binding_locator.binding_index(),
-                    JsValue::Undefined,
-                );
-            }
+            Opcode::Nop => self.execute_nop(),
+            Opcode::Pop => self.execute_pop(),
+            Opcode::Dup => self.execute_dup(),
+            Opcode::Swap => self.execute_swap(),
+            Opcode::PushUndefined => self.execute_push_undefined(),
+            Opcode::PushNull => self.execute_push_null(),
+            Opcode::PushTrue => self.execute_push_true(),
+            Opcode::PushFalse => self.execute_push_false(),
+            Opcode::PushZero => self.execute_push_zero(),
+            Opcode::PushOne => self.execute_push_one(),
+            Opcode::PushInt8 => self.execute_push_int8(),
+            Opcode::PushInt16 => self.execute_push_int16(),
+            Opcode::PushInt32 => self.execute_push_int32(),
+            Opcode::PushRational => self.execute_push_rational(),
+            Opcode::PushNaN => self.execute_push_nan(),
+            Opcode::PushPositiveInfinity => self.execute_push_positive_infinity(),
+            Opcode::PushNegativeInfinity => self.execute_push_negative_infinity(),
+            Opcode::PushLiteral => self.execute_push_literal(),
+            Opcode::PushEmptyObject => self.execute_push_empty_object(),
+            Opcode::PushClassPrototype => self.execute_push_class_prototype(),
+            Opcode::PushNewArray => self.execute_push_new_array(),
+            Opcode::PushValueToArray => self.execute_push_value_to_array(),
+            Opcode::PushElisionToArray => self.execute_push_elision_to_array(),
+            Opcode::PushIteratorToArray => self.execute_push_iterator_to_array(),
+            Opcode::Add => self.execute_add(),
+            Opcode::Sub => self.execute_sub(),
+            Opcode::Mul => self.execute_mul(),
+            Opcode::Div => self.execute_div(),
+            Opcode::Pow => self.execute_pow(),
+            Opcode::Mod => self.execute_mod(),
+            Opcode::BitAnd => self.execute_bit_and(),
+            Opcode::BitOr => self.execute_bit_or(),
+            Opcode::BitXor => self.execute_bit_xor(),
+            Opcode::ShiftLeft => self.execute_shift_left(),
+            Opcode::ShiftRight => self.execute_shift_right(),
+            Opcode::UnsignedShiftRight => self.execute_unsigned_shift_right(),
+            Opcode::Eq => self.execute_eq(),
+            Opcode::NotEq => self.execute_not_eq(),
+            Opcode::StrictEq => self.execute_strict_eq(),
+            Opcode::StrictNotEq => self.execute_strict_not_eq(),
+            Opcode::GreaterThan => self.execute_greater_than(),
+            Opcode::GreaterThanOrEq => self.execute_greater_than_or_eq(),
+            Opcode::LessThan => self.execute_less_than(),
+            Opcode::LessThanOrEq => self.execute_less_than_or_eq(),
+            Opcode::In => self.execute_in(),
+            Opcode::InstanceOf => self.execute_instance_of(),
+            Opcode::Void => self.execute_void(),
+            Opcode::TypeOf => self.execute_type_of(),
+            Opcode::Pos => self.execute_pos(),
+            Opcode::Neg => self.execute_neg(),
+            Opcode::Inc => self.execute_inc(),
+            Opcode::IncPost => self.execute_inc_post(),
+            Opcode::Dec => self.execute_dec(),
+            Opcode::DecPost => self.execute_dec_post(),
+            Opcode::LogicalNot => self.execute_logical_not(),
+            Opcode::BitNot => self.execute_bit_not(),
+            Opcode::DefVar => self.execute_def_var(),
+            Opcode::DefInitVar => self.execute_def_init_var(),
+            Opcode::DefLet => self.execute_def_let(),
             Opcode::DefInitLet | Opcode::DefInitConst | Opcode::DefInitArg => {
-                let index = self.vm.read::<u32>();
-                let value = self.vm.pop();
-                let binding_locator = self.vm.frame().code.bindings[index as usize];
-                self.realm.environments.put_value(
-                    binding_locator.environment_index(),
-                    binding_locator.binding_index(),
-                    value,
-                );
-            }
-            Opcode::GetName => {
-                let index = self.vm.read::<u32>();
-                let binding_locator = self.vm.frame().code.bindings[index as usize];
-                binding_locator.throw_mutate_immutable(self)?;
-
-                let value = if binding_locator.is_global() {
-                    let key: JsString = self
-                        .interner()
-                        .resolve_expect(binding_locator.name())
-                        .into();
-                    match self.global_bindings_mut().get(&key) {
-                        Some(desc) => match desc.kind() {
-                            DescriptorKind::Data {
-                                value: Some(value), ..
-                            } => value.clone(),
-                            DescriptorKind::Accessor { get: Some(get), .. }
-                                if !get.is_undefined() =>
-                            {
-                                let get = get.clone();
-                                self.call(&get, &self.global_object().clone().into(), &[])?
-                            }
-                            _ => {
-                                return self.throw_reference_error(format!("{key} is not defined"))
-                            }
-                        },
-                        _ => return self.throw_reference_error(format!("{key} is not defined")),
-                    }
-                } else if let Some(value) = self.realm.environments.get_value_optional(
-                    binding_locator.environment_index(),
-                    binding_locator.binding_index(),
-                ) {
-                    value
-                } else {
-                    let name =
-                        JsString::from(self.interner().resolve_expect(binding_locator.name()));
-                    return self.throw_reference_error(format!("{name} is not initialized"));
-                };
-
-                self.vm.push(value);
-            }
-            Opcode::GetNameOrUndefined => {
-                let index = self.vm.read::<u32>();
-                let binding_locator = self.vm.frame().code.bindings[index as usize];
-                binding_locator.throw_mutate_immutable(self)?;
-                let value = if binding_locator.is_global() {
-                    let key: JsString = self
-                        .interner()
-                        .resolve_expect(binding_locator.name())
-                        .into();
-                    match self.global_bindings_mut().get(&key) {
-                        Some(desc) => match desc.kind() {
-                            DescriptorKind::Data {
-                                value: Some(value), ..
-                            } => value.clone(),
-                            DescriptorKind::Accessor { get: Some(get), .. }
-                                if !get.is_undefined() =>
-                            {
-                                let get = get.clone();
-                                self.call(&get, &self.global_object().clone().into(), &[])?
-                            }
-                            _ => JsValue::undefined(),
-                        },
-                        _ => JsValue::undefined(),
-                    }
-                } else if let Some(value) = self.realm.environments.get_value_optional(
-                    binding_locator.environment_index(),
-                    binding_locator.binding_index(),
-                ) {
-                    value
-                } else {
-                    JsValue::undefined()
-                };
-
-                self.vm.push(value);
-            }
-            Opcode::SetName => {
-                let index = self.vm.read::<u32>();
-                let binding_locator = self.vm.frame().code.bindings[index as usize];
-                let value = self.vm.pop();
-                binding_locator.throw_mutate_immutable(self)?;
-
-                if binding_locator.is_global() {
-                    let key: JsString = self
-                        .interner()
-                        .resolve_expect(binding_locator.name())
-                        .into();
-                    let exists = self.global_bindings_mut().contains_key(&key);
-
-                    if !exists && (self.strict() || self.vm.frame().code.strict) {
-                        return self.throw_reference_error(format!(
-                            "assignment to undeclared variable {key}"
-                        ));
-                    }
-
-                    let success = crate::object::internal_methods::global::global_set_no_receiver(
-                        &key.clone().into(),
-                        value,
-                        self,
-                    )?;
-
-                    if !success && (self.strict() || self.vm.frame().code.strict) {
-                        return self
-                            .throw_type_error(format!("cannot set non-writable property: {key}",));
-                    }
-                } else if !self.realm.environments.put_value_if_initialized(
-                    binding_locator.environment_index(),
-                    binding_locator.binding_index(),
-                    value,
-                ) {
-                    self.throw_reference_error(format!(
-                        "cannot access '{}' before initialization",
-                        self.interner().resolve_expect(binding_locator.name())
-                    ))?;
-                }
-            }
-            Opcode::Jump => {
-                let address = self.vm.read::<u32>();
-                self.vm.frame_mut().pc = address as usize;
-            }
-            Opcode::JumpIfFalse => {
-                let address = self.vm.read::<u32>();
-                if !self.vm.pop().to_boolean() {
-                    self.vm.frame_mut().pc = address as usize;
-                }
-            }
-            Opcode::JumpIfNotUndefined => {
-                let address = self.vm.read::<u32>();
-                let value = self.vm.pop();
-                if !value.is_undefined() {
-                    self.vm.frame_mut().pc = address as usize;
-                    self.vm.push(value);
-                }
-            }
-            Opcode::LogicalAnd => {
-                let exit = self.vm.read::<u32>();
-                let lhs = self.vm.pop();
-                if !lhs.to_boolean() {
-                    self.vm.frame_mut().pc = exit as usize;
-                    self.vm.push(lhs);
-                }
-            }
-            Opcode::LogicalOr => {
-                let exit = self.vm.read::<u32>();
-                let lhs = self.vm.pop();
-                if lhs.to_boolean() {
-                    self.vm.frame_mut().pc = exit as usize;
-                    self.vm.push(lhs);
-                }
-            }
-            Opcode::Coalesce => {
-                let exit = self.vm.read::<u32>();
-                let lhs = self.vm.pop();
-                if !lhs.is_null_or_undefined() {
-                    self.vm.frame_mut().pc = exit as usize;
-                    self.vm.push(lhs);
-                }
-            }
-            Opcode::ToBoolean => {
-                let value = self.vm.pop();
-                self.vm.push(value.to_boolean());
-            }
-            Opcode::GetPropertyByName => {
-                let index = self.vm.read::<u32>();
-
-                let value = self.vm.pop();
-                let object = if let Some(object) = value.as_object() {
-                    object.clone()
-                } else {
-                    value.to_object(self)?
-                };
-
-                let name = self.vm.frame().code.names[index as usize];
-                let name: PropertyKey = self.interner().resolve_expect(name).into();
-                let result = object.get(name, self)?;
-
-                self.vm.push(result);
-            }
-            Opcode::GetPropertyByValue => {
-                let object = self.vm.pop();
-                let key = self.vm.pop();
-                let object = if let Some(object) = object.as_object() {
-                    object.clone()
-                } else {
-                    object.to_object(self)?
-                };
-
-                let key = key.to_property_key(self)?;
-                let value = object.get(key, self)?;
-
-                self.vm.push(value);
-            }
-            Opcode::SetPropertyByName => {
-                let index = self.vm.read::<u32>();
-
-                let object = self.vm.pop();
-                let value = self.vm.pop();
-                let object = if let Some(object) = object.as_object() {
-                    object.clone()
-                } else {
-                    object.to_object(self)?
-                };
-
-                let name = self.vm.frame().code.names[index as usize];
-                let name: PropertyKey = self.interner().resolve_expect(name).into();
-
-                object.set(
-                    name,
-                    value,
-                    self.strict() || self.vm.frame().code.strict,
-                    self,
-                )?;
-            }
-            Opcode::DefineOwnPropertyByName => {
-                let index = self.vm.read::<u32>();
-                let object = self.vm.pop();
-                let value = self.vm.pop();
-                let object = if let Some(object) = object.as_object() {
-                    object.clone()
-                } else {
-                    object.to_object(self)?
-                };
-                let name = self.vm.frame().code.names[index as usize];
-                let name = self.interner().resolve_expect(name);
-                object.__define_own_property__(
-                    name.into(),
-                    PropertyDescriptor::builder()
-                        .value(value)
-                        .writable(true)
-                        .enumerable(true)
-                        .configurable(true)
-                        .build(),
-                    self,
-                )?;
-            }
-            Opcode::DefineClassMethodByName => {
-                let index = self.vm.read::<u32>();
-                let object = self.vm.pop();
-                let value = self.vm.pop();
-                let object = if let Some(object) = object.as_object() {
-                    object.clone()
-                } else {
-                    object.to_object(self)?
-                };
-                let name = self.vm.frame().code.names[index as usize];
-                let name = self.interner().resolve_expect(name);
-                object.__define_own_property__(
-                    name.into(),
-                    PropertyDescriptor::builder()
-                        .value(value)
-                        .writable(true)
-                        .enumerable(false)
-                        .configurable(true)
-                        .build(),
-                    self,
-                )?;
-            }
-            Opcode::SetPropertyByValue => {
-                let object = self.vm.pop();
-                let key = self.vm.pop();
-                let value = self.vm.pop();
-                let object = if let Some(object) = object.as_object() {
-                    object.clone()
-                } else {
-                    object.to_object(self)?
-                };
-
-                let key = key.to_property_key(self)?;
-                object.set(
-                    key,
-                    value,
-                    self.strict() || self.vm.frame().code.strict,
-                    self,
-                )?;
-            }
-            Opcode::DefineOwnPropertyByValue => {
-                let value = self.vm.pop();
-                let key = self.vm.pop();
-                let object = self.vm.pop();
-                let object = if let Some(object) = object.as_object() {
-                    object.clone()
-                } else {
-                    object.to_object(self)?
-                };
-                let key = key.to_property_key(self)?;
-                object.__define_own_property__(
-                    key,
-                    PropertyDescriptor::builder()
-                        .value(value)
-                        .writable(true)
-                        .enumerable(true)
-                        .configurable(true)
-                        .build(),
-                    self,
-                )?;
-            }
-            Opcode::DefineClassMethodByValue => {
-                let value = self.vm.pop();
-                let key = self.vm.pop();
-                let object = self.vm.pop();
-                let object = if let Some(object) = object.as_object() {
-                    object.clone()
-                } else {
-                    object.to_object(self)?
-                };
-                let key = key.to_property_key(self)?;
-                object.__define_own_property__(
-                    key,
-                    PropertyDescriptor::builder()
-                        .value(value)
-                        .writable(true)
-                        .enumerable(false)
-                        .configurable(true)
-                        .build(),
-                    self,
-                )?;
-            }
-            Opcode::SetPropertyGetterByName => {
-                let index = self.vm.read::<u32>();
-                let object = self.vm.pop();
-                let value = self.vm.pop();
-                let object = object.to_object(self)?;
-                let name = self.vm.frame().code.names[index as usize];
-                let name = self.interner().resolve_expect(name).into();
-                let set = object
-                    .__get_own_property__(&name, self)?
-                    .as_ref()
-                    .and_then(PropertyDescriptor::set)
-                    .cloned();
-                object.__define_own_property__(
-                    name,
-                    PropertyDescriptor::builder()
-                        .maybe_get(Some(value))
-                        .maybe_set(set)
-                        .enumerable(true)
-                        .configurable(true)
-                        .build(),
-                    self,
-                )?;
-            }
-            Opcode::DefineClassGetterByName => {
-                let index = self.vm.read::<u32>();
-                let object = self.vm.pop();
-                let value = self.vm.pop();
-                let object = object.to_object(self)?;
-                let name = self.vm.frame().code.names[index as usize];
-                let name = self.interner().resolve_expect(name).into();
-                let set = object
-                    .__get_own_property__(&name, self)?
-                    .as_ref()
-                    .and_then(PropertyDescriptor::set)
-                    .cloned();
-                object.__define_own_property__(
-                    name,
-                    PropertyDescriptor::builder()
-                        .maybe_get(Some(value))
-                        .maybe_set(set)
-                        .enumerable(false)
-                        .configurable(true)
-                        .build(),
-                    self,
-                )?;
-            }
-            Opcode::SetPropertyGetterByValue => {
-                let value = self.vm.pop();
-                let key = self.vm.pop();
-                let object = self.vm.pop();
-                let object = object.to_object(self)?;
-                let name = key.to_property_key(self)?;
-                let set = object
-                    .__get_own_property__(&name, self)?
-                    .as_ref()
-                    .and_then(PropertyDescriptor::set)
-                    .cloned();
-                object.__define_own_property__(
-                    name,
-                    PropertyDescriptor::builder()
-                        .maybe_get(Some(value))
-                        .maybe_set(set)
-                        .enumerable(true)
-                        .configurable(true)
-                        .build(),
-                    self,
-                )?;
-            }
-            Opcode::DefineClassGetterByValue => {
-                let value = self.vm.pop();
-                let key = self.vm.pop();
-                let object = self.vm.pop();
-                let object = object.to_object(self)?;
-                let name = key.to_property_key(self)?;
-                let set = object
-                    .__get_own_property__(&name, self)?
-                    .as_ref()
-                    .and_then(PropertyDescriptor::set)
-                    .cloned();
-                object.__define_own_property__(
-                    name,
-                    PropertyDescriptor::builder()
-                        .maybe_get(Some(value))
-                        .maybe_set(set)
-                        .enumerable(false)
-                        .configurable(true)
-                        .build(),
-                    self,
-                )?;
-            }
-            Opcode::SetPropertySetterByName => {
-                let index = self.vm.read::<u32>();
-                let object = self.vm.pop();
-                let value = self.vm.pop();
-                let object = object.to_object(self)?;
-                let name = self.vm.frame().code.names[index as usize];
-                let name = self.interner().resolve_expect(name).into();
-                let get = object
-                    .__get_own_property__(&name, self)?
-                    .as_ref()
-                    .and_then(PropertyDescriptor::get)
-                    .cloned();
-                object.__define_own_property__(
-                    name,
-                    PropertyDescriptor::builder()
-                        .maybe_set(Some(value))
-                        .maybe_get(get)
-                        .enumerable(true)
-                        .configurable(true)
-                        .build(),
-                    self,
-                )?;
-            }
-            Opcode::DefineClassSetterByName => {
-                let index = self.vm.read::<u32>();
-                let object = self.vm.pop();
-                let value = self.vm.pop();
-                let object = object.to_object(self)?;
-                let name = self.vm.frame().code.names[index as usize];
-                let name = self.interner().resolve_expect(name).into();
-                let get = object
-                    .__get_own_property__(&name, self)?
-                    .as_ref()
-                    .and_then(PropertyDescriptor::get)
-                    .cloned();
-                object.__define_own_property__(
-                    name,
-                    PropertyDescriptor::builder()
-                        .maybe_set(Some(value))
-                        .maybe_get(get)
-                        .enumerable(false)
-                        .configurable(true)
-                        .build(),
-                    self,
-                )?;
-            }
-            Opcode::SetPropertySetterByValue => {
-                let value = self.vm.pop();
-                let key = self.vm.pop();
-                let object = self.vm.pop();
-                let object = object.to_object(self)?;
-                let name = key.to_property_key(self)?;
-                let get = object
-                    .__get_own_property__(&name, self)?
-                    .as_ref()
-                    .and_then(PropertyDescriptor::get)
-                    .cloned();
-                object.__define_own_property__(
-                    name,
-                    PropertyDescriptor::builder()
-                        .maybe_set(Some(value))
-                        .maybe_get(get)
-                        .enumerable(true)
-                        .configurable(true)
-                        .build(),
-                    self,
-                )?;
-            }
-            Opcode::DefineClassSetterByValue => {
-                let value = self.vm.pop();
-                let key = self.vm.pop();
-                let object = self.vm.pop();
-                let object = object.to_object(self)?;
-                let name = key.to_property_key(self)?;
-                let get = object
-                    .__get_own_property__(&name, self)?
-                    .as_ref()
-                    .and_then(PropertyDescriptor::get)
-                    .cloned();
-                object.__define_own_property__(
-                    name,
-                    PropertyDescriptor::builder()
-                        .maybe_set(Some(value))
-                        .maybe_get(get)
-                        .enumerable(false)
-                        .configurable(true)
-                        .build(),
-                    self,
-                )?;
-            }
-            Opcode::SetPrivateValue => {
-                let index = self.vm.read::<u32>();
-                let name = self.vm.frame().code.names[index as usize];
-                let value = self.vm.pop();
-                let object = self.vm.pop();
-                if let Some(object) = object.as_object() {
-                    let mut object_borrow_mut = object.borrow_mut();
-                    if let Some(PrivateElement::Accessor {
-                        getter: _,
-                        setter: Some(setter),
-                    }) = object_borrow_mut.get_private_element(name)
-                    {
-                        let setter = setter.clone();
-                        drop(object_borrow_mut);
-                        setter.call(&object.clone().into(), &[value], self)?;
-                    } else {
-                        object_borrow_mut.set_private_element(name, PrivateElement::Value(value));
-                    }
-                } else {
-                    return self.throw_type_error("cannot set private property on non-object");
-                }
-            }
-            Opcode::SetPrivateSetter => {
-                let index = self.vm.read::<u32>();
-                let name = self.vm.frame().code.names[index as usize];
-                let value = self.vm.pop();
-                let value = value.as_callable().expect("setter must be callable");
-                let object = self.vm.pop();
-                if let Some(object) = object.as_object() {
-                    let mut object_borrow_mut = object.borrow_mut();
-                    object_borrow_mut.set_private_element_setter(name, value.clone());
-                } else {
-                    return self.throw_type_error("cannot set private setter on non-object");
-                }
-            }
-            Opcode::SetPrivateGetter => {
-                let index = self.vm.read::<u32>();
-                let name = self.vm.frame().code.names[index as usize];
-                let value = self.vm.pop();
-                let value = value.as_callable().expect("getter must be callable");
-                let object = self.vm.pop();
-                if let Some(object) = object.as_object() {
-                    let mut object_borrow_mut = object.borrow_mut();
-                    object_borrow_mut.set_private_element_getter(name, value.clone());
-                } else {
-                    return self.throw_type_error("cannot set private getter on non-object");
-                }
-            }
-            Opcode::GetPrivateField => {
-                let index = self.vm.read::<u32>();
-                let name = self.vm.frame().code.names[index as usize];
-                let value = self.vm.pop();
-                if let Some(object) = value.as_object() {
-                    let object_borrow_mut = object.borrow();
-                    if let Some(element) = object_borrow_mut.get_private_element(name) {
-                        match element {
-                            PrivateElement::Value(value) => self.vm.push(value),
-                            PrivateElement::Accessor {
-                                getter: Some(getter),
-                                setter: _,
-                            } => {
-                                let value = getter.call(&value, &[], self)?;
-                                self.vm.push(value);
-                            }
-                            PrivateElement::Accessor { .. } => {
-                                return self.throw_type_error(
-                                    "private property was defined without a getter",
-                                );
-                            }
-                        }
-                    } else {
-                        return self.throw_type_error("private property does not exist");
-                    }
-                } else {
-                    return self.throw_type_error("cannot read private property from non-object");
-                }
-            }
-            Opcode::PushClassComputedFieldName => {
-                let object = self.vm.pop();
-                let value = self.vm.pop();
-                let value = value.to_property_key(self)?;
-                let object_obj = object
-                    .as_object()
-                    .expect("can only add field to function object");
-                let object = object_obj.borrow();
-                let function = object
-                    .as_function()
-                    .expect("can only add field to function object");
-                if let Function::Ordinary { code, .. } = function {
-                    let code_b = code
-                        .computed_field_names
-                        .as_ref()
-                        .expect("class constructor must have fields");
-                    let mut fields_mut = code_b.borrow_mut();
-                    fields_mut.push(value);
-                }
-            }
-            Opcode::DeletePropertyByName => {
-                let index = self.vm.read::<u32>();
-                let key = self.vm.frame().code.names[index as usize];
-                let key = self.interner().resolve_expect(key).into();
-                let object = self.vm.pop();
-                let result = object.to_object(self)?.__delete__(&key, self)?;
-                if !result && self.strict() || self.vm.frame().code.strict {
-                    return Err(self.construct_type_error("Cannot delete property"));
-                }
-                self.vm.push(result);
-            }
-            Opcode::DeletePropertyByValue => {
-                let object = self.vm.pop();
-                let key = self.vm.pop();
-                let result = object
-                    .to_object(self)?
-                    .__delete__(&key.to_property_key(self)?, self)?;
-                if !result && self.strict() || self.vm.frame().code.strict {
-                    return Err(self.construct_type_error("Cannot delete property"));
-                }
-                self.vm.push(result);
-            }
-            Opcode::CopyDataProperties => {
-                let excluded_key_count = self.vm.read::<u32>();
-                let mut excluded_keys = Vec::with_capacity(excluded_key_count as usize);
-                for _ in 0..excluded_key_count {
-                    excluded_keys.push(self.vm.pop().as_string().expect("not a string").clone());
-                }
-                let value = self.vm.pop();
-                let object = value.as_object().expect("not an object");
-                let source = self.vm.pop();
-                object.copy_data_properties(&source, excluded_keys, self)?;
-                self.vm.push(value);
-            }
-            Opcode::ToPropertyKey => {
-                let value = self.vm.pop();
-                let key = value.to_property_key(self)?;
-                self.vm.push(key);
-            }
-            Opcode::Throw => {
-                let value = self.vm.pop();
-                return Err(value);
-            }
-            Opcode::TryStart => {
-                let next = self.vm.read::<u32>();
-                let finally = self.vm.read::<u32>();
-                let finally = if finally == 0 { None } else { Some(finally) };
-                self.vm
-                    .frame_mut()
-                    .catch
-                    .push(CatchAddresses { next, finally });
-                self.vm.frame_mut().finally_jump.push(None);
-                self.vm.frame_mut().finally_return = FinallyReturn::None;
-                self.vm.frame_mut().try_env_stack.push(TryStackEntry {
-                    num_env: 0,
-                    num_loop_stack_entries: 0,
-                });
-            }
-            Opcode::TryEnd | Opcode::CatchEnd => {
-                self.vm.frame_mut().catch.pop();
-                let try_stack_entry = self.vm.frame_mut().try_env_stack.pop().expect("must exist");
-                for _ in 0..try_stack_entry.num_env {
-                    self.realm.environments.pop();
-                }
-                let mut num_env = try_stack_entry.num_env;
-                for _ in 0..try_stack_entry.num_loop_stack_entries {
-                    num_env -= self
-                        .vm
-                        .frame_mut()
-                        .loop_env_stack
-                        .pop()
-                        .expect("must exist");
-                }
-                *self
-                    .vm
-                    .frame_mut()
-                    .loop_env_stack
-                    .last_mut()
-                    .expect("must exist") -= num_env;
-                self.vm.frame_mut().finally_return = FinallyReturn::None;
-            }
-            Opcode::CatchStart => {
-                let finally = self.vm.read::<u32>();
-                self.vm.frame_mut().catch.push(CatchAddresses {
-                    next: finally,
-                    finally: Some(finally),
-                });
-                self.vm.frame_mut().try_env_stack.push(TryStackEntry {
-                    num_env: 0,
-                    num_loop_stack_entries: 0,
-                });
-            }
-            Opcode::CatchEnd2 => {
-                let frame = self.vm.frame_mut();
-                if frame.finally_return == FinallyReturn::Err {
-                    frame.finally_return = FinallyReturn::None;
-                }
-            }
-            Opcode::FinallyStart => {
-                *self
-                    .vm
-                    .frame_mut()
-                    .finally_jump
-                    .last_mut()
-                    .expect("finally jump must exist here") = None;
-            }
-            Opcode::FinallyEnd => {
-                let address = self
-                    .vm
-                    .frame_mut()
-                    .finally_jump
-                    .pop()
-                    .expect("finally jump must exist here");
-                match self.vm.frame_mut().finally_return {
-                    FinallyReturn::None => {
-                        if let Some(address) = address {
-                            self.vm.frame_mut().pc = address as usize;
-                        }
-                    }
-                    FinallyReturn::Ok => {
-                        return Ok(ShouldExit::True);
-                    }
-                    FinallyReturn::Err => {
-                        return Err(self.vm.pop());
-                    }
-                }
-            }
-            Opcode::FinallySetJump => {
-                let address = self.vm.read::<u32>();
-                *self
-                    .vm
-                    .frame_mut()
-                    .finally_jump
-                    .last_mut()
-                    .expect("finally jump must exist here") = Some(address);
-            }
-            Opcode::This => {
-                let this = self.vm.frame().this.clone();
-                self.vm.push(this);
-            }
-            Opcode::Case => {
-                let address = self.vm.read::<u32>();
-                let cond = self.vm.pop();
-                let value = self.vm.pop();
-
-                if value.strict_equals(&cond) {
-                    self.vm.frame_mut().pc = address as usize;
-                } else {
-                    self.vm.push(value);
-                }
-            }
-            Opcode::Default => {
-                let exit = self.vm.read::<u32>();
-                let _val = self.vm.pop();
-                self.vm.frame_mut().pc = exit as usize;
-            }
-            Opcode::GetFunction => {
-                let index = self.vm.read::<u32>();
-                let code = self.vm.frame().code.functions[index as usize].clone();
-                let function = create_function_object(code, self);
-                self.vm.push(function);
-            }
-            Opcode::GetGenerator => {
-                let index = self.vm.read::<u32>();
-                let code = self.vm.frame().code.functions[index as usize].clone();
-                let function = create_generator_function_object(code, self);
-                self.vm.push(function);
-            }
-            Opcode::Call => {
-                if self.vm.stack_size_limit <= self.vm.stack.len() {
-                    return self.throw_range_error("Maximum call stack size exceeded");
-                }
-                let argument_count = self.vm.read::<u32>();
-                let mut arguments = Vec::with_capacity(argument_count as usize);
-                for _ in 0..argument_count {
-                    arguments.push(self.vm.pop());
-                }
-                arguments.reverse();
-
-                let func = self.vm.pop();
-                let mut this = self.vm.pop();
-
-                let object = match func {
-                    JsValue::Object(ref object) if object.is_callable() => object.clone(),
-                    _ => return self.throw_type_error("not a callable function"),
-                };
-
-                if this.is_null_or_undefined() {
-                    this = self.global_object().clone().into();
-                }
-
-                let result = object.__call__(&this, &arguments, self)?;
-
-                self.vm.push(result);
-            }
-            Opcode::CallWithRest => {
-                if self.vm.stack_size_limit <= self.vm.stack.len() {
-                    return self.throw_range_error("Maximum call stack size exceeded");
-                }
-                let argument_count = self.vm.read::<u32>();
-                let rest_argument = self.vm.pop();
-                let mut arguments = Vec::with_capacity(argument_count as usize);
-                for _ in 0..(argument_count - 1) {
-                    arguments.push(self.vm.pop());
-                }
-                arguments.reverse();
-                let func = self.vm.pop();
-                let mut this = self.vm.pop();
-
-                let iterator_record = rest_argument.get_iterator(self, None, None)?;
-                let mut rest_arguments = Vec::new();
-                while let Some(next) = iterator_record.step(self)? {
-                    rest_arguments.push(next.value(self)?);
-                }
-                arguments.append(&mut rest_arguments);
-
-                let object = match func {
-                    JsValue::Object(ref object) if object.is_callable() => object.clone(),
-                    _ => return self.throw_type_error("not a callable function"),
-                };
-
-                if this.is_null_or_undefined() {
-                    this = self.global_object().clone().into();
-                }
-
-                let result = object.__call__(&this, &arguments, self)?;
-
-                self.vm.push(result);
-            }
-            Opcode::New => {
-                if self.vm.stack_size_limit <= self.vm.stack.len() {
-                    return self.throw_range_error("Maximum call stack size exceeded");
-                }
-                let argument_count = self.vm.read::<u32>();
-                let mut arguments = Vec::with_capacity(argument_count as usize);
-                for _ in 0..argument_count {
-                    arguments.push(self.vm.pop());
-                }
-                arguments.reverse();
-                let func = self.vm.pop();
-
-                let result = func
-                    .as_constructor()
-                    .ok_or_else(|| self.construct_type_error("not a constructor"))
-                    .and_then(|cons| cons.__construct__(&arguments, &cons.clone().into(), self))?;
-
-                self.vm.push(result);
-            }
-            Opcode::NewWithRest => {
-                if self.vm.stack_size_limit <= self.vm.stack.len() {
-                    return self.throw_range_error("Maximum call stack size exceeded");
-                }
-                let argument_count = self.vm.read::<u32>();
-                let rest_argument = self.vm.pop();
-                let mut arguments = Vec::with_capacity(argument_count as usize);
-                for _ in 0..(argument_count - 1) {
-                    arguments.push(self.vm.pop());
-                }
-                arguments.reverse();
-                let func = self.vm.pop();
-
-                let iterator_record = rest_argument.get_iterator(self, None, None)?;
-                let mut rest_arguments = Vec::new();
-                while let Some(next) = iterator_record.step(self)? {
-                    rest_arguments.push(next.value(self)?);
-                }
-                arguments.append(&mut rest_arguments);
-
-                let result = func
-                    .as_constructor()
-                    .ok_or_else(|| self.construct_type_error("not a constructor"))
-                    .and_then(|cons| cons.__construct__(&arguments, &cons.clone().into(), self))?;
-
-                self.vm.push(result);
-            }
-            Opcode::Return => {
-                if let Some(finally_address) = self.vm.frame().catch.last().and_then(|c| c.finally)
-                {
-                    let frame = self.vm.frame_mut();
-                    frame.pc = finally_address as usize;
-                    frame.finally_return = FinallyReturn::Ok;
-                    frame.catch.pop();
-                    let try_stack_entry =
-                        self.vm.frame_mut().try_env_stack.pop().expect("must exist");
-                    for _ in 0..try_stack_entry.num_env {
-                        self.realm.environments.pop();
-                    }
-                    let mut num_env = try_stack_entry.num_env;
-                    for _ in 0..try_stack_entry.num_loop_stack_entries {
-                        num_env -= self
-                            .vm
-                            .frame_mut()
-                            .loop_env_stack
-                            .pop()
-                            .expect("must exist");
-                    }
-                    *self
-                        .vm
-                        .frame_mut()
-                        .loop_env_stack
-                        .last_mut()
-                        .expect("must exist") -= num_env;
-                } else {
-                    return Ok(ShouldExit::True);
-                }
-            }
-            Opcode::PushDeclarativeEnvironment => {
-                let num_bindings = self.vm.read::<u32>();
-                self.realm
-                    .environments
-                    .push_declarative(num_bindings as usize);
-                self.vm.frame_mut().loop_env_stack_inc();
-                self.vm.frame_mut().try_env_stack_inc();
-            }
-            Opcode::PushFunctionEnvironment => {
-                let num_bindings = self.vm.read::<u32>();
-                let is_constructor = self.vm.frame().code.constructor;
-                let is_lexical = self.vm.frame().code.this_mode.is_lexical();
-                let this = if is_constructor || !is_lexical {
-                    self.vm.frame().this.clone()
-                } else {
-                    JsValue::undefined()
-                };
-
-                self.realm
-                    .environments
-                    .push_function(num_bindings as usize, this);
-            }
-            Opcode::PopEnvironment => {
-                self.realm.environments.pop();
-                self.vm.frame_mut().loop_env_stack_dec();
-                self.vm.frame_mut().try_env_stack_dec();
-            }
-            Opcode::LoopStart => {
-                self.vm.frame_mut().loop_env_stack.push(0);
-                self.vm.frame_mut().try_env_stack_loop_inc();
-            }
-            Opcode::LoopContinue => {
-                let env_num = self
-                    .vm
-                    .frame_mut()
-                    .loop_env_stack
-                    .last_mut()
-                    .expect("loop env stack entry must exist");
-                let env_num_copy = *env_num;
-                *env_num = 0;
-                for _ in 0..env_num_copy {
-                    self.realm.environments.pop();
-                }
-            }
-            Opcode::LoopEnd => {
-                let env_num = self
-                    .vm
-                    .frame_mut()
-                    .loop_env_stack
-                    .pop()
-                    .expect("loop env stack entry must exist");
-                for _ in 0..env_num {
-                    self.realm.environments.pop();
-                    self.vm.frame_mut().try_env_stack_dec();
-                }
-                self.vm.frame_mut().try_env_stack_loop_dec();
-            }
-            Opcode::ForInLoopInitIterator => {
-                let address = self.vm.read::<u32>();
-
-                let object = self.vm.pop();
-                if object.is_null_or_undefined() {
-                    self.vm.frame_mut().pc = address as usize;
-                    return Ok(ShouldExit::False);
-                }
-
-                let object = object.to_object(self)?;
-                let iterator = ForInIterator::create_for_in_iterator(JsValue::new(object), self);
-                let next_function = iterator
-                    .get_property("next")
-                    .as_ref()
-                    .map(PropertyDescriptor::expect_value)
-                    .cloned()
-                    .ok_or_else(|| self.construct_type_error("Could not find property `next`"))?;
-
-                self.vm.push(iterator);
-                self.vm.push(next_function);
-            }
-            Opcode::InitIterator => {
-                let object = self.vm.pop();
-                let iterator = object.get_iterator(self, None, None)?;
-                self.vm.push(iterator.iterator_object());
-                self.vm.push(iterator.next_function());
-            }
-            Opcode::IteratorNext => {
-                let next_function = self.vm.pop();
-                let iterator = self.vm.pop();
-
-                let iterator_record = IteratorRecord::new(iterator.clone(), next_function.clone());
-                let next = iterator_record.step(self)?;
-
-                self.vm.push(iterator);
-                self.vm.push(next_function);
-                if let Some(next) = next {
-                    let value = next.value(self)?;
-                    self.vm.push(value);
-                } else {
-                    self.vm.push(JsValue::undefined());
-                }
-            }
-            Opcode::IteratorNextFull => {
-                let next_function = self.vm.pop();
-                let iterator = self.vm.pop();
-
-                let iterator_record = IteratorRecord::new(iterator.clone(), next_function.clone());
-                let next = iterator_record.step(self)?;
-
-                self.vm.push(iterator);
-                self.vm.push(next_function);
-                if let Some(next) = next {
-                    let value = next.value(self)?;
-                    self.vm.push(false);
-                    self.vm.push(value);
-                } else {
-                    self.vm.push(true);
-                    self.vm.push(JsValue::undefined());
-                }
-            }
-            Opcode::IteratorClose => {
-                let done = self.vm.pop();
-                let next_function = self.vm.pop();
-                let iterator = self.vm.pop();
-                if !done.as_boolean().expect("not a boolean") {
-                    let iterator_record = IteratorRecord::new(iterator, next_function);
-                    iterator_record.close(Ok(JsValue::Null), self)?;
-                }
-            }
-            Opcode::IteratorToArray => {
-                let next_function = self.vm.pop();
-                let iterator = self.vm.pop();
-
-                let iterator_record = IteratorRecord::new(iterator.clone(), next_function.clone());
-                let mut values = Vec::new();
+                self.execute_def_init()
+            }
+            Opcode::GetName => self.execute_get_name(),
+            Opcode::GetNameOrUndefined => self.execute_get_name_or_undefined(),
+            Opcode::SetName => self.execute_set_name(),
+            Opcode::Jump => self.execute_jump(),
+            Opcode::JumpIfFalse => self.execute_jump_if_false(),
+            Opcode::JumpIfNotUndefined => self.execute_jump_if_not_undefined(),
+            Opcode::LogicalAnd => self.execute_logical_and(),
+            Opcode::LogicalOr => self.execute_logical_or(),
+            Opcode::Coalesce => self.execute_coalesce(),
+            Opcode::ToBoolean => self.execute_to_boolean(),
+            Opcode::GetPropertyByName => self.execute_get_property_by_name(),
+            Opcode::GetPropertyByValue => self.execute_get_property_by_value(),
+            Opcode::SetPropertyByName => self.execute_set_property_by_name(),
+            Opcode::DefineOwnPropertyByName => self.execute_define_own_property_by_name(),
+            Opcode::DefineClassMethodByName => self.execute_define_class_method_by_name(),
+            Opcode::SetPropertyByValue => self.execute_set_property_by_value(),
+            Opcode::DefineOwnPropertyByValue => self.execute_define_own_property_by_value(),
+            Opcode::DefineClassMethodByValue => self.execute_define_class_method_by_value(),
+            Opcode::SetPropertyGetterByName => self.execute_set_property_getter_by_name(),
+            Opcode::DefineClassGetterByName => self.execute_define_class_getter_by_name(),
+            Opcode::SetPropertyGetterByValue => self.execute_set_property_getter_by_value(),
+            Opcode::DefineClassGetterByValue => self.execute_define_class_getter_by_value(),
+            Opcode::SetPropertySetterByName => self.execute_set_property_setter_by_name(),
+            Opcode::DefineClassSetterByName => self.execute_define_class_setter_by_name(),
+            Opcode::SetPropertySetterByValue => self.execute_set_property_setter_by_value(),
+            Opcode::DefineClassSetterByValue => self.execute_define_class_setter_by_value(),
+            Opcode::SetPrivateValue => self.execute_set_private_value(),
+            Opcode::SetPrivateSetter => self.execute_set_private_setter(),
+            Opcode::SetPrivateGetter => self.execute_set_private_getter(),
+            Opcode::GetPrivateField => self.execute_get_private_field(),
+            Opcode::PushClassComputedFieldName => self.execute_push_class_computed_field_name(),
+            Opcode::DeletePropertyByName => self.execute_delete_property_by_name(),
+            Opcode::DeletePropertyByValue => self.execute_delete_property_by_value(),
+            Opcode::CopyDataProperties => self.execute_copy_data_properties(),
+            Opcode::ToPropertyKey => self.execute_to_property_key(),
+            Opcode::Throw => self.execute_throw(),
+            Opcode::TryStart => self.execute_try_start(),
+            Opcode::TryEnd | Opcode::CatchEnd => self.execute_try_catch_end(),
+            Opcode::CatchStart => self.execute_catch_start(),
+            Opcode::CatchEnd2 => self.execute_catch_end2(),
+            Opcode::FinallyStart => self.execute_finally_start(),
+            Opcode::FinallyEnd => self.execute_finally_end(),
+            Opcode::FinallySetJump => self.execute_finally_set_jump(),
+            Opcode::This => self.execute_this(),
+            Opcode::Case => self.execute_case(),
+            Opcode::Default => self.execute_default(),
+            Opcode::GetFunction => self.execute_get_function(),
+            Opcode::GetGenerator => self.execute_get_generator(),
+            Opcode::Call => self.execute_call(),
+            Opcode::CallWithRest => self.execute_call_with_rest(),
+            Opcode::New => self.execute_new(),
+            Opcode::NewWithRest => self.execute_new_with_rest(),
+            Opcode::Return => self.execute_return(),
+            Opcode::PushDeclarativeEnvironment => self.execute_push_declarative_environment(),
+            Opcode::PushFunctionEnvironment => self.execute_push_function_environment(),
+            Opcode::PopEnvironment => self.execute_pop_environment(),
+            Opcode::LoopStart => self.execute_loop_start(),
+            Opcode::LoopContinue => self.execute_loop_continue(),
+            Opcode::LoopEnd => self.execute_loop_end(),
+            Opcode::ForInLoopInitIterator => self.execute_for_in_loop_init_iterator(),
+            Opcode::InitIterator => self.execute_init_iterator(),
+            Opcode::IteratorNext => self.execute_iterator_next(),
+            Opcode::IteratorNextFull => self.execute_iterator_next_full(),
+            Opcode::IteratorClose => self.execute_iterator_close(),
+            Opcode::IteratorToArray => self.execute_iterator_to_array(),
+            Opcode::ForInLoopNext => self.execute_for_in_loop_next(),
+            Opcode::ConcatToString => self.execute_concat_to_string(),
+            Opcode::RequireObjectCoercible => self.execute_require_object_coercible(),
+            Opcode::ValueNotNullOrUndefined => self.execute_value_not_null_or_undefined(),
+            Opcode::RestParameterInit => self.execute_rest_parameter_init(),
+            Opcode::RestParameterPop => self.execute_rest_parameter_pop(),
+            Opcode::PopOnReturnAdd => self.execute_pop_on_return_add(),
+            Opcode::PopOnReturnSub => self.execute_pop_on_return_sub(),
+            Opcode::Yield => self.execute_yield(),
+            Opcode::GeneratorNext => self.execute_generator_next(),
+            Opcode::GeneratorNextDelegate => self.execute_generator_next_delegate(),
+        }
+    }
 
-                while let Some(result) = iterator_record.step(self)? {
-                    values.push(result.value(self)?);
-                }
+    /// Marks the execution as terminating, returning the uncatchable error that unwinds it.
+    pub(crate) fn terminate(&mut self, reason: TerminationReason) -> JsValue {
+        self.vm.termination = Some(reason);
+        self.construct_range_error(reason.to_string())
+    }
 
-                let array = Array::create_array_from_list(values, self);
+    pub(crate) fn run(&mut self) -> JsResult<(JsValue, ReturnType)> {
+        let _timer = Profiler::global().start_event("run", "vm");
 
-                self.vm.push(iterator);
-                self.vm.push(next_function);
-                self.vm.push(array);
-            }
-            Opcode::ForInLoopNext => {
-                let address = self.vm.read::<u32>();
+        // A frame without a predecessor was pushed by the host, which starts a new execution.
+        let execution_start = self.vm.frame().prev.is_none();
+        let _heap_limit = execution_start.then(|| HeapLimitScope::enter(self.heap_limit()));
+        if execution_start {
+            self.vm.termination = None;
+            self.vm.instruction_count = 0;
+            self.vm.loop_iteration_count = 0;
+            self.debugger_start();
+            self.vm.cpu_profiler.resume();
+        } else if let Some(reason) = self.vm.termination {
+            // A native function called back into JavaScript while a terminated execution unwinds.
+            return Err(self.terminate(reason));
+        } else if self.vm.call_depth > self.vm.runtime_limits.call_depth_limit() {
+            return Err(self.terminate(TerminationReason::CallDepthLimit));
+        }
+        if self.vm.interrupt.take() {
+            return Err(self.terminate(TerminationReason::Interrupted));
+        }
+        #[cfg(feature = "jit")]
+        self.vm
+            .frame()
+            .code
+            .hotness
+            .record_invocation(self.vm.jit_threshold);
 
-                let next_function = self.vm.pop();
-                let iterator = self.vm.pop();
+        let traced = self.vm.tracer.is_attached() && self.trace_frame_enter();
 
-                let iterator_record = IteratorRecord::new(iterator.clone(), next_function.clone());
-                if let Some(next) = iterator_record.step(self)? {
-                    self.vm.push(iterator);
-                    self.vm.push(next_function);
-                    let value = next.value(self)?;
-                    self.vm.push(value);
-                } else {
-                    self.vm.frame_mut().pc = address as usize;
-                    self.vm.frame_mut().loop_env_stack_dec();
-                    self.vm.frame_mut().try_env_stack_dec();
-                    self.realm.environments.pop();
-                    self.vm.push(iterator);
-                    self.vm.push(next_function);
-                }
-            }
-            Opcode::ConcatToString => {
-                let value_count = self.vm.read::<u32>();
-                let mut strings = Vec::with_capacity(value_count as usize);
-                for _ in 0..value_count {
-                    strings.push(self.vm.pop().to_string(self)?);
-                }
-                strings.reverse();
-                let s = JsString::concat_array(
-                    &strings.iter().map(JsString::as_str).collect::<Vec<&str>>(),
-                );
-                self.vm.push(s);
-            }
-            Opcode::RequireObjectCoercible => {
-                let value = self.vm.pop();
-                let value = value.require_object_coercible(self)?;
-                self.vm.push(value);
-            }
-            Opcode::ValueNotNullOrUndefined => {
-                let value = self.vm.pop();
-                if value.is_null() {
-                    return self.throw_type_error("Cannot destructure 'null' value");
-                }
-                if value.is_undefined() {
-                    return self.throw_type_error("Cannot destructure 'undefined' value");
-                }
-                self.vm.push(value);
-            }
-            Opcode::RestParameterInit => {
-                let arg_count = self.vm.frame().arg_count;
-                let param_count = self.vm.frame().param_count;
-                if arg_count >= param_count {
-                    let rest_count = arg_count - param_count + 1;
-                    let mut args = Vec::with_capacity(rest_count);
-                    for _ in 0..rest_count {
-                        args.push(self.vm.pop());
-                    }
-                    let array: _ = Array::create_array_from_list(args, self);
+        let start_stack_size = self.vm.stack.len();
 
-                    self.vm.push(array);
-                } else {
-                    self.vm.pop();
+        if self.vm.debugger.is_attached() {
+            self.debugger_call();
+        }
+        if self.vm.coverage.is_enabled() {
+            self.coverage_call();
+        }
 
-                    let array = Array::array_create(0, None, self)
-                        .expect("could not create an empty array");
-                    self.vm.push(array);
-                }
-            }
-            Opcode::RestParameterPop => {
-                let arg_count = self.vm.frame().arg_count;
-                let param_count = self.vm.frame().param_count;
-                if arg_count > param_count {
-                    for _ in 0..(arg_count - param_count) {
-                        self.vm.pop();
-                    }
+        while self.vm.frame().pc < self.vm.frame().code.code.len() {
+            if self.vm.debugger.is_attached() {
+                let frame = self.vm.frame();
+                if let Some(line) = frame.code.statement_line(frame.pc) {
+                    self.debugger_statement(line);
                 }
             }
-            Opcode::PopOnReturnAdd => {
-                self.vm.frame_mut().pop_on_return += 1;
-            }
-            Opcode::PopOnReturnSub => {
-                self.vm.frame_mut().pop_on_return -= 1;
+            if self.vm.cpu_profiler.is_running() {
+                self.cpu_profiler_sample();
             }
-            Opcode::Yield => return Ok(ShouldExit::Yield),
-            Opcode::GeneratorNext => match self.vm.frame().generator_resume_kind {
-                GeneratorResumeKind::Normal => return Ok(ShouldExit::False),
-                GeneratorResumeKind::Throw => {
-                    let received = self.vm.pop();
-                    return Err(received);
-                }
-                GeneratorResumeKind::Return => {
-                    let mut finally_left = false;
-
-                    while let Some(catch_addresses) = self.vm.frame().catch.last() {
-                        if let Some(finally_address) = catch_addresses.finally {
-                            let frame = self.vm.frame_mut();
-                            frame.pc = finally_address as usize;
-                            frame.finally_return = FinallyReturn::Ok;
-                            frame.catch.pop();
-                            finally_left = true;
-                            break;
-                        }
-                        self.vm.frame_mut().catch.pop();
-                    }
-
-                    if finally_left {
-                        return Ok(ShouldExit::False);
-                    }
-                    return Ok(ShouldExit::True);
-                }
-            },
-            Opcode::GeneratorNextDelegate => {
-                let done_address = self.vm.read::<u32>();
-                let received = self.vm.pop();
-                let next_function = self.vm.pop();
-                let iterator = self.vm.pop();
-
-                match self.vm.frame().generator_resume_kind {
-                    GeneratorResumeKind::Normal => {
-                        let result = self.call(&next_function, &iterator, &[received])?;
-                        let result_object = result.as_object().ok_or_else(|| {
-                            self.construct_type_error("generator next method returned non-object")
-                        })?;
-                        let done = result_object.get("done", self)?.to_boolean();
-                        if done {
-                            self.vm.frame_mut().pc = done_address as usize;
-                            let value = result_object.get("value", self)?;
-                            self.vm.push(value);
-                            return Ok(ShouldExit::False);
-                        }
-                        let value = result_object.get("value", self)?;
-                        self.vm.push(iterator);
-                        self.vm.push(next_function);
-                        self.vm.push(value);
-                        return Ok(ShouldExit::Yield);
-                    }
-                    GeneratorResumeKind::Throw => {
-                        let throw = iterator.get_method("throw", self)?;
-                        if let Some(throw) = throw {
-                            let result = throw.call(&iterator, &[received], self)?;
-                            let result_object = result.as_object().ok_or_else(|| {
-                                self.construct_type_error(
-                                    "generator throw method returned non-object",
-                                )
-                            })?;
-                            let done = result_object.get("done", self)?.to_boolean();
-                            if done {
-                                self.vm.frame_mut().pc = done_address as usize;
-                                let value = result_object.get("value", self)?;
-                                self.vm.push(value);
-                                return Ok(ShouldExit::False);
-                            }
-                            let value = result_object.get("value", self)?;
-                            self.vm.push(iterator);
-                            self.vm.push(next_function);
-                            self.vm.push(value);
-                            return Ok(ShouldExit::Yield);
-                        }
-                        self.vm.frame_mut().pc = done_address as usize;
-                        let iterator_record =
-                            IteratorRecord::new(iterator.clone(), next_function.clone());
-                        iterator_record.close(Ok(JsValue::Undefined), self)?;
-                        let error =
-                            self.construct_type_error("iterator does not have a throw method");
-                        return Err(error);
-                    }
-                    GeneratorResumeKind::Return => {
-                        let r#return = iterator.get_method("return", self)?;
-                        if let Some(r#return) = r#return {
-                            let result = r#return.call(&iterator, &[received], self)?;
-                            let result_object = result.as_object().ok_or_else(|| {
-                                self.construct_type_error(
-                                    "generator return method returned non-object",
-                                )
-                            })?;
-                            let done = result_object.get("done", self)?.to_boolean();
-                            if done {
-                                self.vm.frame_mut().pc = done_address as usize;
-                                let value = result_object.get("value", self)?;
-                                self.vm.push(value);
-                                return Ok(ShouldExit::True);
-                            }
-                            let value = result_object.get("value", self)?;
-                            self.vm.push(iterator);
-                            self.vm.push(next_function);
-                            self.vm.push(value);
-                            return Ok(ShouldExit::Yield);
-                        }
-                        self.vm.frame_mut().pc = done_address as usize;
-                        self.vm.push(received);
-                        return Ok(ShouldExit::True);
-                    }
-                }
-            }
-        }
-
-        Ok(ShouldExit::False)
-    }
-
-    pub(crate) fn run(&mut self) -> JsResult<(JsValue, ReturnType)> {
-        const COLUMN_WIDTH: usize = 26;
-        const TIME_COLUMN_WIDTH: usize = COLUMN_WIDTH / 2;
-        const OPCODE_COLUMN_WIDTH: usize = COLUMN_WIDTH;
-        const OPERAND_COLUMN_WIDTH: usize = COLUMN_WIDTH;
-        const NUMBER_OF_COLUMNS: usize = 4;
-
-        let _timer = Profiler::global().start_event("run", "vm");
-
-        if self.vm.trace {
-            let msg = if self.vm.frame().prev.is_some() {
-                " Call Frame "
+            let branch = if self.vm.coverage.is_enabled() {
+                self.coverage_instruction()
             } else {
-                " VM Start "
+                None
             };
 
-            println!(
-                "{}\n",
-                self.vm.frame().code.to_interned_string(self.interner())
-            );
-            println!(
-                "{msg:-^width$}",
-                width = COLUMN_WIDTH * NUMBER_OF_COLUMNS - 10
-            );
-            println!(
-                "{:<TIME_COLUMN_WIDTH$} {:<OPCODE_COLUMN_WIDTH$} {:<OPERAND_COLUMN_WIDTH$} Top Of Stack\n",
-                "Time",
-                "Opcode",
-                "Operands",
-            );
-        }
-
-        let start_stack_size = self.vm.stack.len();
+            let stats = if self.vm.stats.is_enabled() {
+                Some(self.vm_stats_instruction())
+            } else {
+                None
+            };
 
-        while self.vm.frame().pc < self.vm.frame().code.code.len() {
-            let result = if self.vm.trace {
-                let mut pc = self.vm.frame().pc;
-                let opcode: Opcode = self
-                    .vm
-                    .frame()
-                    .code
-                    .read::<u8>(pc)
-                    .try_into()
-                    .expect("invalid opcode");
+            self.vm.instruction_count += 1;
+            let result = if self.vm.instruction_count > self.vm.runtime_limits.instruction_limit() {
+                Err(self.terminate(TerminationReason::InstructionLimit))
+            } else if traced {
+                let pc = self.vm.frame().pc;
+                let mut next = pc;
                 let operands = self
                     .vm
                     .frame()
                     .code
-                    .instruction_operands(&mut pc, self.interner());
+                    .instruction_operands(&mut next, self.interner());
 
-                let instant = Instant::now();
+                let start = self.vm.tracer.wants_timing().then(Instant::now);
                 let result = self.execute_instruction();
-                let duration = instant.elapsed();
-
-                println!(
-                    "{:<TIME_COLUMN_WIDTH$} {:<OPCODE_COLUMN_WIDTH$} {operands:<OPERAND_COLUMN_WIDTH$} {}",
-                    format!("{}μs", duration.as_micros()),
-                    opcode.as_str(),
-                    match self.vm.stack.last() {
-                        None => "<empty>".to_string(),
-                        Some(value) => {
-                            if value.is_callable() {
-                                "[function]".to_string()
-                            } else if value.is_object() {
-                                "[object]".to_string()
-                            } else {
-                                value.display().to_string()
-                            }
-                        }
-                    },
-                );
+                let duration = start.map(|start| start.elapsed());
 
+                self.trace_instruction(pc, &operands, duration);
                 result
             } else {
                 self.execute_instruction()
             };
+            if let Some((opcode, start)) = stats {
+                self.vm.stats.record_time(opcode, start);
+            }
+
+            let result = match result {
+                Ok(_) if self.heap_limit().is_some() && boa_gc::take_heap_limit_exceeded() => {
+                    Err(self.heap_limit_exceeded())
+                }
+                result => result,
+            };
 
             match result {
                 Ok(ShouldExit::True) => {
+                    if traced {
+                        self.trace_frame_exit();
+                    }
                     let result = self.vm.pop();
                     self.vm.stack.truncate(start_stack_size);
+                    if self.vm.debugger.is_attached() {
+                        self.debugger_return(&result);
+                    }
                     return Ok((result, ReturnType::Normal));
                 }
-                Ok(ShouldExit::False) => {}
+                Ok(ShouldExit::False) => {
+                    if let Some(pc) = branch {
+                        self.coverage_branch(pc);
+                    }
+                }
                 Ok(ShouldExit::Yield) => {
+                    if traced {
+                        self.trace_frame_exit();
+                    }
                     let result = self.vm.stack.pop().unwrap_or(JsValue::Undefined);
+                    if self.vm.debugger.is_attached() {
+                        self.debugger_return(&result);
+                    }
                     return Ok((result, ReturnType::Yield));
                 }
                 Err(e) => {
-                    if let Some(address) = self.vm.frame().catch.last() {
-                        let address = address.next;
+                    let catch_address = if self.vm.termination.is_some() {
+                        None
+                    } else {
+                        if self.vm.debugger.is_attached() {
+                            self.debugger_exception(&e);
+                        }
+                        self.vm.frame().catch.last().map(|address| address.next)
+                    };
+                    if let Some(address) = catch_address {
+                        self.debugger_exception_caught();
                         let try_stack_entry = self
                             .vm
                             .frame_mut()
@@ -1809,6 +487,9 @@ impl Context {
                         self.vm.frame_mut().finally_return = FinallyReturn::Err;
                         self.vm.push(e);
                     } else {
+                        if traced {
+                            self.trace_frame_exit();
+                        }
                         self.vm.stack.truncate(start_stack_size);
                         return Err(e);
                     }
@@ -1816,35 +497,20 @@ impl Context {
             }
         }
 
-        if self.vm.trace {
-            println!("\nStack:");
-            if self.vm.stack.is_empty() {
-                println!("    <empty>");
-            } else {
-                for (i, value) in self.vm.stack.iter().enumerate() {
-                    println!(
-                        "{i:04}{:<width$} {}",
-                        "",
-                        if value.is_callable() {
-                            "[function]".to_string()
-                        } else if value.is_object() {
-                            "[object]".to_string()
-                        } else {
-                            value.display().to_string()
-                        },
-                        width = COLUMN_WIDTH / 2 - 4,
-                    );
-                }
-            }
-            println!("\n");
+        if traced {
+            self.trace_frame_exit();
         }
 
-        if self.vm.stack.is_empty() {
-            return Ok((JsValue::undefined(), ReturnType::Normal));
+        let result = if self.vm.stack.is_empty() {
+            JsValue::undefined()
+        } else {
+            let result = self.vm.pop();
+            self.vm.stack.truncate(start_stack_size);
+            result
+        };
+        if self.vm.debugger.is_attached() {
+            self.debugger_return(&result);
         }
-
-        let result = self.vm.pop();
-        self.vm.stack.truncate(start_stack_size);
         Ok((result, ReturnType::Normal))
     }
 }
@@ -131,11 +131,12 @@ pub enum Opcode {
     /// Stack: **=>** `{}`
     PushEmptyObject,
 
-    /// Get the prototype of a superclass and push it on the stack.
+    /// Set the superclass as the prototype of a class, and push a new object inheriting from the
+    /// prototype of the superclass, which becomes the prototype of the class.
     ///
     /// Operands:
     ///
-    /// Stack: superclass **=>** superclass.prototype
+    /// Stack: class, superclass **=>** class, prototype
     PushClassPrototype,
 
     /// Push an empty array value on the stack.
@@ -1187,6 +1188,151 @@ impl Opcode {
         }
     }
 
+    /// Returns the category of this opcode.
+    pub fn category(self) -> OpcodeCategory {
+        match self {
+            Opcode::Pop
+            | Opcode::Dup
+            | Opcode::Swap
+            | Opcode::PushZero
+            | Opcode::PushOne
+            | Opcode::PushInt8
+            | Opcode::PushInt16
+            | Opcode::PushInt32
+            | Opcode::PushRational
+            | Opcode::PushNaN
+            | Opcode::PushPositiveInfinity
+            | Opcode::PushNegativeInfinity
+            | Opcode::PushNull
+            | Opcode::PushTrue
+            | Opcode::PushFalse
+            | Opcode::PushUndefined
+            | Opcode::PushLiteral
+            | Opcode::PushEmptyObject
+            | Opcode::PushClassPrototype
+            | Opcode::PushNewArray
+            | Opcode::PushValueToArray
+            | Opcode::PushElisionToArray
+            | Opcode::PushIteratorToArray
+            | Opcode::PopOnReturnAdd
+            | Opcode::PopOnReturnSub
+            | Opcode::Nop => OpcodeCategory::Stack,
+            Opcode::Add
+            | Opcode::Sub
+            | Opcode::Div
+            | Opcode::Mul
+            | Opcode::Mod
+            | Opcode::Pow
+            | Opcode::ShiftRight
+            | Opcode::ShiftLeft
+            | Opcode::UnsignedShiftRight
+            | Opcode::BitOr
+            | Opcode::BitAnd
+            | Opcode::BitXor
+            | Opcode::BitNot
+            | Opcode::In
+            | Opcode::Eq
+            | Opcode::StrictEq
+            | Opcode::NotEq
+            | Opcode::StrictNotEq
+            | Opcode::GreaterThan
+            | Opcode::GreaterThanOrEq
+            | Opcode::LessThan
+            | Opcode::LessThanOrEq
+            | Opcode::InstanceOf
+            | Opcode::TypeOf
+            | Opcode::Void
+            | Opcode::LogicalNot
+            | Opcode::Pos
+            | Opcode::Neg
+            | Opcode::Inc
+            | Opcode::IncPost
+            | Opcode::Dec
+            | Opcode::DecPost
+            | Opcode::ToBoolean
+            | Opcode::ToPropertyKey
+            | Opcode::ConcatToString
+            | Opcode::RequireObjectCoercible
+            | Opcode::ValueNotNullOrUndefined => OpcodeCategory::Operator,
+            Opcode::DefInitArg
+            | Opcode::DefVar
+            | Opcode::DefInitVar
+            | Opcode::DefLet
+            | Opcode::DefInitLet
+            | Opcode::DefInitConst
+            | Opcode::GetName
+            | Opcode::GetNameOrUndefined
+            | Opcode::SetName
+            | Opcode::This => OpcodeCategory::Binding,
+            Opcode::GetPropertyByName
+            | Opcode::GetPropertyByValue
+            | Opcode::SetPropertyByName
+            | Opcode::DefineOwnPropertyByName
+            | Opcode::DefineClassMethodByName
+            | Opcode::SetPropertyByValue
+            | Opcode::DefineOwnPropertyByValue
+            | Opcode::DefineClassMethodByValue
+            | Opcode::SetPropertyGetterByName
+            | Opcode::DefineClassGetterByName
+            | Opcode::SetPropertyGetterByValue
+            | Opcode::DefineClassGetterByValue
+            | Opcode::SetPropertySetterByName
+            | Opcode::DefineClassSetterByName
+            | Opcode::SetPropertySetterByValue
+            | Opcode::DefineClassSetterByValue
+            | Opcode::SetPrivateValue
+            | Opcode::SetPrivateSetter
+            | Opcode::SetPrivateGetter
+            | Opcode::GetPrivateField
+            | Opcode::PushClassComputedFieldName
+            | Opcode::DeletePropertyByName
+            | Opcode::DeletePropertyByValue
+            | Opcode::CopyDataProperties => OpcodeCategory::Property,
+            Opcode::LogicalAnd
+            | Opcode::LogicalOr
+            | Opcode::Coalesce
+            | Opcode::Jump
+            | Opcode::JumpIfFalse
+            | Opcode::JumpIfNotUndefined
+            | Opcode::Throw
+            | Opcode::TryStart
+            | Opcode::TryEnd
+            | Opcode::CatchStart
+            | Opcode::CatchEnd
+            | Opcode::CatchEnd2
+            | Opcode::FinallyStart
+            | Opcode::FinallyEnd
+            | Opcode::FinallySetJump
+            | Opcode::Case
+            | Opcode::Default
+            | Opcode::LoopStart
+            | Opcode::LoopContinue
+            | Opcode::LoopEnd => OpcodeCategory::ControlFlow,
+            Opcode::GetFunction
+            | Opcode::GetGenerator
+            | Opcode::Call
+            | Opcode::CallWithRest
+            | Opcode::New
+            | Opcode::NewWithRest
+            | Opcode::Return
+            | Opcode::RestParameterInit
+            | Opcode::RestParameterPop
+            | Opcode::Yield
+            | Opcode::GeneratorNext
+            | Opcode::GeneratorNextDelegate => OpcodeCategory::Call,
+            Opcode::PushDeclarativeEnvironment
+            | Opcode::PushFunctionEnvironment
+            | Opcode::PopEnvironment => OpcodeCategory::Environment,
+            Opcode::ForInLoopInitIterator
+            | Opcode::InitIterator
+            | Opcode::IteratorNext
+            | Opcode::IteratorNextFull
+            | Opcode::IteratorClose
+            | Opcode::IteratorToArray
+            | Opcode::ForInLoopNext => OpcodeCategory::Iteration,
+        }
+    }
+
     /// Name of the profiler event for this opcode
     pub fn as_instruction_str(self) -> &'static str {
         match self {
@@ -1333,6 +1479,55 @@ impl Opcode {
     }
 }
 
+/// The kinds of opcodes, returned by [`Opcode::category`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OpcodeCategory {
+    /// Pushing literals and manipulating the stack.
+    Stack,
+    /// Operators and conversions.
+    Operator,
+    /// Declaring and accessing bindings.
+    Binding,
+    /// Accessing and defining properties.
+    Property,
+    /// Jumps, exceptions and loops.
+    ControlFlow,
+    /// Creating, calling and returning from functions and generators.
+    Call,
+    /// Pushing and popping environments.
+    Environment,
+    /// Iterating over iterators and `for...in` loops.
+    Iteration,
+}
+
+impl OpcodeCategory {
+    /// All the categories.
+    pub const ALL: [Self; 8] = [
+        Self::Stack,
+        Self::Operator,
+        Self::Binding,
+        Self::Property,
+        Self::ControlFlow,
+        Self::Call,
+        Self::Environment,
+        Self::Iteration,
+    ];
+
+    /// Returns the name of the category.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Stack => "Stack",
+            Self::Operator => "Operator",
+            Self::Binding => "Binding",
+            Self::Property => "Property",
+            Self::ControlFlow => "ControlFlow",
+            Self::Call => "Call",
+            Self::Environment => "Environment",
+            Self::Iteration => "Iteration",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct InvalidOpcodeError {
     value: u8,
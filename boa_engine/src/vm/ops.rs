@@ -0,0 +1,1889 @@
+//! The implementation of each opcode, as one function per opcode.
+//!
+//! `Context::execute_instruction` decodes the next opcode and dispatches to these functions.
+//! Keeping them separate lets them be called on their own, one opcode at a time.
+
+use crate::{
+    builtins::{function::Function, iterable::IteratorRecord, Array, ForInIterator, Number},
+    object::{JsObject, ObjectData, PrivateElement},
+    property::{DescriptorKind, PropertyDescriptor, PropertyKey},
+    value::Numeric,
+    vm::{
+        call_frame::CatchAddresses,
+        code_block::{create_function_object, create_generator_function_object},
+        FinallyReturn, GeneratorResumeKind, ShouldExit, TerminationReason, TryStackEntry,
+    },
+    Context, JsBigInt, JsResult, JsString, JsValue,
+};
+use std::ops::Neg;
+
+impl Context {
+    /// Pops the operands of a binary operator and pushes its result.
+    fn execute_binary_operation<T>(
+        &mut self,
+        operation: fn(&JsValue, &JsValue, &mut Self) -> JsResult<T>,
+    ) -> JsResult<ShouldExit>
+    where
+        T: Into<JsValue>,
+    {
+        let rhs = self.vm.pop();
+        let lhs = self.vm.pop();
+        let value = operation(&lhs, &rhs, self)?;
+        self.vm.push(value);
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_nop(&mut self) -> JsResult<ShouldExit> {
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_pop(&mut self) -> JsResult<ShouldExit> {
+        let _val = self.vm.pop();
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_dup(&mut self) -> JsResult<ShouldExit> {
+        let value = self.vm.pop();
+        self.vm.push(value.clone());
+        self.vm.push(value);
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_swap(&mut self) -> JsResult<ShouldExit> {
+        let first = self.vm.pop();
+        let second = self.vm.pop();
+
+        self.vm.push(first);
+        self.vm.push(second);
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_push_undefined(&mut self) -> JsResult<ShouldExit> {
+        self.vm.push(JsValue::undefined());
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_push_null(&mut self) -> JsResult<ShouldExit> {
+        self.vm.push(JsValue::null());
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_push_true(&mut self) -> JsResult<ShouldExit> {
+        self.vm.push(true);
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_push_false(&mut self) -> JsResult<ShouldExit> {
+        self.vm.push(false);
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_push_zero(&mut self) -> JsResult<ShouldExit> {
+        self.vm.push(0);
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_push_one(&mut self) -> JsResult<ShouldExit> {
+        self.vm.push(1);
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_push_int8(&mut self) -> JsResult<ShouldExit> {
+        let value = self.vm.read::<i8>();
+        self.vm.push(i32::from(value));
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_push_int16(&mut self) -> JsResult<ShouldExit> {
+        let value = self.vm.read::<i16>();
+        self.vm.push(i32::from(value));
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_push_int32(&mut self) -> JsResult<ShouldExit> {
+        let value = self.vm.read::<i32>();
+        self.vm.push(value);
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_push_rational(&mut self) -> JsResult<ShouldExit> {
+        let value = self.vm.read::<f64>();
+        self.vm.push(value);
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_push_nan(&mut self) -> JsResult<ShouldExit> {
+        self.vm.push(JsValue::nan());
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_push_positive_infinity(&mut self) -> JsResult<ShouldExit> {
+        self.vm.push(JsValue::positive_infinity());
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_push_negative_infinity(&mut self) -> JsResult<ShouldExit> {
+        self.vm.push(JsValue::negative_infinity());
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_push_literal(&mut self) -> JsResult<ShouldExit> {
+        let index = self.vm.read::<u32>() as usize;
+        let value = self.vm.frame().code.literals[index].clone();
+        self.vm.push(value);
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_push_empty_object(&mut self) -> JsResult<ShouldExit> {
+        self.vm.push(self.construct_object());
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_push_class_prototype(&mut self) -> JsResult<ShouldExit> {
+        let superclass = self.vm.pop();
+        let class = self.vm.pop();
+
+        let prototype_parent = if superclass.is_null() {
+            None
+        } else if let Some(superclass) = superclass.as_constructor() {
+            let prototype_parent = superclass.get("prototype", self)?;
+            let prototype_parent = match prototype_parent {
+                JsValue::Object(ref prototype) => Some(prototype.clone()),
+                JsValue::Null => None,
+                _ => {
+                    return self.throw_type_error("superclass prototype must be an object or null")
+                }
+            };
+            let class = class.as_object().expect("class must be an object");
+            class.set_prototype(Some(superclass.clone()));
+            prototype_parent
+        } else {
+            return self.throw_type_error("superclass must be a constructor");
+        };
+
+        let prototype = JsObject::from_proto_and_data(prototype_parent, ObjectData::ordinary());
+        self.vm.push(class);
+        self.vm.push(prototype);
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_push_new_array(&mut self) -> JsResult<ShouldExit> {
+        let array = Array::array_create(0, None, self)
+            .expect("Array creation with 0 length should never fail");
+        self.vm.push(array);
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_push_value_to_array(&mut self) -> JsResult<ShouldExit> {
+        let value = self.vm.pop();
+        let array = self.vm.pop();
+        let o = array.as_object().expect("should be an object");
+        let len = o
+            .length_of_array_like(self)
+            .expect("should have 'length' property");
+        o.create_data_property_or_throw(len, value, self)
+            .expect("should be able to create new data property");
+        self.vm.push(array);
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_push_elision_to_array(&mut self) -> JsResult<ShouldExit> {
+        let array = self.vm.pop();
+        let o = array.as_object().expect("should always be an object");
+
+        let len = o
+            .length_of_array_like(self)
+            .expect("arrays should always have a 'length' property");
+
+        o.set("length", len + 1, true, self)?;
+        self.vm.push(array);
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_push_iterator_to_array(&mut self) -> JsResult<ShouldExit> {
+        let next_function = self.vm.pop();
+        let iterator = self.vm.pop();
+        let array = self.vm.pop();
+
+        let iterator = IteratorRecord::new(iterator, next_function);
+        while let Some(next) = iterator.step(self)? {
+            Array::push(&array, &[next.value(self)?], self)?;
+        }
+
+        self.vm.push(array);
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_add(&mut self) -> JsResult<ShouldExit> {
+        self.execute_binary_operation(JsValue::add)
+    }
+
+    pub(super) fn execute_sub(&mut self) -> JsResult<ShouldExit> {
+        self.execute_binary_operation(JsValue::sub)
+    }
+
+    pub(super) fn execute_mul(&mut self) -> JsResult<ShouldExit> {
+        self.execute_binary_operation(JsValue::mul)
+    }
+
+    pub(super) fn execute_div(&mut self) -> JsResult<ShouldExit> {
+        self.execute_binary_operation(JsValue::div)
+    }
+
+    pub(super) fn execute_pow(&mut self) -> JsResult<ShouldExit> {
+        self.execute_binary_operation(JsValue::pow)
+    }
+
+    pub(super) fn execute_mod(&mut self) -> JsResult<ShouldExit> {
+        self.execute_binary_operation(JsValue::rem)
+    }
+
+    pub(super) fn execute_bit_and(&mut self) -> JsResult<ShouldExit> {
+        self.execute_binary_operation(JsValue::bitand)
+    }
+
+    pub(super) fn execute_bit_or(&mut self) -> JsResult<ShouldExit> {
+        self.execute_binary_operation(JsValue::bitor)
+    }
+
+    pub(super) fn execute_bit_xor(&mut self) -> JsResult<ShouldExit> {
+        self.execute_binary_operation(JsValue::bitxor)
+    }
+
+    pub(super) fn execute_shift_left(&mut self) -> JsResult<ShouldExit> {
+        self.execute_binary_operation(JsValue::shl)
+    }
+
+    pub(super) fn execute_shift_right(&mut self) -> JsResult<ShouldExit> {
+        self.execute_binary_operation(JsValue::shr)
+    }
+
+    pub(super) fn execute_unsigned_shift_right(&mut self) -> JsResult<ShouldExit> {
+        self.execute_binary_operation(JsValue::ushr)
+    }
+
+    pub(super) fn execute_eq(&mut self) -> JsResult<ShouldExit> {
+        let rhs = self.vm.pop();
+        let lhs = self.vm.pop();
+        let value = lhs.equals(&rhs, self)?;
+        self.vm.push(value);
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_not_eq(&mut self) -> JsResult<ShouldExit> {
+        let rhs = self.vm.pop();
+        let lhs = self.vm.pop();
+        let value = !lhs.equals(&rhs, self)?;
+        self.vm.push(value);
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_strict_eq(&mut self) -> JsResult<ShouldExit> {
+        let rhs = self.vm.pop();
+        let lhs = self.vm.pop();
+        self.vm.push(lhs.strict_equals(&rhs));
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_strict_not_eq(&mut self) -> JsResult<ShouldExit> {
+        let rhs = self.vm.pop();
+        let lhs = self.vm.pop();
+        self.vm.push(!lhs.strict_equals(&rhs));
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_greater_than(&mut self) -> JsResult<ShouldExit> {
+        self.execute_binary_operation(JsValue::gt)
+    }
+
+    pub(super) fn execute_greater_than_or_eq(&mut self) -> JsResult<ShouldExit> {
+        self.execute_binary_operation(JsValue::ge)
+    }
+
+    pub(super) fn execute_less_than(&mut self) -> JsResult<ShouldExit> {
+        self.execute_binary_operation(JsValue::lt)
+    }
+
+    pub(super) fn execute_less_than_or_eq(&mut self) -> JsResult<ShouldExit> {
+        self.execute_binary_operation(JsValue::le)
+    }
+
+    pub(super) fn execute_in(&mut self) -> JsResult<ShouldExit> {
+        let rhs = self.vm.pop();
+        let lhs = self.vm.pop();
+
+        if !rhs.is_object() {
+            return self.throw_type_error(format!(
+                "right-hand side of 'in' should be an object, got {}",
+                rhs.type_of()
+            ));
+        }
+        let key = lhs.to_property_key(self)?;
+        let value = self.has_property(&rhs, &key)?;
+        self.vm.push(value);
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_instance_of(&mut self) -> JsResult<ShouldExit> {
+        let target = self.vm.pop();
+        let v = self.vm.pop();
+        let value = v.instance_of(&target, self)?;
+
+        self.vm.push(value);
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_void(&mut self) -> JsResult<ShouldExit> {
+        let _old = self.vm.pop();
+        self.vm.push(JsValue::undefined());
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_type_of(&mut self) -> JsResult<ShouldExit> {
+        let value = self.vm.pop();
+        self.vm.push(value.type_of());
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_pos(&mut self) -> JsResult<ShouldExit> {
+        let value = self.vm.pop();
+        let value = value.to_number(self)?;
+        self.vm.push(value);
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_neg(&mut self) -> JsResult<ShouldExit> {
+        let value = self.vm.pop();
+        match value.to_numeric(self)? {
+            Numeric::Number(number) => self.vm.push(number.neg()),
+            Numeric::BigInt(bigint) => self.vm.push(JsBigInt::neg(&bigint)),
+        }
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_inc(&mut self) -> JsResult<ShouldExit> {
+        let value = self.vm.pop();
+        match value.to_numeric(self)? {
+            Numeric::Number(number) => self.vm.push(number + 1f64),
+            Numeric::BigInt(bigint) => {
+                self.vm.push(JsBigInt::add(&bigint, &JsBigInt::one()));
+            }
+        }
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_inc_post(&mut self) -> JsResult<ShouldExit> {
+        let value = self.vm.pop();
+        let value = value.to_numeric(self)?;
+        self.vm.push(value.clone());
+        match value {
+            Numeric::Number(number) => self.vm.push(number + 1f64),
+            Numeric::BigInt(bigint) => {
+                self.vm.push(JsBigInt::add(&bigint, &JsBigInt::one()));
+            }
+        }
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_dec(&mut self) -> JsResult<ShouldExit> {
+        let value = self.vm.pop();
+        match value.to_numeric(self)? {
+            Numeric::Number(number) => self.vm.push(number - 1f64),
+            Numeric::BigInt(bigint) => {
+                self.vm.push(JsBigInt::sub(&bigint, &JsBigInt::one()));
+            }
+        }
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_dec_post(&mut self) -> JsResult<ShouldExit> {
+        let value = self.vm.pop();
+        let value = value.to_numeric(self)?;
+        self.vm.push(value.clone());
+        match value {
+            Numeric::Number(number) => self.vm.push(number - 1f64),
+            Numeric::BigInt(bigint) => {
+                self.vm.push(JsBigInt::sub(&bigint, &JsBigInt::one()));
+            }
+        }
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_logical_not(&mut self) -> JsResult<ShouldExit> {
+        let value = self.vm.pop();
+        self.vm.push(!value.to_boolean());
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_bit_not(&mut self) -> JsResult<ShouldExit> {
+        let value = self.vm.pop();
+        match value.to_numeric(self)? {
+            Numeric::Number(number) => self.vm.push(Number::not(number)),
+            Numeric::BigInt(bigint) => self.vm.push(JsBigInt::not(&bigint)),
+        }
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_def_var(&mut self) -> JsResult<ShouldExit> {
+        let index = self.vm.read::<u32>();
+        let binding_locator = self.vm.frame().code.bindings[index as usize];
+
+        if binding_locator.is_global() {
+            let key = self
+                .interner()
+                .resolve_expect(binding_locator.name())
+                .into();
+            self.global_bindings_mut().entry(key).or_insert(
+                PropertyDescriptor::builder()
+                    .value(JsValue::Undefined)
+                    .writable(true)
+                    .enumerable(true)
+                    .configurable(true)
+                    .build(),
+            );
+        } else {
+            self.realm.environments.put_value_if_uninitialized(
+                binding_locator.environment_index(),
+                binding_locator.binding_index(),
+                JsValue::Undefined,
+            );
+        }
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_def_init_var(&mut self) -> JsResult<ShouldExit> {
+        let index = self.vm.read::<u32>();
+        let value = self.vm.pop();
+        let binding_locator = self.vm.frame().code.bindings[index as usize];
+        binding_locator.throw_mutate_immutable(self)?;
+
+        if binding_locator.is_global() {
+            let key = self
+                .interner()
+                .resolve_expect(binding_locator.name())
+                .into();
+            crate::object::internal_methods::global::global_set_no_receiver(&key, value, self)?;
+        } else {
+            self.realm.environments.put_value(
+                binding_locator.environment_index(),
+                binding_locator.binding_index(),
+                value,
+            );
+        }
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_def_let(&mut self) -> JsResult<ShouldExit> {
+        let index = self.vm.read::<u32>();
+        let binding_locator = self.vm.frame().code.bindings[index as usize];
+        self.realm.environments.put_value(
+            binding_locator.environment_index(),
+            binding_locator.binding_index(),
+            JsValue::Undefined,
+        );
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_def_init(&mut self) -> JsResult<ShouldExit> {
+        let index = self.vm.read::<u32>();
+        let value = self.vm.pop();
+        let binding_locator = self.vm.frame().code.bindings[index as usize];
+        self.realm.environments.put_value(
+            binding_locator.environment_index(),
+            binding_locator.binding_index(),
+            value,
+        );
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_get_name(&mut self) -> JsResult<ShouldExit> {
+        let index = self.vm.read::<u32>();
+        let binding_locator = self.vm.frame().code.bindings[index as usize];
+        binding_locator.throw_mutate_immutable(self)?;
+
+        let value = if binding_locator.is_global() {
+            let key: JsString = self
+                .interner()
+                .resolve_expect(binding_locator.name())
+                .into();
+            match self.global_bindings_mut().get(&key) {
+                Some(desc) => match desc.kind() {
+                    DescriptorKind::Data {
+                        value: Some(value), ..
+                    } => value.clone(),
+                    DescriptorKind::Accessor { get: Some(get), .. } if !get.is_undefined() => {
+                        let get = get.clone();
+                        self.call(&get, &self.global_object().clone().into(), &[])?
+                    }
+                    _ => return self.throw_reference_error(format!("{key} is not defined")),
+                },
+                _ => return self.throw_reference_error(format!("{key} is not defined")),
+            }
+        } else if let Some(value) = self.realm.environments.get_value_optional(
+            binding_locator.environment_index(),
+            binding_locator.binding_index(),
+        ) {
+            value
+        } else {
+            let name = JsString::from(self.interner().resolve_expect(binding_locator.name()));
+            return self.throw_reference_error(format!("{name} is not initialized"));
+        };
+
+        self.vm.push(value);
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_get_name_or_undefined(&mut self) -> JsResult<ShouldExit> {
+        let index = self.vm.read::<u32>();
+        let binding_locator = self.vm.frame().code.bindings[index as usize];
+        binding_locator.throw_mutate_immutable(self)?;
+        let value = if binding_locator.is_global() {
+            let key: JsString = self
+                .interner()
+                .resolve_expect(binding_locator.name())
+                .into();
+            match self.global_bindings_mut().get(&key) {
+                Some(desc) => match desc.kind() {
+                    DescriptorKind::Data {
+                        value: Some(value), ..
+                    } => value.clone(),
+                    DescriptorKind::Accessor { get: Some(get), .. } if !get.is_undefined() => {
+                        let get = get.clone();
+                        self.call(&get, &self.global_object().clone().into(), &[])?
+                    }
+                    _ => JsValue::undefined(),
+                },
+                _ => JsValue::undefined(),
+            }
+        } else if let Some(value) = self.realm.environments.get_value_optional(
+            binding_locator.environment_index(),
+            binding_locator.binding_index(),
+        ) {
+            value
+        } else {
+            JsValue::undefined()
+        };
+
+        self.vm.push(value);
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_set_name(&mut self) -> JsResult<ShouldExit> {
+        let index = self.vm.read::<u32>();
+        let binding_locator = self.vm.frame().code.bindings[index as usize];
+        let value = self.vm.pop();
+        binding_locator.throw_mutate_immutable(self)?;
+
+        if binding_locator.is_global() {
+            let key: JsString = self
+                .interner()
+                .resolve_expect(binding_locator.name())
+                .into();
+            let exists = self.global_bindings_mut().contains_key(&key);
+
+            if !exists && (self.strict() || self.vm.frame().code.strict) {
+                return self
+                    .throw_reference_error(format!("assignment to undeclared variable {key}"));
+            }
+
+            let success = crate::object::internal_methods::global::global_set_no_receiver(
+                &key.clone().into(),
+                value,
+                self,
+            )?;
+
+            if !success && (self.strict() || self.vm.frame().code.strict) {
+                return self.throw_type_error(format!("cannot set non-writable property: {key}",));
+            }
+        } else if !self.realm.environments.put_value_if_initialized(
+            binding_locator.environment_index(),
+            binding_locator.binding_index(),
+            value,
+        ) {
+            self.throw_reference_error(format!(
+                "cannot access '{}' before initialization",
+                self.interner().resolve_expect(binding_locator.name())
+            ))?;
+        }
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_jump(&mut self) -> JsResult<ShouldExit> {
+        let address = self.vm.read::<u32>() as usize;
+        if address < self.vm.frame().pc && self.vm.interrupt.take() {
+            return Err(self.terminate(TerminationReason::Interrupted));
+        }
+        self.vm.frame_mut().pc = address;
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_jump_if_false(&mut self) -> JsResult<ShouldExit> {
+        let address = self.vm.read::<u32>();
+        if !self.vm.pop().to_boolean() {
+            self.vm.frame_mut().pc = address as usize;
+        }
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_jump_if_not_undefined(&mut self) -> JsResult<ShouldExit> {
+        let address = self.vm.read::<u32>();
+        let value = self.vm.pop();
+        if !value.is_undefined() {
+            self.vm.frame_mut().pc = address as usize;
+            self.vm.push(value);
+        }
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_logical_and(&mut self) -> JsResult<ShouldExit> {
+        let exit = self.vm.read::<u32>();
+        let lhs = self.vm.pop();
+        if !lhs.to_boolean() {
+            self.vm.frame_mut().pc = exit as usize;
+            self.vm.push(lhs);
+        }
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_logical_or(&mut self) -> JsResult<ShouldExit> {
+        let exit = self.vm.read::<u32>();
+        let lhs = self.vm.pop();
+        if lhs.to_boolean() {
+            self.vm.frame_mut().pc = exit as usize;
+            self.vm.push(lhs);
+        }
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_coalesce(&mut self) -> JsResult<ShouldExit> {
+        let exit = self.vm.read::<u32>();
+        let lhs = self.vm.pop();
+        if !lhs.is_null_or_undefined() {
+            self.vm.frame_mut().pc = exit as usize;
+            self.vm.push(lhs);
+        }
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_to_boolean(&mut self) -> JsResult<ShouldExit> {
+        let value = self.vm.pop();
+        self.vm.push(value.to_boolean());
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_get_property_by_name(&mut self) -> JsResult<ShouldExit> {
+        let index = self.vm.read::<u32>();
+
+        let value = self.vm.pop();
+        let object = if let Some(object) = value.as_object() {
+            object.clone()
+        } else {
+            value.to_object(self)?
+        };
+
+        let name = self.vm.frame().code.names[index as usize];
+        let name: PropertyKey = self.interner().resolve_expect(name).into();
+        let result = object.get(name, self)?;
+
+        self.vm.push(result);
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_get_property_by_value(&mut self) -> JsResult<ShouldExit> {
+        let object = self.vm.pop();
+        let key = self.vm.pop();
+        let object = if let Some(object) = object.as_object() {
+            object.clone()
+        } else {
+            object.to_object(self)?
+        };
+
+        let key = key.to_property_key(self)?;
+        let value = object.get(key, self)?;
+
+        self.vm.push(value);
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_set_property_by_name(&mut self) -> JsResult<ShouldExit> {
+        let index = self.vm.read::<u32>();
+
+        let object = self.vm.pop();
+        let value = self.vm.pop();
+        let object = if let Some(object) = object.as_object() {
+            object.clone()
+        } else {
+            object.to_object(self)?
+        };
+
+        let name = self.vm.frame().code.names[index as usize];
+        let name: PropertyKey = self.interner().resolve_expect(name).into();
+
+        object.set(
+            name,
+            value,
+            self.strict() || self.vm.frame().code.strict,
+            self,
+        )?;
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_define_own_property_by_name(&mut self) -> JsResult<ShouldExit> {
+        let index = self.vm.read::<u32>();
+        let object = self.vm.pop();
+        let value = self.vm.pop();
+        let object = if let Some(object) = object.as_object() {
+            object.clone()
+        } else {
+            object.to_object(self)?
+        };
+        let name = self.vm.frame().code.names[index as usize];
+        let name = self.interner().resolve_expect(name);
+        object.__define_own_property__(
+            name.into(),
+            PropertyDescriptor::builder()
+                .value(value)
+                .writable(true)
+                .enumerable(true)
+                .configurable(true)
+                .build(),
+            self,
+        )?;
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_define_class_method_by_name(&mut self) -> JsResult<ShouldExit> {
+        let index = self.vm.read::<u32>();
+        let object = self.vm.pop();
+        let value = self.vm.pop();
+        let object = if let Some(object) = object.as_object() {
+            object.clone()
+        } else {
+            object.to_object(self)?
+        };
+        let name = self.vm.frame().code.names[index as usize];
+        let name = self.interner().resolve_expect(name);
+        object.__define_own_property__(
+            name.into(),
+            PropertyDescriptor::builder()
+                .value(value)
+                .writable(true)
+                .enumerable(false)
+                .configurable(true)
+                .build(),
+            self,
+        )?;
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_set_property_by_value(&mut self) -> JsResult<ShouldExit> {
+        let object = self.vm.pop();
+        let key = self.vm.pop();
+        let value = self.vm.pop();
+        let object = if let Some(object) = object.as_object() {
+            object.clone()
+        } else {
+            object.to_object(self)?
+        };
+
+        let key = key.to_property_key(self)?;
+        object.set(
+            key,
+            value,
+            self.strict() || self.vm.frame().code.strict,
+            self,
+        )?;
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_define_own_property_by_value(&mut self) -> JsResult<ShouldExit> {
+        let value = self.vm.pop();
+        let key = self.vm.pop();
+        let object = self.vm.pop();
+        let object = if let Some(object) = object.as_object() {
+            object.clone()
+        } else {
+            object.to_object(self)?
+        };
+        let key = key.to_property_key(self)?;
+        object.__define_own_property__(
+            key,
+            PropertyDescriptor::builder()
+                .value(value)
+                .writable(true)
+                .enumerable(true)
+                .configurable(true)
+                .build(),
+            self,
+        )?;
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_define_class_method_by_value(&mut self) -> JsResult<ShouldExit> {
+        let value = self.vm.pop();
+        let key = self.vm.pop();
+        let object = self.vm.pop();
+        let object = if let Some(object) = object.as_object() {
+            object.clone()
+        } else {
+            object.to_object(self)?
+        };
+        let key = key.to_property_key(self)?;
+        object.__define_own_property__(
+            key,
+            PropertyDescriptor::builder()
+                .value(value)
+                .writable(true)
+                .enumerable(false)
+                .configurable(true)
+                .build(),
+            self,
+        )?;
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_set_property_getter_by_name(&mut self) -> JsResult<ShouldExit> {
+        let index = self.vm.read::<u32>();
+        let object = self.vm.pop();
+        let value = self.vm.pop();
+        let object = object.to_object(self)?;
+        let name = self.vm.frame().code.names[index as usize];
+        let name = self.interner().resolve_expect(name).into();
+        let set = object
+            .__get_own_property__(&name, self)?
+            .as_ref()
+            .and_then(PropertyDescriptor::set)
+            .cloned();
+        object.__define_own_property__(
+            name,
+            PropertyDescriptor::builder()
+                .maybe_get(Some(value))
+                .maybe_set(set)
+                .enumerable(true)
+                .configurable(true)
+                .build(),
+            self,
+        )?;
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_define_class_getter_by_name(&mut self) -> JsResult<ShouldExit> {
+        let index = self.vm.read::<u32>();
+        let object = self.vm.pop();
+        let value = self.vm.pop();
+        let object = object.to_object(self)?;
+        let name = self.vm.frame().code.names[index as usize];
+        let name = self.interner().resolve_expect(name).into();
+        let set = object
+            .__get_own_property__(&name, self)?
+            .as_ref()
+            .and_then(PropertyDescriptor::set)
+            .cloned();
+        object.__define_own_property__(
+            name,
+            PropertyDescriptor::builder()
+                .maybe_get(Some(value))
+                .maybe_set(set)
+                .enumerable(false)
+                .configurable(true)
+                .build(),
+            self,
+        )?;
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_set_property_getter_by_value(&mut self) -> JsResult<ShouldExit> {
+        let value = self.vm.pop();
+        let key = self.vm.pop();
+        let object = self.vm.pop();
+        let object = object.to_object(self)?;
+        let name = key.to_property_key(self)?;
+        let set = object
+            .__get_own_property__(&name, self)?
+            .as_ref()
+            .and_then(PropertyDescriptor::set)
+            .cloned();
+        object.__define_own_property__(
+            name,
+            PropertyDescriptor::builder()
+                .maybe_get(Some(value))
+                .maybe_set(set)
+                .enumerable(true)
+                .configurable(true)
+                .build(),
+            self,
+        )?;
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_define_class_getter_by_value(&mut self) -> JsResult<ShouldExit> {
+        let value = self.vm.pop();
+        let key = self.vm.pop();
+        let object = self.vm.pop();
+        let object = object.to_object(self)?;
+        let name = key.to_property_key(self)?;
+        let set = object
+            .__get_own_property__(&name, self)?
+            .as_ref()
+            .and_then(PropertyDescriptor::set)
+            .cloned();
+        object.__define_own_property__(
+            name,
+            PropertyDescriptor::builder()
+                .maybe_get(Some(value))
+                .maybe_set(set)
+                .enumerable(false)
+                .configurable(true)
+                .build(),
+            self,
+        )?;
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_set_property_setter_by_name(&mut self) -> JsResult<ShouldExit> {
+        let index = self.vm.read::<u32>();
+        let object = self.vm.pop();
+        let value = self.vm.pop();
+        let object = object.to_object(self)?;
+        let name = self.vm.frame().code.names[index as usize];
+        let name = self.interner().resolve_expect(name).into();
+        let get = object
+            .__get_own_property__(&name, self)?
+            .as_ref()
+            .and_then(PropertyDescriptor::get)
+            .cloned();
+        object.__define_own_property__(
+            name,
+            PropertyDescriptor::builder()
+                .maybe_set(Some(value))
+                .maybe_get(get)
+                .enumerable(true)
+                .configurable(true)
+                .build(),
+            self,
+        )?;
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_define_class_setter_by_name(&mut self) -> JsResult<ShouldExit> {
+        let index = self.vm.read::<u32>();
+        let object = self.vm.pop();
+        let value = self.vm.pop();
+        let object = object.to_object(self)?;
+        let name = self.vm.frame().code.names[index as usize];
+        let name = self.interner().resolve_expect(name).into();
+        let get = object
+            .__get_own_property__(&name, self)?
+            .as_ref()
+            .and_then(PropertyDescriptor::get)
+            .cloned();
+        object.__define_own_property__(
+            name,
+            PropertyDescriptor::builder()
+                .maybe_set(Some(value))
+                .maybe_get(get)
+                .enumerable(false)
+                .configurable(true)
+                .build(),
+            self,
+        )?;
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_set_property_setter_by_value(&mut self) -> JsResult<ShouldExit> {
+        let value = self.vm.pop();
+        let key = self.vm.pop();
+        let object = self.vm.pop();
+        let object = object.to_object(self)?;
+        let name = key.to_property_key(self)?;
+        let get = object
+            .__get_own_property__(&name, self)?
+            .as_ref()
+            .and_then(PropertyDescriptor::get)
+            .cloned();
+        object.__define_own_property__(
+            name,
+            PropertyDescriptor::builder()
+                .maybe_set(Some(value))
+                .maybe_get(get)
+                .enumerable(true)
+                .configurable(true)
+                .build(),
+            self,
+        )?;
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_define_class_setter_by_value(&mut self) -> JsResult<ShouldExit> {
+        let value = self.vm.pop();
+        let key = self.vm.pop();
+        let object = self.vm.pop();
+        let object = object.to_object(self)?;
+        let name = key.to_property_key(self)?;
+        let get = object
+            .__get_own_property__(&name, self)?
+            .as_ref()
+            .and_then(PropertyDescriptor::get)
+            .cloned();
+        object.__define_own_property__(
+            name,
+            PropertyDescriptor::builder()
+                .maybe_set(Some(value))
+                .maybe_get(get)
+                .enumerable(false)
+                .configurable(true)
+                .build(),
+            self,
+        )?;
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_set_private_value(&mut self) -> JsResult<ShouldExit> {
+        let index = self.vm.read::<u32>();
+        let name = self.vm.frame().code.names[index as usize];
+        let value = self.vm.pop();
+        let object = self.vm.pop();
+        if let Some(object) = object.as_object() {
+            let mut object_borrow_mut = object.borrow_mut();
+            if let Some(PrivateElement::Accessor {
+                getter: _,
+                setter: Some(setter),
+            }) = object_borrow_mut.get_private_element(name)
+            {
+                let setter = setter.clone();
+                drop(object_borrow_mut);
+                setter.call(&object.clone().into(), &[value], self)?;
+            } else {
+                object_borrow_mut.set_private_element(name, PrivateElement::Value(value));
+            }
+        } else {
+            return self.throw_type_error("cannot set private property on non-object");
+        }
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_set_private_setter(&mut self) -> JsResult<ShouldExit> {
+        let index = self.vm.read::<u32>();
+        let name = self.vm.frame().code.names[index as usize];
+        let value = self.vm.pop();
+        let value = value.as_callable().expect("setter must be callable");
+        let object = self.vm.pop();
+        if let Some(object) = object.as_object() {
+            let mut object_borrow_mut = object.borrow_mut();
+            object_borrow_mut.set_private_element_setter(name, value.clone());
+        } else {
+            return self.throw_type_error("cannot set private setter on non-object");
+        }
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_set_private_getter(&mut self) -> JsResult<ShouldExit> {
+        let index = self.vm.read::<u32>();
+        let name = self.vm.frame().code.names[index as usize];
+        let value = self.vm.pop();
+        let value = value.as_callable().expect("getter must be callable");
+        let object = self.vm.pop();
+        if let Some(object) = object.as_object() {
+            let mut object_borrow_mut = object.borrow_mut();
+            object_borrow_mut.set_private_element_getter(name, value.clone());
+        } else {
+            return self.throw_type_error("cannot set private getter on non-object");
+        }
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_get_private_field(&mut self) -> JsResult<ShouldExit> {
+        let index = self.vm.read::<u32>();
+        let name = self.vm.frame().code.names[index as usize];
+        let value = self.vm.pop();
+        if let Some(object) = value.as_object() {
+            let object_borrow_mut = object.borrow();
+            if let Some(element) = object_borrow_mut.get_private_element(name) {
+                match element {
+                    PrivateElement::Value(value) => self.vm.push(value),
+                    PrivateElement::Accessor {
+                        getter: Some(getter),
+                        setter: _,
+                    } => {
+                        let value = getter.call(&value, &[], self)?;
+                        self.vm.push(value);
+                    }
+                    PrivateElement::Accessor { .. } => {
+                        return self
+                            .throw_type_error("private property was defined without a getter");
+                    }
+                }
+            } else {
+                return self.throw_type_error("private property does not exist");
+            }
+        } else {
+            return self.throw_type_error("cannot read private property from non-object");
+        }
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_push_class_computed_field_name(&mut self) -> JsResult<ShouldExit> {
+        let object = self.vm.pop();
+        let value = self.vm.pop();
+        let value = value.to_property_key(self)?;
+        let object_obj = object
+            .as_object()
+            .expect("can only add field to function object");
+        let object = object_obj.borrow();
+        let function = object
+            .as_function()
+            .expect("can only add field to function object");
+        if let Function::Ordinary { code, .. } = function {
+            let code_b = code
+                .computed_field_names
+                .as_ref()
+                .expect("class constructor must have fields");
+            let mut fields_mut = code_b.borrow_mut();
+            fields_mut.push(value);
+        }
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_delete_property_by_name(&mut self) -> JsResult<ShouldExit> {
+        let index = self.vm.read::<u32>();
+        let key = self.vm.frame().code.names[index as usize];
+        let key = self.interner().resolve_expect(key).into();
+        let object = self.vm.pop();
+        let result = object.to_object(self)?.__delete__(&key, self)?;
+        if !result && self.strict() || self.vm.frame().code.strict {
+            return Err(self.construct_type_error("Cannot delete property"));
+        }
+        self.vm.push(result);
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_delete_property_by_value(&mut self) -> JsResult<ShouldExit> {
+        let object = self.vm.pop();
+        let key = self.vm.pop();
+        let result = object
+            .to_object(self)?
+            .__delete__(&key.to_property_key(self)?, self)?;
+        if !result && self.strict() || self.vm.frame().code.strict {
+            return Err(self.construct_type_error("Cannot delete property"));
+        }
+        self.vm.push(result);
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_copy_data_properties(&mut self) -> JsResult<ShouldExit> {
+        let excluded_key_count = self.vm.read::<u32>();
+        let mut excluded_keys = Vec::with_capacity(excluded_key_count as usize);
+        for _ in 0..excluded_key_count {
+            excluded_keys.push(self.vm.pop().as_string().expect("not a string").clone());
+        }
+        let value = self.vm.pop();
+        let object = value.as_object().expect("not an object");
+        let source = self.vm.pop();
+        object.copy_data_properties(&source, excluded_keys, self)?;
+        self.vm.push(value);
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_to_property_key(&mut self) -> JsResult<ShouldExit> {
+        let value = self.vm.pop();
+        let key = value.to_property_key(self)?;
+        self.vm.push(key);
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_throw(&mut self) -> JsResult<ShouldExit> {
+        let value = self.vm.pop();
+        Err(value)
+    }
+
+    pub(super) fn execute_try_start(&mut self) -> JsResult<ShouldExit> {
+        let next = self.vm.read::<u32>();
+        let finally = self.vm.read::<u32>();
+        let finally = if finally == 0 { None } else { Some(finally) };
+        self.vm
+            .frame_mut()
+            .catch
+            .push(CatchAddresses { next, finally });
+        self.vm.frame_mut().finally_jump.push(None);
+        self.vm.frame_mut().finally_return = FinallyReturn::None;
+        self.vm.frame_mut().try_env_stack.push(TryStackEntry {
+            num_env: 0,
+            num_loop_stack_entries: 0,
+        });
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_try_catch_end(&mut self) -> JsResult<ShouldExit> {
+        self.vm.frame_mut().catch.pop();
+        let try_stack_entry = self.vm.frame_mut().try_env_stack.pop().expect("must exist");
+        for _ in 0..try_stack_entry.num_env {
+            self.realm.environments.pop();
+        }
+        let mut num_env = try_stack_entry.num_env;
+        for _ in 0..try_stack_entry.num_loop_stack_entries {
+            num_env -= self
+                .vm
+                .frame_mut()
+                .loop_env_stack
+                .pop()
+                .expect("must exist");
+        }
+        *self
+            .vm
+            .frame_mut()
+            .loop_env_stack
+            .last_mut()
+            .expect("must exist") -= num_env;
+        self.vm.frame_mut().finally_return = FinallyReturn::None;
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_catch_start(&mut self) -> JsResult<ShouldExit> {
+        let finally = self.vm.read::<u32>();
+        self.vm.frame_mut().catch.push(CatchAddresses {
+            next: finally,
+            finally: Some(finally),
+        });
+        self.vm.frame_mut().try_env_stack.push(TryStackEntry {
+            num_env: 0,
+            num_loop_stack_entries: 0,
+        });
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_catch_end2(&mut self) -> JsResult<ShouldExit> {
+        let frame = self.vm.frame_mut();
+        if frame.finally_return == FinallyReturn::Err {
+            frame.finally_return = FinallyReturn::None;
+        }
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_finally_start(&mut self) -> JsResult<ShouldExit> {
+        *self
+            .vm
+            .frame_mut()
+            .finally_jump
+            .last_mut()
+            .expect("finally jump must exist here") = None;
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_finally_end(&mut self) -> JsResult<ShouldExit> {
+        let address = self
+            .vm
+            .frame_mut()
+            .finally_jump
+            .pop()
+            .expect("finally jump must exist here");
+        match self.vm.frame_mut().finally_return {
+            FinallyReturn::None => {
+                if let Some(address) = address {
+                    let address = address as usize;
+                    if address < self.vm.frame().pc && self.vm.interrupt.take() {
+                        return Err(self.terminate(TerminationReason::Interrupted));
+                    }
+                    self.vm.frame_mut().pc = address;
+                }
+            }
+            FinallyReturn::Ok => {
+                return Ok(ShouldExit::True);
+            }
+            FinallyReturn::Err => {
+                return Err(self.vm.pop());
+            }
+        }
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_finally_set_jump(&mut self) -> JsResult<ShouldExit> {
+        let address = self.vm.read::<u32>();
+        *self
+            .vm
+            .frame_mut()
+            .finally_jump
+            .last_mut()
+            .expect("finally jump must exist here") = Some(address);
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_this(&mut self) -> JsResult<ShouldExit> {
+        let this = self.vm.frame().this.clone();
+        self.vm.push(this);
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_case(&mut self) -> JsResult<ShouldExit> {
+        let address = self.vm.read::<u32>();
+        let cond = self.vm.pop();
+        let value = self.vm.pop();
+
+        if value.strict_equals(&cond) {
+            self.vm.frame_mut().pc = address as usize;
+        } else {
+            self.vm.push(value);
+        }
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_default(&mut self) -> JsResult<ShouldExit> {
+        let exit = self.vm.read::<u32>();
+        let _val = self.vm.pop();
+        self.vm.frame_mut().pc = exit as usize;
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_get_function(&mut self) -> JsResult<ShouldExit> {
+        let index = self.vm.read::<u32>();
+        let code = self.vm.frame().code.functions[index as usize].clone();
+        let function = create_function_object(code, self);
+        self.vm.push(function);
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_get_generator(&mut self) -> JsResult<ShouldExit> {
+        let index = self.vm.read::<u32>();
+        let code = self.vm.frame().code.functions[index as usize].clone();
+        let function = create_generator_function_object(code, self);
+        self.vm.push(function);
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_call(&mut self) -> JsResult<ShouldExit> {
+        if self.vm.runtime_limits.stack_size_limit() <= self.vm.stack.len() {
+            return self.throw_range_error("Maximum call stack size exceeded");
+        }
+        let argument_count = self.vm.read::<u32>();
+        let mut arguments = Vec::with_capacity(argument_count as usize);
+        for _ in 0..argument_count {
+            arguments.push(self.vm.pop());
+        }
+        arguments.reverse();
+
+        let func = self.vm.pop();
+        let mut this = self.vm.pop();
+
+        let object = match func {
+            JsValue::Object(ref object) if object.is_callable() => object.clone(),
+            _ => return self.throw_type_error("not a callable function"),
+        };
+
+        if this.is_null_or_undefined() {
+            this = self.global_object().clone().into();
+        }
+
+        let result = object.__call__(&this, &arguments, self)?;
+
+        self.vm.push(result);
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_call_with_rest(&mut self) -> JsResult<ShouldExit> {
+        if self.vm.runtime_limits.stack_size_limit() <= self.vm.stack.len() {
+            return self.throw_range_error("Maximum call stack size exceeded");
+        }
+        let argument_count = self.vm.read::<u32>();
+        let rest_argument = self.vm.pop();
+        let mut arguments = Vec::with_capacity(argument_count as usize);
+        for _ in 0..(argument_count - 1) {
+            arguments.push(self.vm.pop());
+        }
+        arguments.reverse();
+        let func = self.vm.pop();
+        let mut this = self.vm.pop();
+
+        let iterator_record = rest_argument.get_iterator(self, None, None)?;
+        let mut rest_arguments = Vec::new();
+        while let Some(next) = iterator_record.step(self)? {
+            rest_arguments.push(next.value(self)?);
+        }
+        arguments.append(&mut rest_arguments);
+
+        let object = match func {
+            JsValue::Object(ref object) if object.is_callable() => object.clone(),
+            _ => return self.throw_type_error("not a callable function"),
+        };
+
+        if this.is_null_or_undefined() {
+            this = self.global_object().clone().into();
+        }
+
+        let result = object.__call__(&this, &arguments, self)?;
+
+        self.vm.push(result);
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_new(&mut self) -> JsResult<ShouldExit> {
+        if self.vm.runtime_limits.stack_size_limit() <= self.vm.stack.len() {
+            return self.throw_range_error("Maximum call stack size exceeded");
+        }
+        let argument_count = self.vm.read::<u32>();
+        let mut arguments = Vec::with_capacity(argument_count as usize);
+        for _ in 0..argument_count {
+            arguments.push(self.vm.pop());
+        }
+        arguments.reverse();
+        let func = self.vm.pop();
+
+        let result = func
+            .as_constructor()
+            .ok_or_else(|| self.construct_type_error("not a constructor"))
+            .and_then(|cons| cons.__construct__(&arguments, &cons.clone().into(), self))?;
+
+        self.vm.push(result);
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_new_with_rest(&mut self) -> JsResult<ShouldExit> {
+        if self.vm.runtime_limits.stack_size_limit() <= self.vm.stack.len() {
+            return self.throw_range_error("Maximum call stack size exceeded");
+        }
+        let argument_count = self.vm.read::<u32>();
+        let rest_argument = self.vm.pop();
+        let mut arguments = Vec::with_capacity(argument_count as usize);
+        for _ in 0..(argument_count - 1) {
+            arguments.push(self.vm.pop());
+        }
+        arguments.reverse();
+        let func = self.vm.pop();
+
+        let iterator_record = rest_argument.get_iterator(self, None, None)?;
+        let mut rest_arguments = Vec::new();
+        while let Some(next) = iterator_record.step(self)? {
+            rest_arguments.push(next.value(self)?);
+        }
+        arguments.append(&mut rest_arguments);
+
+        let result = func
+            .as_constructor()
+            .ok_or_else(|| self.construct_type_error("not a constructor"))
+            .and_then(|cons| cons.__construct__(&arguments, &cons.clone().into(), self))?;
+
+        self.vm.push(result);
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_return(&mut self) -> JsResult<ShouldExit> {
+        if let Some(finally_address) = self.vm.frame().catch.last().and_then(|c| c.finally) {
+            let frame = self.vm.frame_mut();
+            frame.pc = finally_address as usize;
+            frame.finally_return = FinallyReturn::Ok;
+            frame.catch.pop();
+            let try_stack_entry = self.vm.frame_mut().try_env_stack.pop().expect("must exist");
+            for _ in 0..try_stack_entry.num_env {
+                self.realm.environments.pop();
+            }
+            let mut num_env = try_stack_entry.num_env;
+            for _ in 0..try_stack_entry.num_loop_stack_entries {
+                num_env -= self
+                    .vm
+                    .frame_mut()
+                    .loop_env_stack
+                    .pop()
+                    .expect("must exist");
+            }
+            *self
+                .vm
+                .frame_mut()
+                .loop_env_stack
+                .last_mut()
+                .expect("must exist") -= num_env;
+        } else {
+            return Ok(ShouldExit::True);
+        }
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_push_declarative_environment(&mut self) -> JsResult<ShouldExit> {
+        let num_bindings = self.vm.read::<u32>();
+        self.realm
+            .environments
+            .push_declarative(num_bindings as usize);
+        self.vm.frame_mut().loop_env_stack_inc();
+        self.vm.frame_mut().try_env_stack_inc();
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_push_function_environment(&mut self) -> JsResult<ShouldExit> {
+        let num_bindings = self.vm.read::<u32>();
+        let is_constructor = self.vm.frame().code.constructor;
+        let is_lexical = self.vm.frame().code.this_mode.is_lexical();
+        let this = if is_constructor || !is_lexical {
+            self.vm.frame().this.clone()
+        } else {
+            JsValue::undefined()
+        };
+
+        self.realm
+            .environments
+            .push_function(num_bindings as usize, this);
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_pop_environment(&mut self) -> JsResult<ShouldExit> {
+        self.realm.environments.pop();
+        self.vm.frame_mut().loop_env_stack_dec();
+        self.vm.frame_mut().try_env_stack_dec();
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_loop_start(&mut self) -> JsResult<ShouldExit> {
+        self.vm.frame_mut().loop_env_stack.push(0);
+        self.vm.frame_mut().try_env_stack_loop_inc();
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_loop_continue(&mut self) -> JsResult<ShouldExit> {
+        self.vm.loop_iteration_count += 1;
+        if self.vm.loop_iteration_count > self.vm.runtime_limits.loop_iteration_limit() {
+            return Err(self.terminate(TerminationReason::LoopIterationLimit));
+        }
+        if self.vm.interrupt.take() {
+            return Err(self.terminate(TerminationReason::Interrupted));
+        }
+        #[cfg(feature = "jit")]
+        self.vm
+            .frame()
+            .code
+            .hotness
+            .record_loop_iteration(self.vm.jit_threshold);
+
+        let env_num = self
+            .vm
+            .frame_mut()
+            .loop_env_stack
+            .last_mut()
+            .expect("loop env stack entry must exist");
+        let env_num_copy = *env_num;
+        *env_num = 0;
+        for _ in 0..env_num_copy {
+            self.realm.environments.pop();
+        }
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_loop_end(&mut self) -> JsResult<ShouldExit> {
+        let env_num = self
+            .vm
+            .frame_mut()
+            .loop_env_stack
+            .pop()
+            .expect("loop env stack entry must exist");
+        for _ in 0..env_num {
+            self.realm.environments.pop();
+            self.vm.frame_mut().try_env_stack_dec();
+        }
+        self.vm.frame_mut().try_env_stack_loop_dec();
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_for_in_loop_init_iterator(&mut self) -> JsResult<ShouldExit> {
+        let address = self.vm.read::<u32>();
+
+        let object = self.vm.pop();
+        if object.is_null_or_undefined() {
+            self.vm.frame_mut().pc = address as usize;
+            return Ok(ShouldExit::False);
+        }
+
+        let object = object.to_object(self)?;
+        let iterator = ForInIterator::create_for_in_iterator(JsValue::new(object), self);
+        let next_function = iterator
+            .get_property("next")
+            .as_ref()
+            .map(PropertyDescriptor::expect_value)
+            .cloned()
+            .ok_or_else(|| self.construct_type_error("Could not find property `next`"))?;
+
+        self.vm.push(iterator);
+        self.vm.push(next_function);
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_init_iterator(&mut self) -> JsResult<ShouldExit> {
+        let object = self.vm.pop();
+        let iterator = object.get_iterator(self, None, None)?;
+        self.vm.push(iterator.iterator_object());
+        self.vm.push(iterator.next_function());
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_iterator_next(&mut self) -> JsResult<ShouldExit> {
+        let next_function = self.vm.pop();
+        let iterator = self.vm.pop();
+
+        let iterator_record = IteratorRecord::new(iterator.clone(), next_function.clone());
+        let next = iterator_record.step(self)?;
+
+        self.vm.push(iterator);
+        self.vm.push(next_function);
+        if let Some(next) = next {
+            let value = next.value(self)?;
+            self.vm.push(value);
+        } else {
+            self.vm.push(JsValue::undefined());
+        }
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_iterator_next_full(&mut self) -> JsResult<ShouldExit> {
+        let next_function = self.vm.pop();
+        let iterator = self.vm.pop();
+
+        let iterator_record = IteratorRecord::new(iterator.clone(), next_function.clone());
+        let next = iterator_record.step(self)?;
+
+        self.vm.push(iterator);
+        self.vm.push(next_function);
+        if let Some(next) = next {
+            let value = next.value(self)?;
+            self.vm.push(false);
+            self.vm.push(value);
+        } else {
+            self.vm.push(true);
+            self.vm.push(JsValue::undefined());
+        }
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_iterator_close(&mut self) -> JsResult<ShouldExit> {
+        let done = self.vm.pop();
+        let next_function = self.vm.pop();
+        let iterator = self.vm.pop();
+        if !done.as_boolean().expect("not a boolean") {
+            let iterator_record = IteratorRecord::new(iterator, next_function);
+            iterator_record.close(Ok(JsValue::Null), self)?;
+        }
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_iterator_to_array(&mut self) -> JsResult<ShouldExit> {
+        let next_function = self.vm.pop();
+        let iterator = self.vm.pop();
+
+        let iterator_record = IteratorRecord::new(iterator.clone(), next_function.clone());
+        let mut values = Vec::new();
+
+        while let Some(result) = iterator_record.step(self)? {
+            values.push(result.value(self)?);
+        }
+
+        let array = Array::create_array_from_list(values, self);
+
+        self.vm.push(iterator);
+        self.vm.push(next_function);
+        self.vm.push(array);
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_for_in_loop_next(&mut self) -> JsResult<ShouldExit> {
+        let address = self.vm.read::<u32>();
+
+        let next_function = self.vm.pop();
+        let iterator = self.vm.pop();
+
+        let iterator_record = IteratorRecord::new(iterator.clone(), next_function.clone());
+        if let Some(next) = iterator_record.step(self)? {
+            self.vm.push(iterator);
+            self.vm.push(next_function);
+            let value = next.value(self)?;
+            self.vm.push(value);
+        } else {
+            self.vm.frame_mut().pc = address as usize;
+            self.vm.frame_mut().loop_env_stack_dec();
+            self.vm.frame_mut().try_env_stack_dec();
+            self.realm.environments.pop();
+            self.vm.push(iterator);
+            self.vm.push(next_function);
+        }
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_concat_to_string(&mut self) -> JsResult<ShouldExit> {
+        let value_count = self.vm.read::<u32>();
+        let mut strings = Vec::with_capacity(value_count as usize);
+        for _ in 0..value_count {
+            strings.push(self.vm.pop().to_string(self)?);
+        }
+        strings.reverse();
+        let s =
+            self.concat_strings(&strings.iter().map(JsString::as_str).collect::<Vec<&str>>())?;
+        self.vm.push(s);
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_require_object_coercible(&mut self) -> JsResult<ShouldExit> {
+        let value = self.vm.pop();
+        let value = value.require_object_coercible(self)?;
+        self.vm.push(value);
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_value_not_null_or_undefined(&mut self) -> JsResult<ShouldExit> {
+        let value = self.vm.pop();
+        if value.is_null() {
+            return self.throw_type_error("Cannot destructure 'null' value");
+        }
+        if value.is_undefined() {
+            return self.throw_type_error("Cannot destructure 'undefined' value");
+        }
+        self.vm.push(value);
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_rest_parameter_init(&mut self) -> JsResult<ShouldExit> {
+        let arg_count = self.vm.frame().arg_count;
+        let param_count = self.vm.frame().param_count;
+        if arg_count >= param_count {
+            let rest_count = arg_count - param_count + 1;
+            let mut args = Vec::with_capacity(rest_count);
+            for _ in 0..rest_count {
+                args.push(self.vm.pop());
+            }
+            let array: _ = Array::create_array_from_list(args, self);
+
+            self.vm.push(array);
+        } else {
+            self.vm.pop();
+
+            let array =
+                Array::array_create(0, None, self).expect("could not create an empty array");
+            self.vm.push(array);
+        }
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_rest_parameter_pop(&mut self) -> JsResult<ShouldExit> {
+        let arg_count = self.vm.frame().arg_count;
+        let param_count = self.vm.frame().param_count;
+        if arg_count > param_count {
+            for _ in 0..(arg_count - param_count) {
+                self.vm.pop();
+            }
+        }
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_pop_on_return_add(&mut self) -> JsResult<ShouldExit> {
+        self.vm.frame_mut().pop_on_return += 1;
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_pop_on_return_sub(&mut self) -> JsResult<ShouldExit> {
+        self.vm.frame_mut().pop_on_return -= 1;
+        Ok(ShouldExit::False)
+    }
+
+    pub(super) fn execute_yield(&mut self) -> JsResult<ShouldExit> {
+        Ok(ShouldExit::Yield)
+    }
+
+    pub(super) fn execute_generator_next(&mut self) -> JsResult<ShouldExit> {
+        match self.vm.frame().generator_resume_kind {
+            GeneratorResumeKind::Normal => Ok(ShouldExit::False),
+            GeneratorResumeKind::Throw => {
+                let received = self.vm.pop();
+                Err(received)
+            }
+            GeneratorResumeKind::Return => {
+                let mut finally_left = false;
+
+                while let Some(catch_addresses) = self.vm.frame().catch.last() {
+                    if let Some(finally_address) = catch_addresses.finally {
+                        let frame = self.vm.frame_mut();
+                        frame.pc = finally_address as usize;
+                        frame.finally_return = FinallyReturn::Ok;
+                        frame.catch.pop();
+                        finally_left = true;
+                        break;
+                    }
+                    self.vm.frame_mut().catch.pop();
+                }
+
+                if finally_left {
+                    return Ok(ShouldExit::False);
+                }
+                Ok(ShouldExit::True)
+            }
+        }
+    }
+
+    pub(super) fn execute_generator_next_delegate(&mut self) -> JsResult<ShouldExit> {
+        let done_address = self.vm.read::<u32>();
+        let received = self.vm.pop();
+        let next_function = self.vm.pop();
+        let iterator = self.vm.pop();
+
+        match self.vm.frame().generator_resume_kind {
+            GeneratorResumeKind::Normal => {
+                let result = self.call(&next_function, &iterator, &[received])?;
+                let result_object = result.as_object().ok_or_else(|| {
+                    self.construct_type_error("generator next method returned non-object")
+                })?;
+                let done = result_object.get("done", self)?.to_boolean();
+                if done {
+                    self.vm.frame_mut().pc = done_address as usize;
+                    let value = result_object.get("value", self)?;
+                    self.vm.push(value);
+                    return Ok(ShouldExit::False);
+                }
+                let value = result_object.get("value", self)?;
+                self.vm.push(iterator);
+                self.vm.push(next_function);
+                self.vm.push(value);
+                return Ok(ShouldExit::Yield);
+            }
+            GeneratorResumeKind::Throw => {
+                let throw = iterator.get_method("throw", self)?;
+                if let Some(throw) = throw {
+                    let result = throw.call(&iterator, &[received], self)?;
+                    let result_object = result.as_object().ok_or_else(|| {
+                        self.construct_type_error("generator throw method returned non-object")
+                    })?;
+                    let done = result_object.get("done", self)?.to_boolean();
+                    if done {
+                        self.vm.frame_mut().pc = done_address as usize;
+                        let value = result_object.get("value", self)?;
+                        self.vm.push(value);
+                        return Ok(ShouldExit::False);
+                    }
+                    let value = result_object.get("value", self)?;
+                    self.vm.push(iterator);
+                    self.vm.push(next_function);
+                    self.vm.push(value);
+                    return Ok(ShouldExit::Yield);
+                }
+                self.vm.frame_mut().pc = done_address as usize;
+                let iterator_record = IteratorRecord::new(iterator.clone(), next_function.clone());
+                iterator_record.close(Ok(JsValue::Undefined), self)?;
+                let error = self.construct_type_error("iterator does not have a throw method");
+                return Err(error);
+            }
+            GeneratorResumeKind::Return => {
+                let r#return = iterator.get_method("return", self)?;
+                if let Some(r#return) = r#return {
+                    let result = r#return.call(&iterator, &[received], self)?;
+                    let result_object = result.as_object().ok_or_else(|| {
+                        self.construct_type_error("generator return method returned non-object")
+                    })?;
+                    let done = result_object.get("done", self)?.to_boolean();
+                    if done {
+                        self.vm.frame_mut().pc = done_address as usize;
+                        let value = result_object.get("value", self)?;
+                        self.vm.push(value);
+                        return Ok(ShouldExit::True);
+                    }
+                    let value = result_object.get("value", self)?;
+                    self.vm.push(iterator);
+                    self.vm.push(next_function);
+                    self.vm.push(value);
+                    return Ok(ShouldExit::Yield);
+                }
+                self.vm.frame_mut().pc = done_address as usize;
+                self.vm.push(received);
+                Ok(ShouldExit::True)
+            }
+        }
+    }
+}
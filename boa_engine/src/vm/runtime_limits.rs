@@ -0,0 +1,110 @@
+//! Limits on the resources a script can use while running in the VM.
+
+use std::fmt;
+
+/// Limits checked by the VM while executing code.
+///
+/// The instruction and loop iteration counters are reset whenever the host starts a new
+/// execution, so these limits apply to each call to [`Context::eval`](crate::Context::eval),
+/// [`Context::execute`](crate::Context::execute) or to a JavaScript function called from Rust.
+/// Exceeding the call depth, loop iteration or instruction limit terminates the execution with
+/// an error that cannot be caught by the script, returned as
+/// [`JsError::Termination`](crate::JsError::Termination).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RuntimeLimits {
+    stack_size_limit: usize,
+    call_depth_limit: usize,
+    loop_iteration_limit: u64,
+    instruction_limit: u64,
+}
+
+impl Default for RuntimeLimits {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            stack_size_limit: 1024,
+            call_depth_limit: usize::MAX,
+            loop_iteration_limit: u64::MAX,
+            instruction_limit: u64::MAX,
+        }
+    }
+}
+
+impl RuntimeLimits {
+    /// Returns the maximum size of the value stack before a call throws a catchable
+    /// `RangeError`.
+    #[inline]
+    pub fn stack_size_limit(&self) -> usize {
+        self.stack_size_limit
+    }
+
+    /// Sets the maximum size of the value stack before a call throws a catchable `RangeError`.
+    #[inline]
+    pub fn set_stack_size_limit(&mut self, limit: usize) {
+        self.stack_size_limit = limit;
+    }
+
+    /// Returns the maximum number of nested JavaScript call frames.
+    #[inline]
+    pub fn call_depth_limit(&self) -> usize {
+        self.call_depth_limit
+    }
+
+    /// Sets the maximum number of nested JavaScript call frames.
+    #[inline]
+    pub fn set_call_depth_limit(&mut self, limit: usize) {
+        self.call_depth_limit = limit;
+    }
+
+    /// Returns the maximum number of loop iterations of an execution.
+    #[inline]
+    pub fn loop_iteration_limit(&self) -> u64 {
+        self.loop_iteration_limit
+    }
+
+    /// Sets the maximum number of loop iterations of an execution.
+    #[inline]
+    pub fn set_loop_iteration_limit(&mut self, limit: u64) {
+        self.loop_iteration_limit = limit;
+    }
+
+    /// Returns the maximum number of instructions of an execution.
+    #[inline]
+    pub fn instruction_limit(&self) -> u64 {
+        self.instruction_limit
+    }
+
+    /// Sets the maximum number of instructions of an execution.
+    #[inline]
+    pub fn set_instruction_limit(&mut self, limit: u64) {
+        self.instruction_limit = limit;
+    }
+}
+
+/// The reason why an execution was terminated with an uncatchable error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TerminationReason {
+    /// The heap limit was exceeded, and the
+    /// [`HeapLimitAction`](crate::context::HeapLimitAction) was `Terminate`.
+    HeapLimit,
+    /// The call depth limit was exceeded.
+    CallDepthLimit,
+    /// The loop iteration limit was exceeded.
+    LoopIterationLimit,
+    /// The instruction limit was exceeded.
+    InstructionLimit,
+    /// The termination was requested through an [`InterruptHandle`](crate::vm::InterruptHandle).
+    Interrupted,
+}
+
+impl fmt::Display for TerminationReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::HeapLimit => "heap limit exceeded",
+            Self::CallDepthLimit => "maximum call depth exceeded",
+            Self::LoopIterationLimit => "loop iteration limit exceeded",
+            Self::InstructionLimit => "instruction limit exceeded",
+            Self::Interrupted => "execution interrupted",
+        })
+    }
+}
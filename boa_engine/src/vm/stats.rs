@@ -0,0 +1,247 @@
+//! Execution statistics of the instructions run by the VM.
+
+use crate::{
+    vm::{CodeBlock, Opcode, OpcodeCategory},
+    Context, JsString,
+};
+use boa_gc::Gc;
+use boa_interner::Sym;
+use rustc_hash::FxHashMap;
+use std::{
+    cmp::Reverse,
+    convert::TryFrom,
+    time::{Duration, Instant},
+};
+
+/// Number of opcodes.
+const OPCODE_COUNT: usize = Opcode::Nop as usize + 1;
+
+/// The statistics recorded while [`Context::set_vm_stats`] is enabled, returned by
+/// [`Context::vm_stats`].
+///
+/// Times are measured around each instruction, so they include the time spent in the functions
+/// called by the instruction. They are always zero on `wasm32`, where `std::time::Instant` is not
+/// available.
+#[derive(Debug, Clone, Default)]
+pub struct VmStats {
+    /// Number of instructions executed.
+    pub instructions: u64,
+    /// Executions and time of each executed opcode, most executed first.
+    pub opcodes: Vec<OpcodeStats>,
+    /// Executions and time of each opcode category, most executed first.
+    pub categories: Vec<CategoryStats>,
+    /// Executions of each executed function, most executed first.
+    pub functions: Vec<FunctionStats>,
+    /// Executions of each executed instruction, most executed first.
+    pub hot_instructions: Vec<InstructionStats>,
+}
+
+/// Statistics of an opcode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OpcodeStats {
+    /// The opcode.
+    pub opcode: Opcode,
+    /// Number of executions.
+    pub count: u64,
+    /// Total time of the executions.
+    pub time: Duration,
+}
+
+/// Statistics of an opcode category.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CategoryStats {
+    /// The category.
+    pub category: OpcodeCategory,
+    /// Number of executions of the opcodes of the category.
+    pub count: u64,
+    /// Total time of the executions.
+    pub time: Duration,
+}
+
+/// Statistics of a function.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionStats {
+    /// Name of the function, `<main>` for scripts.
+    pub name: JsString,
+    /// Number of instructions executed in the function.
+    pub instructions: u64,
+}
+
+/// Statistics of an instruction of a function.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstructionStats {
+    /// Name of the function, `<main>` for scripts.
+    pub function: JsString,
+    /// Position of the instruction in the bytecode of the function.
+    pub pc: usize,
+    /// The opcode of the instruction.
+    pub opcode: Opcode,
+    /// The operands of the instruction, formatted.
+    pub operands: String,
+    /// Number of executions.
+    pub count: u64,
+}
+
+/// The statistics of a code block.
+#[derive(Debug)]
+struct BlockStats {
+    code: Gc<CodeBlock>,
+    /// Executions of the instruction at each `pc`.
+    counts: Vec<u64>,
+}
+
+/// The statistics recording state of a `Context`.
+#[derive(Debug)]
+pub(crate) struct VmStatsState {
+    enabled: bool,
+    opcodes: [(u64, Duration); OPCODE_COUNT],
+    blocks: FxHashMap<*const CodeBlock, BlockStats>,
+}
+
+impl Default for VmStatsState {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            opcodes: [(0, Duration::ZERO); OPCODE_COUNT],
+            blocks: FxHashMap::default(),
+        }
+    }
+}
+
+impl VmStatsState {
+    /// Returns `true` if statistics are being recorded.
+    #[inline]
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Records the time spent executing `opcode`, started at `start`, if it was measured.
+    #[inline]
+    pub(crate) fn record_time(&mut self, opcode: u8, start: Option<Instant>) {
+        if let (Some(entry), Some(start)) = (self.opcodes.get_mut(opcode as usize), start) {
+            entry.1 += start.elapsed();
+        }
+    }
+}
+
+impl Context {
+    /// Enables or disables recording the statistics of the instructions executed by this
+    /// `Context`.
+    ///
+    /// Recording statistics slows the execution down.
+    #[inline]
+    pub fn set_vm_stats(&mut self, vm_stats: bool) {
+        self.vm.stats.enabled = vm_stats;
+    }
+
+    /// Clears the statistics recorded so far.
+    #[inline]
+    pub fn reset_vm_stats(&mut self) {
+        let enabled = self.vm.stats.enabled;
+        self.vm.stats = VmStatsState {
+            enabled,
+            ..VmStatsState::default()
+        };
+    }
+
+    /// Returns a report of the statistics recorded so far.
+    pub fn vm_stats(&self) -> VmStats {
+        let stats = &self.vm.stats;
+        let mut report = VmStats::default();
+
+        let mut categories: FxHashMap<OpcodeCategory, (u64, Duration)> = FxHashMap::default();
+        for (index, (count, time)) in stats.opcodes.iter().enumerate() {
+            if *count == 0 {
+                continue;
+            }
+            let opcode = Opcode::try_from(index as u8).expect("index must be a valid opcode");
+            let category = categories.entry(opcode.category()).or_default();
+            category.0 += count;
+            category.1 += *time;
+            report.instructions += count;
+            report.opcodes.push(OpcodeStats {
+                opcode,
+                count: *count,
+                time: *time,
+            });
+        }
+        report.categories = OpcodeCategory::ALL
+            .iter()
+            .filter_map(|category| {
+                categories.get(category).map(|(count, time)| CategoryStats {
+                    category: *category,
+                    count: *count,
+                    time: *time,
+                })
+            })
+            .collect();
+
+        for block in stats.blocks.values() {
+            let code = &block.code;
+            let name: JsString = if code.name == Sym::MAIN {
+                "<main>".into()
+            } else {
+                self.interner().resolve_expect(code.name).into()
+            };
+
+            report.functions.push(FunctionStats {
+                name: name.clone(),
+                instructions: block.counts.iter().sum(),
+            });
+            for (pc, count) in block.counts.iter().enumerate() {
+                if *count == 0 {
+                    continue;
+                }
+                let opcode = Opcode::try_from(code.code[pc]).expect("pc must be an instruction");
+                let mut next = pc;
+                report.hot_instructions.push(InstructionStats {
+                    function: name.clone(),
+                    pc,
+                    opcode,
+                    operands: code.instruction_operands(&mut next, self.interner()),
+                    count: *count,
+                });
+            }
+        }
+
+        report.opcodes.sort_by_key(|opcode| Reverse(opcode.count));
+        report
+            .categories
+            .sort_by_key(|category| Reverse(category.count));
+        report
+            .functions
+            .sort_by_key(|function| Reverse(function.instructions));
+        report
+            .hot_instructions
+            .sort_by_key(|instruction| Reverse(instruction.count));
+        report
+    }
+
+    /// Records the execution of the instruction at the current `pc`, returning its opcode and
+    /// the time it starts executing, to be passed to [`VmStatsState::record_time`].
+    ///
+    /// The time is not measured on `wasm32`, where `Instant::now` panics.
+    pub(crate) fn vm_stats_instruction(&mut self) -> (u8, Option<Instant>) {
+        let frame = self.vm.frame.as_deref().expect("no frame found");
+        let pc = frame.pc;
+        let code = &frame.code;
+        let opcode = code.code[pc];
+
+        let address: *const CodeBlock = &**code;
+        let block = self
+            .vm
+            .stats
+            .blocks
+            .entry(address)
+            .or_insert_with(|| BlockStats {
+                code: code.clone(),
+                counts: vec![0; code.code.len()],
+            });
+        block.counts[pc] += 1;
+        if let Some(entry) = self.vm.stats.opcodes.get_mut(opcode as usize) {
+            entry.0 += 1;
+        }
+
+        (opcode, (!cfg!(target_arch = "wasm32")).then(Instant::now))
+    }
+}
@@ -1,4 +1,21 @@
-use crate::{exec, Context, JsValue};
+use crate::{
+    class::boa_class,
+    exec, forward,
+    syntax::ast::Position,
+    vm::{
+        Debugger, FilteredTracer, InstructionTrace, JsonLinesTracer, OpcodeCategory, PauseReason,
+        RuntimeLimits, StepAction, TerminationReason, Tracer,
+    },
+    Context, JsError, JsNativeError, JsResult, JsValue,
+};
+use boa_gc::{Finalize, Trace};
+use std::{
+    cell::RefCell,
+    io::{self, Write},
+    rc::Rc,
+    thread,
+    time::Duration,
+};
 
 #[test]
 fn typeof_string() {
@@ -43,7 +60,10 @@ fn try_catch_finally_from_init() {
         }
     "#;
 
-    assert_eq!(Context::default().eval(source.as_bytes()), Err("h".into()));
+    assert_eq!(
+        Context::default().eval(source.as_bytes()),
+        Err(JsError::from_opaque("h".into()))
+    );
 }
 
 #[test]
@@ -137,3 +157,1085 @@ fn finally_block_binding_env() {
         Ok(JsValue::from("Hey hey people"))
     );
 }
+
+#[test]
+fn class_extends() {
+    let src = r#"
+        class A {
+            m() { return 1; }
+        }
+        class B extends A {}
+        const b = new B();
+        Object.getPrototypeOf(B) === A
+            && Object.getPrototypeOf(B.prototype) === A.prototype
+            && B.prototype !== A.prototype
+            && b instanceof B
+            && b.m() === 1
+    "#;
+    assert_eq!(&exec(src), "true");
+}
+
+#[test]
+fn native_class_extends() {
+    #[derive(Debug, Trace, Finalize)]
+    struct Animal {
+        name: String,
+    }
+
+    #[boa_class]
+    impl Animal {
+        #[constructor]
+        fn new(name: String) -> Self {
+            Self { name }
+        }
+
+        #[method]
+        fn speak(&self) -> String {
+            format!("{} makes a sound", self.name)
+        }
+
+        #[method]
+        fn rename(&mut self, name: String) {
+            self.name = name;
+        }
+    }
+
+    #[derive(Debug, Trace, Finalize)]
+    struct Dog {
+        animal: Animal,
+        sound: String,
+    }
+
+    impl AsRef<Animal> for Dog {
+        fn as_ref(&self) -> &Animal {
+            &self.animal
+        }
+    }
+
+    impl AsMut<Animal> for Dog {
+        fn as_mut(&mut self) -> &mut Animal {
+            &mut self.animal
+        }
+    }
+
+    #[boa_class(extends = "Animal")]
+    impl Dog {
+        #[constructor]
+        fn new() -> Self {
+            Self {
+                animal: Animal {
+                    name: "Rex".to_owned(),
+                },
+                sound: "woof".to_owned(),
+            }
+        }
+
+        #[method]
+        fn bark(&self) -> String {
+            self.sound.clone()
+        }
+    }
+
+    let mut context = Context::default();
+    context.register_global_class::<Animal>().unwrap();
+    context.register_global_class::<Dog>().unwrap();
+
+    let src = r#"
+        class Cat extends Animal {
+            purr() { return this.speak() + " and purrs"; }
+        }
+        new Cat("Tom").purr()
+    "#;
+    assert_eq!(
+        forward(&mut context, src),
+        "\"Tom makes a sound and purrs\""
+    );
+    assert_eq!(
+        forward(&mut context, "new Cat('Tom') instanceof Animal"),
+        "true"
+    );
+
+    assert_eq!(forward(&mut context, "new Dog().bark()"), "\"woof\"");
+    assert_eq!(forward(&mut context, "new Dog() instanceof Animal"), "true");
+    assert_eq!(
+        forward(&mut context, "new Dog().speak()"),
+        "\"Rex makes a sound\""
+    );
+    assert_eq!(
+        forward(
+            &mut context,
+            "let dog = new Dog(); dog.rename('Max'); dog.speak()"
+        ),
+        "\"Max makes a sound\""
+    );
+    assert!(
+        forward(&mut context, "Dog.prototype.bark.call(new Animal('Tom'))")
+            .starts_with("Uncaught \"TypeError\"")
+    );
+}
+
+#[test]
+fn native_class_functions() {
+    #[derive(Debug, Trace, Finalize)]
+    struct Point {
+        x: f64,
+        y: f64,
+    }
+
+    #[boa_class]
+    impl Point {
+        #[constructor]
+        fn new(x: f64, y: f64) -> Result<Self, JsNativeError> {
+            if x.is_nan() || y.is_nan() {
+                return Err(JsNativeError::range().with_message("coordinates can't be NaN"));
+            }
+            Ok(Self { x, y })
+        }
+
+        #[getter]
+        fn x(&self) -> f64 {
+            self.x
+        }
+
+        #[setter]
+        fn set_x(&mut self, x: f64) {
+            self.x = x;
+        }
+
+        #[getter]
+        #[boa(rename = "ordinate")]
+        fn y(&self) -> f64 {
+            self.y
+        }
+
+        #[method]
+        fn translate(&mut self, dx: f64, dy: f64) {
+            self.x += dx;
+            self.y += dy;
+        }
+
+        #[method]
+        #[boa(rename = "toString")]
+        fn to_js_string(&self) -> String {
+            format!("({}, {})", self.x, self.y)
+        }
+
+        #[method]
+        fn origin(context: &mut Context) -> JsResult<JsValue> {
+            let point = context.class_constructor::<Self>()?;
+            point.construct(&[0.into(), 0.into()], &point.clone().into(), context)
+        }
+
+        #[method]
+        #[boa(skip)]
+        #[allow(dead_code)]
+        fn skipped() {}
+
+        #[allow(dead_code)]
+        fn helper() {}
+    }
+
+    #[derive(Debug, Trace, Finalize)]
+    struct Opaque;
+
+    #[boa_class]
+    impl Opaque {}
+
+    let mut context = Context::default();
+    context.register_global_class::<Point>().unwrap();
+    context.register_global_class::<Opaque>().unwrap();
+
+    let src = r#"
+        let point = new Point(1, 2);
+        point.x = 3;
+        point.translate(1, 1);
+        [point.toString(), point.x, point.ordinate, String(point.y)].join()
+    "#;
+    assert_eq!(forward(&mut context, src), "\"(4, 3),4,3,undefined\"");
+    assert_eq!(forward(&mut context, "Point.length"), "2");
+    assert_eq!(
+        forward(&mut context, "Point.origin().toString()"),
+        "\"(0, 0)\""
+    );
+    assert_eq!(
+        forward(
+            &mut context,
+            "Object.getOwnPropertyNames(Point.prototype).sort().join()"
+        ),
+        "\"constructor,ordinate,toString,translate,x\""
+    );
+    assert_eq!(
+        forward(
+            &mut context,
+            "Object.getOwnPropertyNames(Point).sort().join()"
+        ),
+        "\"length,name,origin,prototype\""
+    );
+
+    // Constructor errors.
+    assert!(forward(&mut context, "new Point(NaN, 1)")
+        .starts_with("Uncaught \"RangeError\": \"coordinates can't be NaN\""));
+    assert!(forward(&mut context, "Point(1, 2)").starts_with("Uncaught \"TypeError\""));
+    assert!(forward(&mut context, "new Opaque()").starts_with("Uncaught \"TypeError\""));
+
+    // Methods and accessors check the native data of `this`.
+    assert!(
+        forward(&mut context, "Point.prototype.translate.call({}, 1, 1)")
+            .starts_with("Uncaught \"TypeError\"")
+    );
+    assert!(forward(
+        &mut context,
+        "Object.getOwnPropertyDescriptor(Point.prototype, 'x').get.call(new Opaque())"
+    )
+    .starts_with("Uncaught \"TypeError\""));
+}
+
+#[test]
+fn heap_limit_throws_range_error() {
+    let mut context = Context::default();
+    let limit = context.gc_stats().heap.live_bytes() + 4 * 1024 * 1024;
+    context.set_heap_limit(Some(limit));
+
+    let source = r#"
+        let objects = [];
+        let name;
+        try {
+            while (true) {
+                objects.push({});
+            }
+        } catch (e) {
+            objects = null;
+            name = e.name;
+        }
+        name
+    "#;
+    assert_eq!(
+        context.eval(source.as_bytes()),
+        Ok(JsValue::from("RangeError"))
+    );
+}
+
+#[test]
+fn heap_limit_counts_strings() {
+    let mut context = Context::default();
+    let limit = context.gc_stats().heap.limited_bytes() + 4 * 1024 * 1024;
+    context.set_heap_limit(Some(limit));
+
+    let source = r#"
+        let s = 'x';
+        let name;
+        try {
+            while (true) {
+                s += s;
+            }
+        } catch (e) {
+            name = e.name;
+        }
+        [name, s.length]
+    "#;
+    let result = context.eval(source.as_bytes()).unwrap();
+    let result = result.as_object().unwrap();
+    assert_eq!(
+        result.get(0, &mut context).unwrap(),
+        JsValue::from("RangeError")
+    );
+    // The string that would have exceeded the limit was never allocated.
+    let length = result.get(1, &mut context).unwrap().as_number().unwrap();
+    assert!((1024.0 * 1024.0..=4.0 * 1024.0 * 1024.0).contains(&length));
+}
+
+#[test]
+fn heap_limit_counts_array_buffers() {
+    let mut context = Context::default();
+    let limit = context.gc_stats().heap.limited_bytes() + 1024 * 1024;
+    context.set_heap_limit(Some(limit));
+
+    let source = r#"
+        try {
+            new ArrayBuffer(2 * 1024 * 1024);
+        } catch (e) {
+            e.name
+        }
+    "#;
+    assert_eq!(
+        context.eval(source.as_bytes()),
+        Ok(JsValue::from("RangeError"))
+    );
+    assert_eq!(
+        context.eval("new ArrayBuffer(1024).byteLength".as_bytes()),
+        Ok(JsValue::new(1024))
+    );
+}
+
+#[test]
+fn heap_limit_is_scoped_to_its_context() {
+    let mut limited = Context::default();
+    let limit = limited.gc_stats().heap.live_bytes() + 1024 * 1024;
+    limited.set_heap_limit(Some(limit));
+
+    let source = r#"
+        var objects = [];
+        for (let i = 0; i < 20000; i++) {
+            objects.push({});
+        }
+        objects.length
+    "#;
+
+    // Other contexts of the thread are not limited.
+    let mut context = Context::default();
+    assert_eq!(context.eval(source.as_bytes()), Ok(JsValue::new(20_000)));
+    assert_eq!(context.heap_limit(), None);
+
+    assert!(limited.eval(source.as_bytes()).is_err());
+    assert_eq!(boa_gc::heap_limit(), None);
+}
+
+#[test]
+fn heap_limit_terminates() {
+    let mut context = Context::default();
+    let limit = context.gc_stats().heap.live_bytes() + 4 * 1024 * 1024;
+    context.set_heap_limit(Some(limit));
+    context.set_heap_limit_action(crate::context::HeapLimitAction::Terminate);
+
+    let source = r#"
+        var objects = [];
+        try {
+            while (true) {
+                objects.push({});
+            }
+        } catch (e) {
+            "caught"
+        } finally {
+            "finally"
+        }
+    "#;
+    assert_eq!(
+        context.eval(source.as_bytes()),
+        Err(JsError::Termination(TerminationReason::HeapLimit))
+    );
+
+    context.set_heap_limit(None);
+    assert_eq!(
+        context.eval("objects = null; 1 + 1".as_bytes()),
+        Ok(JsValue::new(2))
+    );
+}
+
+#[test]
+fn gc_stats_counts_objects() {
+    let mut context = Context::default();
+    context
+        .eval("var arrays = [[], [], []]; var map = new Map();".as_bytes())
+        .unwrap();
+    boa_gc::force_collect();
+
+    let stats = context.gc_stats();
+    assert!(stats.objects["Array"] >= 4);
+    assert!(stats.objects["Map"] >= 1);
+    assert!(stats.heap.live_bytes() > 0);
+    assert!(stats.heap.major_collections >= 1);
+}
+
+#[test]
+fn heap_snapshot() {
+    let mut context = Context::default();
+    context
+        .eval("class Foo {}; var leak = new Foo(); leak.foo = {};".as_bytes())
+        .unwrap();
+
+    let path = std::env::temp_dir().join(format!("boa-{}.heapsnapshot", std::process::id()));
+    context.write_heap_snapshot(&path).unwrap();
+    let snapshot: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    let node_count = snapshot["snapshot"]["node_count"].as_u64().unwrap() as usize;
+    let edge_count = snapshot["snapshot"]["edge_count"].as_u64().unwrap() as usize;
+    let nodes = snapshot["nodes"].as_array().unwrap();
+    let edges = snapshot["edges"].as_array().unwrap();
+    let strings = snapshot["strings"].as_array().unwrap();
+    assert_eq!(nodes.len(), node_count * 6);
+    assert_eq!(edges.len(), edge_count * 3);
+
+    // Find the `Foo` instance, and check that it references its `foo` property.
+    let mut first_edge = 0;
+    let mut found = false;
+    for node in nodes.chunks(6) {
+        let edge_count = node[4].as_u64().unwrap() as usize;
+        let name = &strings[node[1].as_u64().unwrap() as usize];
+        if name == "Foo" && node[0] == 1 {
+            found |= edges[first_edge * 3..(first_edge + edge_count) * 3]
+                .chunks(3)
+                .any(|edge| edge[0] == 2 && strings[edge[1].as_u64().unwrap() as usize] == "foo");
+        }
+        first_edge += edge_count;
+    }
+    assert!(found);
+}
+
+#[test]
+fn loop_iteration_limit() {
+    let mut context = Context::default();
+    let mut limits = RuntimeLimits::default();
+    limits.set_loop_iteration_limit(1000);
+    context.set_runtime_limits(limits);
+
+    let source = r#"
+        try {
+            while (true) {}
+        } catch (e) {
+            "caught"
+        } finally {
+            "finally"
+        }
+    "#;
+    assert_eq!(
+        context.eval(source.as_bytes()),
+        Err(JsError::Termination(TerminationReason::LoopIterationLimit))
+    );
+
+    assert_eq!(
+        context.eval("do {} while (true)".as_bytes()),
+        Err(JsError::Termination(TerminationReason::LoopIterationLimit))
+    );
+
+    // The counter is reset for every execution.
+    let source = "var i = 0; while (i < 600) { i++; } i";
+    assert_eq!(context.eval(source.as_bytes()), Ok(JsValue::new(600)));
+    assert_eq!(context.eval(source.as_bytes()), Ok(JsValue::new(600)));
+    assert_eq!(context.termination_reason(), None);
+}
+
+#[test]
+fn instruction_limit() {
+    let mut context = Context::default();
+    let mut limits = RuntimeLimits::default();
+    limits.set_instruction_limit(10_000);
+    context.set_runtime_limits(limits);
+
+    let source = r#"
+        function spin() {
+            for (;;) {}
+        }
+        try { spin() } catch (e) {}
+    "#;
+    assert_eq!(
+        context.eval(source.as_bytes()),
+        Err(JsError::Termination(TerminationReason::InstructionLimit))
+    );
+}
+
+#[test]
+fn call_depth_limit() {
+    let mut context = Context::default();
+    let mut limits = RuntimeLimits::default();
+    limits.set_call_depth_limit(50);
+    context.set_runtime_limits(limits);
+
+    let source = r#"
+        function depth(n) {
+            return n === 0 ? 0 : 1 + depth(n - 1);
+        }
+        depth(40)
+    "#;
+    assert_eq!(context.eval(source.as_bytes()), Ok(JsValue::new(40)));
+
+    let source = r#"
+        function recurse() {
+            try {
+                return recurse();
+            } catch (e) {
+                return "caught";
+            }
+        }
+        recurse()
+    "#;
+    assert_eq!(
+        context.eval(source.as_bytes()),
+        Err(JsError::Termination(TerminationReason::CallDepthLimit))
+    );
+}
+
+#[test]
+fn interrupt_handle_terminates() {
+    let mut context = Context::default();
+    let handle = context.interrupt_handle();
+
+    // A termination requested while nothing runs terminates the next execution.
+    handle.terminate();
+    assert!(handle.is_terminating());
+    assert_eq!(
+        context.eval("1 + 1".as_bytes()),
+        Err(JsError::Termination(TerminationReason::Interrupted))
+    );
+    assert!(!handle.is_terminating());
+
+    // Unless it is reset before.
+    handle.terminate();
+    handle.reset();
+    assert_eq!(context.eval("1 + 1".as_bytes()), Ok(JsValue::new(2)));
+
+    let interrupter = {
+        let handle = handle.clone();
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(20));
+            handle.terminate();
+        })
+    };
+
+    let source = r#"
+        let log = [];
+        try {
+            while (true) {}
+        } catch (e) {
+            log.push("caught");
+        } finally {
+            log.push("finally");
+        }
+    "#;
+    let result = context.eval(source.as_bytes());
+    interrupter.join().unwrap();
+
+    assert_eq!(
+        result,
+        Err(JsError::Termination(TerminationReason::Interrupted))
+    );
+    assert_eq!(
+        context.termination_reason(),
+        Some(TerminationReason::Interrupted)
+    );
+    assert_eq!(context.eval("log.length".as_bytes()), Ok(JsValue::new(0)));
+    assert_eq!(context.termination_reason(), None);
+
+    // A `continue` through a `finally` block goes back to the loop start without a backward jump.
+    // Stops the test if the termination is missed.
+    let mut limits = RuntimeLimits::default();
+    limits.set_instruction_limit(50_000_000);
+    context.set_runtime_limits(limits);
+    let interrupter = {
+        let handle = handle.clone();
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(20));
+            handle.terminate();
+        })
+    };
+    let result = context.eval("while (true) { try { continue; } finally {} }".as_bytes());
+    interrupter.join().unwrap();
+    assert_eq!(
+        result,
+        Err(JsError::Termination(TerminationReason::Interrupted))
+    );
+}
+
+#[test]
+fn terminated_execution_cannot_reenter() {
+    let mut context = Context::default();
+    // Stops the test if the termination is swallowed.
+    let mut limits = RuntimeLimits::default();
+    limits.set_instruction_limit(50_000_000);
+    context.set_runtime_limits(limits);
+
+    // Calls `first`, ignoring its error, then `second`.
+    context.register_global_function(
+        "guard",
+        2,
+        |_: &JsValue, args: &[JsValue], context: &mut Context| {
+            let first = args.get(0).cloned().unwrap_or_default();
+            let second = args.get(1).cloned().unwrap_or_default();
+            let _ = context.call(&first, &JsValue::undefined(), &[]);
+            context.call(&second, &JsValue::undefined(), &[])
+        },
+    );
+
+    let handle = context.interrupt_handle();
+    let interrupter = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(20));
+        handle.terminate();
+    });
+
+    let source = r#"
+        guard(() => { while (true) {} }, () => { while (true) {} })
+    "#;
+    let result = context.eval(source.as_bytes());
+    interrupter.join().unwrap();
+
+    assert_eq!(
+        result,
+        Err(JsError::Termination(TerminationReason::Interrupted))
+    );
+}
+
+/// Records the lines where the execution pauses, resuming with a fixed action.
+#[derive(Debug)]
+struct Stepper {
+    action: StepAction,
+    lines: Rc<RefCell<Vec<u32>>>,
+}
+
+impl Debugger for Stepper {
+    fn on_pause(&mut self, _context: &mut Context, _reason: PauseReason, line: u32) -> StepAction {
+        self.lines.borrow_mut().push(line);
+        self.action
+    }
+}
+
+#[test]
+fn debugger_steps() {
+    // The source starts with a new line, so `var f` is on line 2.
+    let source = r#"
+        var f = function () {
+            return 1;
+        };
+        var a = f();
+        var b = 2;
+    "#;
+
+    let steps = |action, breakpoint: Option<u32>| {
+        let lines = Rc::new(RefCell::new(Vec::new()));
+        let mut context = Context::default();
+        context.set_debugger(Stepper {
+            action,
+            lines: lines.clone(),
+        });
+        if let Some(line) = breakpoint {
+            context.set_breakpoint("", line);
+        } else {
+            context.debugger_pause();
+        }
+        context.eval(source.as_bytes()).unwrap();
+        lines.take()
+    };
+
+    assert_eq!(steps(StepAction::StepIn, None), [2, 5, 3, 6]);
+    assert_eq!(steps(StepAction::StepOver, None), [2, 5, 6]);
+    assert_eq!(steps(StepAction::StepOut, Some(3)), [3, 6]);
+    assert_eq!(steps(StepAction::Continue, Some(5)), [5]);
+}
+
+#[test]
+fn breakpoints_in_bodies_without_braces() {
+    let source = r#"
+        var count = 0;
+        for (var i = 0; i < 2; i++)
+            count++;
+        if (count == 2)
+            count = 0;
+        else
+            count = 1;
+    "#;
+
+    let lines = Rc::new(RefCell::new(Vec::new()));
+    let mut context = Context::default();
+    context.set_debugger(Stepper {
+        action: StepAction::Continue,
+        lines: lines.clone(),
+    });
+    context.set_breakpoint("", 4);
+    context.set_breakpoint("", 6);
+    context.set_breakpoint("", 8);
+
+    context.eval(source).unwrap();
+    assert_eq!(lines.take(), [4, 4, 6]);
+}
+
+#[test]
+fn breakpoints_are_keyed_by_source() {
+    let source = r#"
+        function f() {
+            return 1;
+        }
+        f();
+    "#;
+
+    let lines = Rc::new(RefCell::new(Vec::new()));
+    let mut context = Context::default();
+    context.set_debugger(Stepper {
+        action: StepAction::Continue,
+        lines: lines.clone(),
+    });
+    context.set_breakpoint("a.js", 3);
+    context.set_breakpoint("a.js", 5);
+
+    context.eval_with_name(source, "b.js").unwrap();
+    context.eval(source).unwrap();
+    assert!(lines.borrow().is_empty());
+
+    context.eval_with_name(source, "a.js").unwrap();
+    assert_eq!(lines.take(), [5, 3]);
+
+    assert!(context.remove_breakpoint("a.js", 5));
+    assert!(!context.remove_breakpoint("b.js", 3));
+    context.eval_with_name(source, "a.js").unwrap();
+    assert_eq!(lines.take(), [3]);
+
+    context.clear_source_breakpoints("a.js");
+    context.eval_with_name(source, "a.js").unwrap();
+    assert!(lines.borrow().is_empty());
+}
+
+#[test]
+fn debugger_inspects_frames() {
+    #[derive(Debug)]
+    struct Inspector(Rc<RefCell<Vec<String>>>);
+
+    impl Debugger for Inspector {
+        fn on_pause(
+            &mut self,
+            context: &mut Context,
+            reason: PauseReason,
+            line: u32,
+        ) -> StepAction {
+            assert_eq!(reason, PauseReason::Breakpoint);
+            assert_eq!(context.frame_count(), 2);
+
+            let mut log = self.0.borrow_mut();
+            log.push(format!("line {line}"));
+            for depth in 0..context.frame_count() {
+                let frame = context.debug_frame(depth).unwrap();
+                let mut locals: Vec<_> = frame
+                    .locals()
+                    .iter()
+                    .filter(|(name, _)| name.as_str() != "arguments")
+                    .map(|(name, value)| match value {
+                        Some(value) => format!("{name}={}", value.display()),
+                        None => format!("{name}=<uninitialized>"),
+                    })
+                    .collect();
+                locals.sort();
+                log.push(format!(
+                    "{} {} this={}",
+                    frame.name(),
+                    locals.join(","),
+                    frame.this().is_object()
+                ));
+            }
+            assert!(context.debug_frame(2).is_none());
+
+            assert!(context.set_frame_local(0, "b", JsValue::new(10)));
+            assert!(context.set_frame_local(1, "x", JsValue::new(20)));
+            assert!(!context.set_frame_local(1, "y", JsValue::new(30)));
+            assert!(!context.set_frame_local(0, "missing", JsValue::new(40)));
+            StepAction::Continue
+        }
+    }
+
+    let source = r#"
+        let x = 1;
+        function f(a) {
+            let b = a + 1;
+            return b;
+        }
+        let y = f(x);
+    "#;
+
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let mut context = Context::default();
+    context.set_debugger(Inspector(log.clone()));
+    context.set_breakpoint("", 5);
+    context.eval(source.as_bytes()).unwrap();
+    assert_eq!(context.eval("[x, y].join()"), Ok(JsValue::from("20,10")));
+
+    assert_eq!(
+        *log.borrow(),
+        [
+            "line 5",
+            "f a=1,b=2 this=true",
+            "<main> x=1,y=<uninitialized> this=true",
+        ]
+    );
+}
+
+#[test]
+fn debugger_hooks() {
+    #[derive(Debug, Default)]
+    struct Counter {
+        calls: Rc<RefCell<(usize, usize, usize)>>,
+    }
+
+    impl Debugger for Counter {
+        fn on_call(&mut self, _context: &mut Context) {
+            self.calls.borrow_mut().0 += 1;
+        }
+
+        fn on_return(&mut self, _context: &mut Context, _value: &JsValue) {
+            self.calls.borrow_mut().1 += 1;
+        }
+
+        fn on_exception(&mut self, _context: &mut Context, error: &JsValue) {
+            assert_eq!(error, &JsValue::new(1));
+            self.calls.borrow_mut().2 += 1;
+        }
+    }
+
+    let source = r#"
+        function f() {
+            throw 1;
+        }
+        try {
+            f();
+        } catch (e) {}
+    "#;
+
+    let counter = Counter::default();
+    let calls = counter.calls.clone();
+    let mut context = Context::default();
+    context.set_debugger(counter);
+    context.eval(source.as_bytes()).unwrap();
+
+    // `f` throws, so only the script returns.
+    assert_eq!(*calls.borrow(), (2, 1, 1));
+}
+
+#[test]
+fn coverage() {
+    let source = r#"
+        function f(x) {
+            if (x) {
+                return 1;
+            }
+            return 2;
+        }
+        function g() {
+            return 3;
+        }
+        f(true);
+        f(true);
+        let y = 0; for (let i = 0; i < 3; i++) { y++; }
+    "#;
+
+    let mut context = Context::default();
+    context.set_coverage(true);
+    context.eval(source.as_bytes()).unwrap();
+    let coverage = context.take_coverage();
+
+    let functions: Vec<_> = coverage
+        .functions()
+        .iter()
+        .map(|f| (f.name().to_string(), f.line(), f.count()))
+        .collect();
+    assert_eq!(functions, [("f".to_owned(), 3, 2), ("g".to_owned(), 9, 0)]);
+
+    let lines = coverage.lines();
+    assert_eq!(lines.get(&3), Some(&2));
+    assert_eq!(lines.get(&4), Some(&2));
+    assert_eq!(lines.get(&6), Some(&0));
+    assert_eq!(lines.get(&9), Some(&0));
+    assert_eq!(lines.get(&12), Some(&1));
+    assert_eq!(lines.get(&13), Some(&3));
+
+    // The statements sharing a line are counted separately.
+    let statements: Vec<_> = coverage
+        .statements()
+        .range(Position::new(13, 1)..)
+        .map(|(position, count)| (position.column_number(), *count))
+        .collect();
+    assert_eq!(statements, [(9, 1), (20, 1), (51, 3)]);
+
+    let branches: Vec<_> = coverage
+        .branches()
+        .iter()
+        .map(|b| (b.position(), b.taken(), b.not_taken()))
+        .collect();
+    assert_eq!(
+        branches,
+        [(Position::new(3, 13), 0, 2), (Position::new(13, 20), 1, 3)]
+    );
+
+    let lcov = coverage.to_lcov("test.js");
+    assert!(lcov.starts_with("TN:\nSF:test.js\n"));
+    assert!(lcov.contains("FNDA:2,f\n"));
+    assert!(lcov.contains("BRDA:3,0,1,2\n"));
+    assert!(lcov.contains("DA:6,0\n"));
+    assert!(lcov.contains("DA:13,3\n"));
+    assert!(lcov.contains("BRDA:13,0,1,3\n"));
+    assert!(lcov.ends_with("end_of_record\n"));
+
+    // Taking the coverage resets it.
+    assert!(context.take_coverage().lines().is_empty());
+}
+
+#[test]
+fn cpu_profile() {
+    let source = r#"
+        function f() {
+            let sum = 0;
+            for (let i = 0; i < 1000; i++) {
+                sum += i;
+            }
+            return sum;
+        }
+        f();
+    "#;
+
+    let mut context = Context::default();
+    assert!(context.stop_cpu_profile().is_none());
+    context.start_cpu_profile(Duration::ZERO);
+    context.eval(source.as_bytes()).unwrap();
+    let profile = context.stop_cpu_profile().unwrap();
+
+    let nodes = profile.nodes();
+    assert_eq!(nodes[0].name().as_str(), "(root)");
+    let main = &nodes[nodes[0].children()[0]];
+    assert_eq!(main.name().as_str(), "<main>");
+    let f = &nodes[main.children()[0]];
+    assert_eq!((f.name().as_str(), f.line()), ("f", 3));
+    assert!(f.hit_count() > 0);
+
+    let cpuprofile = profile.to_cpuprofile();
+    assert_eq!(cpuprofile["nodes"].as_array().unwrap().len(), nodes.len());
+    assert_eq!(
+        cpuprofile["nodes"][0]["callFrame"]["functionName"],
+        "(root)"
+    );
+    assert!(profile
+        .to_folded()
+        .lines()
+        .any(|line| line.starts_with("<main>;f ")));
+}
+
+#[test]
+fn tracer() {
+    #[derive(Clone, Default)]
+    struct Buffer(Rc<RefCell<Vec<u8>>>);
+
+    impl Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.borrow_mut().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let source = r#"
+        function f() {
+            return 1;
+        }
+        f();
+    "#;
+
+    let buffer = Buffer::default();
+    let mut context = Context::default();
+    context.set_tracer(FilteredTracer::new(
+        JsonLinesTracer::new(buffer.clone()),
+        ["f"],
+    ));
+    context.eval(source.as_bytes()).unwrap();
+
+    let output = String::from_utf8(buffer.0.take()).unwrap();
+    let events: Vec<serde_json::Value> = output
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+
+    // Only the frame of `f` is traced.
+    assert!(events.iter().all(|event| event["function"] == "f"));
+    assert_eq!(events[0]["event"], "enter");
+    assert_eq!(events[0]["depth"], 2);
+    let last = events.last().unwrap();
+    assert_eq!(last["event"], "exit");
+    assert!(events
+        .iter()
+        .any(|event| event["event"] == "instruction" && event["opcode"] == "Return"));
+}
+
+#[test]
+fn tracer_without_timing() {
+    #[derive(Clone, Default)]
+    struct Durations(Rc<RefCell<Vec<Option<Duration>>>>);
+
+    impl Tracer for Durations {
+        fn wants_timing(&self) -> bool {
+            false
+        }
+
+        fn on_instruction(&mut self, event: &InstructionTrace<'_>) {
+            self.0.borrow_mut().push(event.duration);
+        }
+    }
+
+    let durations = Durations::default();
+    let mut context = Context::default();
+    context.set_tracer(durations.clone());
+    context.eval("1 + 1".as_bytes()).unwrap();
+
+    let durations = durations.0.take();
+    assert!(!durations.is_empty());
+    assert!(durations.iter().all(Option::is_none));
+}
+
+#[test]
+fn vm_stats() {
+    let source = r#"
+        function f() {
+            let sum = 0;
+            for (let i = 0; i < 10; i++) {
+                sum += i;
+            }
+            return sum;
+        }
+        f();
+    "#;
+
+    let mut context = Context::default();
+    context.set_vm_stats(true);
+    context.eval(source.as_bytes()).unwrap();
+    let stats = context.vm_stats();
+
+    assert!(stats.instructions > 0);
+    assert_eq!(
+        stats.opcodes.iter().map(|op| op.count).sum::<u64>(),
+        stats.instructions
+    );
+    assert!(stats
+        .opcodes
+        .windows(2)
+        .all(|pair| pair[0].count >= pair[1].count));
+    assert!(stats
+        .categories
+        .iter()
+        .any(|category| category.category == OpcodeCategory::ControlFlow));
+
+    assert_eq!(stats.functions[0].name.as_str(), "f");
+    let hottest = &stats.hot_instructions[0];
+    assert_eq!(hottest.function.as_str(), "f");
+    assert!(hottest.count >= 10);
+
+    context.reset_vm_stats();
+    assert_eq!(context.vm_stats().instructions, 0);
+}
+
+#[cfg(feature = "jit")]
+#[test]
+fn jit_hotness_counters() {
+    let source = r#"
+        function f() {}
+        function g() {
+            for (let i = 0; i < 5; i++) {}
+        }
+        for (let i = 0; i < 20; i++) {
+            f();
+        }
+        g();
+    "#;
+
+    let mut context = Context::default();
+    context.set_jit_threshold(10);
+    let statement_list = context.parse(source).unwrap();
+    let code = context.compile(&statement_list).unwrap();
+    context.execute(code.clone()).unwrap();
+
+    assert_eq!(code.invocation_count(), 1);
+    assert_eq!(code.loop_iteration_count(), 20);
+    assert!(code.is_hot());
+
+    let (f, g) = (&code.functions[0], &code.functions[1]);
+    assert_eq!(f.invocation_count(), 20);
+    assert_eq!(f.loop_iteration_count(), 0);
+    assert!(f.is_hot());
+    assert_eq!(g.invocation_count(), 1);
+    assert_eq!(g.loop_iteration_count(), 5);
+    assert!(!g.is_hot());
+}
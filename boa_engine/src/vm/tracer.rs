@@ -0,0 +1,383 @@
+//! Tracing of the instructions executed by the VM.
+
+use crate::{
+    vm::{CodeBlock, Opcode},
+    Context, JsValue,
+};
+use boa_interner::{Interner, Sym, ToInternedString};
+use rustc_hash::FxHashSet;
+use serde_json::json;
+use std::{fmt, io::Write, time::Duration};
+
+/// Receives the events of the execution of the VM, once attached with [`Context::set_tracer`].
+///
+/// All the methods have an empty default implementation.
+pub trait Tracer {
+    /// Returns `true` if the frames of `function` must be traced. Called once for each frame,
+    /// before [`Tracer::on_frame_enter`].
+    fn should_trace(&mut self, _function: &str) -> bool {
+        true
+    }
+
+    /// Returns `true` if the duration of the instructions must be measured, which reads the
+    /// clock around each traced instruction.
+    fn wants_timing(&self) -> bool {
+        true
+    }
+
+    /// Called when a traced frame starts or resumes executing.
+    fn on_frame_enter(&mut self, _event: &FrameEnter<'_>) {}
+
+    /// Called after executing an instruction of a traced frame.
+    fn on_instruction(&mut self, _event: &InstructionTrace<'_>) {}
+
+    /// Called when a traced frame stops executing, because it returned, yielded or threw.
+    fn on_frame_exit(&mut self, _event: &FrameExit<'_>) {}
+}
+
+/// A frame starts or resumes executing.
+#[derive(Debug, Clone, Copy)]
+pub struct FrameEnter<'a> {
+    /// Name of the function of the frame, `<main>` for scripts.
+    pub function: &'a str,
+    /// Number of frames on the call stack, including this frame.
+    pub depth: usize,
+    /// Code of the frame, which can be disassembled with `interner`.
+    pub code: &'a CodeBlock,
+    /// Interner of the `Context`.
+    pub interner: &'a Interner,
+}
+
+/// An instruction was executed.
+#[derive(Debug, Clone, Copy)]
+pub struct InstructionTrace<'a> {
+    /// Name of the function of the frame.
+    pub function: &'a str,
+    /// Position of the instruction in the bytecode of the frame.
+    pub pc: usize,
+    /// The instruction.
+    pub opcode: Opcode,
+    /// The operands of the instruction, formatted.
+    pub operands: &'a str,
+    /// The whole stack of the VM after the instruction, top last.
+    pub stack: &'a [JsValue],
+    /// Time spent executing the instruction, including the functions it called, or `None` if
+    /// the tracer doesn't [want it](Tracer::wants_timing) or the target can't measure it, like
+    /// `wasm32`, where `std::time::Instant` is not available.
+    pub duration: Option<Duration>,
+}
+
+/// A frame stops executing.
+#[derive(Debug, Clone, Copy)]
+pub struct FrameExit<'a> {
+    /// Name of the function of the frame.
+    pub function: &'a str,
+    /// Number of frames on the call stack, including this frame.
+    pub depth: usize,
+    /// The whole stack of the VM, top last.
+    pub stack: &'a [JsValue],
+}
+
+/// The tracer of a `Context`.
+#[derive(Default)]
+pub(crate) struct TracerState {
+    tracer: Option<Box<dyn Tracer>>,
+}
+
+impl fmt::Debug for TracerState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TracerState")
+            .field("attached", &self.tracer.is_some())
+            .finish()
+    }
+}
+
+impl TracerState {
+    /// Returns `true` if a tracer is attached.
+    #[inline]
+    pub(crate) fn is_attached(&self) -> bool {
+        self.tracer.is_some()
+    }
+
+    /// Returns `true` if the duration of the instructions must be measured.
+    #[inline]
+    pub(crate) fn wants_timing(&self) -> bool {
+        !cfg!(target_arch = "wasm32") && self.tracer.as_ref().map_or(false, |t| t.wants_timing())
+    }
+}
+
+impl Context {
+    /// Attaches a tracer to this `Context`, replacing the previous one.
+    #[inline]
+    pub fn set_tracer<T>(&mut self, tracer: T)
+    where
+        T: Tracer + 'static,
+    {
+        self.vm.tracer.tracer = Some(Box::new(tracer));
+    }
+
+    /// Detaches the tracer of this `Context`, returning it.
+    #[inline]
+    pub fn remove_tracer(&mut self) -> Option<Box<dyn Tracer>> {
+        self.vm.tracer.tracer.take()
+    }
+
+    /// Returns the name of the function of the current frame.
+    fn frame_function_name(&self) -> &str {
+        let name = self.vm.frame().code.name;
+        if name == Sym::MAIN {
+            "<main>"
+        } else {
+            self.interner().resolve_expect(name)
+        }
+    }
+
+    /// Reports that the current frame starts executing, returning `true` if it is traced.
+    pub(crate) fn trace_frame_enter(&mut self) -> bool {
+        let mut tracer = match self.vm.tracer.tracer.take() {
+            Some(tracer) => tracer,
+            None => return false,
+        };
+
+        let function = self.frame_function_name();
+        let traced = tracer.should_trace(function);
+        if traced {
+            tracer.on_frame_enter(&FrameEnter {
+                function,
+                depth: self.vm.call_depth,
+                code: &self.vm.frame().code,
+                interner: self.interner(),
+            });
+        }
+
+        self.vm.tracer.tracer = Some(tracer);
+        traced
+    }
+
+    /// Reports the execution of the instruction at `pc` of the current frame.
+    pub(crate) fn trace_instruction(
+        &mut self,
+        pc: usize,
+        operands: &str,
+        duration: Option<Duration>,
+    ) {
+        let mut tracer = match self.vm.tracer.tracer.take() {
+            Some(tracer) => tracer,
+            None => return,
+        };
+
+        if let Ok(opcode) = Opcode::try_from(self.vm.frame().code.code[pc]) {
+            tracer.on_instruction(&InstructionTrace {
+                function: self.frame_function_name(),
+                pc,
+                opcode,
+                operands,
+                stack: &self.vm.stack,
+                duration,
+            });
+        }
+
+        self.vm.tracer.tracer = Some(tracer);
+    }
+
+    /// Reports that the current frame stops executing.
+    pub(crate) fn trace_frame_exit(&mut self) {
+        let mut tracer = match self.vm.tracer.tracer.take() {
+            Some(tracer) => tracer,
+            None => return,
+        };
+
+        tracer.on_frame_exit(&FrameExit {
+            function: self.frame_function_name(),
+            depth: self.vm.call_depth,
+            stack: &self.vm.stack,
+        });
+
+        self.vm.tracer.tracer = Some(tracer);
+    }
+}
+
+/// Describes a value of the stack in one line.
+fn describe(value: &JsValue) -> String {
+    if value.is_callable() {
+        "[function]".to_string()
+    } else if value.is_object() {
+        "[object]".to_string()
+    } else {
+        value.display().to_string()
+    }
+}
+
+/// A tracer printing the code of the traced frames and a table of their instructions to stdout.
+///
+/// This is the tracer attached by [`Context::set_trace`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ConsoleTracer;
+
+impl ConsoleTracer {
+    const COLUMN_WIDTH: usize = 26;
+    const TIME_COLUMN_WIDTH: usize = Self::COLUMN_WIDTH / 2;
+    const OPCODE_COLUMN_WIDTH: usize = Self::COLUMN_WIDTH;
+    const OPERAND_COLUMN_WIDTH: usize = Self::COLUMN_WIDTH;
+    const NUMBER_OF_COLUMNS: usize = 4;
+}
+
+impl Tracer for ConsoleTracer {
+    fn on_frame_enter(&mut self, event: &FrameEnter<'_>) {
+        let msg = if event.depth > 1 {
+            " Call Frame "
+        } else {
+            " VM Start "
+        };
+
+        println!("{}\n", event.code.to_interned_string(event.interner));
+        println!(
+            "{msg:-^width$}",
+            width = Self::COLUMN_WIDTH * Self::NUMBER_OF_COLUMNS - 10
+        );
+        println!(
+            "{:<time$} {:<opcode$} {:<operand$} Top Of Stack\n",
+            "Time",
+            "Opcode",
+            "Operands",
+            time = Self::TIME_COLUMN_WIDTH,
+            opcode = Self::OPCODE_COLUMN_WIDTH,
+            operand = Self::OPERAND_COLUMN_WIDTH,
+        );
+    }
+
+    fn on_instruction(&mut self, event: &InstructionTrace<'_>) {
+        println!(
+            "{:<time$} {:<opcode$} {:<operand$} {}",
+            event.duration.map_or_else(
+                || "-".to_string(),
+                |duration| format!("{}μs", duration.as_micros())
+            ),
+            event.opcode.as_str(),
+            event.operands,
+            event
+                .stack
+                .last()
+                .map_or_else(|| "<empty>".to_string(), describe),
+            time = Self::TIME_COLUMN_WIDTH,
+            opcode = Self::OPCODE_COLUMN_WIDTH,
+            operand = Self::OPERAND_COLUMN_WIDTH,
+        );
+    }
+
+    fn on_frame_exit(&mut self, event: &FrameExit<'_>) {
+        println!("\nStack:");
+        if event.stack.is_empty() {
+            println!("    <empty>");
+        } else {
+            for (i, value) in event.stack.iter().enumerate() {
+                println!(
+                    "{i:04}{:<width$} {}",
+                    "",
+                    describe(value),
+                    width = Self::COLUMN_WIDTH / 2 - 4,
+                );
+            }
+        }
+        println!("\n");
+    }
+}
+
+/// A tracer writing each event as a line of JSON.
+///
+/// Each line is an object with an `event` field, which is `enter`, `instruction` or `exit`, and
+/// the fields of the event. Durations are in nanoseconds, or `null` when they are not measured,
+/// and values of the stack are described
+/// by strings. Write errors are ignored.
+#[derive(Debug)]
+pub struct JsonLinesTracer<W> {
+    writer: W,
+}
+
+impl<W: Write> JsonLinesTracer<W> {
+    /// Creates a tracer writing to `writer`.
+    #[inline]
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+
+    fn write(&mut self, event: &serde_json::Value) {
+        let _ = writeln!(self.writer, "{event}");
+    }
+}
+
+impl<W: Write> Tracer for JsonLinesTracer<W> {
+    fn on_frame_enter(&mut self, event: &FrameEnter<'_>) {
+        self.write(&json!({
+            "event": "enter",
+            "function": event.function,
+            "depth": event.depth,
+        }));
+    }
+
+    fn on_instruction(&mut self, event: &InstructionTrace<'_>) {
+        self.write(&json!({
+            "event": "instruction",
+            "function": event.function,
+            "pc": event.pc,
+            "opcode": event.opcode.as_str(),
+            "operands": event.operands,
+            "stack": event.stack.iter().map(describe).collect::<Vec<_>>(),
+            "duration": event.duration.map(|duration| duration.as_nanos() as u64),
+        }));
+    }
+
+    fn on_frame_exit(&mut self, event: &FrameExit<'_>) {
+        self.write(&json!({
+            "event": "exit",
+            "function": event.function,
+            "depth": event.depth,
+            "stack": event.stack.iter().map(describe).collect::<Vec<_>>(),
+        }));
+    }
+}
+
+/// A tracer forwarding to another tracer the events of the frames of some functions only.
+#[derive(Debug)]
+pub struct FilteredTracer<T> {
+    tracer: T,
+    functions: FxHashSet<String>,
+}
+
+impl<T: Tracer> FilteredTracer<T> {
+    /// Creates a tracer forwarding to `tracer` the events of the frames of `functions`.
+    ///
+    /// Scripts are named `<main>`.
+    pub fn new<I, S>(tracer: T, functions: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            tracer,
+            functions: functions.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl<T: Tracer> Tracer for FilteredTracer<T> {
+    fn should_trace(&mut self, function: &str) -> bool {
+        self.functions.contains(function) && self.tracer.should_trace(function)
+    }
+
+    fn wants_timing(&self) -> bool {
+        self.tracer.wants_timing()
+    }
+
+    fn on_frame_enter(&mut self, event: &FrameEnter<'_>) {
+        self.tracer.on_frame_enter(event);
+    }
+
+    fn on_instruction(&mut self, event: &InstructionTrace<'_>) {
+        self.tracer.on_instruction(event);
+    }
+
+    fn on_frame_exit(&mut self, event: &FrameExit<'_>) {
+        self.tracer.on_frame_exit(event);
+    }
+}
@@ -0,0 +1,7 @@
+//! Checks the diagnostics of `#[boa_class]`.
+
+#[test]
+fn class_diagnostics() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/class/*.rs");
+}
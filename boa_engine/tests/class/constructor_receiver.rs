@@ -0,0 +1,15 @@
+use boa_engine::class::boa_class;
+use boa_gc::{Finalize, Trace};
+
+#[derive(Debug, Trace, Finalize)]
+struct Counter;
+
+#[boa_class]
+impl Counter {
+    #[constructor]
+    fn new(&self) -> Self {
+        Self
+    }
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use boa_engine::class::boa_class;
+use boa_gc::{Finalize, Trace};
+
+#[derive(Debug, Trace, Finalize)]
+struct Counter;
+
+#[boa_class]
+impl Counter {
+    #[method]
+    fn log(&self, context: &mut boa_engine::Context) {}
+}
+
+fn main() {}
@@ -0,0 +1,20 @@
+use boa_engine::class::boa_class;
+use boa_gc::{Finalize, Trace};
+
+#[derive(Debug, Trace, Finalize)]
+struct Counter;
+
+#[boa_class]
+impl Counter {
+    #[constructor]
+    fn new() -> Self {
+        Self
+    }
+
+    #[constructor]
+    fn with_count(_count: u32) -> Self {
+        Self
+    }
+}
+
+fn main() {}
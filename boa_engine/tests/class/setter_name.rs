@@ -0,0 +1,17 @@
+use boa_engine::class::boa_class;
+use boa_gc::{Finalize, Trace};
+
+#[derive(Debug, Trace, Finalize)]
+struct Counter {
+    count: u32,
+}
+
+#[boa_class]
+impl Counter {
+    #[setter]
+    fn count(&mut self, count: u32) {
+        self.count = count;
+    }
+}
+
+fn main() {}
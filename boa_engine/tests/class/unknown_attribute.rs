@@ -0,0 +1,14 @@
+use boa_engine::class::boa_class;
+use boa_gc::{Finalize, Trace};
+
+#[derive(Debug, Trace, Finalize)]
+struct Counter;
+
+#[boa_class]
+impl Counter {
+    #[method]
+    #[boa(hidden)]
+    fn hidden(&self) {}
+}
+
+fn main() {}
@@ -0,0 +1,557 @@
+//! The `#[boa_class]` attribute, implementing `Class` from the functions of an `impl` block.
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{
+    AttributeArgs, FnArg, ImplItem, ImplItemMethod, ItemImpl, Lit, LitStr, Meta, MetaNameValue,
+    NestedMeta, ReturnType, Type,
+};
+
+/// How a function of the `impl` block is exposed to JavaScript.
+enum Kind {
+    Constructor,
+    Method,
+    Getter,
+    Setter,
+}
+
+/// A function of the `impl` block exposed to JavaScript.
+struct ClassFunction {
+    kind: Kind,
+    ident: syn::Ident,
+    /// The name of the method or accessor property.
+    name: LitStr,
+    /// The receiver of the function, `Some(true)` if it is `&mut self`.
+    receiver: Option<bool>,
+    /// The types of the parameters converted from the arguments of the call.
+    parameters: Vec<Type>,
+    /// The function takes the `&mut Context` as its last parameter.
+    ///
+    /// This is never the case of functions with a receiver: the native data of `this` is
+    /// borrowed during their call, so calling back into JavaScript could borrow it again.
+    context: bool,
+    output: ReturnType,
+}
+
+/// Expands `#[boa_class]` on `item`.
+pub(crate) fn expand(args: AttributeArgs, mut item: ItemImpl) -> syn::Result<TokenStream> {
+    if let Some(trait_) = &item.trait_ {
+        return Err(syn::Error::new_spanned(
+            &trait_.1,
+            "`#[boa_class]` must be used on an inherent `impl` block",
+        ));
+    }
+    if !item.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &item.generics,
+            "`#[boa_class]` can't be used on generic types",
+        ));
+    }
+
+    let self_ident = match &*item.self_ty {
+        Type::Path(path) => path.path.segments.last().map(|segment| &segment.ident),
+        _ => None,
+    }
+    .ok_or_else(|| syn::Error::new_spanned(&item.self_ty, "expected the name of a type"))?
+    .clone();
+
+    let mut class_name = LitStr::new(&self_ident.to_string(), self_ident.span());
+    let mut parent = None;
+    for arg in args {
+        match arg {
+            NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                path,
+                lit: Lit::Str(lit),
+                ..
+            })) if path.is_ident("name") => class_name = lit,
+            NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                path,
+                lit: Lit::Str(lit),
+                ..
+            })) if path.is_ident("extends") => parent = Some(lit.parse::<syn::Path>()?),
+            arg => {
+                return Err(syn::Error::new_spanned(
+                    arg,
+                    "expected `name = \"...\"` or `extends = \"...\"`",
+                ))
+            }
+        }
+    }
+    let (parent, upcast) = match parent {
+        Some(parent) => (
+            quote! {
+                const PARENT: ::std::option::Option<::boa_engine::class::ClassParent> =
+                    ::std::option::Option::Some(::boa_engine::Context::class_constructor::<#parent>);
+            },
+            upcast(&parent),
+        ),
+        None => (quote!(), quote!()),
+    };
+
+    let mut functions = Vec::new();
+    for impl_item in &mut item.items {
+        if let ImplItem::Method(method) = impl_item {
+            if let Some(function) = class_function(method, &self_ident)? {
+                functions.push(function);
+            }
+        }
+    }
+
+    let mut constructors = functions
+        .iter()
+        .filter(|function| matches!(function.kind, Kind::Constructor));
+    let constructor = constructors.next();
+    if let Some(duplicate) = constructors.next() {
+        return Err(syn::Error::new_spanned(
+            &duplicate.ident,
+            "a class can only have one `#[constructor]`",
+        ));
+    }
+
+    let (class_length, constructor_body) = match constructor {
+        Some(constructor) => {
+            let ClassFunction {
+                ident,
+                parameters,
+                context,
+                output,
+                ..
+            } = constructor;
+            let call = call(quote!(Self::#ident), None, parameters, *context);
+            let result = if returns_self(output, &self_ident) {
+                quote!(::std::result::Result::Ok(result))
+            } else {
+                quote! {
+                    match result {
+                        ::std::result::Result::Ok(value) => ::std::result::Result::Ok(value),
+                        ::std::result::Result::Err(error) => context.throw(error),
+                    }
+                }
+            };
+            (
+                length(parameters),
+                quote! {
+                    #call
+                    #result
+                },
+            )
+        }
+        None => (
+            quote!(0),
+            quote! {
+                context.throw_type_error(::std::format!(
+                    "native class `{}` has no constructor",
+                    <Self as ::boa_engine::class::Class>::NAME,
+                ))
+            },
+        ),
+    };
+
+    let mut registrations = Vec::new();
+    let mut accessors: Vec<(&LitStr, Option<TokenStream>, Option<TokenStream>)> = Vec::new();
+    for function in &functions {
+        let ClassFunction {
+            kind,
+            ident,
+            name,
+            receiver,
+            parameters,
+            ..
+        } = function;
+        let function_length = length(parameters);
+        match kind {
+            Kind::Constructor => {}
+            Kind::Method if receiver.is_none() => registrations.push(quote! {
+                class.static_method(#name, #function_length, Self::#ident);
+            }),
+            Kind::Method => {
+                let native = native_method(ident, *receiver, parameters);
+                registrations.push(quote! {
+                    class.method(#name, #function_length, #native);
+                });
+            }
+            Kind::Getter | Kind::Setter => {
+                let is_getter = matches!(kind, Kind::Getter);
+                let prefix = if is_getter { "get" } else { "set" };
+                let function_name = LitStr::new(&format!("{prefix} {}", name.value()), name.span());
+                let native = native_method(ident, *receiver, parameters);
+                let builder = quote! {
+                    ::boa_engine::object::FunctionBuilder::typed(class.context(), #native)
+                        .name(#function_name)
+                        .length(#function_length)
+                        .build()
+                };
+
+                let index = match accessors
+                    .iter()
+                    .position(|(accessor, _, _)| accessor.value() == name.value())
+                {
+                    Some(index) => index,
+                    None => {
+                        accessors.push((name, None, None));
+                        accessors.len() - 1
+                    }
+                };
+                let slot = if is_getter {
+                    &mut accessors[index].1
+                } else {
+                    &mut accessors[index].2
+                };
+                if slot.is_some() {
+                    return Err(syn::Error::new_spanned(
+                        ident,
+                        format!("the accessor `{}` is already defined", name.value()),
+                    ));
+                }
+                *slot = Some(builder);
+            }
+        }
+    }
+
+    for (name, get, set) in accessors {
+        let get = option(get);
+        let set = option(set);
+        registrations.push(quote! {
+            let get = #get;
+            let set = #set;
+            class.accessor(
+                #name,
+                get,
+                set,
+                ::boa_engine::property::Attribute::CONFIGURABLE
+                    | ::boa_engine::property::Attribute::NON_ENUMERABLE,
+            );
+        });
+    }
+
+    let self_ty = &item.self_ty;
+    Ok(quote! {
+        #item
+
+        impl ::boa_engine::class::Class for #self_ty {
+            const NAME: &'static str = #class_name;
+            const LENGTH: usize = #class_length;
+            #parent
+
+            #[allow(unused_variables, unused_mut)]
+            fn constructor(
+                this: &::boa_engine::JsValue,
+                args: &[::boa_engine::JsValue],
+                context: &mut ::boa_engine::Context,
+            ) -> ::boa_engine::JsResult<Self> {
+                #constructor_body
+            }
+
+            fn init(
+                class: &mut ::boa_engine::class::ClassBuilder<'_>,
+            ) -> ::boa_engine::JsResult<()> {
+                #(#registrations)*
+                ::std::result::Result::Ok(())
+            }
+
+            #upcast
+        }
+    })
+}
+
+/// The `upcast` functions of a class extending `parent`, through the `AsRef` and `AsMut` impls of
+/// the native data of the class.
+fn upcast(parent: &syn::Path) -> TokenStream {
+    quote! {
+        fn upcast(
+            &self,
+            ancestor: ::std::any::TypeId,
+        ) -> ::std::option::Option<&dyn ::boa_engine::object::NativeObject> {
+            let parent = ::std::convert::AsRef::<#parent>::as_ref(self);
+            if ancestor == ::std::any::TypeId::of::<#parent>() {
+                ::std::option::Option::Some(parent)
+            } else {
+                <#parent as ::boa_engine::class::Class>::upcast(parent, ancestor)
+            }
+        }
+
+        fn upcast_mut(
+            &mut self,
+            ancestor: ::std::any::TypeId,
+        ) -> ::std::option::Option<&mut dyn ::boa_engine::object::NativeObject> {
+            let parent = ::std::convert::AsMut::<#parent>::as_mut(self);
+            if ancestor == ::std::any::TypeId::of::<#parent>() {
+                ::std::option::Option::Some(parent)
+            } else {
+                <#parent as ::boa_engine::class::Class>::upcast_mut(parent, ancestor)
+            }
+        }
+    }
+}
+
+/// Reads the attributes of `method`, removing the ones of `#[boa_class]`.
+///
+/// Returns `None` if the function is not exposed, because it has none of the attributes of
+/// `#[boa_class]`, or because it is marked with `#[boa(skip)]`.
+fn class_function(
+    method: &mut ImplItemMethod,
+    self_ident: &syn::Ident,
+) -> syn::Result<Option<ClassFunction>> {
+    let mut kind = None;
+    let mut rename = None;
+    let mut skip = false;
+
+    let mut attrs = Vec::with_capacity(method.attrs.len());
+    for attr in method.attrs.drain(..) {
+        let attr_kind = if attr.path.is_ident("constructor") {
+            Kind::Constructor
+        } else if attr.path.is_ident("method") {
+            Kind::Method
+        } else if attr.path.is_ident("getter") {
+            Kind::Getter
+        } else if attr.path.is_ident("setter") {
+            Kind::Setter
+        } else if attr.path.is_ident("boa") {
+            let list = match attr.parse_meta()? {
+                Meta::List(list) => list,
+                meta => return Err(syn::Error::new_spanned(meta, "expected `#[boa(...)]`")),
+            };
+            for nested in list.nested {
+                match nested {
+                    NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                        path,
+                        lit: Lit::Str(lit),
+                        ..
+                    })) if path.is_ident("rename") => rename = Some(lit),
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("skip") => skip = true,
+                    nested => {
+                        return Err(syn::Error::new_spanned(
+                            nested,
+                            "expected `rename = \"...\"` or `skip`",
+                        ))
+                    }
+                }
+            }
+            continue;
+        } else {
+            attrs.push(attr);
+            continue;
+        };
+
+        if !attr.tokens.is_empty() {
+            return Err(syn::Error::new_spanned(attr, "expected no arguments"));
+        }
+        if kind.is_some() {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "only one of `#[constructor]`, `#[method]`, `#[getter]` and `#[setter]` can be \
+                 used",
+            ));
+        }
+        kind = Some(attr_kind);
+    }
+    method.attrs = attrs;
+
+    let kind = match kind {
+        _ if skip => return Ok(None),
+        Some(kind) => kind,
+        None => return Ok(None),
+    };
+
+    let sig = &method.sig;
+    let ident = sig.ident.clone();
+    let mut receiver = None;
+    let mut parameters = Vec::new();
+    let mut context = false;
+    for input in &sig.inputs {
+        if context {
+            return Err(syn::Error::new_spanned(
+                input,
+                "the `&mut Context` must be the last parameter",
+            ));
+        }
+        match input {
+            FnArg::Receiver(self_) => match &self_.reference {
+                Some(_) => receiver = Some(self_.mutability.is_some()),
+                None => {
+                    return Err(syn::Error::new_spanned(
+                        self_,
+                        "expected `&self` or `&mut self`",
+                    ))
+                }
+            },
+            FnArg::Typed(pat) if is_context(&pat.ty) => context = true,
+            FnArg::Typed(pat) => parameters.push((*pat.ty).clone()),
+        }
+    }
+    if context && receiver.is_some() {
+        return Err(syn::Error::new_spanned(
+            &sig.inputs,
+            "a function taking `self` can't take the `&mut Context`, because the native data of \
+             `this` is borrowed during its call",
+        ));
+    }
+
+    let name = match (&kind, rename) {
+        (_, Some(rename)) => rename,
+        (Kind::Setter, None) => {
+            let ident = ident.to_string();
+            let property = ident.strip_prefix("set_").ok_or_else(|| {
+                syn::Error::new_spanned(
+                    &sig.ident,
+                    "the name of a setter must start with `set_`, or be set with \
+                     `#[boa(rename = \"...\")]`",
+                )
+            })?;
+            LitStr::new(property, sig.ident.span())
+        }
+        (_, None) => LitStr::new(&ident.to_string(), sig.ident.span()),
+    };
+
+    match kind {
+        Kind::Constructor if receiver.is_some() => {
+            return Err(syn::Error::new_spanned(
+                &sig.inputs,
+                "a constructor can't take `self`",
+            ))
+        }
+        Kind::Constructor if !matches!(sig.output, ReturnType::Type(..)) => {
+            return Err(syn::Error::new_spanned(
+                sig,
+                format!("a constructor must return `Self` or a `Result<{self_ident}, _>`"),
+            ))
+        }
+        Kind::Getter | Kind::Setter if receiver.is_none() => {
+            return Err(syn::Error::new_spanned(
+                sig,
+                "an accessor must take `&self` or `&mut self`",
+            ))
+        }
+        Kind::Getter if !parameters.is_empty() => {
+            return Err(syn::Error::new_spanned(
+                &sig.inputs,
+                "a getter can't take arguments",
+            ))
+        }
+        Kind::Setter if parameters.len() != 1 => {
+            return Err(syn::Error::new_spanned(
+                &sig.inputs,
+                "a setter must take one argument",
+            ))
+        }
+        _ => {}
+    }
+
+    Ok(Some(ClassFunction {
+        kind,
+        ident,
+        name,
+        receiver,
+        parameters,
+        context,
+        output: sig.output.clone(),
+    }))
+}
+
+/// Returns `true` if `ty` is `&mut Context`.
+fn is_context(ty: &Type) -> bool {
+    match ty {
+        Type::Reference(reference) if reference.mutability.is_some() => match &*reference.elem {
+            Type::Path(path) => path
+                .path
+                .segments
+                .last()
+                .map_or(false, |segment| segment.ident == "Context"),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// Returns `true` if `output` is `Self`, rather than a `Result`.
+fn returns_self(output: &ReturnType, self_ident: &syn::Ident) -> bool {
+    match output {
+        ReturnType::Type(_, ty) => match &**ty {
+            Type::Path(path) => path.path.segments.last().map_or(false, |segment| {
+                segment.ident == "Self" || segment.ident == *self_ident
+            }),
+            _ => false,
+        },
+        ReturnType::Default => false,
+    }
+}
+
+/// The sum of the arguments taken by `parameters`.
+fn length(parameters: &[Type]) -> TokenStream {
+    quote! {
+        0 #(+ <#parameters as ::boa_engine::builtins::function::JsFunctionParameter>::ARGUMENTS)*
+    }
+}
+
+/// Converts the arguments of a call to `parameters`, then calls `function` with `receiver` and
+/// stores its return value in `result`.
+fn call(
+    function: TokenStream,
+    receiver: Option<TokenStream>,
+    parameters: &[Type],
+    context: bool,
+) -> TokenStream {
+    let args = (0..parameters.len())
+        .map(|index| format_ident!("arg{}", index))
+        .collect::<Vec<_>>();
+    let receiver = receiver.into_iter();
+    let context = context.then(|| quote!(context));
+    quote! {
+        let mut index = 0;
+        #(
+            let #args = <#parameters as ::boa_engine::builtins::function::JsFunctionParameter>
+                ::from_arguments(this, args, &mut index, context)?;
+        )*
+        let result = #function(#(#receiver,)* #(#args,)* #context);
+    }
+}
+
+/// A native function calling a method with a receiver, on the native data of `this`.
+///
+/// The native data stays borrowed during the call, which is why the method can't take the
+/// `&mut Context`.
+fn native_method(ident: &syn::Ident, receiver: Option<bool>, parameters: &[Type]) -> TokenStream {
+    // `JsObject::downcast_mut` takes `&mut self`, so the object must be a mutable binding.
+    let (binding, receiver) = if receiver == Some(true) {
+        (
+            quote!(mut object),
+            quote!(&mut *object
+                .downcast_mut::<Self>()
+                .expect("the native data of the object was checked before the call")),
+        )
+    } else {
+        (
+            quote!(object),
+            quote!(&*object
+                .downcast_ref::<Self>()
+                .expect("the native data of the object was checked before the call")),
+        )
+    };
+    let call = call(quote!(Self::#ident), Some(receiver), parameters, false);
+    quote! {
+        |this: &::boa_engine::JsValue,
+         args: &[::boa_engine::JsValue],
+         context: &mut ::boa_engine::Context|
+         -> ::boa_engine::JsResult<::boa_engine::JsValue> {
+            let #binding = match this.as_object() {
+                ::std::option::Option::Some(object) if object.is::<Self>() => object.clone(),
+                _ => {
+                    return context.throw_type_error(::std::format!(
+                        "`this` is not a `{}` object",
+                        <Self as ::boa_engine::class::Class>::NAME,
+                    ))
+                }
+            };
+            #call
+            ::boa_engine::value::TryIntoJs::try_into_js(result, context)
+        }
+    }
+}
+
+/// `Some(value)` if `value` is set, or `None`.
+fn option(value: Option<TokenStream>) -> TokenStream {
+    match value {
+        Some(value) => quote!(::std::option::Option::Some(#value)),
+        None => quote!(::std::option::Option::None),
+    }
+}
@@ -0,0 +1,178 @@
+//! Derives for the value conversion traits and native classes of Boa.
+//!
+//! The macros in this crate generate code that refers to `::boa_engine`, so they should be used
+//! through the re-exports of `boa_engine::value` and `boa_engine::class`.
+
+use quote::quote;
+use syn::{Data, DataStruct, Fields, Lit, Meta, MetaNameValue, NestedMeta};
+use synstructure::{decl_derive, Structure};
+
+mod class;
+
+/// Implements `Class` for the type of an `impl` block, exposing its functions to JavaScript.
+///
+/// See the documentation of `boa_engine::class::boa_class`.
+#[proc_macro_attribute]
+pub fn boa_class(
+    attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let args = syn::parse_macro_input!(attr as syn::AttributeArgs);
+    let item = syn::parse_macro_input!(item as syn::ItemImpl);
+    class::expand(args, item)
+        .unwrap_or_else(|error| error.to_compile_error())
+        .into()
+}
+
+/// A field of a struct converted from and to a property of an object.
+struct PropertyField {
+    ident: syn::Ident,
+    /// The name of the property.
+    name: syn::LitStr,
+    /// Use `Default::default()` if the property is `undefined`.
+    default: bool,
+}
+
+/// Returns the fields of a struct with named fields, with their `#[boa(...)]` attributes.
+fn property_fields(s: &Structure<'_>) -> syn::Result<Vec<PropertyField>> {
+    let ast = s.ast();
+    let fields = match &ast.data {
+        Data::Struct(DataStruct {
+            fields: Fields::Named(fields),
+            ..
+        }) => fields,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &ast.ident,
+                "only structs with named fields can be converted from and to objects",
+            ))
+        }
+    };
+
+    let mut properties = Vec::with_capacity(fields.named.len());
+    for field in &fields.named {
+        let ident = field
+            .ident
+            .clone()
+            .expect("named fields must have an identifier");
+        let mut name = syn::LitStr::new(&ident.to_string(), ident.span());
+        let mut default = false;
+
+        for attr in field.attrs.iter().filter(|attr| attr.path.is_ident("boa")) {
+            let list = match attr.parse_meta()? {
+                Meta::List(list) => list,
+                meta => return Err(syn::Error::new_spanned(meta, "expected `#[boa(...)]`")),
+            };
+            for nested in list.nested {
+                match nested {
+                    NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                        path,
+                        lit: Lit::Str(lit),
+                        ..
+                    })) if path.is_ident("rename") => name = lit,
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("default") => {
+                        default = true;
+                    }
+                    nested => {
+                        return Err(syn::Error::new_spanned(
+                            nested,
+                            "expected `rename = \"...\"` or `default`",
+                        ))
+                    }
+                }
+            }
+        }
+
+        properties.push(PropertyField {
+            ident,
+            name,
+            default,
+        });
+    }
+    Ok(properties)
+}
+
+decl_derive!([TryFromJs, attributes(boa)] => derive_try_from_js);
+
+/// Derives `TryFromJs` for a struct with named fields, converting each field from a property of
+/// an object.
+fn derive_try_from_js(mut s: Structure<'_>) -> proc_macro2::TokenStream {
+    s.underscore_const(true);
+    let fields = match property_fields(&s) {
+        Ok(fields) => fields,
+        Err(error) => return error.to_compile_error(),
+    };
+
+    let conversions = fields.iter().map(|field| {
+        let PropertyField {
+            ident,
+            name,
+            default,
+        } = field;
+        if *default {
+            quote! {
+                #ident: {
+                    let value = object.get(#name, context)?;
+                    if value.is_undefined() {
+                        ::std::default::Default::default()
+                    } else {
+                        ::boa_engine::value::TryFromJs::try_from_js(&value, context)?
+                    }
+                }
+            }
+        } else {
+            quote! {
+                #ident: ::boa_engine::value::TryFromJs::try_from_js(
+                    &object.get(#name, context)?,
+                    context,
+                )?
+            }
+        }
+    });
+
+    s.bound_impl(
+        quote!(::boa_engine::value::TryFromJs),
+        quote! {
+            fn try_from_js(
+                value: &::boa_engine::JsValue,
+                context: &mut ::boa_engine::Context,
+            ) -> ::boa_engine::JsResult<Self> {
+                let object: ::boa_engine::object::JsObject =
+                    ::boa_engine::value::TryFromJs::try_from_js(value, context)?;
+                ::std::result::Result::Ok(Self { #(#conversions,)* })
+            }
+        },
+    )
+}
+
+decl_derive!([IntoJs, attributes(boa)] => derive_into_js);
+
+/// Derives `IntoJs` for a struct with named fields, converting each field to a property of a new
+/// object.
+fn derive_into_js(mut s: Structure<'_>) -> proc_macro2::TokenStream {
+    s.underscore_const(true);
+    let fields = match property_fields(&s) {
+        Ok(fields) => fields,
+        Err(error) => return error.to_compile_error(),
+    };
+
+    let properties = fields.iter().map(|PropertyField { ident, name, .. }| {
+        quote! {
+            let value = ::boa_engine::value::IntoJs::into_js(self.#ident, context);
+            object
+                .create_data_property_or_throw(#name, value, context)
+                .expect("defining a property of a new object must not fail");
+        }
+    });
+
+    s.bound_impl(
+        quote!(::boa_engine::value::IntoJs),
+        quote! {
+            fn into_js(self, context: &mut ::boa_engine::Context) -> ::boa_engine::JsValue {
+                let object = context.construct_object();
+                #(#properties)*
+                object.into()
+            }
+        },
+    )
+}
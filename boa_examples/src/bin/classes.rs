@@ -90,21 +90,15 @@ impl Class for Person {
         //
         // This function is added to the `Person` prototype.
         class.method("sayHello", 0, Self::say_hello);
-        // We add a static method `is` using a closure, but it must be convertible
-        // to a NativeFunction.
-        // This means it must not contain state, or the code won't compile.
+        // We add a static method `is` using a closure with typed parameters, whose arguments are
+        // converted before calling it. The closure must be `Copy`, so it can't capture state.
         //
         // This function is added to the `Person` class.
-        class.static_method("is", 1, |_this, args, _ctx| {
-            if let Some(arg) = args.get(0) {
-                if let Some(object) = arg.as_object() {
-                    // We check if the type of `args[0]` is `Person`
-                    if object.is::<Person>() {
-                        return Ok(true.into()); // and return `true` if it is.
-                    }
-                }
-            }
-            Ok(false.into()) // Otherwise we return `false`.
+        class.static_method("is", 1, |value: JsValue| {
+            // We check if the type of `value` is `Person`.
+            value
+                .as_object()
+                .map_or(false, |object| object.is::<Person>())
         });
 
         // We add an `"inheritedProperty"` property to the prototype of `Person` with
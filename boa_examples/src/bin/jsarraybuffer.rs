@@ -0,0 +1,35 @@
+// This example shows how to manipulate Javascript array buffers and data views using Rust code.
+
+use boa_engine::{
+    object::{JsArrayBuffer, JsDataView},
+    property::Attribute,
+    Context, JsError, JsResult,
+};
+
+fn main() -> JsResult<(), JsError> {
+    // We create a new `Context` to create a new Javascript executor.
+    let context = &mut Context::default();
+
+    // Array buffers own their bytes, which can be read and written through a data view.
+    let buffer = JsArrayBuffer::from_byte_block(vec![0; 8], context);
+    let view = JsDataView::new(&buffer, None, None, context)?;
+    view.set_uint16(0, 0x1234, false, context)?;
+    view.set_float32(4, 1.5, true, context)?;
+
+    assert_eq!(view.get_uint8(0, false, context)?, 0x12);
+    assert_eq!(
+        view.get_float32(4, true, context)?.to_bits(),
+        1.5_f32.to_bits()
+    );
+    assert_eq!(
+        buffer.to_vec(),
+        Some(vec![0x12, 0x34, 0, 0, 0, 0, 0xc0, 0x3f])
+    );
+
+    // Scripts see the same bytes.
+    context.register_global_property("buffer", buffer.clone(), Attribute::all());
+    context.eval("new Uint8Array(buffer)[1] = 0xff")?;
+    assert_eq!(view.get_uint8(1, false, context)?, 0xff);
+
+    Ok(())
+}
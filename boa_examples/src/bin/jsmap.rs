@@ -0,0 +1,49 @@
+// This example shows how to manipulate Javascript maps and sets using Rust code.
+
+use boa_engine::{
+    object::{JsMap, JsSet},
+    property::Attribute,
+    Context, JsError, JsResult, JsValue,
+};
+
+fn main() -> JsResult<(), JsError> {
+    // We create a new `Context` to create a new Javascript executor.
+    let context = &mut Context::default();
+
+    // Create a map and fill it from Rust.
+    let map = JsMap::new(context);
+    map.set("answer", 42, context)?;
+    map.set(1, "one", context)?;
+
+    assert_eq!(map.size(), 2);
+    assert_eq!(map.get("answer", context)?, JsValue::new(42));
+    assert!(map.has(1, context)?);
+
+    assert!(map.delete(1, context)?);
+    assert!(!map.has(1, context)?);
+
+    // Sets work the same way.
+    let set = JsSet::from_iter([JsValue::new(1), JsValue::new(2), JsValue::new(1)], context)?;
+    assert_eq!(set.size(), 2);
+    assert!(set.has(2, context)?);
+
+    // Maps and sets can be handed to scripts, and the changes made by scripts are visible from
+    // Rust.
+    context.register_global_property("map", map.clone(), Attribute::all());
+    context.register_global_property("set", set.clone(), Attribute::all());
+    context.eval("map.set('fromScript', true); set.clear();")?;
+
+    assert_eq!(map.get("fromScript", context)?, JsValue::new(true));
+    assert!(set.is_empty());
+
+    // Maps created by scripts can be wrapped too.
+    let object = context.eval("new Map([['a', 1]])")?;
+    let object = object
+        .as_object()
+        .expect("`new Map` returns an object")
+        .clone();
+    let map = JsMap::from_object(object, context)?;
+    assert_eq!(map.get("a", context)?, JsValue::new(1));
+
+    Ok(())
+}
@@ -0,0 +1,25 @@
+// This example shows how to use Javascript regular expressions from Rust code.
+
+use boa_engine::{object::JsRegExp, Context, JsResult, JsValue};
+
+fn main() -> JsResult<()> {
+    // We create a new `Context` to create a new Javascript executor.
+    let context = &mut Context::default();
+
+    // Regular expressions are compiled by the engine, with the usual Javascript syntax.
+    let regexp = JsRegExp::new(r"(\w+)@(\w+)\.com", "g", context)?;
+    assert_eq!(regexp.flags(context)?.as_str(), "g");
+    assert!(regexp.test("boa@example.com", context)?);
+
+    // Global regular expressions remember where the last match ended.
+    regexp.set("lastIndex", 0, true, context)?;
+    let result = regexp
+        .exec("contact: boa@example.com", context)?
+        .expect("the input matches the regular expression");
+    assert_eq!(result.get(1, context)?, JsValue::new("boa"));
+    assert_eq!(result.get(2, context)?, JsValue::new("example"));
+
+    assert_eq!(regexp.to_string(context)?.as_str(), r"/(\w+)@(\w+)\.com/g");
+
+    Ok(())
+}
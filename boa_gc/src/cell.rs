@@ -0,0 +1,468 @@
+//! A garbage-collected mutable memory location.
+
+use crate::{
+    internals::{with_write_barrier, write_barrier_active},
+    trace::{Finalize, Trace},
+};
+use std::{
+    cell::{Cell, UnsafeCell},
+    cmp::Ordering,
+    fmt::{self, Debug, Display},
+    hash::{Hash, Hasher},
+    ops::{Deref, DerefMut},
+};
+
+/// The borrow state of a `GcCell`.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum BorrowState {
+    Reading,
+    Writing,
+    Unused,
+}
+
+/// Whether the cell lives outside of the garbage-collected heap.
+const ROOTED: usize = 0b01;
+/// Whether the cell lives inside an object of the old generation.
+const OLD: usize = 0b10;
+const FLAGS: usize = ROOTED | OLD;
+const WRITING: usize = !FLAGS;
+const UNUSED: usize = 0;
+const ONE_READER: usize = FLAGS + 1;
+
+/// The borrow flag of a `GcCell`.
+///
+/// The two lowest bits store the `ROOTED` and `OLD` flags, while the remaining bits store the
+/// number of readers, or `WRITING` if the cell is mutably borrowed.
+#[derive(Copy, Clone)]
+struct BorrowFlag(usize);
+
+const BORROWFLAG_INIT: BorrowFlag = BorrowFlag(ROOTED);
+
+impl BorrowFlag {
+    fn borrowed(self) -> BorrowState {
+        match self.0 & !FLAGS {
+            UNUSED => BorrowState::Unused,
+            WRITING => BorrowState::Writing,
+            _ => BorrowState::Reading,
+        }
+    }
+
+    fn rooted(self) -> bool {
+        self.0 & ROOTED != 0
+    }
+
+    fn set_rooted(self, rooted: bool) -> Self {
+        Self((self.0 & !ROOTED) | if rooted { ROOTED } else { 0 })
+    }
+
+    fn old(self) -> bool {
+        self.0 & OLD != 0
+    }
+
+    fn set_old(self, old: bool) -> Self {
+        Self((self.0 & !OLD) | if old { OLD } else { 0 })
+    }
+
+    fn set_writing(self) -> Self {
+        Self(self.0 | WRITING)
+    }
+
+    fn set_unused(self) -> Self {
+        Self(self.0 & FLAGS)
+    }
+
+    fn add_reading(self) -> Self {
+        assert!(self.borrowed() != BorrowState::Writing);
+        let flags = Self(self.0 + ONE_READER);
+        assert!(
+            flags.borrowed() != BorrowState::Writing,
+            "too many immutable borrows of a GcCell"
+        );
+        flags
+    }
+
+    fn sub_reading(self) -> Self {
+        assert!(self.borrowed() == BorrowState::Reading);
+        Self(self.0 - ONE_READER)
+    }
+}
+
+/// A mutable memory location with dynamically checked borrow rules that can be used inside of a
+/// garbage-collected pointer.
+///
+/// Mutably borrowing a `GcCell` that lives in an object of the old generation activates the
+/// write barrier of the collector: when the borrow ends, every young object that was stored in
+/// the cell is remembered, so that minor collections don't free it.
+pub struct GcCell<T: ?Sized + 'static> {
+    flags: Cell<BorrowFlag>,
+    cell: UnsafeCell<T>,
+}
+
+impl<T: Trace> GcCell<T> {
+    /// Creates a new `GcCell` containing `value`.
+    #[inline]
+    pub fn new(value: T) -> Self {
+        Self {
+            flags: Cell::new(BORROWFLAG_INIT),
+            cell: UnsafeCell::new(value),
+        }
+    }
+
+    /// Consumes the `GcCell`, returning the wrapped value.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.cell.into_inner()
+    }
+}
+
+impl<T: Trace + ?Sized> GcCell<T> {
+    /// Immutably borrows the wrapped value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is currently mutably borrowed.
+    #[inline]
+    pub fn borrow(&self) -> GcCellRef<'_, T> {
+        match self.try_borrow() {
+            Ok(value) => value,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Mutably borrows the wrapped value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is currently borrowed.
+    #[inline]
+    pub fn borrow_mut(&self) -> GcCellRefMut<'_, T> {
+        match self.try_borrow_mut() {
+            Ok(value) => value,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Immutably borrows the wrapped value, returning an error if the value is currently mutably
+    /// borrowed.
+    pub fn try_borrow(&self) -> Result<GcCellRef<'_, T>, BorrowError> {
+        if self.flags.get().borrowed() == BorrowState::Writing {
+            return Err(BorrowError);
+        }
+        self.flags.set(self.flags.get().add_reading());
+
+        // SAFETY: no mutable borrow exists.
+        unsafe {
+            Ok(GcCellRef {
+                flags: &self.flags,
+                value: &*self.cell.get(),
+            })
+        }
+    }
+
+    /// Mutably borrows the wrapped value, returning an error if the value is currently borrowed.
+    pub fn try_borrow_mut(&self) -> Result<GcCellRefMut<'_, T>, BorrowMutError> {
+        if self.flags.get().borrowed() != BorrowState::Unused {
+            return Err(BorrowMutError);
+        }
+        self.flags.set(self.flags.get().set_writing());
+
+        // SAFETY: no other borrow exists. While the value is mutably borrowed, anything moved
+        // into it could come from outside of the heap, so the contents need to be rooted.
+        unsafe {
+            if !self.flags.get().rooted() {
+                (*self.cell.get()).root();
+            }
+
+            Ok(GcCellRefMut {
+                gc_cell: self,
+                value: &mut *self.cell.get(),
+            })
+        }
+    }
+}
+
+/// An error returned by [`GcCell::try_borrow`](struct.GcCell.html#method.try_borrow).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct BorrowError;
+
+impl Display for BorrowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt("GcCell<T> already mutably borrowed", f)
+    }
+}
+
+impl std::error::Error for BorrowError {}
+
+/// An error returned by [`GcCell::try_borrow_mut`](struct.GcCell.html#method.try_borrow_mut).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct BorrowMutError;
+
+impl Display for BorrowMutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt("GcCell<T> already borrowed", f)
+    }
+}
+
+impl std::error::Error for BorrowMutError {}
+
+impl<T: Trace + ?Sized> Finalize for GcCell<T> {}
+
+unsafe impl<T: Trace + ?Sized> Trace for GcCell<T> {
+    #[inline]
+    unsafe fn trace(&self) {
+        // Cells are only traced when their holder survives a collection, which moves it to the
+        // old generation.
+        self.flags.set(self.flags.get().set_old(true));
+        match self.flags.get().borrowed() {
+            // A mutably borrowed value is rooted, so it doesn't need to be traced.
+            BorrowState::Writing => (),
+            _ => (*self.cell.get()).trace(),
+        }
+    }
+
+    #[inline]
+    unsafe fn root(&self) {
+        assert!(!self.flags.get().rooted(), "Can't root a GcCell twice!");
+        self.flags.set(self.flags.get().set_rooted(true));
+
+        match self.flags.get().borrowed() {
+            BorrowState::Writing => (),
+            _ => (*self.cell.get()).root(),
+        }
+    }
+
+    #[inline]
+    unsafe fn unroot(&self) {
+        assert!(self.flags.get().rooted(), "Can't unroot a GcCell twice!");
+        // The cell is moving into the holder that is currently being unrooted, and inherits its
+        // generation.
+        self.flags.set(
+            self.flags
+                .get()
+                .set_rooted(false)
+                .set_old(write_barrier_active()),
+        );
+
+        match self.flags.get().borrowed() {
+            BorrowState::Writing => (),
+            _ => (*self.cell.get()).unroot(),
+        }
+    }
+
+    #[inline]
+    fn finalize_glue(&self) {
+        Finalize::finalize(self);
+        match self.flags.get().borrowed() {
+            BorrowState::Writing => (),
+            // SAFETY: the value is not mutably borrowed.
+            _ => unsafe { (*self.cell.get()).finalize_glue() },
+        }
+    }
+}
+
+/// A wrapper type for an immutably borrowed value from a `GcCell<T>`.
+pub struct GcCellRef<'a, T: ?Sized + 'static> {
+    flags: &'a Cell<BorrowFlag>,
+    value: &'a T,
+}
+
+impl<'a, T: ?Sized> GcCellRef<'a, T> {
+    /// Copies a `GcCellRef`.
+    ///
+    /// This is an associated function, because `GcCellRef` implements `Deref` to the borrowed
+    /// value.
+    #[allow(clippy::should_implement_trait)]
+    #[inline]
+    pub fn clone(orig: &GcCellRef<'a, T>) -> GcCellRef<'a, T> {
+        orig.flags.set(orig.flags.get().add_reading());
+        GcCellRef {
+            flags: orig.flags,
+            value: orig.value,
+        }
+    }
+
+    /// Makes a new `GcCellRef` for a component of the borrowed data.
+    #[inline]
+    pub fn map<U: ?Sized, F>(orig: Self, f: F) -> GcCellRef<'a, U>
+    where
+        F: FnOnce(&T) -> &U,
+    {
+        let ret = GcCellRef {
+            flags: orig.flags,
+            value: f(orig.value),
+        };
+
+        // We have to tell the compiler not to call the destructor of `orig`, because it would
+        // decrease the reader count that was moved into `ret`.
+        std::mem::forget(orig);
+
+        ret
+    }
+}
+
+impl<T: ?Sized> Deref for GcCellRef<'_, T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        self.value
+    }
+}
+
+impl<T: ?Sized> Drop for GcCellRef<'_, T> {
+    fn drop(&mut self) {
+        debug_assert!(self.flags.get().borrowed() == BorrowState::Reading);
+        self.flags.set(self.flags.get().sub_reading());
+    }
+}
+
+impl<T: ?Sized + Debug> Debug for GcCellRef<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&**self, f)
+    }
+}
+
+impl<T: ?Sized + Display> Display for GcCellRef<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&**self, f)
+    }
+}
+
+/// A wrapper type for a mutably borrowed value from a `GcCell<T>`.
+///
+/// `U` is the type of the borrowed component, which differs from `T` after a
+/// [`GcCellRefMut::map`].
+pub struct GcCellRefMut<'a, T: Trace + ?Sized + 'static, U: ?Sized = T> {
+    gc_cell: &'a GcCell<T>,
+    value: &'a mut U,
+}
+
+impl<'a, T: Trace + ?Sized, U: ?Sized> GcCellRefMut<'a, T, U> {
+    /// Makes a new `GcCellRefMut` for a component of the borrowed data.
+    #[inline]
+    pub fn map<V: ?Sized, F>(orig: Self, f: F) -> GcCellRefMut<'a, T, V>
+    where
+        F: FnOnce(&mut U) -> &mut V,
+    {
+        // SAFETY: `orig` is forgotten below, so the mutable reference stays unique.
+        let value = unsafe { &mut *(orig.value as *mut U) };
+
+        let ret = GcCellRefMut {
+            gc_cell: orig.gc_cell,
+            value: f(value),
+        };
+
+        // We have to tell the compiler not to call the destructor of `orig`, because it would
+        // end the mutable borrow that was moved into `ret`.
+        std::mem::forget(orig);
+
+        ret
+    }
+}
+
+impl<T: Trace + ?Sized, U: ?Sized> Deref for GcCellRefMut<'_, T, U> {
+    type Target = U;
+
+    #[inline]
+    fn deref(&self) -> &U {
+        self.value
+    }
+}
+
+impl<T: Trace + ?Sized, U: ?Sized> DerefMut for GcCellRefMut<'_, T, U> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut U {
+        self.value
+    }
+}
+
+impl<T: Trace + ?Sized, U: ?Sized> Drop for GcCellRefMut<'_, T, U> {
+    #[inline]
+    fn drop(&mut self) {
+        let flags = self.gc_cell.flags.get();
+        debug_assert!(flags.borrowed() == BorrowState::Writing);
+
+        // The contents move back into the heap, so they need to be unrooted. If the cell lives in
+        // the old generation, the write barrier remembers the young objects stored in it.
+        if !flags.rooted() {
+            // SAFETY: the contents were rooted when the mutable borrow started.
+            with_write_barrier(flags.old(), || unsafe {
+                (*self.gc_cell.cell.get()).unroot();
+            });
+        }
+        self.gc_cell.flags.set(flags.set_unused());
+    }
+}
+
+impl<T: Trace + ?Sized, U: Debug + ?Sized> Debug for GcCellRefMut<'_, T, U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&**self, f)
+    }
+}
+
+impl<T: Trace + ?Sized, U: Display + ?Sized> Display for GcCellRefMut<'_, T, U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&**self, f)
+    }
+}
+
+// SAFETY: `GcCell` only gives out access to its contents through the borrow checked wrappers.
+unsafe impl<T: ?Sized + Send> Send for GcCell<T> {}
+
+impl<T: Trace + Clone> Clone for GcCell<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self::new(self.borrow().clone())
+    }
+}
+
+impl<T: Trace + Default> Default for GcCell<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(Default::default())
+    }
+}
+
+impl<T: Trace + ?Sized + PartialEq> PartialEq for GcCell<T> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        *self.borrow() == *other.borrow()
+    }
+}
+
+impl<T: Trace + ?Sized + Eq> Eq for GcCell<T> {}
+
+impl<T: Trace + ?Sized + PartialOrd> PartialOrd for GcCell<T> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        (*self.borrow()).partial_cmp(&*other.borrow())
+    }
+}
+
+impl<T: Trace + ?Sized + Ord> Ord for GcCell<T> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        (*self.borrow()).cmp(&*other.borrow())
+    }
+}
+
+impl<T: Trace + ?Sized + Hash> Hash for GcCell<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (*self.borrow()).hash(state);
+    }
+}
+
+impl<T: Trace + ?Sized + Debug> Debug for GcCell<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.flags.get().borrowed() {
+            BorrowState::Unused | BorrowState::Reading => f
+                .debug_struct("GcCell")
+                .field("value", &self.borrow())
+                .finish(),
+            BorrowState::Writing => f
+                .debug_struct("GcCell")
+                .field("value", &"<borrowed>")
+                .finish(),
+        }
+    }
+}
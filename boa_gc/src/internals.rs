@@ -0,0 +1,707 @@
+//! The garbage-collected heap.
+//!
+//! Objects are allocated in a young generation (the nursery). A minor collection only marks
+//! young objects and promotes the survivors to the old generation, while a major collection
+//! marks the whole heap. Edges from old objects to young objects are recorded by the write
+//! barrier of `GcCell` in the remembered set, which is used as an additional root set by minor
+//! collections.
+
+use crate::trace::{Finalize, Trace};
+use std::{
+    any::Any,
+    cell::{Cell, RefCell},
+    mem::{self, size_of, size_of_val},
+    ptr::NonNull,
+    time::Duration,
+};
+
+/// A `Trace` type that can be downcast while walking the heap.
+pub(crate) trait Traceable: Trace + Any {
+    fn as_any(&self) -> &dyn Any;
+}
+
+impl<T: Trace + Any> Traceable for T {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// The generation an object currently belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Generation {
+    Young,
+    Old,
+}
+
+pub(crate) struct GcBoxHeader {
+    roots: Cell<usize>,
+    marked: Cell<bool>,
+    generation: Cell<Generation>,
+    remembered: Cell<bool>,
+    next: Cell<Option<NonNull<GcBox<dyn Traceable>>>>,
+}
+
+/// A garbage-collected allocation.
+pub(crate) struct GcBox<T: Trace + ?Sized + 'static> {
+    header: GcBoxHeader,
+    data: T,
+}
+
+/// Tuning parameters of the collector.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GcConfig {
+    /// Number of bytes that can be allocated in the young generation before a minor collection
+    /// is triggered.
+    pub nursery_size: usize,
+    /// Number of bytes the old generation can grow to before a major collection is triggered.
+    ///
+    /// The threshold grows after a major collection if the live old generation exceeds
+    /// `used_space_ratio` of it.
+    pub threshold: usize,
+    /// Maximum ratio of live bytes to the major collection threshold after a major collection.
+    pub used_space_ratio: f64,
+}
+
+impl Default for GcConfig {
+    fn default() -> Self {
+        Self {
+            nursery_size: 1024 * 1024,
+            threshold: 8 * 1024 * 1024,
+            used_space_ratio: 0.7,
+        }
+    }
+}
+
+/// Statistics about the activity of the collector on the current thread.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GcStatistics {
+    /// Number of minor (young generation) collections.
+    pub minor_collections: usize,
+    /// Number of major (full heap) collections.
+    pub major_collections: usize,
+    /// Bytes currently allocated in the young generation.
+    pub young_bytes: usize,
+    /// Bytes currently allocated in the old generation.
+    pub old_bytes: usize,
+    /// Number of objects in the young generation.
+    pub young_objects: usize,
+    /// Number of objects in the old generation.
+    pub old_objects: usize,
+    /// Total number of bytes allocated since the thread started.
+    pub total_allocated_bytes: u64,
+    /// Total number of bytes freed since the thread started.
+    pub total_freed_bytes: u64,
+    /// Duration of the last collection.
+    pub last_pause: Duration,
+    /// Duration of the longest collection.
+    pub max_pause: Duration,
+    /// Accumulated duration of all collections.
+    pub total_pause: Duration,
+    /// Bytes owned by objects of the heap outside of it, reported with
+    /// [`reserve_external_memory`].
+    pub external_bytes: usize,
+}
+
+impl GcStatistics {
+    /// Bytes currently allocated in the heap.
+    #[inline]
+    pub fn live_bytes(&self) -> usize {
+        self.young_bytes + self.old_bytes
+    }
+
+    /// Number of objects currently allocated in the heap.
+    #[inline]
+    pub fn live_objects(&self) -> usize {
+        self.young_objects + self.old_objects
+    }
+
+    /// Total number of collections.
+    #[inline]
+    pub fn collections(&self) -> usize {
+        self.minor_collections + self.major_collections
+    }
+
+    /// Bytes counted against the heap limit: the live bytes of the heap and the external memory.
+    #[inline]
+    pub fn limited_bytes(&self) -> usize {
+        self.live_bytes() + self.external_bytes
+    }
+}
+
+/// The fraction of the heap limit allowed above it after the limit is exceeded.
+const HEAP_LIMIT_HEADROOM_DIVISOR: usize = 4;
+
+struct GcState {
+    config: GcConfig,
+    threshold: usize,
+    heap_limit: Option<usize>,
+    /// Whether the heap limit was exceeded and the heap has not gone back under it yet.
+    over_heap_limit: bool,
+    stats: GcStatistics,
+    young_start: Option<NonNull<GcBox<dyn Traceable>>>,
+    old_start: Option<NonNull<GcBox<dyn Traceable>>>,
+    /// Whether the state is being destroyed because the thread exits.
+    tearing_down: bool,
+}
+
+impl Drop for GcState {
+    fn drop(&mut self) {
+        // Free everything that is not reachable anymore. Objects that are still rooted at this
+        // point are leaked, since their roots outlive the thread-local state.
+        //
+        // Finalizers don't run here: the state can't be accessed anymore, so a finalizer that
+        // allocates would panic while the thread-local storage is destroyed.
+        self.tearing_down = true;
+        collect_garbage(self, Generation::Old);
+    }
+}
+
+thread_local! {
+    /// Whether the collector is currently running a minor collection.
+    static MINOR_COLLECTION: Cell<bool> = Cell::new(false);
+
+    /// Whether it is unsafe to run finalizers, because the collector is freeing objects.
+    static GC_DROPPING: Cell<bool> = Cell::new(false);
+
+    /// Whether the values being unrooted are moving into an object of the old generation.
+    static WRITE_BARRIER: Cell<bool> = Cell::new(false);
+
+    /// Whether an allocation exceeded the heap limit since the last call to
+    /// `take_heap_limit_exceeded`.
+    static HEAP_LIMIT_EXCEEDED: Cell<bool> = Cell::new(false);
+
+    /// Bytes of external memory, kept outside of `GcState` because it is released by
+    /// destructors, which can run while the collector borrows the state.
+    static EXTERNAL_BYTES: Cell<usize> = Cell::new(0);
+
+    /// Young objects referenced by objects of the old generation.
+    static REMEMBERED_SET: RefCell<Vec<NonNull<GcBox<dyn Traceable>>>> = RefCell::new(Vec::new());
+
+    static GC_STATE: RefCell<GcState> = RefCell::new(GcState {
+        config: GcConfig::default(),
+        threshold: GcConfig::default().threshold,
+        heap_limit: None,
+        over_heap_limit: false,
+        stats: GcStatistics::default(),
+        young_start: None,
+        old_start: None,
+        tearing_down: false,
+    });
+}
+
+/// Starts measuring the pause of a collection, returning a function that yields the elapsed
+/// time.
+///
+/// `std::time::Instant` is not available on `wasm32`, where pauses are reported as zero.
+#[cfg(not(target_arch = "wasm32"))]
+fn start_timer() -> impl FnOnce() -> Duration {
+    let start = std::time::Instant::now();
+    move || start.elapsed()
+}
+
+#[cfg(target_arch = "wasm32")]
+fn start_timer() -> impl FnOnce() -> Duration {
+    || Duration::ZERO
+}
+
+struct DropGuard;
+
+impl DropGuard {
+    fn new() -> Self {
+        GC_DROPPING.with(|dropping| dropping.set(true));
+        Self
+    }
+}
+
+impl Drop for DropGuard {
+    fn drop(&mut self) {
+        GC_DROPPING.with(|dropping| dropping.set(false));
+    }
+}
+
+/// Returns `true` if it is safe for a type to run [`Finalize::finalize`](crate::Finalize).
+///
+/// This is `false` while the collector frees unreachable objects.
+#[inline]
+pub fn finalizer_safe() -> bool {
+    GC_DROPPING.with(|dropping| !dropping.get())
+}
+
+/// Runs `f` with the write barrier set to `active`.
+///
+/// While the barrier is active, every `Gc` that gets unrooted is considered to be stored in an
+/// object of the old generation, and its target is added to the remembered set if it is young.
+#[inline]
+pub(crate) fn with_write_barrier<R>(active: bool, f: impl FnOnce() -> R) -> R {
+    let previous = WRITE_BARRIER.with(|barrier| barrier.replace(active));
+    let result = f();
+    WRITE_BARRIER.with(|barrier| barrier.set(previous));
+    result
+}
+
+/// Returns `true` if values being unrooted are moving into an object of the old generation.
+#[inline]
+pub(crate) fn write_barrier_active() -> bool {
+    WRITE_BARRIER.with(Cell::get)
+}
+
+impl<T: Trace> GcBox<T> {
+    /// Allocates a new `GcBox` in the young generation, with a root count of one.
+    ///
+    /// This may run a collection before the allocation.
+    pub(crate) fn new(value: T) -> NonNull<Self> {
+        GC_STATE.with(|state| {
+            let mut state = state.borrow_mut();
+
+            if state.stats.young_bytes >= state.config.nursery_size {
+                collect_garbage(&mut state, Generation::Young);
+            }
+            if state.stats.old_bytes >= state.threshold {
+                collect_garbage(&mut state, Generation::Old);
+            }
+
+            let size = size_of::<Self>();
+            if let Some(limit) = current_heap_limit(&state) {
+                // Only try to make room once until the limit violation is observed, to avoid
+                // running a major collection on every allocation.
+                if limited_bytes(&state) + size > limit && !HEAP_LIMIT_EXCEEDED.with(Cell::get) {
+                    collect_garbage(&mut state, Generation::Old);
+                    if limited_bytes(&state) + size > limit {
+                        HEAP_LIMIT_EXCEEDED.with(|exceeded| exceeded.set(true));
+                        state.over_heap_limit = true;
+                    }
+                }
+            }
+
+            let gcbox = Box::new(Self {
+                header: GcBoxHeader {
+                    roots: Cell::new(1),
+                    marked: Cell::new(false),
+                    generation: Cell::new(Generation::Young),
+                    remembered: Cell::new(false),
+                    next: Cell::new(state.young_start.take()),
+                },
+                data: value,
+            });
+
+            // SAFETY: `Box::into_raw` never returns a null pointer.
+            let ptr = unsafe { NonNull::new_unchecked(Box::into_raw(gcbox)) };
+            state.young_start = Some(ptr);
+            state.stats.young_bytes += size;
+            state.stats.young_objects += 1;
+            state.stats.total_allocated_bytes += size as u64;
+
+            ptr
+        })
+    }
+}
+
+impl<T: Trace + ?Sized> GcBox<T> {
+    /// Marks this `GcBox` and traces its contents.
+    ///
+    /// During a minor collection, objects of the old generation are neither marked nor traced.
+    pub(crate) unsafe fn trace_inner(&self) {
+        if self.header.marked.get() {
+            return;
+        }
+        if self.header.generation.get() == Generation::Old && MINOR_COLLECTION.with(Cell::get) {
+            return;
+        }
+        self.header.marked.set(true);
+        self.data.trace();
+    }
+
+    /// Increases the root count of this `GcBox`.
+    pub(crate) fn root_inner(&self) {
+        // abort on overflow; the program is already leaking memory at this point.
+        let roots = self
+            .header
+            .roots
+            .get()
+            .checked_add(1)
+            .expect("root count overflow");
+        self.header.roots.set(roots);
+    }
+
+    /// Decreases the root count of this `GcBox`.
+    pub(crate) fn unroot_inner(&self) {
+        self.header.roots.set(self.header.roots.get() - 1);
+    }
+
+    /// Returns a reference to the value stored in this `GcBox`.
+    pub(crate) fn value(&self) -> &T {
+        &self.data
+    }
+}
+
+/// Adds a young object to the remembered set, if it is not already part of it.
+pub(crate) fn remember(gcbox: NonNull<GcBox<dyn Traceable>>) {
+    // SAFETY: the pointer comes from a live `Gc`.
+    let header = unsafe { &gcbox.as_ref().header };
+    if header.generation.get() == Generation::Young && !header.remembered.get() {
+        header.remembered.set(true);
+        REMEMBERED_SET.with(|set| set.borrow_mut().push(gcbox));
+    }
+}
+
+/// Iterates over the linked list of boxes starting at `start`.
+fn for_each_box(
+    start: Option<NonNull<GcBox<dyn Traceable>>>,
+    mut f: impl FnMut(&GcBox<dyn Traceable>),
+) {
+    let mut next = start;
+    while let Some(node) = next {
+        // SAFETY: every box in the lists is alive until it is swept.
+        let node = unsafe { node.as_ref() };
+        f(node);
+        next = node.header.next.get();
+    }
+}
+
+/// Runs a collection of the given generation.
+///
+/// Collecting the young generation runs a minor collection, while collecting the old generation
+/// runs a major collection of the whole heap.
+fn collect_garbage(state: &mut GcState, generation: Generation) {
+    let elapsed = start_timer();
+    let minor = generation == Generation::Young;
+
+    // Mark
+    MINOR_COLLECTION.with(|m| m.set(minor));
+    // The remembered set may already be destroyed if this runs while the thread exits.
+    let remembered = REMEMBERED_SET
+        .try_with(|set| mem::take(&mut *set.borrow_mut()))
+        .unwrap_or_default();
+    // SAFETY: we are only marking boxes that are part of the heap.
+    unsafe {
+        let mark_roots = |node: &GcBox<dyn Traceable>| {
+            if node.header.roots.get() > 0 {
+                node.trace_inner();
+            }
+        };
+        for_each_box(state.young_start, mark_roots);
+        if minor {
+            for node in &remembered {
+                let node = node.as_ref();
+                node.header.remembered.set(false);
+                node.trace_inner();
+            }
+        } else {
+            for node in &remembered {
+                node.as_ref().header.remembered.set(false);
+            }
+            for_each_box(state.old_start, mark_roots);
+        }
+    }
+    MINOR_COLLECTION.with(|m| m.set(false));
+
+    // Sweep
+    let mut unreachable = Vec::new();
+    let mut old_start = if minor {
+        state.old_start
+    } else {
+        sweep_old(state.old_start.take(), &mut unreachable, &mut state.stats)
+    };
+    let mut young_start = state.young_start.take();
+    // Promote the surviving young objects.
+    while let Some(node) = young_start {
+        // SAFETY: every box in the lists is alive until it is swept.
+        let gcbox = unsafe { node.as_ref() };
+        young_start = gcbox.header.next.take();
+        let size = size_of_val(gcbox);
+        state.stats.young_bytes -= size;
+        state.stats.young_objects -= 1;
+        if gcbox.header.marked.replace(false) {
+            gcbox.header.generation.set(Generation::Old);
+            gcbox.header.next.set(old_start);
+            old_start = Some(node);
+            state.stats.old_bytes += size;
+            state.stats.old_objects += 1;
+        } else {
+            unreachable.push(node);
+            state.stats.total_freed_bytes += size as u64;
+        }
+    }
+    state.old_start = old_start;
+
+    // SAFETY: the boxes are unreachable, so nothing can observe them anymore.
+    unsafe {
+        if !state.tearing_down {
+            for node in &unreachable {
+                node.as_ref().data.finalize_glue();
+            }
+        }
+        let _guard = DropGuard::new();
+        for node in unreachable {
+            drop(Box::from_raw(node.as_ptr()));
+        }
+    }
+
+    if minor {
+        state.stats.minor_collections += 1;
+    } else {
+        state.stats.major_collections += 1;
+        if state.stats.old_bytes as f64 > state.threshold as f64 * state.config.used_space_ratio {
+            state.threshold =
+                (state.stats.old_bytes as f64 / state.config.used_space_ratio) as usize;
+        }
+    }
+
+    if let Some(limit) = state.heap_limit {
+        if limited_bytes(state) <= limit {
+            state.over_heap_limit = false;
+        }
+    }
+
+    let pause = elapsed();
+    state.stats.last_pause = pause;
+    state.stats.max_pause = state.stats.max_pause.max(pause);
+    state.stats.total_pause += pause;
+}
+
+/// Returns the number of bytes counted against the heap limit.
+fn limited_bytes(state: &GcState) -> usize {
+    state.stats.live_bytes() + EXTERNAL_BYTES.with(Cell::get)
+}
+
+/// Returns the heap limit allocations are checked against.
+///
+/// Once the limit has been exceeded, this allows some headroom above it until the heap goes back
+/// under the limit, so that the embedder can allocate the error that reports it without
+/// exceeding the limit again.
+fn current_heap_limit(state: &GcState) -> Option<usize> {
+    let limit = state.heap_limit?;
+    Some(if state.over_heap_limit {
+        limit.saturating_add(limit / HEAP_LIMIT_HEADROOM_DIVISOR)
+    } else {
+        limit
+    })
+}
+
+/// Removes all unmarked boxes of the old generation list, pushing them to `unreachable`, and
+/// clears the mark of the surviving boxes.
+///
+/// Returns the head of the new list.
+fn sweep_old(
+    mut start: Option<NonNull<GcBox<dyn Traceable>>>,
+    unreachable: &mut Vec<NonNull<GcBox<dyn Traceable>>>,
+    stats: &mut GcStatistics,
+) -> Option<NonNull<GcBox<dyn Traceable>>> {
+    let mut tail = None;
+    while let Some(node) = start {
+        // SAFETY: every box in the lists is alive until it is swept.
+        let gcbox = unsafe { node.as_ref() };
+        start = gcbox.header.next.take();
+        if gcbox.header.marked.replace(false) {
+            gcbox.header.next.set(tail);
+            tail = Some(node);
+        } else {
+            let size = size_of_val(gcbox);
+            stats.old_bytes -= size;
+            stats.old_objects -= 1;
+            stats.total_freed_bytes += size as u64;
+            unreachable.push(node);
+        }
+    }
+    tail
+}
+
+/// Immediately runs a major collection of the whole heap.
+///
+/// This frees all objects that are not reachable from a root.
+pub fn force_collect() {
+    GC_STATE.with(|state| collect_garbage(&mut state.borrow_mut(), Generation::Old));
+}
+
+/// Immediately runs a minor collection of the young generation.
+pub fn force_minor_collect() {
+    GC_STATE.with(|state| collect_garbage(&mut state.borrow_mut(), Generation::Young));
+}
+
+/// Returns the configuration of the collector of the current thread.
+pub fn config() -> GcConfig {
+    GC_STATE.with(|state| state.borrow().config)
+}
+
+/// Changes the configuration of the collector of the current thread.
+///
+/// This resets the major collection threshold to the one of the new configuration.
+pub fn set_config(config: GcConfig) {
+    GC_STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        state.threshold = config.threshold;
+        state.config = config;
+    });
+}
+
+/// Returns the statistics of the collector of the current thread.
+pub fn statistics() -> GcStatistics {
+    GC_STATE.with(|state| GcStatistics {
+        external_bytes: EXTERNAL_BYTES.with(Cell::get),
+        ..state.borrow().stats
+    })
+}
+
+/// Sets the maximum number of bytes the heap of the current thread should use, or removes the
+/// limit if `limit` is `None`.
+///
+/// The limit is soft: allocations never fail, but an allocation that would exceed the limit
+/// runs a major collection, and if the heap is still too large afterwards,
+/// [`take_heap_limit_exceeded`] returns `true`. It is up to the embedder to stop the execution.
+///
+/// After the limit is exceeded, the heap can grow a quarter of the limit above it before
+/// [`take_heap_limit_exceeded`] returns `true` again, until a collection brings it back under
+/// the limit.
+///
+/// Besides the `GcBox`es of the heap, the limit counts the external memory reported with
+/// [`reserve_external_memory`] and [`try_reserve_external_memory`]. Other memory owned by the
+/// objects outside of the heap, such as the contents of a `Vec`, is not counted, so the limit is
+/// not a hard bound on the memory used by the thread.
+pub fn set_heap_limit(limit: Option<usize>) {
+    GC_STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        state.heap_limit = limit;
+        state.over_heap_limit = false;
+    });
+    HEAP_LIMIT_EXCEEDED.with(|exceeded| exceeded.set(false));
+}
+
+/// Returns the heap limit of the current thread.
+pub fn heap_limit() -> Option<usize> {
+    GC_STATE.with(|state| state.borrow().heap_limit)
+}
+
+/// Returns `true` if an allocation exceeded the heap limit since the last call, and resets the
+/// flag.
+#[inline]
+pub fn take_heap_limit_exceeded() -> bool {
+    HEAP_LIMIT_EXCEEDED.with(|exceeded| exceeded.replace(false))
+}
+
+/// Counts `size` bytes of memory owned outside of the heap against the heap limit of the current
+/// thread, until they are released with [`release_external_memory`].
+///
+/// This never fails: like an allocation in the heap, it makes
+/// [`take_heap_limit_exceeded`] return `true` if the limit is exceeded, but it doesn't run a
+/// collection, so it can be called from destructors and finalizers.
+pub fn reserve_external_memory(size: usize) {
+    let bytes = EXTERNAL_BYTES.with(|bytes| {
+        bytes.set(bytes.get() + size);
+        bytes.get()
+    });
+    let _ = GC_STATE.try_with(|state| {
+        if let Ok(mut state) = state.try_borrow_mut() {
+            if let Some(limit) = current_heap_limit(&state) {
+                if state.stats.live_bytes() + bytes > limit {
+                    HEAP_LIMIT_EXCEEDED.with(|exceeded| exceeded.set(true));
+                    state.over_heap_limit = true;
+                }
+            }
+        }
+    });
+}
+
+/// Counts `size` bytes of memory owned outside of the heap against the heap limit of the current
+/// thread, if they fit under it.
+///
+/// This is meant to be called before allocating the memory. If the bytes would exceed the limit,
+/// a major collection runs to make room for them, and if they still don't fit, nothing is
+/// counted and this returns `false`.
+///
+/// # Panics
+///
+/// Panics if called during a collection, for instance from a finalizer.
+pub fn try_reserve_external_memory(size: usize) -> bool {
+    GC_STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        if let Some(limit) = current_heap_limit(&state) {
+            if limited_bytes(&state) + size > limit {
+                collect_garbage(&mut state, Generation::Old);
+                if limited_bytes(&state) + size > limit {
+                    state.over_heap_limit = true;
+                    return false;
+                }
+            }
+        }
+        EXTERNAL_BYTES.with(|bytes| bytes.set(bytes.get() + size));
+        true
+    })
+}
+
+/// Stops counting `size` bytes of external memory against the heap limit of the current thread.
+///
+/// The bytes must have been counted with [`reserve_external_memory`] or
+/// [`try_reserve_external_memory`].
+pub fn release_external_memory(size: usize) {
+    // The counter may already be destroyed if this runs while the thread exits.
+    let _ = EXTERNAL_BYTES.try_with(|bytes| bytes.set(bytes.get().saturating_sub(size)));
+}
+
+/// External memory counted against the heap limit of the current thread for as long as the
+/// value is alive.
+///
+/// This is meant to be stored next to the memory it accounts for, in the object that owns it.
+#[derive(Debug)]
+pub struct ExternalMemory {
+    size: usize,
+}
+
+impl ExternalMemory {
+    /// Counts `size` bytes, see [`reserve_external_memory`].
+    #[inline]
+    pub fn reserve(size: usize) -> Self {
+        reserve_external_memory(size);
+        Self { size }
+    }
+
+    /// Counts `size` bytes if they fit under the heap limit, see
+    /// [`try_reserve_external_memory`].
+    #[inline]
+    pub fn try_reserve(size: usize) -> Option<Self> {
+        try_reserve_external_memory(size).then(|| Self { size })
+    }
+
+    /// Returns the number of bytes counted.
+    #[inline]
+    pub fn size(&self) -> usize {
+        self.size
+    }
+}
+
+impl Clone for ExternalMemory {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self::reserve(self.size)
+    }
+}
+
+impl Drop for ExternalMemory {
+    #[inline]
+    fn drop(&mut self) {
+        release_external_memory(self.size);
+    }
+}
+
+impl Finalize for ExternalMemory {}
+
+// SAFETY: `ExternalMemory` doesn't contain any `Gc` pointer.
+unsafe impl Trace for ExternalMemory {
+    crate::unsafe_empty_trace!();
+}
+
+/// Calls `f` with the value of every object in the heap of the current thread.
+///
+/// The values can be downcast to the type `T` of the `Gc<T>` that owns them. Objects that are
+/// not reachable anymore but have not been collected yet are visited as well; run
+/// [`force_collect`] first to only visit reachable objects.
+///
+/// # Panics
+///
+/// Panics if `f` allocates a new `Gc`.
+pub fn walk_heap(mut f: impl FnMut(&dyn Any)) {
+    GC_STATE.with(|state| {
+        let state = state.borrow();
+        for_each_box(state.young_start, |node| f(node.data.as_any()));
+        for_each_box(state.old_start, |node| f(node.data.as_any()));
+    });
+}
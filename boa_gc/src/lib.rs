@@ -1,6 +1,50 @@
 //! Garbage collector for the Boa JavaScript engine.
+//!
+//! This is a generational mark-and-sweep collector. New objects are allocated in a nursery that
+//! is collected by cheap minor collections once it grows past [`GcConfig::nursery_size`]; objects
+//! that survive a collection are promoted to the old generation, which is only collected by a
+//! major collection once it grows past [`GcConfig::threshold`].
+//!
+//! The heap is thread-local. Use [`set_config`] to tune the collector, [`set_heap_limit`] to
+//! bound its size, and [`statistics`] and [`walk_heap`] to observe it.
 
-pub use gc::{
-    custom_trace, force_collect, unsafe_empty_trace, Finalize, Gc, GcCell as Cell,
-    GcCellRef as Ref, GcCellRefMut as RefMut, Trace,
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/boa-dev/boa/main/assets/logo.svg",
+    html_favicon_url = "https://raw.githubusercontent.com/boa-dev/boa/main/assets/logo.svg"
+)]
+#![warn(
+    clippy::perf,
+    clippy::single_match,
+    clippy::match_wild_err_arm,
+    clippy::cast_lossless,
+    clippy::semicolon_if_nothing_returned
+)]
+#![deny(
+    unreachable_pub,
+    missing_debug_implementations,
+    unused_qualifications,
+    trivial_numeric_casts
+)]
+
+extern crate self as boa_gc;
+
+mod cell;
+mod internals;
+mod pointer;
+mod trace;
+
+#[cfg(test)]
+mod tests;
+
+pub use crate::{
+    cell::{BorrowError, BorrowMutError, GcCell as Cell, GcCellRef as Ref, GcCellRefMut as RefMut},
+    internals::{
+        config, finalizer_safe, force_collect, force_minor_collect, heap_limit,
+        release_external_memory, reserve_external_memory, set_config, set_heap_limit, statistics,
+        take_heap_limit_exceeded, try_reserve_external_memory, walk_heap, ExternalMemory, GcConfig,
+        GcStatistics,
+    },
+    pointer::Gc,
+    trace::{Finalize, Trace},
 };
+pub use boa_macros::{Finalize, Trace};
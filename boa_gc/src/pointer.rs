@@ -0,0 +1,231 @@
+//! The garbage-collected pointer type.
+
+use crate::{
+    internals::{remember, with_write_barrier, write_barrier_active, GcBox, Traceable},
+    trace::{Finalize, Trace},
+};
+use std::{
+    cell::Cell,
+    cmp::Ordering,
+    fmt::{self, Debug, Display},
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+    ops::Deref,
+    ptr::{self, NonNull},
+    rc::Rc,
+};
+
+/// A garbage-collected pointer type over an immutable value.
+///
+/// The lowest bit of the pointer stores whether this `Gc` is a root, that is, whether it lives
+/// outside of the garbage-collected heap.
+pub struct Gc<T: Trace + 'static> {
+    ptr_root: Cell<NonNull<GcBox<T>>>,
+    marker: PhantomData<Rc<T>>,
+}
+
+impl<T: Trace> Gc<T> {
+    /// Constructs a new `Gc<T>` with the given value.
+    ///
+    /// This may trigger a collection.
+    pub fn new(value: T) -> Self {
+        let ptr = GcBox::new(value);
+
+        // SAFETY: the box was just allocated. The value moved into the heap, so its contents
+        // don't need to be rooted anymore.
+        unsafe {
+            with_write_barrier(false, || ptr.as_ref().value().unroot());
+        }
+
+        let gc = Self {
+            ptr_root: Cell::new(ptr),
+            marker: PhantomData,
+        };
+        // SAFETY: the box starts with a root count of one.
+        unsafe { gc.set_root() };
+        gc
+    }
+
+    /// Returns `true` if the two `Gc`s point to the same allocation.
+    #[inline]
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        ptr::eq(this.inner_ptr(), other.inner_ptr())
+    }
+
+    #[inline]
+    fn rooted(&self) -> bool {
+        self.ptr_root.get().as_ptr() as *mut u8 as usize & 1 != 0
+    }
+
+    #[inline]
+    unsafe fn set_root(&self) {
+        let ptr = self.ptr_root.get().as_ptr() as *mut u8;
+        let ptr = ptr.wrapping_add(usize::from(!self.rooted()));
+        self.ptr_root
+            .set(NonNull::new_unchecked(ptr.cast::<GcBox<T>>()));
+    }
+
+    #[inline]
+    unsafe fn clear_root(&self) {
+        self.ptr_root
+            .set(NonNull::new_unchecked(self.inner_ptr() as *mut GcBox<T>));
+    }
+
+    #[inline]
+    fn inner_ptr(&self) -> *const GcBox<T> {
+        // `GcBox` is aligned to at least 2 bytes, so the lowest bit is free to store the
+        // rooted flag.
+        let ptr = self.ptr_root.get().as_ptr() as *mut u8;
+        ptr.wrapping_sub(usize::from(self.rooted()))
+            .cast::<GcBox<T>>()
+    }
+
+    #[inline]
+    fn inner(&self) -> &GcBox<T> {
+        // SAFETY: the box is alive as long as this `Gc` is reachable.
+        unsafe { &*self.inner_ptr() }
+    }
+}
+
+impl<T: Trace> Finalize for Gc<T> {}
+
+unsafe impl<T: Trace> Trace for Gc<T> {
+    #[inline]
+    unsafe fn trace(&self) {
+        self.inner().trace_inner();
+    }
+
+    #[inline]
+    unsafe fn root(&self) {
+        assert!(!self.rooted(), "Can't double-root a Gc<T>");
+        self.inner().root_inner();
+        self.set_root();
+    }
+
+    #[inline]
+    unsafe fn unroot(&self) {
+        assert!(self.rooted(), "Can't double-unroot a Gc<T>");
+        self.inner().unroot_inner();
+        self.clear_root();
+
+        // Write barrier: this pointer is being stored into an object of the old generation.
+        if write_barrier_active() {
+            let ptr: NonNull<GcBox<dyn Traceable>> =
+                NonNull::new_unchecked(self.inner_ptr() as *mut GcBox<T>);
+            remember(ptr);
+        }
+    }
+
+    #[inline]
+    fn finalize_glue(&self) {
+        Finalize::finalize(self);
+    }
+}
+
+impl<T: Trace> Clone for Gc<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        self.inner().root_inner();
+        let gc = Self {
+            ptr_root: Cell::new(self.ptr_root.get()),
+            marker: PhantomData,
+        };
+        // SAFETY: the root count of the box was increased above.
+        unsafe {
+            gc.clear_root();
+            gc.set_root();
+        }
+        gc
+    }
+}
+
+impl<T: Trace> Deref for Gc<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        self.inner().value()
+    }
+}
+
+impl<T: Trace> Drop for Gc<T> {
+    #[inline]
+    fn drop(&mut self) {
+        // If this pointer was a root, we should unroot it.
+        if self.rooted() {
+            self.inner().unroot_inner();
+        }
+    }
+}
+
+impl<T: Trace + Default> Default for Gc<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(Default::default())
+    }
+}
+
+impl<T: Trace + PartialEq> PartialEq for Gc<T> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+impl<T: Trace + Eq> Eq for Gc<T> {}
+
+impl<T: Trace + PartialOrd> PartialOrd for Gc<T> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        (**self).partial_cmp(&**other)
+    }
+}
+
+impl<T: Trace + Ord> Ord for Gc<T> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        (**self).cmp(&**other)
+    }
+}
+
+impl<T: Trace + Hash> Hash for Gc<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (**self).hash(state);
+    }
+}
+
+impl<T: Trace + Display> Display for Gc<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&**self, f)
+    }
+}
+
+impl<T: Trace + Debug> Debug for Gc<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&**self, f)
+    }
+}
+
+impl<T: Trace> fmt::Pointer for Gc<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Pointer::fmt(&self.inner_ptr(), f)
+    }
+}
+
+impl<T: Trace> From<T> for Gc<T> {
+    fn from(t: T) -> Self {
+        Self::new(t)
+    }
+}
+
+impl<T: Trace> std::borrow::Borrow<T> for Gc<T> {
+    fn borrow(&self) -> &T {
+        self
+    }
+}
+
+impl<T: Trace> AsRef<T> for Gc<T> {
+    fn as_ref(&self) -> &T {
+        self
+    }
+}
@@ -0,0 +1,180 @@
+use crate::{
+    force_collect, force_minor_collect, set_heap_limit, statistics, take_heap_limit_exceeded,
+    walk_heap, Cell, ExternalMemory, Finalize, Gc, Trace,
+};
+use std::{cell::Cell as StdCell, thread};
+
+thread_local!(static FINALIZED: StdCell<usize> = StdCell::new(0));
+
+#[derive(Trace)]
+struct Node {
+    value: i32,
+    next: Cell<Option<Gc<Node>>>,
+}
+
+impl Finalize for Node {
+    fn finalize(&self) {
+        FINALIZED.with(|f| f.set(f.get() + 1));
+    }
+}
+
+fn node(value: i32) -> Gc<Node> {
+    Gc::new(Node {
+        value,
+        next: Cell::new(None),
+    })
+}
+
+fn finalized() -> usize {
+    FINALIZED.with(StdCell::get)
+}
+
+#[test]
+fn unreachable_objects_are_freed() {
+    let kept = node(1);
+    *kept.next.borrow_mut() = Some(node(2));
+    drop(node(3));
+
+    force_collect();
+    assert_eq!(finalized(), 1);
+    assert_eq!(statistics().live_objects(), 2);
+    assert_eq!(kept.next.borrow().as_ref().map(|n| n.value), Some(2));
+
+    drop(kept);
+    force_collect();
+    assert_eq!(finalized(), 3);
+    assert_eq!(statistics().live_objects(), 0);
+    assert_eq!(statistics().major_collections, 2);
+}
+
+#[test]
+fn survivors_are_promoted() {
+    let kept = node(1);
+    force_minor_collect();
+
+    let stats = statistics();
+    assert_eq!(stats.minor_collections, 1);
+    assert_eq!(stats.young_objects, 0);
+    assert_eq!(stats.old_objects, 1);
+    assert_eq!(kept.value, 1);
+}
+
+#[test]
+fn write_barrier_keeps_young_objects() {
+    let old = node(1);
+    force_minor_collect();
+
+    // `old` lives in the old generation, and the only reference to the new node is stored in
+    // it, which a minor collection doesn't trace.
+    *old.next.borrow_mut() = Some(node(2));
+    force_minor_collect();
+    force_minor_collect();
+
+    assert_eq!(finalized(), 0);
+    assert_eq!(old.next.borrow().as_ref().map(|n| n.value), Some(2));
+
+    // Replacing the reference makes the young node unreachable.
+    *old.next.borrow_mut() = None;
+    force_collect();
+    assert_eq!(finalized(), 1);
+}
+
+#[test]
+fn cycles_are_collected() {
+    let a = node(1);
+    let b = node(2);
+    *a.next.borrow_mut() = Some(b.clone());
+    *b.next.borrow_mut() = Some(a.clone());
+    force_minor_collect();
+
+    drop((a, b));
+    force_minor_collect();
+    assert_eq!(finalized(), 0);
+    force_collect();
+    assert_eq!(finalized(), 2);
+}
+
+#[test]
+fn heap_limit() {
+    let kept = node(1);
+    let limit = statistics().live_bytes() * 3;
+    set_heap_limit(Some(limit));
+
+    // Garbage is collected to stay under the limit.
+    for i in 0..10 {
+        drop(node(i));
+    }
+    assert!(!take_heap_limit_exceeded());
+
+    let nodes: Vec<_> = (0..3).map(node).collect();
+    assert!(take_heap_limit_exceeded());
+    assert!(!take_heap_limit_exceeded());
+
+    drop(nodes);
+    set_heap_limit(None);
+    assert_eq!(kept.value, 1);
+}
+
+#[test]
+fn external_memory_counts_against_heap_limit() {
+    let limit = statistics().limited_bytes() + 1024;
+    set_heap_limit(Some(limit));
+
+    let reserved = ExternalMemory::try_reserve(1000).expect("the memory fits under the limit");
+    assert_eq!(statistics().external_bytes, 1000);
+    assert!(ExternalMemory::try_reserve(1000).is_none());
+    assert!(!take_heap_limit_exceeded());
+
+    drop(reserved);
+    assert_eq!(statistics().external_bytes, 0);
+    let reserved = ExternalMemory::try_reserve(1000).expect("the memory was released");
+
+    // Infallible reservations only report the violation.
+    let over = ExternalMemory::reserve(limit);
+    assert!(take_heap_limit_exceeded());
+
+    drop((reserved, over));
+    set_heap_limit(None);
+}
+
+#[test]
+fn walk_heap_visits_all_objects() {
+    let _a = node(1);
+    let _b = Gc::new(String::from("b"));
+    force_minor_collect();
+    let _c = node(3);
+
+    let mut values = Vec::new();
+    let mut strings = 0;
+    walk_heap(|value| {
+        if let Some(node) = value.downcast_ref::<Node>() {
+            values.push(node.value);
+        } else if value.is::<String>() {
+            strings += 1;
+        }
+    });
+    values.sort_unstable();
+    assert_eq!(values, [1, 3]);
+    assert_eq!(strings, 1);
+}
+
+/// Allocates a new object when finalized.
+#[derive(Trace)]
+struct Allocating {
+    value: i32,
+}
+
+impl Finalize for Allocating {
+    fn finalize(&self) {
+        drop(Gc::new(self.value));
+    }
+}
+
+#[test]
+fn finalizers_dont_run_at_thread_exit() {
+    let handle = thread::spawn(|| {
+        // The object is unreachable, but only gets freed when the thread exits.
+        drop(Gc::new(Allocating { value: 1 }));
+    });
+    assert!(handle.join().is_ok());
+}
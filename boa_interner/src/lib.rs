@@ -74,7 +74,7 @@ mod tests;
 
 use std::{fmt::Display, num::NonZeroUsize};
 
-use gc::{unsafe_empty_trace, Finalize, Trace};
+use boa_gc::{unsafe_empty_trace, Finalize, Trace};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 use string_interner::{backend::BucketBackend, StringInterner, Symbol};
@@ -0,0 +1,94 @@
+//! Procedural macros used in Boa.
+//!
+//! The macros in this crate generate code that refers to `::boa_gc` or `::boa_engine`, so they
+//! should be used through the re-exports of the `boa_gc` and `boa_engine` crates.
+
+use quote::quote;
+use synstructure::{decl_derive, AddBounds, Structure};
+
+decl_derive!([Trace, attributes(unsafe_ignore_trace)] => derive_trace);
+
+/// Derives the `Trace` trait, marking every field that is not annotated with
+/// `#[unsafe_ignore_trace]`.
+fn derive_trace(mut s: Structure<'_>) -> proc_macro2::TokenStream {
+    s.underscore_const(true);
+    s.filter(|bi| {
+        !bi.ast()
+            .attrs
+            .iter()
+            .any(|attr| attr.path.is_ident("unsafe_ignore_trace"))
+    });
+    let trace_body = s.each(|bi| quote!(mark(#bi)));
+
+    s.add_bounds(AddBounds::Fields);
+    let trace_impl = s.unsafe_bound_impl(
+        quote!(::boa_gc::Trace),
+        quote! {
+            #[inline]
+            unsafe fn trace(&self) {
+                #[allow(dead_code)]
+                #[inline]
+                unsafe fn mark<T: ::boa_gc::Trace + ?Sized>(it: &T) {
+                    ::boa_gc::Trace::trace(it);
+                }
+                match *self { #trace_body }
+            }
+            #[inline]
+            unsafe fn root(&self) {
+                #[allow(dead_code)]
+                #[inline]
+                unsafe fn mark<T: ::boa_gc::Trace + ?Sized>(it: &T) {
+                    ::boa_gc::Trace::root(it);
+                }
+                match *self { #trace_body }
+            }
+            #[inline]
+            unsafe fn unroot(&self) {
+                #[allow(dead_code)]
+                #[inline]
+                unsafe fn mark<T: ::boa_gc::Trace + ?Sized>(it: &T) {
+                    ::boa_gc::Trace::unroot(it);
+                }
+                match *self { #trace_body }
+            }
+            #[inline]
+            fn finalize_glue(&self) {
+                ::boa_gc::Finalize::finalize(self);
+                #[allow(dead_code)]
+                #[inline]
+                fn mark<T: ::boa_gc::Trace + ?Sized>(it: &T) {
+                    ::boa_gc::Trace::finalize_glue(it);
+                }
+                match *self { #trace_body }
+            }
+        },
+    );
+
+    // The generated `Drop` implementation makes the compilation fail if the type already
+    // implements `Drop`, since a destructor could access `Gc` pointers that were already freed.
+    let drop_impl = s.unbound_impl(
+        quote!(::std::ops::Drop),
+        quote! {
+            #[allow(clippy::inline_always)]
+            #[inline(always)]
+            fn drop(&mut self) {
+                if ::boa_gc::finalizer_safe() {
+                    ::boa_gc::Finalize::finalize(self);
+                }
+            }
+        },
+    );
+
+    quote! {
+        #trace_impl
+        #drop_impl
+    }
+}
+
+decl_derive!([Finalize] => derive_finalize);
+
+/// Derives an empty `Finalize` implementation.
+fn derive_finalize(mut s: Structure<'_>) -> proc_macro2::TokenStream {
+    s.underscore_const(true);
+    s.unbound_impl(quote!(::boa_gc::Finalize), quote!())
+}
@@ -87,7 +87,9 @@ fn eval_script(_this: &JsValue, args: &[JsValue], context: &mut Context) -> JsRe
             Err(e) => context.throw_type_error(format!("Uncaught Syntax Error: {e}")),
             // Calling eval here parses the code a second time.
             // TODO: We can fix this after we have have defined the public api for the vm executer.
-            Ok(_) => context.eval(source_text.as_str()),
+            Ok(_) => context
+                .eval(source_text.as_str())
+                .map_err(|error| error.to_opaque(context)),
         }
     } else {
         Ok(JsValue::undefined())
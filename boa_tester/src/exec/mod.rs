@@ -170,7 +170,9 @@ impl Test {
                             let passed = res.is_ok();
                             let text = match res {
                                 Ok(val) => val.display().to_string(),
-                                Err(e) => format!("Uncaught {}", e.display()),
+                                Err(e) => {
+                                    format!("Uncaught {}", e.to_opaque(&mut context).display())
+                                }
                             };
 
                             (passed, text)
@@ -219,6 +221,7 @@ impl Test {
                                 match context.eval(&self.content.as_ref()) {
                                     Ok(res) => (false, res.display().to_string()),
                                     Err(e) => {
+                                        let e = e.to_opaque(&mut context);
                                         let passed = e
                                             .display()
                                             .internals(true)
@@ -322,15 +325,15 @@ impl Test {
         if strict {
             context
                 .eval(r#""use strict";"#)
-                .map_err(|e| format!("could not set strict mode:\n{}", e.display()))?;
+                .map_err(|e| format!("could not set strict mode:\n{e}"))?;
         }
 
         context
             .eval(&harness.assert.as_ref())
-            .map_err(|e| format!("could not run assert.js:\n{}", e.display()))?;
+            .map_err(|e| format!("could not run assert.js:\n{e}"))?;
         context
             .eval(&harness.sta.as_ref())
-            .map_err(|e| format!("could not run sta.js:\n{}", e.display()))?;
+            .map_err(|e| format!("could not run sta.js:\n{e}"))?;
 
         for include in self.includes.iter() {
             context
@@ -341,12 +344,7 @@ impl Test {
                         .ok_or_else(|| format!("could not find the {include} include file."))?
                         .as_ref(),
                 )
-                .map_err(|e| {
-                    format!(
-                        "could not run the {include} include file:\nUncaught {}",
-                        e.display()
-                    )
-                })?;
+                .map_err(|e| format!("could not run the {include} include file:\nUncaught {e}"))?;
         }
 
         Ok(())
@@ -65,8 +65,9 @@ use wasm_bindgen::prelude::*;
 #[wasm_bindgen]
 pub fn evaluate(src: &str) -> Result<String, JsValue> {
     // Setup executor
-    Context::default()
+    let mut context = Context::default();
+    context
         .eval(src)
-        .map_err(|e| JsValue::from(format!("Uncaught {}", e.display())))
+        .map_err(|e| JsValue::from(format!("Uncaught {}", e.to_opaque(&mut context).display())))
         .map(|v| v.display().to_string())
 }